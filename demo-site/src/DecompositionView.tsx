@@ -1,8 +1,9 @@
 import type { WDecomposition, WField } from "./wasm";
 
-// Each grammar field gets an underline + legend dot color. `amount` matches the
+// Each field gets an underline + legend dot color. `amount` matches the
 // rich-text measure highlight (blue), `name` the existing name underline
-// (accent/emerald), `modifier` a distinct amber.
+// (accent/emerald), `modifier` a distinct amber; the notes that qualify the
+// ingredient (alternative, usage, optional) get their own hues.
 const FIELD_STYLES: Record<
   WField,
   { underline: string; dot: string; label: string }
@@ -14,15 +15,32 @@ const FIELD_STYLES: Record<
     dot: "bg-amber-400",
     label: "modifier",
   },
+  alternative: {
+    underline: "border-violet-400",
+    dot: "bg-violet-400",
+    label: "alternative",
+  },
+  usage: { underline: "border-rose-400", dot: "bg-rose-400", label: "usage" },
+  optional: {
+    underline: "border-zinc-400",
+    dot: "bg-zinc-400",
+    label: "optional",
+  },
 };
 
-const FIELD_ORDER: WField[] = ["amount", "name", "modifier"];
+const FIELD_ORDER: WField[] = [
+  "amount",
+  "name",
+  "modifier",
+  "alternative",
+  "usage",
+  "optional",
+];
 
 /**
- * Diagnostic-style view of how the grammar carved the line: the (normalized)
- * source in monospace with a colored underline under each amount/name/modifier
- * span, plus a legend. Mirrors `parse-ingredient --explain`. Renders nothing
- * until there's input.
+ * Diagnostic-style view of how the parse carved the line: the source as typed
+ * in monospace with a colored underline under each field span, plus a legend.
+ * Mirrors `parse-ingredient --explain`. Renders nothing until there's input.
  */
 export function DecompositionView({
   decomp,
@@ -38,7 +56,7 @@ export function DecompositionView({
   return (
     <div className="mb-5">
       <div className="mb-2 text-sm font-medium text-zinc-500">
-        How the grammar carved it
+        How the parser carved it
       </div>
       <div className="overflow-x-auto rounded-lg border border-zinc-200 bg-zinc-50 px-4 py-3">
         <div className="font-mono text-base whitespace-pre text-zinc-900">
@@ -71,8 +89,7 @@ export function DecompositionView({
         </div>
       ) : (
         <p className="mt-2 text-xs text-zinc-400">
-          No grammar decomposition — handled by a recognizer or name-only
-          fallback.
+          No decomposition — handled by the name-only fallback.
         </p>
       )}
     </div>
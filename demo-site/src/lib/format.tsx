@@ -60,6 +60,19 @@ export const formatRichText = (text: RichItem[]) => {
           </span>
         );
       }
+      case "Dimension": {
+        const { sides, unit, aspect } = t.value;
+        // Inches serialize as `"`, which reads best glued to the number.
+        const size = sides.join("x") + (unit === '"' ? unit : ` ${unit}`);
+        return (
+          <span
+            className="mx-0.5 inline rounded-md border border-blue-200 bg-blue-100 px-1.5 py-0.5 font-semibold text-blue-800"
+            key={`dimension-${index}`}
+          >
+            {aspect ? `${size} ${aspect}` : size}
+          </span>
+        );
+      }
       default:
         return null;
     }
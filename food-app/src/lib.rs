@@ -3,9 +3,10 @@
 //! ingredient line parsed, and inspect the parse trace stage-by-stage.
 //!
 //! [`MyApp`] is the crate root and the `eframe::App` implementation; its tabs
-//! (Recipe/Debug/Test/Cookbook/Corpus, in `tabs/`) cover live scraping,
-//! per-ingredient trace inspection, batch corpus-style testing against pasted
-//! lines, EPUB cookbook extraction, and a live view of the accuracy corpus.
+//! (Recipe/Debug/Test/Cookbook/Corpus/Label, in `tabs/`) cover live scraping,
+//! per-ingredient trace inspection (or two saved traces side by side), batch
+//! corpus-style testing against pasted lines, EPUB cookbook extraction, a live
+//! view of the accuracy corpus, and labeling new corpus rows.
 //! `main.rs` is just the native binary entry point that constructs [`MyApp`].
 
 // UI code uses unwrap for display purposes where panics are acceptable
@@ -20,7 +21,7 @@ use ingredient::trace::ParseTrace;
 use poll_promise::Promise;
 use rand::RngExt;
 use recipe_scraper::{ParsedRecipe, ScrapedRecipe};
-use tabs::{CookbookTab, CorpusAction, CorpusTab, TestTab};
+use tabs::{CookbookTab, CorpusAction, CorpusTab, LabelTab, TestTab, TraceCompare};
 use tabs::{show_debug_tab, show_parsed, show_raw};
 
 #[derive(PartialEq, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
@@ -31,6 +32,7 @@ enum Tab {
     Test,
     Cookbook,
     Corpus,
+    Label,
 }
 
 struct Wrapper {
@@ -46,12 +48,16 @@ pub struct MyApp {
     current_tab: Tab,
     theme: theme::ThemeChoice,
     selected_ingredient_idx: Option<usize>,
+    // Debug tab: two saved traces compared side by side
+    trace_compare: TraceCompare,
     // Test tab state
     test: TestTab,
     // Cookbook (EPUB) tab state
     cookbook: CookbookTab,
     // Corpus QA tab state
     corpus: CorpusTab,
+    // Corpus labeling tab state
+    label: LabelTab,
 }
 
 impl Default for MyApp {
@@ -63,9 +69,11 @@ impl Default for MyApp {
             current_tab: Tab::Recipe,
             theme: theme::ThemeChoice::default(),
             selected_ingredient_idx: None,
+            trace_compare: TraceCompare::default(),
             test: TestTab::default(),
             cookbook: CookbookTab::default(),
             corpus: CorpusTab::default(),
+            label: LabelTab::default(),
         }
     }
 }
@@ -147,6 +155,11 @@ impl eframe::App for MyApp {
                     Tab::Corpus,
                     format!("{} Corpus", theme::icon::CORPUS),
                 );
+                ui.selectable_value(
+                    &mut self.current_tab,
+                    Tab::Label,
+                    format!("{} Label", theme::icon::LABEL),
+                );
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     let icon = match self.theme {
                         theme::ThemeChoice::Mocha => theme::icon::LIGHT_MODE,
@@ -289,7 +302,10 @@ impl eframe::App for MyApp {
                 }
             }
             Tab::Debug => {
-                if let Some(promise) = &self.promise {
+                ui.checkbox(&mut self.trace_compare.open, "Compare saved traces");
+                if self.trace_compare.open {
+                    self.trace_compare.show(ui);
+                } else if let Some(promise) = &self.promise {
                     match promise.ready() {
                         None => {
                             ui.spinner();
@@ -318,6 +334,9 @@ impl eframe::App for MyApp {
                     }
                 }
             }
+            Tab::Label => {
+                self.label.show(ui);
+            }
         });
     }
 }
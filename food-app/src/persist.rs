@@ -20,6 +20,10 @@ pub(crate) struct PersistedState {
     pub use_ai_fallback: bool,
     pub library_grid: bool,
     pub corpus_path: String,
+    pub label_lines_path: String,
+    pub label_corpus_path: String,
+    pub trace_before_path: String,
+    pub trace_after_path: String,
 }
 
 impl Default for PersistedState {
@@ -43,6 +47,10 @@ impl PersistedState {
             use_ai_fallback: app.cookbook.use_ai_fallback,
             library_grid: app.cookbook.library_grid,
             corpus_path: app.corpus.path.clone(),
+            label_lines_path: app.label.lines_path.clone(),
+            label_corpus_path: app.label.corpus_path.clone(),
+            trace_before_path: app.trace_compare.before_path.clone(),
+            trace_after_path: app.trace_compare.after_path.clone(),
         }
     }
 
@@ -61,6 +69,10 @@ impl PersistedState {
         app.cookbook.use_ai_fallback = self.use_ai_fallback;
         app.cookbook.library_grid = self.library_grid;
         app.corpus.path = self.corpus_path;
+        app.label.lines_path = self.label_lines_path;
+        app.label.corpus_path = self.label_corpus_path;
+        app.trace_compare.before_path = self.trace_before_path;
+        app.trace_compare.after_path = self.trace_after_path;
     }
 }
 
@@ -85,6 +97,10 @@ mod tests {
         app.cookbook.use_ai_fallback = true;
         app.cookbook.library_grid = false;
         app.corpus.path = "some/other/corpus.jsonl".to_string();
+        app.label.lines_path = "/tmp/dump.jsonl".to_string();
+        app.label.corpus_path = "scratch/corpus.jsonl".to_string();
+        app.trace_compare.before_path = "/tmp/before.json".to_string();
+        app.trace_compare.after_path = "/tmp/after.json".to_string();
 
         let ron = ron::to_string(&PersistedState::capture(&app)).unwrap();
         let restored: PersistedState = ron::from_str(&ron).unwrap();
@@ -101,6 +117,13 @@ mod tests {
         assert!(fresh.cookbook.use_ai_fallback);
         assert!(!fresh.cookbook.library_grid);
         assert_eq!(fresh.corpus.path, app.corpus.path);
+        assert_eq!(fresh.label.lines_path, app.label.lines_path);
+        assert_eq!(fresh.label.corpus_path, app.label.corpus_path);
+        assert_eq!(
+            fresh.trace_compare.before_path,
+            app.trace_compare.before_path
+        );
+        assert_eq!(fresh.trace_compare.after_path, app.trace_compare.after_path);
     }
 
     /// An older/empty snapshot must fall back to the app's defaults (per-field
@@ -48,6 +48,9 @@ struct ScannedBook {
     /// Lowercased "title authors" haystack, precomputed at scan time so the
     /// library filter doesn't rebuild it per book per frame.
     search_key: String,
+    /// Recipes the persistent catalog holds for this book (`food-cli catalog
+    /// sync`), or `None` when it hasn't been cataloged yet.
+    cataloged: Option<usize>,
 }
 
 /// The `bytes://` URI a library cover is registered under (keyed by book path so
@@ -617,18 +620,26 @@ impl CookbookTab {
 /// Free function rather than closure body so it is reachable without a live
 /// egui context; the thread and repaint stay at the call site.
 fn scan_library(dir: &std::path::Path, use_ai: bool) -> ScanResult {
+    // The catalog is optional context here: a missing or unreadable one just
+    // means no counts are shown.
+    let catalog = recipe_epub::Catalog::open(&recipe_epub::default_catalog_path()).ok();
     let mut books: Vec<ScannedBook> = recipe_epub::find_epubs(dir)
         .iter()
         .filter_map(|p| recipe_epub::book_metadata(p).ok())
         .map(|meta| {
             let guess = recipe_epub::classify_by_tags(&meta);
             let search_key = format!("{} {}", meta.title, meta.authors.join(" ")).to_lowercase();
+            let cataloged = catalog
+                .as_ref()
+                .and_then(|c| c.book(&meta.path))
+                .map(|b| b.recipes.len());
             ScannedBook {
                 is_cookbook: guess == CookbookGuess::Yes,
                 guess,
                 meta,
                 cover: None,
                 search_key,
+                cataloged,
             }
         })
         .collect();
@@ -762,11 +773,14 @@ fn show_library(
             } else {
                 // A compact one-line-per-book list (the space-saving view).
                 for b in visible() {
-                    let label = if b.meta.authors.is_empty() {
+                    let mut label = if b.meta.authors.is_empty() {
                         b.meta.title.clone()
                     } else {
                         format!("{} — {}", b.meta.title, b.meta.authors.join(", "))
                     };
+                    if let Some(n) = b.cataloged {
+                        label.push_str(&format!(" · {n} recipes"));
+                    }
                     let resp = ui.selectable_label(false, label).on_hover_text(
                         if b.meta.subjects.is_empty() {
                             "no tags".to_string()
@@ -819,10 +833,15 @@ fn book_card(ui: &mut egui::Ui, b: &ScannedBook) -> egui::Response {
     inner
         .response
         .interact(egui::Sense::click())
-        .on_hover_text(if b.meta.subjects.is_empty() {
-            b.meta.title.clone()
-        } else {
-            format!("{} · {}", b.meta.title, b.meta.subjects.join(", "))
+        .on_hover_text({
+            let mut text = b.meta.title.clone();
+            if let Some(n) = b.cataloged {
+                text.push_str(&format!(" · {n} cataloged recipes"));
+            }
+            if !b.meta.subjects.is_empty() {
+                text.push_str(&format!(" · {}", b.meta.subjects.join(", ")));
+            }
+            text
         })
 }
 
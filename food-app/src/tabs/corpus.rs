@@ -407,7 +407,7 @@ mod tests {
         let scored = ScoredRow::score(&committed);
         assert_eq!(scored.status, Status::Exact);
         assert_eq!(scored.input, "1 cup flour");
-        assert_eq!(scored.diffs.len(), 5);
+        assert_eq!(scored.diffs.len(), 6);
         assert!(scored.diffs.iter().all(|d| d.ok));
         assert!(scored.xfail_reason.is_none());
 
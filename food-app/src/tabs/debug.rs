@@ -1,7 +1,7 @@
 use crate::theme;
 use eframe::egui::{self, RichText};
 use egui_ltreeview::TreeView;
-use ingredient::trace::{ParseTrace, TraceNode, TraceOutcome};
+use ingredient::trace::{ParseTrace, TraceDiff, TraceNode, TraceOutcome};
 use ingredient::util::truncate_str;
 
 /// Context for the trace tree to generate unique IDs
@@ -9,6 +9,134 @@ use ingredient::util::truncate_str;
 pub enum TraceTreeContext {
     Test,
     Debug,
+    CompareBefore,
+    CompareAfter,
+}
+
+/// Two traces saved with `food-cli parse-ingredient --trace-out`, shown side
+/// by side with the first node where they part ways — the GUI half of
+/// `--trace-diff`.
+#[derive(Default)]
+pub struct TraceCompare {
+    /// Show the comparison instead of the fetched recipe's traces.
+    pub(crate) open: bool,
+    /// The trace from before the change (editable). Persisted across runs.
+    pub(crate) before_path: String,
+    /// The trace from after the change (editable). Persisted across runs.
+    pub(crate) after_path: String,
+    loaded: Option<Loaded>,
+    /// A load error (bad path / not a trace), shown inline.
+    load_error: Option<String>,
+}
+
+struct Loaded {
+    before: ParseTrace,
+    after: ParseTrace,
+    diff: TraceDiff,
+}
+
+impl TraceCompare {
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        let mut load = false;
+        egui::Grid::new("trace_compare_paths")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (label, path) in [
+                    ("Before:", &mut self.before_path),
+                    ("After:", &mut self.after_path),
+                ] {
+                    ui.label(label);
+                    let resp = ui.add(
+                        egui::TextEdit::singleline(path)
+                            .hint_text("parse-ingredient --trace-out FILE")
+                            .desired_width(360.0)
+                            .font(egui::TextStyle::Monospace),
+                    );
+                    // Load on Enter (not bare lost_focus, which also fires on
+                    // Tab/click-away — same idiom as the Label tab).
+                    load |= resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    ui.end_row();
+                }
+            });
+        if ui.button("Load").clicked() || load {
+            self.load();
+        }
+        if let Some(err) = &self.load_error {
+            ui.colored_label(theme::palette().trace_fail(), err);
+        }
+        let Some(loaded) = &self.loaded else {
+            ui.add_space(8.0);
+            ui.label(RichText::new("Pick two saved traces and click Load.").weak());
+            return;
+        };
+        ui.separator();
+
+        match loaded.diff.first_divergence() {
+            None => {
+                ui.label(
+                    RichText::new("Traces match (timings aside)")
+                        .color(theme::palette().trace_ok()),
+                );
+            }
+            Some(path) => {
+                let names: Vec<&str> = path.iter().map(|n| n.name.as_str()).collect();
+                ui.label(
+                    RichText::new(format!("First divergence: {}", names.join(" › ")))
+                        .color(theme::palette().trace_fail())
+                        .strong(),
+                );
+            }
+        }
+        egui::CollapsingHeader::new("Structural diff")
+            .id_salt("trace_compare_diff")
+            .show(ui, |ui| {
+                egui::ScrollArea::both()
+                    .id_salt("trace_compare_diff_text")
+                    .max_height(240.0)
+                    .show(ui, |ui| {
+                        ui.label(RichText::new(loaded.diff.format(false)).monospace());
+                    });
+            });
+        ui.separator();
+
+        ui.columns(2, |columns| {
+            for (column, heading, trace, context) in [
+                (0, "Before", &loaded.before, TraceTreeContext::CompareBefore),
+                (1, "After", &loaded.after, TraceTreeContext::CompareAfter),
+            ] {
+                let ui = &mut columns[column];
+                ui.heading(heading);
+                ui.label(format!("Input: \"{}\"", trace.input));
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .id_salt(("trace_compare_tree", column))
+                    .show(ui, |ui| show_trace_tree(ui, trace, context));
+            }
+        });
+    }
+
+    fn load(&mut self) {
+        let read = |path: &str| -> Result<ParseTrace, String> {
+            let text = std::fs::read_to_string(path.trim())
+                .map_err(|e| format!("failed to read {path}: {e}"))?;
+            serde_json::from_str(&text).map_err(|e| format!("{path} is not a saved trace: {e}"))
+        };
+        match read(&self.before_path).and_then(|b| Ok((b, read(&self.after_path)?))) {
+            Ok((before, after)) => {
+                let diff = before.diff(&after);
+                self.loaded = Some(Loaded {
+                    before,
+                    after,
+                    diff,
+                });
+                self.load_error = None;
+            }
+            Err(e) => {
+                self.loaded = None;
+                self.load_error = Some(e);
+            }
+        }
+    }
 }
 
 pub fn show_debug_tab(ui: &mut egui::Ui, traces: &[ParseTrace], selected: &mut Option<usize>) {
@@ -56,6 +184,8 @@ pub fn show_trace_tree(ui: &mut egui::Ui, trace: &ParseTrace, context: TraceTree
     let id_salt = match context {
         TraceTreeContext::Test => "test_parse_trace_tree",
         TraceTreeContext::Debug => "debug_parse_trace_tree",
+        TraceTreeContext::CompareBefore => "compare_before_trace_tree",
+        TraceTreeContext::CompareAfter => "compare_after_trace_tree",
     };
     let id = ui.make_persistent_id(id_salt);
     TreeView::new(id).show(ui, |builder| {
@@ -0,0 +1,431 @@
+//! Label tab: rank unlabeled lines from a parse dump and turn a reviewed parse
+//! into a corpus row — the GUI half of `food-cli corpus label`.
+//!
+//! Ranking, prefilling, validation and filing all come from
+//! `food_cli::corpus_label`, so a row saved here is exactly the row the CLI
+//! would write: a committed row only when the parse matches it, otherwise an
+//! xfail with a reason. A saved row goes straight into the corpus file under
+//! its section.
+
+use crate::theme;
+use eframe::egui::{self, RichText};
+use egui_extras::{Column, TableBuilder};
+use ingredient::util::truncate_str;
+use ingredient_corpus::label::{self, Candidate, Draft};
+
+/// Default corpus path, relative to the workspace root (the app's cwd under
+/// `cargo run --bin food-app`). Editable in the tab's path field.
+const DEFAULT_CORPUS_PATH: &str = ingredient_corpus::CORPUS_RELATIVE_PATH;
+
+/// State for the Label tab.
+pub struct LabelTab {
+    /// The parse dump to draw lines from (editable). Persisted across runs.
+    pub(crate) lines_path: String,
+    /// The corpus rows are written to (editable). Persisted across runs.
+    pub(crate) corpus_path: String,
+    /// Unlabeled lines, best first; a line leaves once saved or skipped.
+    candidates: Vec<Candidate>,
+    /// The corpus's section names, offered in the section picker.
+    sections: Vec<String>,
+    selected: Option<usize>,
+    /// A load error (bad path / malformed corpus), shown inline.
+    load_error: Option<String>,
+    /// The last save's outcome: where the row went, or why it was refused.
+    status: Option<Result<String, String>>,
+    /// Rows written since the last load.
+    written: usize,
+    /// Distinguishes "not loaded yet" from "loaded, nothing left to label".
+    loaded: bool,
+}
+
+impl Default for LabelTab {
+    fn default() -> Self {
+        Self {
+            lines_path: String::new(),
+            corpus_path: DEFAULT_CORPUS_PATH.to_string(),
+            candidates: Vec::new(),
+            sections: Vec::new(),
+            selected: None,
+            load_error: None,
+            status: None,
+            written: 0,
+            loaded: false,
+        }
+    }
+}
+
+/// What the editor asked for this frame, applied once its borrow ends.
+enum EditorAction {
+    Save,
+    Skip,
+}
+
+impl LabelTab {
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Label");
+        ui.label(
+            RichText::new(
+                "Ranks lines from a parse dump by how much a label would teach the \
+                 corpus, prefilled from the current parse.",
+            )
+            .weak()
+            .small(),
+        );
+        ui.separator();
+
+        let mut load = false;
+        egui::Grid::new("label_paths")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (label, path, hint) in [
+                    (
+                        "Lines:",
+                        &mut self.lines_path,
+                        "parse-lines / --dump-parsed JSONL, or plain text",
+                    ),
+                    ("Corpus:", &mut self.corpus_path, ""),
+                ] {
+                    ui.label(label);
+                    let resp = ui.add(
+                        egui::TextEdit::singleline(path)
+                            .hint_text(hint)
+                            .desired_width(360.0)
+                            .font(egui::TextStyle::Monospace),
+                    );
+                    // Load on Enter (not bare lost_focus, which also fires on
+                    // Tab/click-away — same idiom as the Corpus tab).
+                    load |= resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    ui.end_row();
+                }
+            });
+        if ui.button("Load").clicked() || load {
+            self.load();
+        }
+
+        if let Some(err) = &self.load_error {
+            ui.colored_label(theme::palette().trace_fail(), err);
+        }
+        if !self.loaded {
+            ui.add_space(8.0);
+            ui.label(RichText::new("Pick a parse dump and click Load.").weak());
+            return;
+        }
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("{} to label", self.candidates.len())).strong());
+            ui.separator();
+            ui.label(format!("{} written", self.written));
+            match &self.status {
+                Some(Ok(msg)) => {
+                    ui.colored_label(theme::palette().trace_ok(), msg);
+                }
+                Some(Err(msg)) => {
+                    ui.colored_label(theme::palette().trace_fail(), msg);
+                }
+                None => {}
+            }
+        });
+        ui.separator();
+
+        if let Some(idx) = self.selected
+            && let Some(candidate) = self.candidates.get_mut(idx)
+        {
+            match show_editor(ui, candidate, &self.sections) {
+                Some(EditorAction::Save) => self.save(idx),
+                Some(EditorAction::Skip) => self.remove(idx),
+                None => {}
+            }
+        }
+
+        self.show_table(ui);
+    }
+
+    /// Read the lines and the corpus, and rank the lines against it.
+    fn load(&mut self) {
+        let read = |path: &str| {
+            std::fs::read_to_string(path).map_err(|e| format!("failed to read {path}: {e}"))
+        };
+        let (lines, source) = match (read(&self.lines_path), read(&self.corpus_path)) {
+            (Ok(lines), Ok(source)) => (lines, source),
+            (Err(e), _) | (_, Err(e)) => {
+                self.load_error = Some(e);
+                return;
+            }
+        };
+        // A malformed row would be invisible to the ranking and get a row
+        // filed next to it, so refuse the load like the Corpus tab does.
+        let corpus = ingredient_corpus::parse(&source);
+        if let Some((entry, problem)) = corpus.problems().next() {
+            self.load_error = Some(format!(
+                "invalid corpus row (line {}): {}\n  {}",
+                entry.line_no, problem.message, problem.line
+            ));
+            return;
+        }
+        let coverage = ingredient_corpus::coverage::report_stages_over(&corpus.inputs());
+        let lines = ingredient_corpus::load_lines(&lines);
+        self.candidates = label::rank(&lines, &corpus, &coverage);
+        self.sections = corpus
+            .sections
+            .iter()
+            .filter(|s| *s != "(ungrouped)")
+            .cloned()
+            .collect();
+        self.load_error = None;
+        self.status = None;
+        self.written = 0;
+        self.loaded = true;
+        self.selected = (!self.candidates.is_empty()).then_some(0);
+    }
+
+    /// Validate the candidate's draft and file it into the corpus on disk.
+    /// A refusal (labels disagree with the parse and no xfail, a bad amounts
+    /// array) is shown and the candidate stays for another edit.
+    fn save(&mut self, idx: usize) {
+        let Some(draft) = self.candidates.get(idx).map(|c| &c.draft) else {
+            return;
+        };
+        self.status = Some(write_row(&self.corpus_path, draft));
+        if matches!(self.status, Some(Ok(_))) {
+            self.written += 1;
+            self.remove(idx);
+        }
+    }
+
+    /// Drop a candidate, keeping the selection on the line that slides into
+    /// its place.
+    fn remove(&mut self, idx: usize) {
+        if idx < self.candidates.len() {
+            self.candidates.remove(idx);
+        }
+        self.selected = (idx < self.candidates.len())
+            .then_some(idx)
+            .or_else(|| self.candidates.len().checked_sub(1));
+    }
+
+    fn show_table(&mut self, ui: &mut egui::Ui) {
+        if self.candidates.is_empty() {
+            ui.add_space(8.0);
+            ui.label(RichText::new("Nothing left to label.").weak());
+            return;
+        }
+        let nav_changed = super::arrow_nav(ui, &mut self.selected, self.candidates.len());
+        let mut clicked = None;
+        let selected = self.selected;
+        let candidates = &self.candidates;
+
+        let row_height = egui::TextStyle::Body.resolve(ui.style()).size + 8.0;
+        let mut table = TableBuilder::new(ui)
+            .striped(true)
+            .sense(egui::Sense::click())
+            .column(Column::auto().at_least(50.0))
+            .column(Column::initial(360.0).clip(true))
+            .column(Column::remainder().clip(true));
+        if nav_changed && let Some(idx) = selected {
+            table = table.scroll_to_row(idx, Some(egui::Align::Center));
+        }
+        table
+            .header(22.0, |mut header| {
+                for title in ["Score", "Input", "Why"] {
+                    header.col(|ui| {
+                        ui.label(RichText::new(title).strong());
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(row_height, candidates.len(), |mut table_row| {
+                    let idx = table_row.index();
+                    let c = &candidates[idx];
+                    table_row.set_selected(selected == Some(idx));
+                    table_row.col(|ui| {
+                        ui.label(
+                            RichText::new(format!("{:.1}", c.score))
+                                .color(theme::palette().amount()),
+                        );
+                    });
+                    table_row.col(|ui| {
+                        ui.label(truncate_str(&c.input, 60)).on_hover_text(&c.input);
+                    });
+                    table_row.col(|ui| {
+                        let why: Vec<String> = c.signals.iter().map(ToString::to_string).collect();
+                        ui.label(RichText::new(why.join("; ")).weak());
+                    });
+                    if table_row.response().clicked() {
+                        clicked = Some(idx);
+                    }
+                });
+            });
+
+        if let Some(idx) = clicked {
+            self.selected = Some(idx);
+        }
+    }
+}
+
+/// Validate `draft` and insert its row into the corpus file at `path`.
+fn write_row(path: &str, draft: &Draft) -> Result<String, String> {
+    let row = draft.to_row()?;
+    let source =
+        std::fs::read_to_string(path).map_err(|e| format!("failed to read {path}: {e}"))?;
+    std::fs::write(path, label::insert_row(&source, &draft.section, &row))
+        .map_err(|e| format!("failed to write {path}: {e}"))?;
+    Ok(format!("wrote {:?} to {}", draft.input, draft.section))
+}
+
+/// The selected line's signals and editable label, with Save/Skip buttons.
+fn show_editor(
+    ui: &mut egui::Ui,
+    candidate: &mut Candidate,
+    sections: &[String],
+) -> Option<EditorAction> {
+    let mut action = None;
+    theme::card(ui, |ui| {
+        ui.label(RichText::new(&candidate.input).monospace().strong());
+        for signal in &candidate.signals {
+            ui.label(RichText::new(format!("· {signal}")).weak());
+        }
+        ui.separator();
+
+        let draft = &mut candidate.draft;
+        egui::Grid::new("label_fields")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("name");
+                ui.text_edit_singleline(&mut draft.name);
+                ui.end_row();
+
+                ui.label("amounts");
+                ui.add(
+                    egui::TextEdit::singleline(&mut draft.amounts)
+                        .desired_width(f32::INFINITY)
+                        .font(egui::TextStyle::Monospace),
+                );
+                ui.end_row();
+
+                ui.label("modifier");
+                ui.text_edit_singleline(&mut draft.modifier);
+                ui.end_row();
+
+                ui.label("optional");
+                ui.checkbox(&mut draft.optional, "");
+                ui.end_row();
+
+                ui.label("usage");
+                egui::ComboBox::from_id_salt("label_usage")
+                    .selected_text(format!("{:?}", draft.usage))
+                    .show_ui(ui, |ui| {
+                        for usage in label::USAGES {
+                            ui.selectable_value(&mut draft.usage, usage, format!("{usage:?}"));
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("section");
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("label_section")
+                        .selected_text(truncate_str(&draft.section, 40))
+                        .show_ui(ui, |ui| {
+                            for section in sections {
+                                ui.selectable_value(
+                                    &mut draft.section,
+                                    section.clone(),
+                                    truncate_str(section, 60),
+                                );
+                            }
+                        });
+                    ui.add(
+                        egui::TextEdit::singleline(&mut draft.section)
+                            .hint_text("or a new section"),
+                    );
+                });
+                ui.end_row();
+
+                ui.label("xfail");
+                ui.horizontal(|ui| {
+                    let mut known_gap = draft.xfail.is_some();
+                    if ui.checkbox(&mut known_gap, "").changed() {
+                        draft.xfail = known_gap.then(String::new);
+                    }
+                    if let Some(reason) = &mut draft.xfail {
+                        ui.add(egui::TextEdit::singleline(reason).hint_text("why it fails"));
+                    }
+                });
+                ui.end_row();
+            });
+
+        ui.separator();
+        match draft.to_json() {
+            Ok(row) => ui.label(RichText::new(row).monospace().small()),
+            Err(e) => ui.colored_label(theme::palette().trace_fail(), e),
+        };
+        ui.horizontal(|ui| {
+            if ui.button("💾 Save row").clicked() {
+                action = Some(EditorAction::Save);
+            }
+            if ui.button("Skip").clicked() {
+                action = Some(EditorAction::Skip);
+            }
+        });
+    });
+    action
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A loaded tab over a scratch corpus and line dump, both written to a
+    /// fresh temp directory.
+    fn loaded(name: &str, lines: &str) -> (LabelTab, std::path::PathBuf) {
+        let dir =
+            std::env::temp_dir().join(format!("food-app-label-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let corpus = dir.join("corpus.jsonl");
+        std::fs::write(
+            &corpus,
+            "// --- basics ---\n{\"input\": \"1 cup flour\", \"name\": \"flour\", \"amounts\": [{\"unit\": \"cup\", \"value\": 1}]}\n",
+        )
+        .unwrap();
+        let dump = dir.join("lines.txt");
+        std::fs::write(&dump, lines).unwrap();
+        let mut tab = LabelTab {
+            lines_path: dump.display().to_string(),
+            corpus_path: corpus.display().to_string(),
+            ..Default::default()
+        };
+        tab.load();
+        assert!(tab.load_error.is_none(), "{:?}", tab.load_error);
+        (tab, corpus)
+    }
+
+    #[test]
+    fn save_writes_the_row_and_moves_on() {
+        let (mut tab, corpus) = loaded("save", "2 cups sugar\n3 eggs\n");
+        assert_eq!(tab.candidates.len(), 2);
+        assert_eq!(tab.sections, ["basics"]);
+        let input = tab.candidates[0].input.clone();
+
+        tab.save(0);
+        assert!(matches!(tab.status, Some(Ok(_))), "{:?}", tab.status);
+        assert_eq!(tab.written, 1);
+        assert_eq!(tab.candidates.len(), 1);
+        assert_eq!(tab.selected, Some(0));
+
+        let written = ingredient_corpus::read(&corpus).unwrap();
+        assert!(written.rows().any(|r| r.input == input));
+        let _ = std::fs::remove_dir_all(corpus.parent().unwrap());
+    }
+
+    #[test]
+    fn a_disagreeing_label_is_refused_and_kept() {
+        let (mut tab, corpus) = loaded("refuse", "2 cups sugar\n");
+        tab.candidates[0].draft.name = "brown sugar".to_string();
+
+        tab.save(0);
+        assert!(matches!(tab.status, Some(Err(_))), "{:?}", tab.status);
+        assert_eq!(tab.candidates.len(), 1);
+        assert_eq!(ingredient_corpus::read(&corpus).unwrap().rows().count(), 1);
+        let _ = std::fs::remove_dir_all(corpus.parent().unwrap());
+    }
+}
@@ -1,12 +1,14 @@
 mod cookbook;
 mod corpus;
 mod debug;
+mod label;
 mod recipe;
 mod test;
 
 pub use cookbook::CookbookTab;
 pub use corpus::{CorpusAction, CorpusTab};
-pub use debug::show_debug_tab;
+pub use debug::{TraceCompare, show_debug_tab};
+pub use label::LabelTab;
 pub use recipe::{show_parsed, show_raw};
 pub use test::TestTab;
 
@@ -76,6 +76,9 @@ pub(crate) fn show_instruction_chunks(ui: &mut egui::Ui, chunks: &[ingredient::r
                             ui.label(RichText::new(m.to_string()).color(theme::palette().amount()));
                         }
                     }
+                    ingredient::rich_text::Chunk::Dimension(d) => {
+                        ui.label(RichText::new(d.to_string()).color(theme::palette().amount()));
+                    }
                     ingredient::rich_text::Chunk::Text(t) => {
                         ui.label(t);
                     }
@@ -291,6 +291,7 @@ pub mod icon {
     pub const DEBUG: &str = icons::ICON_SEARCH.codepoint;
     pub const COOKBOOK: &str = icons::ICON_AUTO_STORIES.codepoint;
     pub const CORPUS: &str = icons::ICON_FACT_CHECK.codepoint;
+    pub const LABEL: &str = icons::ICON_NEW_LABEL.codepoint;
     pub const YIELD: &str = icons::ICON_SCALE.codepoint;
     pub const SERVINGS: &str = icons::ICON_RESTAURANT.codepoint;
     pub const TIME: &str = icons::ICON_SCHEDULE.codepoint;
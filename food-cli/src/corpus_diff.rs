@@ -0,0 +1,346 @@
+//! `corpus diff` — how the parser's output moved between two configurations.
+//!
+//! [`ingredient_corpus::score`] says whether labeled rows still match; this
+//! says what changed, on any line set: xfail rows, unlabeled harvests from
+//! `parse-lines` or `scrape-epub --dump-parsed`, plain text. Each side is a
+//! [`snapshot`] of one configuration's outputs, either parsed now (a
+//! different [`ParserProfile`](ingredient::ParserProfile)) or loaded from a
+//! JSONL file saved earlier ([`to_jsonl`] / [`parse_snapshot`]), so "before
+//! my change" can be captured once and compared against after.
+//!
+//! A line is reported when one of its fields changed, filed under every pass
+//! that newly fired or stopped firing on it (from the [`StageReport`]), so a
+//! pass edit's blast radius reads as one group.
+//!
+//! [`StageReport`]: ingredient::trace::StageReport
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use ingredient::{IngredientParser, IngredientUsage};
+use ingredient_corpus::LabeledField;
+use ingredient_corpus::coverage::fired_passes;
+use serde::{Deserialize, Serialize};
+use tabled::{builder::Builder, settings::Style};
+
+/// The group for changed lines whose passes didn't move.
+pub const NO_PASS_CHANGE: &str = "(no pass change)";
+
+/// One line's parse under one configuration: the labeled fields, amounts
+/// rendered as the parser displays them, and the passes that fired. One row
+/// of a snapshot file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LineOutput {
+    pub input: String,
+    pub name: String,
+    /// The amounts' `Display`, comma-joined (qualifiers included).
+    pub amounts: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modifier: Option<String>,
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(default)]
+    pub usage: IngredientUsage,
+    /// `stage:pass` for each pass that fired, e.g. `refine:extract_adjectives_from_name`.
+    #[serde(default)]
+    pub passes: Vec<String>,
+}
+
+impl LineOutput {
+    /// The compared fields, rendered, in corpus order.
+    fn fields(&self) -> [(LabeledField, String); 5] {
+        [
+            (LabeledField::Name, format!("{:?}", self.name)),
+            (LabeledField::Amounts, format!("[{}]", self.amounts)),
+            (LabeledField::Modifier, format!("{:?}", self.modifier)),
+            (LabeledField::Optional, self.optional.to_string()),
+            (LabeledField::Usage, format!("{:?}", self.usage)),
+        ]
+    }
+}
+
+/// Parse every line through the traced path under `parser`.
+pub fn snapshot(parser: &IngredientParser, lines: &[String]) -> Vec<LineOutput> {
+    lines
+        .iter()
+        .map(|input| {
+            let traced = parser.parse_with_trace(input);
+            // The traced path keeps `from_str`'s fallback, so `result` is never
+            // an error in practice; an empty output still diffs sensibly.
+            let ingredient = traced.result.unwrap_or_default();
+            let passes = fired_passes(&traced.trace.stages())
+                .into_iter()
+                .flat_map(|(stage, names)| names.into_iter().map(move |n| format!("{stage}:{n}")))
+                .collect();
+            LineOutput {
+                input: input.clone(),
+                name: ingredient.name,
+                amounts: ingredient
+                    .amounts
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                modifier: ingredient.modifier,
+                optional: ingredient.optional,
+                usage: ingredient.usage,
+                passes,
+            }
+        })
+        .collect()
+}
+
+/// Serialize a snapshot as JSONL, one [`LineOutput`] per line.
+pub fn to_jsonl(outputs: &[LineOutput]) -> String {
+    outputs
+        .iter()
+        .filter_map(|o| serde_json::to_string(o).ok())
+        .map(|row| row + "\n")
+        .collect()
+}
+
+/// Load a snapshot saved by [`to_jsonl`]. Blank lines are skipped; a
+/// malformed row is an error naming its line number.
+pub fn parse_snapshot(text: &str) -> Result<Vec<LineOutput>, String> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| serde_json::from_str(line).map_err(|e| format!("line {}: {e}", i + 1)))
+        .collect()
+}
+
+/// One field that differs between the two sides.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: LabeledField,
+    pub before: String,
+    pub after: String,
+}
+
+/// A line whose output changed, and how its passes moved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineChange {
+    pub input: String,
+    pub changes: Vec<FieldChange>,
+    /// Passes firing now that didn't before.
+    pub fired: Vec<String>,
+    /// Passes that fired before and no longer do.
+    pub stopped: Vec<String>,
+}
+
+/// The result of comparing two snapshots.
+#[derive(Debug, Clone, Default)]
+pub struct DiffReport {
+    /// Lines present on both sides.
+    pub compared: usize,
+    /// Lines with at least one changed field.
+    pub changed: Vec<LineChange>,
+    /// Lines whose passes moved but whose output didn't.
+    pub passes_only: usize,
+    /// Candidate lines the baseline has no output for.
+    pub unmatched: Vec<String>,
+}
+
+impl DiffReport {
+    /// The changed lines grouped by pass movement: `+stage:pass` for a pass
+    /// that newly fired, `-stage:pass` for one that stopped, and
+    /// [`NO_PASS_CHANGE`] for lines whose passes didn't move. A line that
+    /// moved several passes is listed under each.
+    pub fn by_pass(&self) -> BTreeMap<String, Vec<&LineChange>> {
+        let mut groups: BTreeMap<String, Vec<&LineChange>> = BTreeMap::new();
+        for line in &self.changed {
+            let keys: Vec<String> = line
+                .fired
+                .iter()
+                .map(|p| format!("+{p}"))
+                .chain(line.stopped.iter().map(|p| format!("-{p}")))
+                .collect();
+            if keys.is_empty() {
+                groups
+                    .entry(NO_PASS_CHANGE.to_string())
+                    .or_default()
+                    .push(line);
+            }
+            for key in keys {
+                groups.entry(key).or_default().push(line);
+            }
+        }
+        groups
+    }
+}
+
+/// Compare the candidate `after` against the `baseline`, matching lines by
+/// input.
+pub fn diff(baseline: &[LineOutput], after: &[LineOutput]) -> DiffReport {
+    let before: HashMap<&str, &LineOutput> =
+        baseline.iter().map(|o| (o.input.as_str(), o)).collect();
+    let mut report = DiffReport::default();
+    for now in after {
+        let Some(was) = before.get(now.input.as_str()) else {
+            report.unmatched.push(now.input.clone());
+            continue;
+        };
+        report.compared += 1;
+        let changes: Vec<FieldChange> = was
+            .fields()
+            .into_iter()
+            .zip(now.fields())
+            .filter(|((_, b), (_, a))| b != a)
+            .map(|((field, before), (_, after))| FieldChange {
+                field,
+                before,
+                after,
+            })
+            .collect();
+        let was_passes: BTreeSet<&String> = was.passes.iter().collect();
+        let now_passes: BTreeSet<&String> = now.passes.iter().collect();
+        let fired: Vec<String> = now_passes
+            .difference(&was_passes)
+            .map(|p| p.to_string())
+            .collect();
+        let stopped: Vec<String> = was_passes
+            .difference(&now_passes)
+            .map(|p| p.to_string())
+            .collect();
+        if changes.is_empty() {
+            report.passes_only += usize::from(!fired.is_empty() || !stopped.is_empty());
+            continue;
+        }
+        report.changed.push(LineChange {
+            input: now.input.clone(),
+            changes,
+            fired,
+            stopped,
+        });
+    }
+    report
+}
+
+/// One-line summary of a report.
+pub fn summary(report: &DiffReport) -> String {
+    let mut out = format!(
+        "{} line(s) compared · {} changed · {} moved passes only",
+        report.compared,
+        report.changed.len(),
+        report.passes_only
+    );
+    if !report.unmatched.is_empty() {
+        out += &format!(" · {} not in the baseline", report.unmatched.len());
+    }
+    out
+}
+
+/// Render the report as text: the summary, then one input/field/before/after
+/// table per pass group.
+pub fn render_table(report: &DiffReport) -> String {
+    use std::fmt::Write as _;
+
+    let mut out = String::new();
+    // Writing into a String is infallible; see `corpus_lint::render_report`.
+    let _ = writeln!(out, "{}", summary(report));
+    for (group, lines) in report.by_pass() {
+        let mut b = Builder::default();
+        b.push_record(["input", "field", "before", "after"]);
+        for line in &lines {
+            for change in &line.changes {
+                b.push_record([
+                    line.input.clone(),
+                    change.field.as_str().to_string(),
+                    change.before.clone(),
+                    change.after.clone(),
+                ]);
+            }
+        }
+        let _ = writeln!(
+            out,
+            "\n{group} ({} line(s))\n{}",
+            lines.len(),
+            b.build().with(Style::rounded())
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn output(input: &str, name: &str, passes: &[&str]) -> LineOutput {
+        LineOutput {
+            input: input.to_string(),
+            name: name.to_string(),
+            amounts: "2 cups".to_string(),
+            modifier: None,
+            optional: false,
+            usage: IngredientUsage::Normal,
+            passes: passes.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn groups_changed_fields_by_pass_movement() {
+        let before = [
+            output("2 cups chopped walnuts", "chopped walnuts", &[]),
+            output("2 cups flour", "flour", &["refine:a"]),
+            output("2 cups sugar", "sugar", &["refine:a"]),
+        ];
+        let after = [
+            output("2 cups chopped walnuts", "walnuts", &["refine:b"]),
+            output("2 cups flour", "flour", &[]),
+            output("2 cups sugar", "Sugar", &["refine:a"]),
+            output("1 egg", "egg", &[]),
+        ];
+        let report = diff(&before, &after);
+        assert_eq!(report.compared, 3);
+        assert_eq!(report.changed.len(), 2);
+        assert_eq!(report.passes_only, 1);
+        assert_eq!(report.unmatched, vec!["1 egg"]);
+
+        let groups = report.by_pass();
+        let inputs =
+            |key: &str| -> Vec<&str> { groups[key].iter().map(|l| l.input.as_str()).collect() };
+        assert_eq!(inputs("+refine:b"), vec!["2 cups chopped walnuts"]);
+        assert_eq!(inputs(NO_PASS_CHANGE), vec!["2 cups sugar"]);
+        let change = &report.changed[0].changes[0];
+        assert_eq!(change.field, LabeledField::Name);
+        assert_eq!(
+            (change.before.as_str(), change.after.as_str()),
+            ("\"chopped walnuts\"", "\"walnuts\"")
+        );
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_jsonl() {
+        let lines = vec!["2 cups chopped walnuts".to_string(), "salt".to_string()];
+        let outputs = snapshot(&IngredientParser::new(), &lines);
+        assert!(
+            outputs[0]
+                .passes
+                .contains(&"refine:extract_adjectives_from_name".to_string())
+        );
+        let back = parse_snapshot(&to_jsonl(&outputs)).unwrap();
+        assert_eq!(back, outputs);
+        assert!(diff(&outputs, &back).changed.is_empty());
+    }
+
+    #[test]
+    fn profiles_diff_over_the_same_lines() {
+        let mut profile = ingredient::ParserProfile::default();
+        profile.preparation_adjectives.retain(|a| a != "chopped");
+        let lines = vec!["2 cups chopped walnuts".to_string()];
+        let before = snapshot(&IngredientParser::new(), &lines);
+        let after = snapshot(&IngredientParser::new().with_profile(&profile), &lines);
+        let report = diff(&before, &after);
+        assert_eq!(report.changed.len(), 1);
+        let table = render_table(&report);
+        assert!(table.contains("1 line(s) compared · 1 changed"));
+        assert!(table.contains("-refine:extract_adjectives_from_name"));
+        assert!(table.contains("\"chopped walnuts\""));
+    }
+
+    #[test]
+    fn malformed_snapshot_names_the_line() {
+        let err = parse_snapshot("\n{not json").unwrap_err();
+        assert!(err.starts_with("line 2:"), "{err}");
+    }
+}
@@ -0,0 +1,148 @@
+//! `corpus profile` — which stage, rewrite, recognizer or refine pass costs
+//! the most across a line set.
+//!
+//! Runs the lines through [`IngredientParser::profile_passes`] and renders the
+//! [`PerfReport`]: time per stage, then the costliest passes with their call
+//! and fire counts, then the regexes by use count. The report's folded
+//! stacks ([`PerfReport::folded`]) feed `flamegraph.pl` or
+//! `inferno-flamegraph` for the same data as a flame graph.
+
+use ingredient::IngredientParser;
+use ingredient::trace::perf::PerfReport;
+use tabled::{builder::Builder, settings::Style};
+
+/// Profile `repeat` passes over `lines` (at least one). More passes steady the
+/// timings; the regex compiles land in the first.
+pub fn profile(parser: &IngredientParser, lines: &[String], repeat: usize) -> PerfReport {
+    let passes = repeat.max(1);
+    parser.profile_passes(
+        std::iter::repeat_n(lines, passes)
+            .flatten()
+            .map(String::as_str),
+    )
+}
+
+/// Microseconds, to one decimal place.
+fn micros(nanos: u64) -> String {
+    format!("{:.1}", nanos as f64 / 1000.0)
+}
+
+/// Nanoseconds per call; `-` for a pass that never ran.
+fn per_call(nanos: u64, calls: u64) -> String {
+    nanos
+        .checked_div(calls)
+        .map_or_else(|| "-".to_string(), |n| n.to_string())
+}
+
+/// Render the report: a summary line, every stage, the `top` costliest passes
+/// and the `top` most-used regexes.
+///
+/// Returns the text rather than printing it (see `lib.rs`).
+pub fn render_report(report: &PerfReport, top: usize) -> String {
+    use std::fmt::Write as _;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{} line(s) parsed in {} ms ({} µs/line)\n",
+        report.lines,
+        report.total_nanos / 1_000_000,
+        micros(report.total_nanos / report.lines.max(1) as u64)
+    );
+
+    let mut b = Builder::default();
+    b.push_record(["stage", "calls", "total µs", "self µs", "ns/call"]);
+    for s in &report.stages {
+        b.push_record([
+            s.name.to_string(),
+            s.calls.to_string(),
+            micros(s.total_nanos),
+            micros(s.self_nanos),
+            per_call(s.total_nanos, s.calls),
+        ]);
+    }
+    let _ = writeln!(out, "stages\n{}\n", b.build().with(Style::rounded()));
+
+    let mut b = Builder::default();
+    b.push_record([
+        "stage",
+        "pass",
+        "calls",
+        "fired",
+        "total µs",
+        "self µs",
+        "ns/call",
+    ]);
+    for p in report.passes.iter().take(top) {
+        b.push_record([
+            p.stage.to_string(),
+            p.name.to_string(),
+            p.calls.to_string(),
+            p.fired.to_string(),
+            micros(p.total_nanos),
+            micros(p.self_nanos),
+            per_call(p.total_nanos, p.calls),
+        ]);
+    }
+    let _ = writeln!(
+        out,
+        "passes, costliest first\n{}\n",
+        b.build().with(Style::rounded())
+    );
+    if report.passes.len() > top {
+        let _ = writeln!(
+            out,
+            "  … {} cheaper pass(es) not shown\n",
+            report.passes.len() - top
+        );
+    }
+
+    let mut b = Builder::default();
+    b.push_record(["regex", "compiles", "compile µs", "uses"]);
+    for r in report.regexes.iter().take(top) {
+        b.push_record([
+            r.name.to_string(),
+            r.compiles.to_string(),
+            micros(r.compile_nanos),
+            r.uses.to_string(),
+        ]);
+    }
+    let _ = writeln!(out, "regexes\n{}", b.build().with(Style::rounded()));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn lines() -> Vec<String> {
+        ["2 cups flour", "butter (see note)", "Juice of 1 lemon"]
+            .map(String::from)
+            .to_vec()
+    }
+
+    #[test]
+    fn repeats_the_line_set() {
+        let report = profile(&IngredientParser::new(), &lines(), 3);
+        assert_eq!(report.lines, 9);
+        let root = report
+            .stages
+            .iter()
+            .find(|s| s.name == "parse_line")
+            .unwrap();
+        assert_eq!(root.calls, 9);
+    }
+
+    #[test]
+    fn report_lists_stages_passes_and_regexes() {
+        let report = profile(&IngredientParser::new(), &lines(), 1);
+        let text = render_report(&report, 5);
+        assert!(text.starts_with("3 line(s) parsed in"), "{text}");
+        assert!(text.contains("parse_line"));
+        assert!(text.contains("passes, costliest first"));
+        assert!(text.contains("cheaper pass(es) not shown"));
+        assert!(text.contains("regexes"));
+    }
+}
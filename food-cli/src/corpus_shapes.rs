@@ -0,0 +1,317 @@
+//! `corpus shapes` — cluster the accuracy corpus by token-shape and find the
+//! shapes it lacks.
+//!
+//! Every row is reduced to its [`LineShape`] signature (amount pattern, clause
+//! kinds, paren kinds, recognizer; see [`ingredient::shape`]), and rows sharing
+//! a signature form a [`Cluster`]. The report answers three questions:
+//! - **where is the corpus thick?** per-cluster row counts and `xfail` ratios;
+//! - **what is a near-copy?** pairs in one cluster whose words also mostly
+//!   overlap ([`NEAR_DUPLICATE`]) — the second row guards nothing the first
+//!   doesn't;
+//! - **what is missing?** with a harvested line dump, the shapes no row has,
+//!   most frequent first, each with a few example lines to label.
+//!
+//! [`LineShape`]: ingredient::shape::LineShape
+
+use std::collections::{BTreeMap, HashSet};
+
+use ingredient::IngredientParser;
+use ingredient::section::{LineKind, classify_line};
+use ingredient_corpus::Corpus;
+use ingredient_corpus::label::{similarity, words};
+use tabled::{builder::Builder, settings::Style};
+
+/// Two rows of one cluster sharing at least this share of their words are
+/// near-duplicates.
+pub const NEAR_DUPLICATE: f64 = 0.8;
+
+/// Example lines listed per proposed shape.
+pub const PROPOSAL_EXAMPLES: usize = 3;
+
+/// Corpus rows sharing one shape signature.
+pub struct Cluster {
+    pub signature: String,
+    /// `(line_no, input)` of each row, in corpus order.
+    pub members: Vec<(usize, String)>,
+    /// How many of [`Cluster::members`] are `xfail` rows.
+    pub xfail: usize,
+}
+
+impl Cluster {
+    /// The share of rows that are known gaps, 0.0–1.0.
+    pub fn xfail_ratio(&self) -> f64 {
+        if self.members.is_empty() {
+            0.0
+        } else {
+            self.xfail as f64 / self.members.len() as f64
+        }
+    }
+}
+
+/// Two rows of one cluster with nearly the same words.
+pub struct NearDuplicate {
+    pub signature: String,
+    /// `(line_no, input)` of the earlier row.
+    pub first: (usize, String),
+    /// `(line_no, input)` of the later row.
+    pub second: (usize, String),
+    pub similarity: f64,
+}
+
+/// A shape the harvest has and the corpus doesn't.
+pub struct Proposal {
+    pub signature: String,
+    /// How many distinct harvested lines have this shape.
+    pub lines: usize,
+    /// Up to [`PROPOSAL_EXAMPLES`] of them, in harvest order.
+    pub examples: Vec<String>,
+}
+
+/// The analysis [`render_report`] prints.
+pub struct ShapeReport {
+    pub rows: usize,
+    /// Largest cluster first; ties by signature.
+    pub clusters: Vec<Cluster>,
+    /// Most similar pair first.
+    pub near_duplicates: Vec<NearDuplicate>,
+    /// Most frequent shape first; empty without a harvest.
+    pub proposals: Vec<Proposal>,
+}
+
+/// Cluster `corpus` by shape, pair up its near-duplicates, and propose the
+/// shapes of `harvest` it doesn't cover. Harvest lines that aren't ingredient
+/// lines, or are already corpus rows, are skipped.
+pub fn analyze(corpus: &Corpus, harvest: &[String]) -> ShapeReport {
+    let parser = IngredientParser::new();
+
+    let mut by_signature: BTreeMap<String, Cluster> = BTreeMap::new();
+    let mut rows = 0;
+    for entry in &corpus.entries {
+        let Ok(row) = &entry.parsed else { continue };
+        rows += 1;
+        let signature = parser.shape(&row.input).signature();
+        let cluster = by_signature
+            .entry(signature.clone())
+            .or_insert_with(|| Cluster {
+                signature,
+                members: Vec::new(),
+                xfail: 0,
+            });
+        cluster.members.push((entry.line_no, row.input.clone()));
+        if row.xfail.is_some() {
+            cluster.xfail += 1;
+        }
+    }
+
+    let mut near_duplicates = Vec::new();
+    for cluster in by_signature.values() {
+        let members: Vec<_> = cluster.members.iter().map(|m| (m, words(&m.1))).collect();
+        for (i, (a, a_words)) in members.iter().enumerate() {
+            for (b, b_words) in &members[i + 1..] {
+                let sim = similarity(a_words, b_words);
+                if sim >= NEAR_DUPLICATE {
+                    near_duplicates.push(NearDuplicate {
+                        signature: cluster.signature.clone(),
+                        first: (*a).clone(),
+                        second: (*b).clone(),
+                        similarity: sim,
+                    });
+                }
+            }
+        }
+    }
+    near_duplicates.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+
+    let mut seen: HashSet<&str> = corpus.rows().map(|r| r.input.as_str()).collect();
+    let mut uncovered: BTreeMap<String, Proposal> = BTreeMap::new();
+    for line in harvest {
+        let line = line.trim();
+        if classify_line(line) != LineKind::Ingredient || !seen.insert(line) {
+            continue;
+        }
+        let signature = parser.shape(line).signature();
+        if by_signature.contains_key(&signature) {
+            continue;
+        }
+        let proposal = uncovered
+            .entry(signature.clone())
+            .or_insert_with(|| Proposal {
+                signature,
+                lines: 0,
+                examples: Vec::new(),
+            });
+        proposal.lines += 1;
+        if proposal.examples.len() < PROPOSAL_EXAMPLES {
+            proposal.examples.push(line.to_string());
+        }
+    }
+    let mut proposals: Vec<Proposal> = uncovered.into_values().collect();
+    proposals.sort_by_key(|p| std::cmp::Reverse(p.lines));
+
+    let mut clusters: Vec<Cluster> = by_signature.into_values().collect();
+    clusters.sort_by_key(|c| std::cmp::Reverse(c.members.len()));
+
+    ShapeReport {
+        rows,
+        clusters,
+        near_duplicates,
+        proposals,
+    }
+}
+
+/// Render the report: a summary line, then the `top` largest clusters, every
+/// near-duplicate pair, and the `top` proposals.
+///
+/// Returns the text rather than printing it (see `lib.rs`).
+pub fn render_report(report: &ShapeReport, top: usize) -> String {
+    use std::fmt::Write as _;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{} corpus row(s) in {} shape(s); {} near-duplicate pair(s)\n",
+        report.rows,
+        report.clusters.len(),
+        report.near_duplicates.len()
+    );
+
+    let mut b = Builder::default();
+    b.push_record(["rows", "xfail", "xfail %", "shape", "example"]);
+    for c in report.clusters.iter().take(top) {
+        b.push_record([
+            c.members.len().to_string(),
+            c.xfail.to_string(),
+            format!("{:.0}", 100.0 * c.xfail_ratio()),
+            c.signature.clone(),
+            c.members
+                .first()
+                .map(|(_, input)| input.clone())
+                .unwrap_or_default(),
+        ]);
+    }
+    let _ = writeln!(out, "clusters\n{}\n", b.build().with(Style::rounded()));
+    if report.clusters.len() > top {
+        let _ = writeln!(
+            out,
+            "  … {} smaller cluster(s) not shown\n",
+            report.clusters.len() - top
+        );
+    }
+
+    if !report.near_duplicates.is_empty() {
+        let mut b = Builder::default();
+        b.push_record(["similarity", "line", "row", "line", "near-copy"]);
+        for d in &report.near_duplicates {
+            b.push_record([
+                format!("{:.2}", d.similarity),
+                d.first.0.to_string(),
+                d.first.1.clone(),
+                d.second.0.to_string(),
+                d.second.1.clone(),
+            ]);
+        }
+        let _ = writeln!(
+            out,
+            "near-duplicates\n{}\n",
+            b.build().with(Style::rounded())
+        );
+    }
+
+    if !report.proposals.is_empty() {
+        let mut b = Builder::default();
+        b.push_record(["lines", "uncovered shape", "examples"]);
+        for p in report.proposals.iter().take(top) {
+            b.push_record([
+                p.lines.to_string(),
+                p.signature.clone(),
+                p.examples.join("\n"),
+            ]);
+        }
+        let _ = writeln!(
+            out,
+            "worth labeling (shapes no corpus row has)\n{}",
+            b.build().with(Style::rounded())
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn corpus() -> Corpus {
+        ingredient_corpus::parse(
+            r#"// --- basics ---
+{"input": "1 cup flour", "name": "flour", "amounts": [{"unit": "cup", "value": 1}]}
+{"input": "2 cups flour", "name": "flour", "amounts": [{"unit": "cup", "value": 2}]}
+{"input": "3 tbsp sugar", "name": "sugar", "amounts": [{"unit": "tbsp", "value": 3}]}
+{"input": "juice of 1 lemon", "name": "lemon juice", "amounts": [{"unit": "whole", "value": 1}], "xfail": "made up"}
+"#,
+        )
+    }
+
+    #[test]
+    fn clusters_rows_by_shape_and_counts_xfail() {
+        let report = analyze(&corpus(), &[]);
+        assert_eq!(report.rows, 4);
+        let volume = &report.clusters[0];
+        assert_eq!(volume.signature, "N volume | head_candidate | - | -");
+        assert_eq!(volume.members.len(), 3);
+        assert_eq!(volume.xfail, 0);
+        let lemon = &report.clusters[1];
+        assert_eq!(lemon.members, [(5, "juice of 1 lemon".to_string())]);
+        assert_eq!(lemon.xfail_ratio(), 1.0);
+    }
+
+    #[test]
+    fn flags_same_shape_rows_with_the_same_words() {
+        let report = analyze(&corpus(), &[]);
+        assert_eq!(report.near_duplicates.len(), 1);
+        let pair = &report.near_duplicates[0];
+        assert_eq!(pair.first, (2, "1 cup flour".to_string()));
+        assert_eq!(pair.second, (3, "2 cups flour".to_string()));
+    }
+
+    #[test]
+    fn proposes_uncovered_harvest_shapes_by_frequency() {
+        let harvest: Vec<String> = [
+            "INGREDIENTS",
+            "1 cup flour",
+            "4 cups milk",
+            "salt, to taste",
+            "2 onions, diced",
+            "3 carrots, peeled",
+            "3 carrots, peeled",
+        ]
+        .map(String::from)
+        .to_vec();
+        let report = analyze(&corpus(), &harvest);
+        let shapes: Vec<(&str, usize)> = report
+            .proposals
+            .iter()
+            .map(|p| (p.signature.as_str(), p.lines))
+            .collect();
+        assert_eq!(
+            shapes[0],
+            ("N count | head_candidate, prep_chain | - | -", 2)
+        );
+        assert_eq!(shapes.len(), 2, "{shapes:?}");
+        assert_eq!(
+            report.proposals[0].examples,
+            ["2 onions, diced", "3 carrots, peeled"]
+        );
+    }
+
+    #[test]
+    fn report_lists_clusters_pairs_and_proposals() {
+        let report = analyze(&corpus(), &["2 onions, diced".to_string()]);
+        let text = render_report(&report, 10);
+        assert!(text.starts_with("4 corpus row(s) in 2 shape(s); 1 near-duplicate pair(s)"));
+        assert!(text.contains("near-duplicates"));
+        assert!(text.contains("worth labeling"));
+        assert!(text.contains("2 onions, diced"));
+    }
+}
@@ -2,10 +2,13 @@
 //!
 //! The *viewer*: shows rows as the file spells them, via
 //! [`ingredient_corpus::render_authored`]. Deliberately not `Measure`'s
-//! `Display`, which would re-spell them.
+//! `Display`, which would re-spell them. [`render_diff_html`] renders a
+//! [`corpus diff`](crate::corpus_diff) report in the same style.
 
 use ingredient_corpus::{Corpus, CorpusRow, Entry, render_authored};
 
+use crate::corpus_diff::{DiffReport, summary};
+
 const CORPUS_STYLE: &str = "\
 body { font-family: -apple-system, system-ui, sans-serif; margin: 2rem; color: #1a1a1a; }
 h1 { font-size: 1.4rem; }
@@ -18,7 +21,9 @@ tbody tr:nth-child(even) { background: #fafafa; }
 td code { font-family: ui-monospace, monospace; white-space: pre-wrap; }
 tr.xfail, tr.xfail:nth-child(even) { background: #fff8e1; }
 tr.err, tr.err:nth-child(even) { background: #fdecea; }
-.opt { text-align: center; color: #2e7d32; }";
+.opt { text-align: center; color: #2e7d32; }
+td.before { color: #b71c1c; }
+td.after { color: #1b5e20; }";
 
 /// The cells one row contributes. A malformed line still renders, so the
 /// viewer never silently shows a short corpus.
@@ -107,6 +112,49 @@ pub fn render_html(corpus: &Corpus<CorpusRow>) -> (String, usize) {
     (markup.into_string(), total)
 }
 
+/// Render a `corpus diff` report as a self-contained static HTML doc: the
+/// summary, then one `<h2>` + `<table>` per pass group, one row per changed
+/// field.
+pub fn render_diff_html(report: &DiffReport) -> String {
+    use maud::{DOCTYPE, PreEscaped, html};
+
+    let markup = html! {
+        (DOCTYPE)
+        html lang="en" {
+            head {
+                meta charset="utf-8";
+                title { "Ingredient parser corpus diff" }
+                style { (PreEscaped(CORPUS_STYLE)) }
+            }
+            body {
+                h1 { "Ingredient parser corpus diff" }
+                p.summary { (summary(report)) }
+                @for (group, lines) in report.by_pass() {
+                    h2 { (group) " (" (lines.len()) ")" }
+                    table {
+                        thead { tr {
+                            th { "input" } th { "field" } th { "before" } th { "after" }
+                        } }
+                        tbody {
+                            @for line in &lines {
+                                @for change in &line.changes {
+                                    tr {
+                                        td { code { (line.input) } }
+                                        td { (change.field.as_str()) }
+                                        td.before { code { (change.before) } }
+                                        td.after { code { (change.after) } }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+    markup.into_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,4 +194,22 @@ not valid json
         // The malformed line is tolerated and flagged, not dropped.
         assert!(html.contains(r#"class="err""#));
     }
+
+    #[test]
+    fn render_diff_groups_by_pass() {
+        use crate::corpus_diff::{diff, snapshot};
+        let lines = vec!["2 cups chopped walnuts & pecans".to_string()];
+        let mut profile = ingredient::ParserProfile::default();
+        profile.preparation_adjectives.retain(|a| a != "chopped");
+        let before = snapshot(&ingredient::IngredientParser::new(), &lines);
+        let after = snapshot(
+            &ingredient::IngredientParser::new().with_profile(&profile),
+            &lines,
+        );
+        let html = render_diff_html(&diff(&before, &after));
+        assert!(html.contains("1 line(s) compared · 1 changed"));
+        assert!(html.contains("<h2>"));
+        assert!(html.contains("walnuts &amp; pecans"));
+        assert!(html.contains(r#"class="after""#));
+    }
 }
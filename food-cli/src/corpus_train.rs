@@ -0,0 +1,100 @@
+//! `corpus train` — fit the opt-in token tagger ([`ingredient::rerank`]) to the
+//! accuracy corpus.
+//!
+//! Every row's labels (name, modifier, amount units) become a
+//! [`LabeledLine`]; `xfail` rows are included, since their labels describe the
+//! parse we want. Before the final fit, every [`HOLDOUT_EVERY`]th row is held
+//! out and scored against a model trained on the rest, so the report says how
+//! the tagger does on lines it hasn't seen — the training accuracy alone would
+//! flatter it.
+
+use ingredient::IngredientParser;
+use ingredient::rerank::{LabeledLine, TagAccuracy, TokenTagger};
+use ingredient_corpus::{Corpus, CorpusRow};
+
+/// One row in this many is held out for the accuracy estimate.
+pub const HOLDOUT_EVERY: usize = 5;
+
+/// A trained tagger and how well it did.
+pub struct Trained {
+    pub tagger: TokenTagger,
+    /// Accuracy of a model trained without the held-out rows, on those rows.
+    pub held_out: TagAccuracy,
+    /// Accuracy of the final model (trained on every row) on its training rows.
+    pub train: TagAccuracy,
+}
+
+impl Trained {
+    /// Human summary of both accuracy figures.
+    pub fn report(&self) -> String {
+        let line = |label: &str, a: &TagAccuracy| {
+            format!(
+                "{label}: {:.1}% of {} tokens over {} lines ({} skipped: name doesn't align)\n",
+                a.accuracy() * 100.0,
+                a.tokens,
+                a.lines,
+                a.skipped
+            )
+        };
+        line("held-out", &self.held_out) + &line("train", &self.train)
+    }
+}
+
+/// The corpus rows as training lines, in file order.
+pub fn labeled_lines(corpus: &Corpus<CorpusRow>) -> Vec<LabeledLine> {
+    corpus
+        .rows()
+        .map(|row| LabeledLine {
+            input: row.input.clone(),
+            name: row.name.clone(),
+            modifier: row.modifier.clone(),
+            amount_units: row.measures().iter().map(|m| m.unit_as_string()).collect(),
+        })
+        .collect()
+}
+
+/// Estimate held-out accuracy, then train on every row. Deterministic: the
+/// same corpus and epochs always give the same model.
+pub fn train(corpus: &Corpus<CorpusRow>, epochs: usize) -> Trained {
+    let parser = IngredientParser::new();
+    let lines = labeled_lines(corpus);
+    let (fit, held): (Vec<_>, Vec<_>) = lines
+        .iter()
+        .cloned()
+        .enumerate()
+        .partition(|(i, _)| (i + 1) % HOLDOUT_EVERY != 0);
+    let strip = |v: Vec<(usize, LabeledLine)>| v.into_iter().map(|(_, l)| l).collect::<Vec<_>>();
+    let (partial, _) = TokenTagger::train(&parser, &strip(fit), epochs);
+    let held_out = partial.evaluate(&parser, &strip(held));
+    let (tagger, train) = TokenTagger::train(&parser, &lines, epochs);
+    Trained {
+        tagger,
+        held_out,
+        train,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trains_on_a_small_corpus() {
+        let corpus = ingredient_corpus::parse(
+            r#"{"input": "2 cups flour, sifted", "name": "flour", "modifier": "sifted", "amounts": [{"unit": "cups", "value": 2}]}
+{"input": "1 onion, diced", "name": "onion", "modifier": "diced", "amounts": [{"unit": "whole", "value": 1}]}
+{"input": "3 tablespoons butter", "name": "butter", "amounts": [{"unit": "tablespoons", "value": 3}]}
+{"input": "1 cup chicken stock", "name": "chicken stock", "amounts": [{"unit": "cup", "value": 1}]}
+{"input": "2 cups sugar", "name": "sugar", "amounts": [{"unit": "cups", "value": 2}]}
+"#,
+        );
+        let lines = labeled_lines(&corpus);
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0].amount_units, ["cups"]);
+        let trained = train(&corpus, 5);
+        // The fifth row is the held-out one.
+        assert_eq!(trained.held_out.lines, 1);
+        assert_eq!(trained.train.lines, 5);
+        assert!(trained.report().starts_with("held-out: "));
+    }
+}
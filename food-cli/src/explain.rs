@@ -1,24 +1,31 @@
 //! Diagnostic rendering for `parse-ingredient --explain`.
 //!
-//! Two modes, both rendered with miette over the *normalized* string the
-//! grammar parsed:
+//! Two modes, both rendered with miette over the raw line as typed:
 //!
-//! - **Decomposition** (the common case): when the core grammar carved the line,
-//!   [`ingredient::IngredientParser::decompose`] hands us byte spans for each
-//!   amount / name / modifier; we label each one. This shows *how the grammar
-//!   split the input*.
-//! - **Digit caret** (fallback): when a recognizer or the name-only fallback
-//!   produced the result there are no grammar spans, so if a digit was present
-//!   but produced no amount we underline the digit run(s) instead.
+//! - **Decomposition** (the common case): when a recognizer or the grammar
+//!   carved the line, [`ingredient::IngredientParser::decompose`] hands us byte
+//!   spans for each amount / name / modifier / alternative / usage / optional
+//!   note; we label each one. This shows *how the parse split the input*.
+//! - **Digit caret** (fallback): when the name-only fallback produced the
+//!   result there are no spans, so if a digit was present but produced no
+//!   amount we underline the digit run(s) instead.
 //!
 //! miette lives only here — the published `ingredient` crate stays miette-free.
+//!
+//! Two plain-text views sit alongside: [`render_usage`] (why the line got its
+//! usage, for `--explain`) and [`render_conversion`] (each hop of a `convert`
+//! path and the mapping behind it).
 
 use std::ops::Range;
 
+use ingredient::unit::{ConversionStep, Measure, MeasureKind, mapping_target_kind};
+use ingredient::unit_mapping::ParsedUnitMapping;
+use ingredient::usage::{Haystack, UsageExplanation};
 use ingredient::{Confidence, Decomposition, Field, ParseNotes};
 use miette::{
     GraphicalReportHandler, GraphicalTheme, LabeledSpan, MietteDiagnostic, Report, Severity,
 };
+use tabled::{builder::Builder, settings::Style};
 
 /// Unicode vulgar-fraction glyphs the parser treats as part of a quantity, so
 /// `5½` is reported as one span rather than `5` with the `½` orphaned.
@@ -63,6 +70,9 @@ fn field_label(field: Field) -> &'static str {
         Field::Amount => "amount",
         Field::Name => "name",
         Field::Modifier => "modifier",
+        Field::Alternative => "alternative",
+        Field::Usage => "usage",
+        Field::Optional => "optional",
     }
 }
 
@@ -110,12 +120,12 @@ fn decomposition_diagnostic(decomp: &Decomposition, diag: &ParseNotes) -> Miette
         .with_severity(severity)
         .with_labels(labels)
         .with_help(format!(
-            "{} · grammar-stage carve (refine may move prep words — see stage view)",
+            "{} · spans follow the parse, refine moves included — see stage view",
             format_args!("confidence: {:?}", diag.confidence)
         ))
 }
 
-/// The fallback diagnostic when the grammar didn't carve the line: a digit caret
+/// The fallback diagnostic when the parse didn't carve the line: a digit caret
 /// when a number produced no amount, otherwise just the confidence header.
 fn fallback_diagnostic(decomp: &Decomposition, diag: &ParseNotes) -> MietteDiagnostic {
     let mut d = MietteDiagnostic::new(message_for(diag))
@@ -132,9 +142,9 @@ fn fallback_diagnostic(decomp: &Decomposition, diag: &ParseNotes) -> MietteDiagn
     d
 }
 
-/// Render the miette report block for `--explain`. When the grammar carved the
-/// line, labels each amount/name/modifier span; otherwise falls back to a digit
-/// caret. Rendered over `decomp.source` (the normalized line the grammar saw).
+/// Render the miette report block for `--explain`. When the parse carved the
+/// line, labels each field span; otherwise falls back to a digit caret.
+/// Rendered over `decomp.source` (the raw line).
 /// `use_color` mirrors the caller's `IsTerminal` gate.
 pub fn render(decomp: &Decomposition, diag: &ParseNotes, use_color: bool) -> String {
     let d = if decomp.spans.is_empty() {
@@ -156,8 +166,127 @@ pub fn render(decomp: &Decomposition, diag: &ParseNotes, use_color: bool) -> Str
     out
 }
 
+fn haystack_label(haystack: Haystack) -> &'static str {
+    match haystack {
+        Haystack::Modifier => "modifier",
+        Haystack::RawLine => "line",
+        Haystack::Name => "name",
+        Haystack::Section => "section",
+    }
+}
+
+/// Render why `classify_usage` chose the usage: each phrase hit in the order
+/// it was found, the vetoed ones marked, the deciding one last.
+pub fn render_usage(why: &UsageExplanation) -> String {
+    use std::fmt::Write as _;
+
+    let usage = serde_json::to_value(why.usage)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{:?}", why.usage));
+    let mut out = format!("usage: {usage}\n");
+    for m in &why.matches {
+        let mark = if m.vetoed { '✗' } else { '✓' };
+        let _ = write!(
+            out,
+            "  {mark} {:?} in {} {:?}",
+            m.phrase,
+            haystack_label(m.haystack),
+            m.text
+        );
+        if m.vetoed {
+            out.push_str(" — vetoed: a surplus mention (\"plus more …\"), not the row's role");
+        }
+        out.push('\n');
+    }
+    if why.decided_by().is_none() {
+        out.push_str("  no usage phrase in the modifier, line or name\n");
+    }
+    out
+}
+
+/// The conversion target for `convert --to`: a kind (`weight`, `volume`,
+/// `money`, `calories`, …, or `nutrient:g protein`) or else a unit, converted
+/// to through the mappings (`cup`, `g`, `bag`).
+pub fn conversion_target(to: &str) -> MeasureKind {
+    use std::str::FromStr;
+
+    match MeasureKind::from_str(to) {
+        Ok(MeasureKind::Other(unit)) if !to.to_lowercase().starts_with("other:") => {
+            mapping_target_kind(&unit)
+        }
+        Ok(kind) => kind,
+        Err(()) => mapping_target_kind(to),
+    }
+}
+
+/// `x` to six significant figures, without trailing zeros.
+fn sig6(x: f64) -> String {
+    if x == 0.0 || !x.is_finite() {
+        return x.to_string();
+    }
+    let decimals = (5 - x.abs().log10().floor() as i32).max(0) as usize;
+    let s = format!("{x:.decimals$}");
+    if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        s
+    }
+}
+
+/// A bound pair as `lo` or `lo–hi`.
+fn bounds(lo: f64, hi: f64) -> String {
+    if sig6(lo) == sig6(hi) {
+        sig6(lo)
+    } else {
+        format!("{}–{}", sig6(lo), sig6(hi))
+    }
+}
+
+/// Render an explained conversion: `amount → result`, then one row per hop
+/// with its factor (both bounds for a ranged mapping), the amount's bounds
+/// after the hop, and the mapping (and its `@ source`) that supplied the edge.
+/// Hops the engine owns (the tsp↔ml bridge, the pinch/dash rescale) read
+/// `built-in`. `mappings` is the list the graph was built from, in order.
+pub fn render_conversion(
+    amount: &Measure,
+    result: &Measure,
+    steps: &[ConversionStep],
+    mappings: &[ParsedUnitMapping],
+) -> String {
+    let mut out = format!("{amount} → {result}\n");
+    if steps.is_empty() {
+        out.push_str("  no hops: the amount is already in the target's base unit\n");
+        return out;
+    }
+    let pairs: Vec<(Measure, Measure)> = mappings
+        .iter()
+        .map(|m| (m.a.clone(), m.b.clone()))
+        .collect();
+
+    let mut b = Builder::default();
+    b.push_record(["hop", "factor", "amount", "mapping", "source"]);
+    for step in steps {
+        let mapping = step.mapping_index(&pairs).and_then(|i| mappings.get(i));
+        b.push_record([
+            format!("{} → {}", step.from_unit, step.to_unit),
+            format!("×{}", bounds(step.factor, step.factor_upper)),
+            format!("{} {}", bounds(step.value, step.upper_value), step.to_unit),
+            mapping.map_or_else(|| "built-in".to_string(), |m| format!("{} = {}", m.a, m.b)),
+            mapping
+                .and_then(|m| m.source.clone())
+                .unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+    out.push_str(&b.build().with(Style::rounded()).to_string());
+    out.push('\n');
+    out
+}
+
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
+
     use super::*;
     use rstest::rstest;
 
@@ -236,4 +365,74 @@ mod tests {
         assert!(out.contains("part of the name/modifier"));
         assert!(!out.contains("this number didn't become an amount"));
     }
+
+    #[test]
+    fn render_usage_marks_the_vetoed_surplus_mention() {
+        let why = ingredient::usage::explain_usage(
+            "flour",
+            Some("plus more for dusting"),
+            Some("flour, for dusting"),
+            None,
+        );
+        let out = render_usage(&why);
+        assert!(out.starts_with("usage: dredging"), "{out}");
+        assert!(out.contains("✗ \"for dusting\" in modifier"), "{out}");
+        assert!(out.contains("vetoed"));
+        assert!(out.contains("✓ \"for dusting\" in line"), "{out}");
+    }
+
+    #[test]
+    fn render_usage_says_when_nothing_matched() {
+        let why = ingredient::usage::explain_usage("flour", None, Some("2 cups flour"), None);
+        let out = render_usage(&why);
+        assert!(out.starts_with("usage: normal"));
+        assert!(out.contains("no usage phrase"));
+    }
+
+    #[rstest]
+    #[case("weight", MeasureKind::Weight)]
+    #[case("Volume", MeasureKind::Volume)]
+    #[case("nutrient:g protein", MeasureKind::Nutrient("g protein".to_string()))]
+    // A unit converts through the mapping graph's node for it: cups live at tsp.
+    #[case("cup", MeasureKind::Other("tsp".to_string()))]
+    #[case("other:bag", MeasureKind::Other("bag".to_string()))]
+    fn parses_conversion_targets(#[case] to: &str, #[case] expected: MeasureKind) {
+        assert_eq!(conversion_target(to), expected);
+    }
+
+    #[test]
+    fn render_conversion_names_each_edge_and_its_source() {
+        use ingredient::unit::{convert_measure_with_graph_explained, make_graph};
+        use ingredient::unit_mapping::parse_unit_mapping;
+
+        let mappings = vec![
+            parse_unit_mapping("1 cup = 120 g @ king arthur").unwrap(),
+            parse_unit_mapping("1 lb = $5").unwrap(),
+        ];
+        let pairs: Vec<_> = mappings
+            .iter()
+            .map(|m| (m.a.clone(), m.b.clone()))
+            .collect();
+        let amount = Measure::new("ml", 100.0);
+        let (result, steps) = convert_measure_with_graph_explained(
+            &amount,
+            conversion_target("money"),
+            &make_graph(&pairs),
+        )
+        .unwrap();
+        let out = render_conversion(&amount, &result, &steps, &mappings);
+
+        // ml → tsp (bridge) → g (cup mapping) → $ (price mapping)
+        assert_eq!(steps.len(), 3);
+        assert!(out.contains("built-in"), "{out}");
+        assert!(out.contains("king arthur"), "{out}");
+        assert!(out.contains("1 lb = $5"), "{out}");
+    }
+
+    #[test]
+    fn sig6_trims_to_six_significant_figures() {
+        assert_eq!(sig6(120.0), "120");
+        assert_eq!(sig6(1.0 / 48.0), "0.0208333");
+        assert_eq!(sig6(4.92892159375), "4.92892");
+    }
 }
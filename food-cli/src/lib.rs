@@ -10,7 +10,13 @@
 //! opening a browser and `std::process::exit` all live in `main.rs`, which is
 //! the only place allowed to decide a process exit code.
 
+pub mod corpus_diff;
 pub mod corpus_lint;
+pub mod corpus_profile;
+pub mod corpus_shapes;
 pub mod corpus_table;
+pub mod corpus_train;
 pub mod explain;
+#[cfg(feature = "otlp")]
+pub mod otlp;
 pub mod tables;
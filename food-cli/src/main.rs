@@ -2,12 +2,21 @@
 #![allow(clippy::unwrap_used)]
 
 use clap::{Parser, Subcommand};
+use ingredient::section::{LineKind, classify_line};
 use recipe_epub::CookbookRecipeExt; // .parse() / .low_confidence_lines() on CookbookRecipe
+use tracing::Instrument;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 
 // The corpus/diagnostic verbs live in the library half so tests and other
 // crates can call them; this binary is argument parsing, printing and exit
 // codes. See src/lib.rs.
-use food_cli::{corpus_lint, corpus_table, explain, tables};
+use food_cli::{
+    corpus_diff, corpus_lint, corpus_profile, corpus_shapes, corpus_table, corpus_train, explain,
+    tables,
+};
+use ingredient_corpus::{coverage, label};
 
 /// Default path to the accuracy corpus, relative to this crate's manifest.
 const DEFAULT_CORPUS_PATH: &str = concat!(
@@ -23,6 +32,27 @@ struct Cli {
     command: Commands,
 }
 
+/// Where `scrape` / `scrape-epub` send the import's OpenTelemetry trace.
+#[cfg(feature = "otlp")]
+#[derive(clap::Args)]
+struct OtlpArgs {
+    /// Write the import's trace (fetch, EPUB chunk extraction and LLM calls,
+    /// every ingredient line's parse) to FILE as OTLP/JSON
+    #[arg(long, value_name = "FILE")]
+    otlp_out: Option<String>,
+    /// Send the import's trace to an OTLP/HTTP collector at URL, e.g.
+    /// http://localhost:4318 (POSTed to URL/v1/traces)
+    #[arg(long, value_name = "URL")]
+    otlp_endpoint: Option<String>,
+}
+
+#[cfg(feature = "otlp")]
+impl OtlpArgs {
+    fn enabled(&self) -> bool {
+        self.otlp_out.is_some() || self.otlp_endpoint.is_some()
+    }
+}
+
 #[derive(Subcommand)]
 enum Commands {
     Scrape {
@@ -31,6 +61,9 @@ enum Commands {
         json: bool,
         #[arg(short, long)]
         parse: bool,
+        #[cfg(feature = "otlp")]
+        #[command(flatten)]
+        otlp: OtlpArgs,
     },
     /// Scrape every recipe from a local EPUB cookbook file (AI-assisted)
     ScrapeEpub {
@@ -53,15 +86,47 @@ enum Commands {
         /// different chunks, so a fallback recovers the primary's misses.
         #[arg(long)]
         escalate_model: Option<String>,
+        /// Also re-run chunks through --escalate-model when their quality score
+        /// (share of extracted lines found verbatim in the source, see
+        /// `debug-epub`) is below this, keeping the better output. 0.0–1.0.
+        #[arg(long, requires = "escalate_model")]
+        escalate_below_quality: Option<f64>,
+        /// Stop sending chunks once this many USD have been spent (escalations
+        /// included); the recipes extracted so far are still printed. See
+        /// `estimate-epub` for a pre-flight figure.
+        #[arg(long)]
+        max_cost: Option<f64>,
         /// Bypass the on-disk extraction cache
         #[arg(long)]
         no_cache: bool,
+        #[cfg(feature = "otlp")]
+        #[command(flatten)]
+        otlp: OtlpArgs,
+    },
+    /// Estimate what `scrape-epub` would cost before spending anything: chunk
+    /// count, projected input/output tokens and USD per model, for one .epub or
+    /// every .epub under a directory. Chunks already in the on-disk cache are
+    /// counted as free. Makes no API calls.
+    EstimateEpub {
+        /// Path to an .epub file or a directory to scan recursively
+        path: String,
+        /// Model id(s) to price (repeatable; default: a spread of gemini and
+        /// claude models)
+        #[arg(long)]
+        model: Vec<String>,
+        /// Price a cold run, ignoring the on-disk extraction cache
+        #[arg(long)]
+        no_cache: bool,
+        #[arg(short, long)]
+        json: bool,
     },
     /// Debug a single EPUB: re-run every chunk through the model and report any
     /// whose raw payload fails to deserialize, with the offending JSON path. This
     /// is the view `scrape-epub` HIDES — it silently skips bad chunks (and the
     /// wasm cookbook import aborts the whole book on the first one). Bypasses the
     /// cache. Defaults to `claude-haiku-4-5` to mirror cubby's cookbook import.
+    /// Chunks that parse are also checked against their source text, listing
+    /// paraphrased or invented lines and quantity lines no recipe claimed.
     DebugEpub {
         /// Path to the .epub file
         path: String,
@@ -100,12 +165,35 @@ enum Commands {
         /// Export trace to Jaeger JSON format and write to file
         #[arg(long)]
         jaeger_output: Option<String>,
+        /// Save the parse trace as JSON, for a later --trace-diff
+        #[arg(long, value_name = "FILE")]
+        trace_out: Option<String>,
+        /// Diff a trace saved with --trace-out (before) against this parse
+        /// (after), marking the first node where they part ways
+        #[arg(long, value_name = "FILE")]
+        trace_diff: Option<String>,
         /// Print exactly one JSONL corpus row for the parse, ready to append to
         /// tests/corpus/corpus.jsonl. Refuses (stderr + non-zero exit) when the
         /// parse fell back or is low-confidence, so a garbage row can't be
         /// appended blindly. Suppresses the normal JSON output.
         #[arg(long)]
         emit_corpus_row: bool,
+        /// Parser vocabulary profile (.toml or .json) to parse with instead of
+        /// the built-in lists
+        #[arg(long)]
+        profile: Option<String>,
+        /// Print up to N alternative interpretations (JSON array, best first)
+        /// with per-field confidence and the passes behind each
+        #[arg(long, value_name = "N")]
+        candidates: Option<usize>,
+        /// Token-tagger model from `corpus train`: re-ranks --candidates, or
+        /// otherwise labels the line when the rule parse falls back
+        #[arg(long, value_name = "FILE")]
+        model: Option<String>,
+        /// Correct misspelled units and OCR glyph confusions ("tablspoon",
+        /// "l cup", "1% cups") before parsing; --explain lists each correction
+        #[arg(long)]
+        spellcheck: bool,
     },
     /// Parse a file of ingredient lines (one per line) and emit one JSONL object
     /// per line: {line, name, amounts, modifier} — the same shape as
@@ -113,12 +201,50 @@ enum Commands {
     /// through the current parser (free), or a website's lines via
     /// `scrape <url> --json | jq -r '.sections[].ingredients[]'`.
     ParseLines {
-        /// Path to a file with one ingredient line per line (blank lines skipped)
+        /// Path to a file with one ingredient line per line (blank lines,
+        /// section headers and notes skipped)
         file: String,
+        /// Parser vocabulary profile (.toml or .json) to parse with instead of
+        /// the built-in lists
+        #[arg(long)]
+        profile: Option<String>,
     },
-    /// Corpus tooling (lint, coverage reporting). See `corpus lint --help`.
+    /// Corpus tooling (lint, coverage reporting, tagger training). See `corpus lint --help`.
     #[command(subcommand)]
     Corpus(CorpusCommand),
+    /// Persistent cookbook catalog (sync a library, query its recipes). See
+    /// `catalog sync --help`.
+    #[command(subcommand)]
+    Catalog(CatalogCommand),
+    /// Search every cataloged recipe by what's on hand, e.g. `search leeks
+    /// potatoes cream`. Ranked by how much of each recipe's required ingredient
+    /// list (seasonings, garnishes and optional lines excluded) you have.
+    Search {
+        /// Ingredients on hand (whole-word match; plurals fold)
+        have: Vec<String>,
+        /// Free-text term each hit must mention in its title, category,
+        /// ingredients or instructions (repeatable)
+        #[arg(short, long)]
+        text: Vec<String>,
+        /// Only recipes whose total time is known and at most this long
+        #[arg(long)]
+        max_minutes: Option<u32>,
+        /// Only recipes whose active time is known and at most this long
+        #[arg(long)]
+        max_active_minutes: Option<u32>,
+        /// Only recipes whose printed yield is at least this many
+        #[arg(long)]
+        min_yield: Option<u32>,
+        /// How many hits to show
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Catalog file (defaults to $XDG_DATA_HOME/recipe-epub/catalog.jsonl)
+        #[arg(long)]
+        catalog: Option<String>,
+        /// Output as JSON (one object per hit)
+        #[arg(short, long)]
+        json: bool,
+    },
     /// Render the accuracy corpus (tests/corpus/corpus.jsonl) as an HTML table
     /// and open it in the default browser (like `cargo doc --open`). Read-only;
     /// does not touch the corpus.
@@ -150,6 +276,22 @@ enum Commands {
         #[arg(short, long)]
         json: bool,
     },
+    /// Convert an amount through unit mappings and print the path: each hop's
+    /// factor (both bounds for a ranged mapping), the amount after it, and the
+    /// mapping and source behind the edge, e.g.
+    /// `convert "2 cups" --to money -m "1 cup = 120 g @ kaf" -m "1 lb = $5"`
+    Convert {
+        /// The amount to convert (e.g., "2 cups", "1-2 pinches")
+        amount: String,
+        /// A kind (weight, volume, money, calories, nutrient:g protein) or a
+        /// unit to convert to
+        #[arg(long)]
+        to: String,
+        /// A unit mapping ("1 cup = 120 g", "$5/4lb", "4 lb = $5 @ costco");
+        /// repeatable. The latest of two conflicting mappings wins.
+        #[arg(short, long = "mapping", value_name = "MAPPING")]
+        mappings: Vec<String>,
+    },
     /// Validate if a unit string is recognized
     ValidateUnit {
         /// The unit to validate (e.g., "cup", "tablespoon")
@@ -174,6 +316,146 @@ enum CorpusCommand {
         #[arg(long)]
         report_stages: bool,
     },
+    /// Compare the parser's output under two configurations over a line set and
+    /// report every changed field, grouped by the passes that newly fired or
+    /// stopped firing. The baseline is a snapshot saved with --save, another
+    /// --baseline-profile, or (by default) the built-in vocabulary.
+    Diff {
+        /// Lines to parse: a corpus, snapshot or --dump-parsed JSONL (its
+        /// `input`/`line` keys) or plain text, one line per line. Defaults to
+        /// the baseline snapshot's lines, else the repo's corpus.jsonl
+        /// (xfail rows included)
+        #[arg(long)]
+        lines: Option<String>,
+        /// Baseline outputs saved earlier with --save
+        #[arg(long, conflicts_with = "baseline_profile")]
+        baseline: Option<String>,
+        /// Baseline parser vocabulary profile (.toml or .json)
+        #[arg(long)]
+        baseline_profile: Option<String>,
+        /// Candidate parser vocabulary profile (built-in lists when absent)
+        #[arg(long)]
+        profile: Option<String>,
+        /// Write the candidate's outputs as a snapshot here instead of diffing
+        #[arg(long)]
+        save: Option<String>,
+        /// Write the report as HTML here instead of printing tables ("-" for
+        /// stdout)
+        #[arg(long)]
+        html: Option<String>,
+    },
+    /// Label lines from a parse dump, most informative first: flagged parses,
+    /// lines firing thinly-covered passes, and lines unlike any corpus row.
+    /// Each line's current parse is prefilled; an accepted row is written to
+    /// the corpus under the section of its nearest existing row.
+    Label {
+        /// Lines to label: a `parse-lines` or `--dump-parsed` JSONL dump (its
+        /// `input`/`line` keys) or plain text, one line per line
+        lines: String,
+        /// Corpus file to add rows to (defaults to the repo's corpus.jsonl)
+        #[arg(long, default_value = DEFAULT_CORPUS_PATH)]
+        corpus: String,
+        /// Offer at most this many lines
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Print the ranked lines and why they rank there, without prompting
+        #[arg(long)]
+        list: bool,
+    },
+    /// Cluster the corpus by token-shape (amount pattern, clause kinds, paren
+    /// kinds, recognizer): per-shape row counts and xfail ratios, near-duplicate
+    /// rows, and the shapes of a harvest that no row covers yet.
+    Shapes {
+        /// Corpus file to cluster (defaults to the repo's corpus.jsonl)
+        #[arg(long, default_value = DEFAULT_CORPUS_PATH)]
+        corpus: String,
+        /// Harvested lines to propose uncovered shapes from (same formats as
+        /// `corpus label`)
+        #[arg(long)]
+        lines: Option<String>,
+        /// Show at most this many clusters and proposals
+        #[arg(long, default_value_t = 30)]
+        top: usize,
+    },
+    /// Time every stage, normalize rewrite, recognizer and refine pass over a
+    /// line set, and count each regex's compiles and uses. Prints the
+    /// costliest passes; --folded writes stacks for a flamegraph.
+    Profile {
+        /// Lines to parse (same formats as `corpus label`; defaults to the
+        /// repo's corpus.jsonl)
+        #[arg(long)]
+        lines: Option<String>,
+        /// Parse the line set this many times, for steadier timings
+        #[arg(long, default_value_t = 1)]
+        repeat: usize,
+        /// Show at most this many passes and regexes
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+        /// Write folded stacks (`flamegraph.pl` / `inferno-flamegraph` input,
+        /// weights in nanoseconds) here ("-" for stdout, instead of the tables)
+        #[arg(long)]
+        folded: Option<String>,
+    },
+    /// Train the opt-in token tagger on the corpus and write the model (JSON).
+    /// Prints held-out and training token accuracy.
+    Train {
+        /// Corpus file to train on (defaults to the repo's corpus.jsonl)
+        #[arg(long, default_value = DEFAULT_CORPUS_PATH)]
+        corpus: String,
+        /// Where to write the model
+        #[arg(long)]
+        out: String,
+        /// Passes over the training rows
+        #[arg(long, default_value_t = 10)]
+        epochs: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum CatalogCommand {
+    /// Bring the catalog in line with a directory of epubs: extract new and
+    /// changed books (through the extraction cache), drop ones removed from the
+    /// directory, and leave unchanged books and other directories' books alone.
+    Sync {
+        /// Directory to scan recursively for .epub files
+        dir: String,
+        /// Catalog file (defaults to $XDG_DATA_HOME/recipe-epub/catalog.jsonl)
+        #[arg(long)]
+        catalog: Option<String>,
+        /// Model id override (default: gemini-2.5-flash)
+        #[arg(long)]
+        model: Option<String>,
+        /// Bypass the on-disk extraction cache
+        #[arg(long)]
+        no_cache: bool,
+    },
+    /// Find cataloged recipes, e.g. `catalog query -i buttermilk --max-minutes 30`.
+    Query {
+        /// Ingredient every hit must use (repeatable; whole-word match)
+        #[arg(short, long)]
+        ingredient: Vec<String>,
+        /// Only recipes whose total time is known and at most this long
+        #[arg(long)]
+        max_minutes: Option<u32>,
+        /// Only recipes whose title contains this text
+        #[arg(long)]
+        title: Option<String>,
+        /// Catalog file (defaults to $XDG_DATA_HOME/recipe-epub/catalog.jsonl)
+        #[arg(long)]
+        catalog: Option<String>,
+        /// Output as JSON (one object per hit: book, title, url, total_minutes)
+        #[arg(short, long)]
+        json: bool,
+    },
+}
+
+/// Open the catalog at `path` (or the default location) or exit.
+fn open_catalog_or_exit(path: Option<&str>) -> recipe_epub::Catalog {
+    let path = path.map_or_else(recipe_epub::default_catalog_path, std::path::PathBuf::from);
+    recipe_epub::Catalog::open(&path).unwrap_or_else(|e| {
+        eprintln!("failed to open catalog: {e}");
+        std::process::exit(1);
+    })
 }
 
 /// Emit one JSONL object for an ingredient line zipped with its parse:
@@ -190,42 +472,11 @@ fn emit_parsed_line(ip: &ingredient::IngredientParser, line: &str) {
     println!("{}", serde_json::to_string(&obj).unwrap());
 }
 
-/// Render a corpus amount value: an exact fraction *string* (`"2/3"`) for a
-/// non-terminating decimal, else a plain JSON number with no trailing `.0`
-/// (`2.0` → `2`, `0.5` → `0.5`) — matching the hand-authored corpus convention so
-/// ⅔ round-trips exactly rather than as `0.666…`.
-fn corpus_value_json(frac: Option<String>, val: f64) -> String {
-    match frac {
-        Some(s) => serde_json::json!(s).to_string(),
-        // An integer-valued f64 writes as a bare int; keep `serde_json`'s float
-        // rendering (shortest round-trip) for the rest.
-        None if val.fract() == 0.0 && val.abs() < i64::MAX as f64 => (val as i64).to_string(),
-        None => serde_json::json!(val).to_string(),
-    }
-}
-
-/// Serialize one amount as a corpus `{"unit": .., "value": ..}` object string,
-/// with keys in corpus order (unit, value, upper_value). `upper_value` is omitted
-/// entirely when the amount is not a range. Built as a string (not a
-/// `serde_json::Map`, which would alphabetize the keys) to pin that order.
-fn corpus_amount_json(m: &ingredient::unit::Measure) -> String {
-    let unit = serde_json::json!(m.unit().to_string());
-    let value = corpus_value_json(m.value_as_fraction_str(), m.value());
-    match m.upper_value() {
-        Some(upper) => {
-            let upper = corpus_value_json(m.upper_value_as_fraction_str(), upper);
-            format!(r#"{{"unit": {unit}, "value": {value}, "upper_value": {upper}}}"#)
-        }
-        None => format!(r#"{{"unit": {unit}, "value": {value}}}"#),
-    }
-}
-
 /// Build the one-line JSONL corpus row for `input`'s parse, with keys in corpus
-/// order (`input, name, amounts, modifier, optional, usage`) and the optional
-/// keys omitted per corpus convention: `modifier` when `None`, `optional` when
-/// `false`, `usage` when `Normal`. `amounts` is omitted when empty (a bare
-/// name-only row). Returns the row string, or `Err` describing why the parse is
-/// unfit to author (fell back, or low confidence) so the caller can refuse it.
+/// order and the optional keys omitted per corpus convention (see
+/// [`label::Draft::to_json`]). Returns the row string, or `Err`
+/// describing why the parse is unfit to author (fell back, or low confidence)
+/// so the caller can refuse it.
 fn build_corpus_row(ip: &ingredient::IngredientParser, input: &str) -> Result<String, String> {
     let ing = ip.from_str(input);
     // Refuse whatever the parser says needs review, rather than re-deriving the
@@ -236,31 +487,127 @@ fn build_corpus_row(ip: &ingredient::IngredientParser, input: &str) -> Result<St
     if ing.name.trim().is_empty() {
         return Err("parse produced an empty name".to_string());
     }
+    label::Draft::from_parse(input, &ing, label::DEFAULT_SECTION).to_json()
+}
 
-    // Assemble by hand so key order is stable without the serde_json
-    // `preserve_order` feature. Each `to_string` value is valid JSON already.
-    let mut parts: Vec<String> = Vec::new();
-    let field = |k: &str, v: &serde_json::Value| format!("{}: {}", serde_json::json!(k), v);
-
-    parts.push(field("input", &serde_json::json!(input)));
-    parts.push(field("name", &serde_json::json!(ing.name)));
-    if !ing.amounts.is_empty() {
-        let amounts: Vec<String> = ing.amounts.iter().map(corpus_amount_json).collect();
-        parts.push(format!("\"amounts\": [{}]", amounts.join(", ")));
+/// Print `prompt` and read one line of stdin; `None` at end of input.
+fn ask(prompt: &str) -> Option<String> {
+    use std::io::Write as _;
+    print!("{prompt}");
+    let _ = std::io::stdout().flush();
+    let mut line = String::new();
+    match std::io::stdin().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.trim_end_matches(['\n', '\r']).to_string()),
     }
-    if let Some(modifier) = &ing.modifier {
-        parts.push(field("modifier", &serde_json::json!(modifier)));
+}
+
+/// Walk `draft`'s fields, each prompt showing the current value; Enter keeps
+/// it. `None` at end of input.
+fn edit_draft(draft: &mut label::Draft) -> Option<()> {
+    let keep = |answer: String, current: &mut String| {
+        if !answer.is_empty() {
+            *current = answer;
+        }
+    };
+    keep(ask(&format!("  name [{}]: ", draft.name))?, &mut draft.name);
+    keep(
+        ask(&format!("  amounts [{}]: ", draft.amounts))?,
+        &mut draft.amounts,
+    );
+    match ask(&format!("  modifier ('-' for none) [{}]: ", draft.modifier))?.as_str() {
+        "" => {}
+        "-" => draft.modifier.clear(),
+        answer => draft.modifier = answer.to_string(),
     }
-    if ing.optional {
-        parts.push(field("optional", &serde_json::json!(true)));
+    match ask(&format!("  optional (y/n) [{}]: ", draft.optional))?.as_str() {
+        "y" => draft.optional = true,
+        "n" => draft.optional = false,
+        _ => {}
     }
-    // `usage` is serialized to its snake_case string; omit the `normal` default.
-    let usage = serde_json::to_value(ing.usage).map_err(|e| e.to_string())?;
-    if usage.as_str() != Some("normal") {
-        parts.push(field("usage", &usage));
+    let usage = ask(&format!(
+        "  usage [{}]: ",
+        serde_json::json!(draft.usage).as_str().unwrap_or_default()
+    ))?;
+    if !usage.is_empty() {
+        match serde_json::from_value(serde_json::json!(usage)) {
+            Ok(u) => draft.usage = u,
+            Err(_) => println!("  unknown usage {usage:?}; kept"),
+        }
     }
+    keep(
+        ask(&format!("  section [{}]: ", draft.section))?,
+        &mut draft.section,
+    );
+    Some(())
+}
 
-    Ok(format!("{{{}}}", parts.join(", ")))
+/// Offer each candidate in turn and write the accepted rows into `corpus` as
+/// they are accepted, so quitting midway keeps them. Returns how many were
+/// written.
+fn label_loop(candidates: &[label::Candidate], corpus: &str) -> usize {
+    let total = candidates.len();
+    let mut written = 0;
+    'lines: for (i, candidate) in candidates.iter().enumerate() {
+        let mut draft = candidate.draft.clone();
+        println!(
+            "\n[{}/{total}] score {:.1}  {}",
+            i + 1,
+            candidate.score,
+            candidate.input
+        );
+        for signal in &candidate.signals {
+            println!("  · {signal}");
+        }
+        loop {
+            match draft.to_json() {
+                Ok(row) => println!("  {}: {row}", draft.section),
+                Err(e) => println!("  {}: ({e})", draft.section),
+            }
+            let Some(choice) = ask("  [a]ccept [e]dit [x]fail [s]kip [q]uit > ") else {
+                break 'lines;
+            };
+            match choice.trim() {
+                "a" | "x" => {
+                    if choice.trim() == "x" {
+                        let Some(reason) = ask("  xfail reason: ") else {
+                            break 'lines;
+                        };
+                        draft.xfail = Some(reason);
+                    }
+                    match draft.to_row() {
+                        Ok(row) => {
+                            let source = read_or_exit(corpus);
+                            let updated = label::insert_row(&source, &draft.section, &row);
+                            if let Err(e) = std::fs::write(corpus, updated) {
+                                eprintln!("failed to write {corpus}: {e}");
+                                std::process::exit(1);
+                            }
+                            written += 1;
+                            continue 'lines;
+                        }
+                        Err(e) => {
+                            println!("  not written: {e}");
+                            // A refused xfail reason shouldn't ride along on
+                            // the next accept.
+                            if choice.trim() == "x" {
+                                draft.xfail = None;
+                            }
+                        }
+                    }
+                }
+                "e" => {
+                    if edit_draft(&mut draft).is_none() {
+                        break 'lines;
+                    }
+                }
+                "" | "s" => continue 'lines,
+                "q" => break 'lines,
+                other => println!("  unknown choice {other:?}"),
+            }
+        }
+    }
+    written
 }
 
 /// Collect dotted paths of every `null`-valued key in a JSON payload, e.g.
@@ -303,24 +650,130 @@ fn read_or_exit(path: &str) -> String {
     })
 }
 
+/// The parser for `--profile` (built-in vocabulary when absent), or exit with
+/// every problem the profile has. TOML and JSON files share one schema; the
+/// format is picked by extension.
+fn parser_or_exit(profile: Option<&str>) -> ingredient::IngredientParser {
+    let Some(path) = profile else {
+        return ingredient::IngredientParser::new();
+    };
+    let text = read_or_exit(path);
+    let loaded = if path.ends_with(".toml") {
+        toml::from_str::<ingredient::ParserProfile>(&text)
+            .map_err(|e| ingredient::ProfileError::Parse(e.to_string()))
+            .and_then(ingredient::ParserProfile::resolve)
+    } else {
+        ingredient::ParserProfile::from_json(&text)
+    };
+    match loaded {
+        Ok(p) => ingredient::IngredientParser::new().with_profile(&p),
+        Err(e) => {
+            eprintln!("failed to load profile {path}: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// The collector for this run's import, when the command is one that takes
+/// `--otlp-out` / `--otlp-endpoint` and either was given.
+#[cfg(feature = "otlp")]
+fn otlp_import(command: &Commands) -> Option<(food_cli::otlp::OtlpCollector, &OtlpArgs)> {
+    let (source, args) = match command {
+        Commands::Scrape { url, otlp, .. } => (url, otlp),
+        Commands::ScrapeEpub { path, otlp, .. } => (path, otlp),
+        _ => return None,
+    };
+    args.enabled()
+        .then(|| (food_cli::otlp::OtlpCollector::new(source), args))
+}
+
+/// Write and/or send the finished import trace. Runs before the import's own
+/// error exit, so a failed import is exported too.
+#[cfg(feature = "otlp")]
+async fn export_otlp(otlp: Option<(food_cli::otlp::OtlpCollector, &OtlpArgs)>) {
+    let Some((collector, args)) = otlp else {
+        return;
+    };
+    let trace = collector.finish();
+    let body = trace.to_json_string();
+    if let Some(out) = &args.otlp_out {
+        if let Err(e) = std::fs::write(out, &body) {
+            eprintln!("failed to write OTLP trace to {out}: {e}");
+            std::process::exit(1);
+        }
+        eprintln!(
+            "Wrote OTLP trace {} ({} spans) to: {out}",
+            trace.trace_id(),
+            trace.spans().len()
+        );
+    }
+    if let Some(endpoint) = &args.otlp_endpoint {
+        let url = format!(
+            "{}{}",
+            endpoint.trim_end_matches('/'),
+            food_cli::otlp::TRACES_PATH
+        );
+        let sent = reqwest::Client::new()
+            .post(&url)
+            .header("content-type", "application/json")
+            .body(body)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status);
+        if let Err(e) = sent {
+            eprintln!("failed to send OTLP trace to {url}: {e}");
+            std::process::exit(1);
+        }
+        eprintln!("Sent OTLP trace {} to: {url}", trace.trace_id());
+    }
+}
+
 #[tokio::main]
 async fn main() {
     // Surface the extractor's tracing (chunk skips, escalation, truncation) on
     // stderr. Off unless RUST_LOG is set, so normal --json stdout stays clean;
     // try `RUST_LOG=recipe_epub=info`. Without this, those warns went nowhere.
-    tracing_subscriber::fmt()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .with_writer(std::io::stderr)
-        .init();
+    // With --otlp-out / --otlp-endpoint, a second layer collects the import's
+    // spans into one OpenTelemetry trace.
+    let cli = Cli::parse();
+    #[cfg(feature = "otlp")]
+    let otlp = otlp_import(&cli.command);
+    let registry = tracing_subscriber::registry().with(
+        tracing_subscriber::fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_filter(tracing_subscriber::EnvFilter::from_default_env()),
+    );
+    #[cfg(feature = "otlp")]
+    let registry = registry.with(otlp.as_ref().map(|(collector, _)| collector.layer()));
+    registry.init();
     // Load AI gateway creds (AI_GATEWAY_API_KEY, CLOUDFLARE_AI_GATEWAY_BASE_URL)
     // from a repo-root .env. Missing file is fine; real exported vars take precedence.
     let _ = dotenvy::dotenv();
-    let cli = Cli::parse();
 
     match &cli.command {
-        Commands::Scrape { url, json, parse } => {
-            let s = recipe_scraper_fetcher::Fetcher::new();
-            let scraped = match s.scrape_url(url).await {
+        Commands::Scrape {
+            url, json, parse, ..
+        } => {
+            let scraped = async {
+                let scraped = recipe_scraper_fetcher::Fetcher::new().scrape_url(url).await;
+                #[cfg(feature = "otlp")]
+                if let (Some((collector, _)), Ok(s)) = (&otlp, &scraped) {
+                    collector.trace_lines(s.ingredients());
+                }
+                if scraped.is_err() {
+                    tracing::Span::current().record("otel.status_code", "ERROR");
+                }
+                scraped
+            }
+            .instrument(tracing::info_span!(
+                "import_recipe",
+                source = %url,
+                otel.status_code = tracing::field::Empty
+            ))
+            .await;
+            #[cfg(feature = "otlp")]
+            export_otlp(otlp).await;
+            let scraped = match scraped {
                 Ok(s) => s,
                 Err(e) => {
                     eprintln!("scrape error: {e}");
@@ -347,7 +800,10 @@ async fn main() {
             dump_parsed,
             model,
             escalate_model,
+            escalate_below_quality,
+            max_cost,
             no_cache,
+            ..
         } => {
             let bytes = std::fs::read(path).unwrap_or_else(|e| {
                 eprintln!("failed to read {path}: {e}");
@@ -356,10 +812,35 @@ async fn main() {
             let opts = recipe_epub::Options {
                 model: model.clone(),
                 escalate_model: escalate_model.clone(),
+                escalate_below_quality: *escalate_below_quality,
+                max_cost_usd: *max_cost,
                 use_cache: !no_cache,
                 ..Default::default()
             };
-            match recipe_epub::extract_cookbook(&bytes, path, &opts).await {
+            let extracted = async {
+                let extracted = recipe_epub::extract_cookbook(&bytes, path, &opts).await;
+                #[cfg(feature = "otlp")]
+                if let (Some((collector, _)), Ok((recipes, _))) = (&otlp, &extracted) {
+                    collector.trace_lines(recipes.iter().flat_map(|r| {
+                        r.sections
+                            .iter()
+                            .flat_map(|sec| sec.ingredients.iter().map(String::as_str))
+                    }));
+                }
+                if extracted.is_err() {
+                    tracing::Span::current().record("otel.status_code", "ERROR");
+                }
+                extracted
+            }
+            .instrument(tracing::info_span!(
+                "import_cookbook",
+                source = %path,
+                otel.status_code = tracing::field::Empty
+            ))
+            .await;
+            #[cfg(feature = "otlp")]
+            export_otlp(otlp).await;
+            match extracted {
                 Ok((recipes, stats)) => {
                     // Cost/cache summary goes to stderr so --json stdout stays clean.
                     eprintln!("[{}] {}", stats.model, stats.summary());
@@ -487,6 +968,29 @@ async fn main() {
                     }
                 }
             }
+            // Chunks that parsed but don't match their source: paraphrased or
+            // invented lines, and ingredient lines no recipe picked up.
+            for c in &chunks {
+                let Some(q) = c.quality.as_ref().filter(|q| !q.is_clean()) else {
+                    continue;
+                };
+                println!("\n--- quality: {} ({}) ---", c.doc_path, q.summary());
+                for (r, l) in q.flagged() {
+                    let verdict = match &l.alignment {
+                        recipe_epub::Alignment::Paraphrased { source, overlap } => {
+                            format!("paraphrased ({overlap:.2}) of {source:?}")
+                        }
+                        recipe_epub::Alignment::Hallucinated { overlap } => {
+                            format!("hallucinated ({overlap:.2})")
+                        }
+                        recipe_epub::Alignment::Verbatim => continue,
+                    };
+                    println!("  [{}] {:?}: {verdict}", r.title, l.line);
+                }
+                for line in &q.unclaimed {
+                    println!("  unclaimed: {line:?}");
+                }
+            }
             if failures.is_empty() {
                 println!(
                     "no parse failures — all {} chunk(s) deserialized cleanly",
@@ -496,6 +1000,60 @@ async fn main() {
             // Non-zero exit when any chunk failed, so this is scriptable in CI.
             std::process::exit(if failures.is_empty() { 0 } else { 2 });
         }
+        Commands::EstimateEpub {
+            path,
+            model,
+            no_cache,
+            json,
+        } => {
+            let root = std::path::Path::new(path);
+            let books = if root.is_dir() {
+                let mut epubs = recipe_epub::find_epubs(root);
+                epubs.sort();
+                epubs
+            } else {
+                vec![root.to_path_buf()]
+            };
+            let models: Vec<&str> = if model.is_empty() {
+                recipe_epub::ESTIMATE_MODELS.to_vec()
+            } else {
+                model.iter().map(String::as_str).collect()
+            };
+            let opts = recipe_epub::Options {
+                use_cache: !no_cache,
+                ..Default::default()
+            };
+            // One running total per model, summed over every book.
+            let mut totals: Vec<recipe_epub::CostEstimate> = models
+                .iter()
+                .map(|m| recipe_epub::CostEstimate {
+                    model: m.to_string(),
+                    ..Default::default()
+                })
+                .collect();
+            for book in &books {
+                let estimates = std::fs::read(book)
+                    .map_err(|e| e.to_string())
+                    .and_then(|bytes| {
+                        recipe_epub::estimate_cookbook(&bytes, &opts, &models)
+                            .map_err(|e| e.to_string())
+                    });
+                match estimates {
+                    Ok(estimates) => {
+                        for (total, e) in totals.iter_mut().zip(&estimates) {
+                            total.add(e);
+                        }
+                    }
+                    Err(e) => eprintln!("skipping {}: {e}", book.display()),
+                }
+            }
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&totals).unwrap());
+            } else {
+                eprintln!("{} book(s)", books.len());
+                println!("{}", tables::estimate_table(&totals));
+            }
+        }
         Commands::ScanCookbooks {
             dir,
             limit,
@@ -594,12 +1152,39 @@ async fn main() {
             debug,
             explain,
             jaeger_output,
+            trace_out,
+            trace_diff,
             emit_corpus_row,
+            profile,
+            candidates,
+            model,
+            spellcheck,
         } => {
+            let parser = parser_or_exit(profile.as_deref()).with_spellcheck(*spellcheck);
+            let tagger = model.as_deref().map(|path| {
+                ingredient::rerank::TokenTagger::from_json(&read_or_exit(path)).unwrap_or_else(
+                    |e| {
+                        eprintln!("failed to load model {path}: {e}");
+                        std::process::exit(1);
+                    },
+                )
+            });
+            if let Some(n) = candidates {
+                let mut found = parser.parse_candidates(name, *n);
+                if let Some(tagger) = &tagger {
+                    found = tagger.rerank(&parser, name, found);
+                }
+                println!("{}", serde_json::to_string_pretty(&found).unwrap());
+                return;
+            }
+            if let Some(tagger) = &tagger {
+                let labeled = tagger.label_fallback(&parser, name);
+                println!("{}", serde_json::to_string_pretty(&labeled).unwrap());
+                return;
+            }
             if *emit_corpus_row {
                 // Authoring helper: one JSONL row for the corpus, or a refusal.
-                let ip = ingredient::IngredientParser::new();
-                match build_corpus_row(&ip, name) {
+                match build_corpus_row(&parser, name) {
                     Ok(row) => println!("{row}"),
                     Err(reason) => {
                         eprintln!(
@@ -611,12 +1196,35 @@ async fn main() {
                 }
                 return;
             }
-            if *debug || *explain || jaeger_output.is_some() {
+            if *debug
+                || *explain
+                || jaeger_output.is_some()
+                || trace_out.is_some()
+                || trace_diff.is_some()
+            {
                 // Use parse_with_trace for debug output or Jaeger export
-                let parser = ingredient::IngredientParser::new();
                 let result = parser.parse_with_trace(name);
                 let use_color = std::io::IsTerminal::is_terminal(&std::io::stdout());
 
+                // Read the saved trace before writing ours: --trace-out and
+                // --trace-diff may name the same file.
+                let before = trace_diff.as_deref().map(|path| {
+                    serde_json::from_str::<ingredient::trace::ParseTrace>(&read_or_exit(path))
+                        .unwrap_or_else(|e| {
+                            eprintln!("{path} is not a saved trace: {e}");
+                            std::process::exit(1);
+                        })
+                });
+
+                if let Some(output_path) = trace_out {
+                    let json = serde_json::to_string_pretty(&result.trace).unwrap();
+                    if let Err(e) = std::fs::write(output_path, json) {
+                        eprintln!("Failed to write trace to {output_path}: {e}");
+                        std::process::exit(1);
+                    }
+                    eprintln!("Wrote trace to: {output_path}");
+                }
+
                 // Export to Jaeger JSON if requested
                 if let Some(output_path) = jaeger_output {
                     let jaeger_json = result.trace.to_jaeger_json();
@@ -628,7 +1236,7 @@ async fn main() {
                 }
 
                 // Compact stage report — the routing view. The miette header
-                // labels how the grammar carved the line (amount/name/modifier),
+                // labels how the parse carved the line (amount/name/modifier…),
                 // or falls back to a caret on a digit that produced no amount;
                 // the stage view below shows which pipeline stage shaped the line.
                 if *explain {
@@ -636,6 +1244,11 @@ async fn main() {
                     let decomp = parser.decompose(name);
                     print!("{}", explain::render(&decomp, &diag, use_color));
                     println!();
+                    print!(
+                        "{}",
+                        explain::render_usage(&parser.explain_usage(name, None))
+                    );
+                    println!();
                     println!("{}", result.trace.format_stages(use_color));
                 }
 
@@ -644,6 +1257,18 @@ async fn main() {
                     println!("{}", result.trace.format_tree(use_color));
                 }
 
+                if let Some(before) = before {
+                    let diff = before.diff(&result.trace);
+                    match diff.first_divergence() {
+                        None => println!("traces match (timings aside)"),
+                        Some(path) => {
+                            let names: Vec<&str> = path.iter().map(|n| n.name.as_str()).collect();
+                            println!("first divergence: {}", names.join(" › "));
+                        }
+                    }
+                    println!("{}", diff.format(use_color));
+                }
+
                 // Print the result
                 match result.result {
                     Ok(ingredient) => {
@@ -657,11 +1282,11 @@ async fn main() {
             } else {
                 // JSON only — stdout must stay pipeable (`… | jq`); the human-
                 // readable Display line was breaking that.
-                let res = ingredient::from_str(name);
+                let res = parser.from_str(name);
                 println!("{}", serde_json::to_string_pretty(&res).unwrap());
             }
         }
-        Commands::ParseLines { file } => {
+        Commands::ParseLines { file, profile } => {
             let contents = match std::fs::read_to_string(file) {
                 Ok(c) => c,
                 Err(e) => {
@@ -669,10 +1294,12 @@ async fn main() {
                     std::process::exit(1);
                 }
             };
-            let ip = ingredient::IngredientParser::new();
+            let ip = parser_or_exit(profile.as_deref());
             for line in contents.lines() {
                 let line = line.trim();
-                if line.is_empty() {
+                // Pasted lists carry their section headers, notes and blank
+                // lines inline; only the ingredient lines are emitted.
+                if classify_line(line) != LineKind::Ingredient {
                     continue;
                 }
                 emit_parsed_line(&ip, line);
@@ -702,6 +1329,217 @@ async fn main() {
             }
             print!("{}", outcome.report);
         }
+        Commands::Corpus(CorpusCommand::Diff {
+            lines,
+            baseline,
+            baseline_profile,
+            profile,
+            save,
+            html,
+        }) => {
+            let saved = baseline.as_deref().map(|path| {
+                corpus_diff::parse_snapshot(&read_or_exit(path)).unwrap_or_else(|e| {
+                    eprintln!("malformed snapshot {path}: {e}");
+                    std::process::exit(1);
+                })
+            });
+            let line_set = match (lines, &saved) {
+                (Some(path), _) => ingredient_corpus::load_lines(&read_or_exit(path)),
+                (None, Some(snapshot)) => snapshot.iter().map(|o| o.input.clone()).collect(),
+                (None, None) => ingredient_corpus::load_lines(&read_or_exit(DEFAULT_CORPUS_PATH)),
+            };
+            let after = corpus_diff::snapshot(&parser_or_exit(profile.as_deref()), &line_set);
+            if let Some(path) = save {
+                if let Err(e) = std::fs::write(path, corpus_diff::to_jsonl(&after)) {
+                    eprintln!("failed to write {path}: {e}");
+                    std::process::exit(1);
+                }
+                eprintln!("wrote {path} ({} lines)", after.len());
+                return;
+            }
+            let before = saved.unwrap_or_else(|| {
+                corpus_diff::snapshot(&parser_or_exit(baseline_profile.as_deref()), &line_set)
+            });
+            let report = corpus_diff::diff(&before, &after);
+            match html.as_deref() {
+                Some("-") => print!("{}", corpus_table::render_diff_html(&report)),
+                Some(path) => {
+                    if let Err(e) = std::fs::write(path, corpus_table::render_diff_html(&report)) {
+                        eprintln!("failed to write {path}: {e}");
+                        std::process::exit(1);
+                    }
+                    eprintln!("wrote {path} ({})", corpus_diff::summary(&report));
+                }
+                None => print!("{}", corpus_diff::render_table(&report)),
+            }
+        }
+        Commands::Corpus(CorpusCommand::Shapes { corpus, lines, top }) => {
+            let labeled = ingredient_corpus::parse(&read_or_exit(corpus));
+            let harvest = lines
+                .as_ref()
+                .map(|path| ingredient_corpus::load_lines(&read_or_exit(path)))
+                .unwrap_or_default();
+            let report = corpus_shapes::analyze(&labeled, &harvest);
+            print!("{}", corpus_shapes::render_report(&report, *top));
+        }
+        Commands::Corpus(CorpusCommand::Profile {
+            lines,
+            repeat,
+            top,
+            folded,
+        }) => {
+            let path = lines.as_deref().unwrap_or(DEFAULT_CORPUS_PATH);
+            let line_set = ingredient_corpus::load_lines(&read_or_exit(path));
+            let report =
+                corpus_profile::profile(&ingredient::IngredientParser::new(), &line_set, *repeat);
+            match folded.as_deref() {
+                Some("-") => print!("{}", report.folded()),
+                Some(out) => {
+                    if let Err(e) = std::fs::write(out, report.folded()) {
+                        eprintln!("failed to write {out}: {e}");
+                        std::process::exit(1);
+                    }
+                    eprintln!("wrote {out} ({} stacks)", report.stacks.len());
+                    print!("{}", corpus_profile::render_report(&report, *top));
+                }
+                None => print!("{}", corpus_profile::render_report(&report, *top)),
+            }
+        }
+        Commands::Corpus(CorpusCommand::Label {
+            lines,
+            corpus,
+            limit,
+            list,
+        }) => {
+            let labeled = ingredient_corpus::parse(&read_or_exit(corpus));
+            let coverage = coverage::report_stages_over(&labeled.inputs());
+            let line_set = ingredient_corpus::load_lines(&read_or_exit(lines));
+            let mut ranked = label::rank(&line_set, &labeled, &coverage);
+            ranked.truncate(*limit);
+            if *list {
+                // Tab-separated stays grep/copy-friendly, like `--bottom`.
+                for c in &ranked {
+                    let why: Vec<String> = c.signals.iter().map(ToString::to_string).collect();
+                    println!("{:.1}\t{}\t{}", c.score, c.input, why.join("; "));
+                }
+                return;
+            }
+            let written = label_loop(&ranked, corpus);
+            eprintln!("wrote {written} row(s) to {corpus}");
+        }
+        Commands::Corpus(CorpusCommand::Train {
+            corpus,
+            out,
+            epochs,
+        }) => {
+            let contents = read_or_exit(corpus);
+            let trained = corpus_train::train(&ingredient_corpus::parse(&contents), *epochs);
+            if let Err(e) = std::fs::write(out, trained.tagger.to_json()) {
+                eprintln!("failed to write {out}: {e}");
+                std::process::exit(1);
+            }
+            print!("{}", trained.report());
+            println!("wrote {out}");
+        }
+        Commands::Catalog(CatalogCommand::Sync {
+            dir,
+            catalog,
+            model,
+            no_cache,
+        }) => {
+            if !std::path::Path::new(dir).is_dir() {
+                eprintln!("error: '{dir}' is not a directory");
+                std::process::exit(1);
+            }
+            let mut cat = open_catalog_or_exit(catalog.as_deref());
+            let opts = recipe_epub::Options {
+                model: model.clone(),
+                use_cache: !no_cache,
+                ..Default::default()
+            };
+            let report = cat.sync(std::path::Path::new(dir), &opts).await;
+            for (path, e) in &report.failed {
+                eprintln!("{}: error: {e}", path.display());
+            }
+            if let Err(e) = cat.save() {
+                eprintln!("failed to save catalog: {e}");
+                std::process::exit(1);
+            }
+            let recipes = cat.recipes().count();
+            println!(
+                "{} · {} book(s), {recipes} recipe(s) in {}",
+                report.summary(),
+                cat.books().len(),
+                cat.path().display()
+            );
+        }
+        Commands::Catalog(CatalogCommand::Query {
+            ingredient,
+            max_minutes,
+            title,
+            catalog,
+            json,
+        }) => {
+            let cat = open_catalog_or_exit(catalog.as_deref());
+            let query = recipe_epub::CatalogQuery {
+                ingredients: ingredient.clone(),
+                max_total_minutes: *max_minutes,
+                title: title.clone(),
+            };
+            let hits = cat.query(&query);
+            if *json {
+                for h in &hits {
+                    let obj = serde_json::json!({
+                        "book": h.book.meta.title,
+                        "title": h.recipe.recipe.meta.title,
+                        "url": h.recipe.recipe.url,
+                        "total_minutes": h.recipe.total_minutes(),
+                    });
+                    println!("{}", serde_json::to_string(&obj).unwrap());
+                }
+            } else {
+                println!("{}", tables::catalog_table(&hits));
+                eprintln!("{} hit(s)", hits.len());
+            }
+        }
+        Commands::Search {
+            have,
+            text,
+            max_minutes,
+            max_active_minutes,
+            min_yield,
+            limit,
+            catalog,
+            json,
+        } => {
+            let cat = open_catalog_or_exit(catalog.as_deref());
+            let index = cat.search_index();
+            let query = recipe_epub::SearchQuery {
+                have: have.clone(),
+                text: text.clone(),
+                max_total_minutes: *max_minutes,
+                max_active_minutes: *max_active_minutes,
+                min_yield: *min_yield,
+            };
+            let hits = index.search(&query);
+            let shown = &hits[..hits.len().min(*limit)];
+            if *json {
+                for h in shown {
+                    let obj = serde_json::json!({
+                        "title": h.recipe.meta.title,
+                        "source": h.recipe.source,
+                        "url": h.recipe.url,
+                        "coverage": h.coverage(),
+                        "matched": h.matched,
+                        "missing": h.missing,
+                    });
+                    println!("{}", serde_json::to_string(&obj).unwrap());
+                }
+            } else {
+                println!("{}", tables::search_table(shown));
+                eprintln!("{} hit(s) across {} recipe(s)", hits.len(), index.len());
+            }
+        }
         Commands::CorpusTable { corpus, out } => {
             let contents = match std::fs::read_to_string(corpus) {
                 Ok(c) => c,
@@ -770,6 +1608,52 @@ async fn main() {
                 }
             }
         }
+        Commands::Convert {
+            amount,
+            to,
+            mappings,
+        } => {
+            let parser = ingredient::IngredientParser::new();
+            let measure = match parser.parse_amount(amount) {
+                Ok(amounts) if !amounts.is_empty() => amounts[0].clone(),
+                Ok(_) => {
+                    eprintln!("no amount in {amount:?}");
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("Parse error: {e}");
+                    std::process::exit(1);
+                }
+            };
+            let mappings: Vec<_> = mappings
+                .iter()
+                .map(|m| {
+                    ingredient::unit_mapping::parse_unit_mapping(m).unwrap_or_else(|e| {
+                        eprintln!("bad mapping {m:?}: {e}");
+                        std::process::exit(1);
+                    })
+                })
+                .collect();
+            let pairs: Vec<_> = mappings
+                .iter()
+                .map(|m| (m.a.clone(), m.b.clone()))
+                .collect();
+            let graph = ingredient::unit::make_graph(&pairs);
+            match ingredient::unit::convert_measure_with_graph_explained(
+                &measure,
+                explain::conversion_target(to),
+                &graph,
+            ) {
+                Some((result, steps)) => print!(
+                    "{}",
+                    explain::render_conversion(&measure, &result, &steps, &mappings)
+                ),
+                None => {
+                    eprintln!("no conversion path from {measure} to {to}");
+                    std::process::exit(1);
+                }
+            }
+        }
         Commands::ValidateUnit { unit, extra_units } => {
             // Validate by attempting to parse a simple measurement with this unit
             let mut parser = ingredient::IngredientParser::new();
@@ -0,0 +1,299 @@
+//! One OTLP trace per recipe import: `tracing` spans from the fetcher, the
+//! HTTP client and the EPUB extractor, plus the parse trace of every
+//! ingredient line, under one trace ID.
+//!
+//! [`OtlpCollector::layer`] is a `tracing_subscriber` layer that turns each
+//! span into an [`OtlpSpan`] when it closes, keeping the parent links `tracing`
+//! knows about. `otel.name`, `otel.kind` and `otel.status_code` fields (the
+//! `reqwest-tracing` conventions) set the span's name, kind and status; every
+//! other field becomes an attribute. [`OtlpCollector::trace_lines`] parses
+//! lines with [`IngredientParser::parse_with_trace`] and hangs each
+//! [`TraceNode`](ingredient::trace::TraceNode) tree under the current span.
+//!
+//! Writing the file or posting to a collector is `main.rs`'s job (see
+//! `lib.rs`); [`OtlpCollector::finish`] hands over the finished trace.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ingredient::IngredientParser;
+use ingredient::trace::otlp::{AttributeValue, OtlpSpan, OtlpTrace, SpanId, SpanKind, SpanStatus};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Subscriber, info_span};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// `service.name` of every exported trace.
+pub const SERVICE_NAME: &str = "food-cli";
+
+/// The path an OTLP/HTTP collector accepts traces on, after its base URL.
+pub const TRACES_PATH: &str = "/v1/traces";
+
+/// A span that has started and not yet closed.
+struct OpenSpan {
+    span_id: SpanId,
+    parent_span_id: Option<SpanId>,
+    name: String,
+    kind: SpanKind,
+    start_unix_nanos: u64,
+    attributes: Vec<(String, AttributeValue)>,
+    status: SpanStatus,
+}
+
+struct State {
+    trace: OtlpTrace,
+    open: HashMap<Id, OpenSpan>,
+}
+
+/// Collects one import's spans; cheap to clone, every clone feeds one trace.
+#[derive(Clone)]
+pub struct OtlpCollector {
+    state: Arc<Mutex<State>>,
+}
+
+impl OtlpCollector {
+    /// A collector for an import of `source` (a URL or a file path). The trace
+    /// ID is seeded with the source and the current time, so two imports of
+    /// one source are two traces.
+    pub fn new(source: &str) -> Self {
+        let seed = format!("{source}@{}", now_unix_nanos());
+        Self {
+            state: Arc::new(Mutex::new(State {
+                trace: OtlpTrace::new(SERVICE_NAME, &seed),
+                open: HashMap::new(),
+            })),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The layer to install on the `tracing` registry. It takes spans at
+    /// `INFO` and above: the parser's own `tracing` spans are `TRACE`, and its
+    /// parses arrive through [`trace_lines`](Self::trace_lines) instead.
+    pub fn layer<S>(&self) -> impl Layer<S> + use<S>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        OtlpLayer {
+            collector: self.clone(),
+        }
+        .with_filter(LevelFilter::INFO)
+    }
+
+    /// Parse each line with a trace, inside a `parse_ingredients` span under
+    /// the current one, and add every parse trace beneath it.
+    pub fn trace_lines<'a>(&self, lines: impl IntoIterator<Item = &'a str>) {
+        let parser = IngredientParser::new();
+        let lines: Vec<&str> = lines.into_iter().collect();
+        let span = info_span!("parse_ingredients", lines = lines.len());
+        let _entered = span.enter();
+        let parent = span
+            .id()
+            .and_then(|id| self.lock().open.get(&id).map(|o| o.span_id));
+        for line in lines {
+            let trace = parser.parse_with_trace(line).trace;
+            self.lock().trace.add_parse_trace(&trace, parent);
+        }
+    }
+
+    /// The spans closed so far. Spans still open (there should be none once
+    /// the import's root span has closed) are left out.
+    pub fn finish(&self) -> OtlpTrace {
+        self.lock().trace.clone()
+    }
+}
+
+/// The `tracing_subscriber` layer behind [`OtlpCollector::layer`].
+pub struct OtlpLayer {
+    collector: OtlpCollector,
+}
+
+impl<S: Subscriber> Layer<S> for OtlpLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let parent = match attrs.parent() {
+            Some(parent) => Some(parent.clone()),
+            None if attrs.is_contextual() => ctx.current_span().id().cloned(),
+            None => None,
+        };
+        let mut span = OpenSpan {
+            span_id: SpanId([0; 8]),
+            parent_span_id: None,
+            name: attrs.metadata().name().to_string(),
+            kind: SpanKind::Internal,
+            start_unix_nanos: now_unix_nanos(),
+            attributes: Vec::new(),
+            status: SpanStatus::Unset,
+        };
+        attrs.record(&mut FieldVisitor(&mut span));
+
+        let mut state = self.collector.lock();
+        span.span_id = state.trace.next_span_id();
+        span.parent_span_id = parent.and_then(|p| state.open.get(&p).map(|o| o.span_id));
+        state.open.insert(id.clone(), span);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        if let Some(span) = self.collector.lock().open.get_mut(id) {
+            values.record(&mut FieldVisitor(span));
+        }
+    }
+
+    fn on_close(&self, id: Id, _ctx: Context<'_, S>) {
+        let mut state = self.collector.lock();
+        let Some(span) = state.open.remove(&id) else {
+            return;
+        };
+        state.trace.push(OtlpSpan {
+            span_id: span.span_id,
+            parent_span_id: span.parent_span_id,
+            name: span.name,
+            kind: span.kind,
+            start_unix_nanos: span.start_unix_nanos,
+            end_unix_nanos: now_unix_nanos(),
+            attributes: span.attributes,
+            status: span.status,
+        });
+    }
+}
+
+/// Records a span's fields, reading the `otel.*` conventions.
+struct FieldVisitor<'a>(&'a mut OpenSpan);
+
+impl FieldVisitor<'_> {
+    fn set(&mut self, field: &Field, value: AttributeValue) {
+        let span = &mut *self.0;
+        match (field.name(), &value) {
+            ("otel.name", AttributeValue::String(name)) => span.name = name.clone(),
+            ("otel.kind", AttributeValue::String(kind)) => {
+                span.kind = match kind.to_ascii_lowercase().as_str() {
+                    "client" => SpanKind::Client,
+                    "server" => SpanKind::Server,
+                    _ => SpanKind::Internal,
+                };
+            }
+            ("otel.status_code", AttributeValue::String(code)) => {
+                span.status = match code.to_ascii_uppercase().as_str() {
+                    "OK" => SpanStatus::Ok,
+                    "ERROR" => SpanStatus::Error(String::new()),
+                    _ => SpanStatus::Unset,
+                };
+            }
+            (name, _) => {
+                span.attributes.retain(|(key, _)| key != name);
+                span.attributes.push((name.to_string(), value));
+            }
+        }
+    }
+}
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.set(field, value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.set(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.set(field, i64::try_from(value).unwrap_or(i64::MAX).into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.set(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.set(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.set(field, format!("{value:?}").into());
+    }
+}
+
+fn now_unix_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| u64::try_from(d.as_nanos()).unwrap_or(u64::MAX))
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    fn attribute<'a>(span: &'a OtlpSpan, key: &str) -> Option<&'a AttributeValue> {
+        span.attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    #[test]
+    fn nests_tracing_spans_and_parse_traces_under_one_root() {
+        let collector = OtlpCollector::new("https://example.com/recipe");
+        let subscriber = tracing_subscriber::registry().with(collector.layer());
+        tracing::subscriber::with_default(subscriber, || {
+            let root = info_span!("import_recipe", source = "https://example.com/recipe");
+            let _root = root.enter();
+            {
+                let fetch = info_span!(
+                    "fetch",
+                    otel.name = "GET example.com",
+                    otel.kind = "client",
+                    otel.status_code = tracing::field::Empty,
+                    http.response.status_code = tracing::field::Empty,
+                );
+                let _fetch = fetch.enter();
+                fetch.record("http.response.status_code", 404u64);
+                fetch.record("otel.status_code", "ERROR");
+            }
+            collector.trace_lines(["2 cups flour", "salt"]);
+        });
+        let trace = collector.finish();
+        let spans = trace.spans();
+
+        let by_name = |name: &str| spans.iter().find(|s| s.name == name).unwrap();
+        let root = by_name("import_recipe");
+        assert_eq!(root.parent_span_id, None);
+
+        let fetch = by_name("GET example.com");
+        assert_eq!(fetch.parent_span_id, Some(root.span_id));
+        assert_eq!(fetch.kind, SpanKind::Client);
+        assert_eq!(fetch.status, SpanStatus::Error(String::new()));
+        assert_eq!(
+            attribute(fetch, "http.response.status_code"),
+            Some(&AttributeValue::Int(404))
+        );
+
+        let parse = by_name("parse_ingredients");
+        assert_eq!(parse.parent_span_id, Some(root.span_id));
+        let lines: Vec<&OtlpSpan> = spans
+            .iter()
+            .filter(|s| s.parent_span_id == Some(parse.span_id))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|s| attribute(s, "parser.pass").is_some()));
+
+        let ids: std::collections::HashSet<SpanId> = spans.iter().map(|s| s.span_id).collect();
+        assert_eq!(ids.len(), spans.len(), "span IDs must be unique");
+    }
+
+    #[test]
+    fn imports_of_one_source_are_separate_traces() {
+        let a = OtlpCollector::new("book.epub").finish();
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        let b = OtlpCollector::new("book.epub").finish();
+        assert_ne!(a.trace_id(), b.trace_id());
+    }
+}
@@ -18,3 +18,65 @@ pub fn amount_table(amounts: &[Measure]) -> String {
     }
     b.build().with(Style::rounded()).to_string()
 }
+
+/// Render catalog query hits as a recipe/book/time/page table.
+pub fn catalog_table(hits: &[recipe_epub::CatalogHit<'_>]) -> String {
+    let mut b = Builder::default();
+    b.push_record(["recipe", "book", "minutes", "page"]);
+    for h in hits {
+        let meta = &h.recipe.recipe.meta;
+        let minutes = h
+            .recipe
+            .total_minutes()
+            .map(|m| m.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let page = meta.page.clone().unwrap_or_else(|| "-".to_string());
+        b.push_record([meta.title.clone(), h.book.meta.title.clone(), minutes, page]);
+    }
+    b.build().with(Style::rounded()).to_string()
+}
+
+/// Render ranked search hits as a recipe/source/have/missing table. `have` is
+/// "matched/required" so the ranking is legible at a glance.
+pub fn search_table(hits: &[recipe_epub::SearchHit<'_>]) -> String {
+    let mut b = Builder::default();
+    b.push_record(["recipe", "source", "have", "missing"]);
+    for h in hits {
+        let source = std::path::Path::new(&h.recipe.source)
+            .file_stem()
+            .map_or_else(
+                || h.recipe.source.clone(),
+                |s| s.to_string_lossy().into_owned(),
+            );
+        let have = format!("{}/{}", h.matched.len(), h.matched.len() + h.missing.len());
+        b.push_record([
+            h.recipe.meta.title.clone(),
+            source,
+            have,
+            h.missing.join(", "),
+        ]);
+    }
+    b.build().with(Style::rounded()).to_string()
+}
+
+/// Render pre-flight cost estimates as one row per model.
+pub fn estimate_table(estimates: &[recipe_epub::CostEstimate]) -> String {
+    let mut b = Builder::default();
+    b.push_record(["model", "chunks", "to call", "in tok", "out tok", "cost"]);
+    for e in estimates {
+        let u = &e.usage;
+        let input = u.input_tokens + u.cache_creation_input_tokens + u.cache_read_input_tokens;
+        let cost = e
+            .cost_usd()
+            .map_or_else(|| "n/a".to_string(), |c| format!("${c:.4}"));
+        b.push_record([
+            e.model.clone(),
+            e.chunks.to_string(),
+            e.calls().to_string(),
+            input.to_string(),
+            u.output_tokens.to_string(),
+            cost,
+        ]);
+    }
+    b.build().with(Style::rounded()).to_string()
+}
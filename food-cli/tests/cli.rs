@@ -78,6 +78,20 @@ fn emit_corpus_row_fraction_and_modifier() {
     assert_eq!(line.lines().count(), 1);
 }
 
+#[test]
+fn emit_corpus_row_carries_the_qualifier() {
+    let output = food_cli()
+        .args(["parse-ingredient", "scant 1 cup flour", "--emit-corpus-row"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let line = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        line.contains(r#"{"unit": "cup", "value": 1, "qualifier": "scant"}"#),
+        "{line}"
+    );
+}
+
 #[test]
 fn emit_corpus_row_refuses_fallback() {
     // A line that falls back to a name-only parse must be refused (non-zero exit,
@@ -115,6 +129,39 @@ fn scrape_epub_missing_path_exits_cleanly() {
     );
 }
 
+#[test]
+#[cfg(feature = "otlp")]
+fn scrape_epub_exports_a_failed_import_as_otlp() {
+    // Not a zip: the import fails before any network call, and the trace is
+    // still written, with the failure on its root span.
+    let dir = std::env::temp_dir();
+    let id = std::process::id();
+    let epub = dir.join(format!("food-cli-otlp-{id}.epub"));
+    let out = dir.join(format!("food-cli-otlp-{id}.json"));
+    std::fs::write(&epub, "not an epub").unwrap();
+
+    let output = food_cli()
+        .args(["scrape-epub", epub.to_str().unwrap()])
+        .args(["--otlp-out", out.to_str().unwrap()])
+        .output()
+        .unwrap();
+    let written = std::fs::read_to_string(&out);
+    for path in [&epub, &out] {
+        let _ = std::fs::remove_file(path);
+    }
+    assert!(!output.status.success());
+    let json: serde_json::Value = serde_json::from_str(&written.unwrap()).unwrap();
+    let spans = json["resourceSpans"][0]["scopeSpans"][0]["spans"]
+        .as_array()
+        .unwrap();
+    let root = spans
+        .iter()
+        .find(|s| s["name"] == "import_cookbook")
+        .unwrap();
+    assert_eq!(root["status"]["code"], 2);
+    assert!(root.get("parentSpanId").is_none());
+}
+
 #[test]
 fn scan_cookbooks_nonexistent_dir_errors() {
     let output = food_cli()
@@ -191,6 +238,163 @@ fn parse_lines_emits_jsonl_per_line() {
     }
 }
 
+#[test]
+fn corpus_diff_against_saved_snapshot() {
+    let dir = std::env::temp_dir();
+    let id = std::process::id();
+    let lines = dir.join(format!("food-cli-diff-lines-{id}.txt"));
+    let snapshot = dir.join(format!("food-cli-diff-base-{id}.jsonl"));
+    let profile = dir.join(format!("food-cli-diff-profile-{id}.toml"));
+    std::fs::write(&lines, "2 cups chopped walnuts\n1 cup flour\n").unwrap();
+    std::fs::write(
+        &profile,
+        "name = \"nochop\"\n[remove]\npreparation_adjectives = [\"chopped\"]\n",
+    )
+    .unwrap();
+
+    let save = food_cli()
+        .args(["corpus", "diff", "--lines", lines.to_str().unwrap()])
+        .args(["--save", snapshot.to_str().unwrap()])
+        .output()
+        .unwrap();
+    let diff = food_cli()
+        .args(["corpus", "diff", "--baseline", snapshot.to_str().unwrap()])
+        .args(["--profile", profile.to_str().unwrap()])
+        .output()
+        .unwrap();
+    for path in [&lines, &snapshot, &profile] {
+        let _ = std::fs::remove_file(path);
+    }
+    assert!(
+        save.status.success() && diff.status.success(),
+        "stderr: {}{}",
+        String::from_utf8_lossy(&save.stderr),
+        String::from_utf8_lossy(&diff.stderr)
+    );
+    let stdout = String::from_utf8(diff.stdout).unwrap();
+    assert!(
+        stdout.contains("2 line(s) compared · 1 changed"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("-refine:extract_adjectives_from_name"));
+    assert!(stdout.contains("\"chopped walnuts\""));
+}
+
+#[test]
+fn parse_ingredient_trace_out_then_trace_diff() {
+    let dir = std::env::temp_dir();
+    let id = std::process::id();
+    let trace = dir.join(format!("food-cli-trace-{id}.json"));
+    let profile = dir.join(format!("food-cli-trace-profile-{id}.toml"));
+    std::fs::write(
+        &profile,
+        "name = \"nochop\"\n[remove]\npreparation_adjectives = [\"chopped\"]\n",
+    )
+    .unwrap();
+    let line = "2 cups chopped walnuts";
+
+    let save = food_cli()
+        .args([
+            "parse-ingredient",
+            line,
+            "--trace-out",
+            trace.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    let same = food_cli()
+        .args([
+            "parse-ingredient",
+            line,
+            "--trace-diff",
+            trace.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    let changed = food_cli()
+        .args([
+            "parse-ingredient",
+            line,
+            "--trace-diff",
+            trace.to_str().unwrap(),
+        ])
+        .args(["--profile", profile.to_str().unwrap()])
+        .output()
+        .unwrap();
+    for path in [&trace, &profile] {
+        let _ = std::fs::remove_file(path);
+    }
+    for run in [&save, &same, &changed] {
+        assert!(
+            run.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&run.stderr)
+        );
+    }
+    let same = String::from_utf8(same.stdout).unwrap();
+    assert!(same.starts_with("traces match"), "{same}");
+    let changed = String::from_utf8(changed.stdout).unwrap();
+    assert!(
+        changed.contains("first divergence: parse_line › extract_adjectives_from_name"),
+        "{changed}"
+    );
+    assert!(changed.contains("◀ first divergence"));
+}
+
+#[test]
+fn corpus_label_writes_accepted_rows_into_their_section() {
+    use std::io::Write as _;
+
+    let dir = std::env::temp_dir();
+    let id = std::process::id();
+    let lines = dir.join(format!("food-cli-label-lines-{id}.txt"));
+    let corpus = dir.join(format!("food-cli-label-corpus-{id}.jsonl"));
+    std::fs::write(&lines, "For the glaze:\n2 cups sugar\n1 cup flour\n").unwrap();
+    std::fs::write(
+        &corpus,
+        "// --- basics ---\n{\"input\": \"1 cup flour\", \"name\": \"flour\", \"amounts\": [{\"unit\": \"cup\", \"value\": 1}]}\n// --- other ---\n",
+    )
+    .unwrap();
+
+    let list = food_cli()
+        .args(["corpus", "label", lines.to_str().unwrap(), "--list"])
+        .args(["--corpus", corpus.to_str().unwrap()])
+        .output()
+        .unwrap();
+    let mut child = food_cli()
+        .args(["corpus", "label", lines.to_str().unwrap()])
+        .args(["--corpus", corpus.to_str().unwrap()])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"a\n").unwrap();
+    let label = child.wait_with_output().unwrap();
+    let written = std::fs::read_to_string(&corpus).unwrap();
+    for path in [&lines, &corpus] {
+        let _ = std::fs::remove_file(path);
+    }
+
+    assert!(
+        list.status.success() && label.status.success(),
+        "stderr: {}{}",
+        String::from_utf8_lossy(&list.stderr),
+        String::from_utf8_lossy(&label.stderr)
+    );
+    // The header and the already-labeled line are not offered.
+    let listed = String::from_utf8(list.stdout).unwrap();
+    assert_eq!(listed.lines().count(), 1, "{listed}");
+    assert!(listed.contains("\t2 cups sugar\t"));
+    // Filed beside its nearest row, ahead of the next section's header.
+    let rows: Vec<&str> = written.lines().collect();
+    assert_eq!(
+        rows[2],
+        r#"{"input": "2 cups sugar", "name": "sugar", "amounts": [{"unit": "cup", "value": 2}]}"#
+    );
+    assert_eq!(rows[3], "// --- other ---");
+}
+
 #[test]
 fn corpus_lint_report_stages_runs() {
     // The default corpus path resolves relative to the crate manifest, so a bare
@@ -244,3 +448,40 @@ fn parse_rich_text_json() {
             .any(|c| c.get("kind") == Some(&serde_json::json!("Measure")))
     );
 }
+
+#[test]
+fn corpus_shapes_reports_clusters_and_uncovered_harvest_shapes() {
+    let dir = std::env::temp_dir();
+    let id = std::process::id();
+    let lines = dir.join(format!("food-cli-shapes-lines-{id}.txt"));
+    let corpus = dir.join(format!("food-cli-shapes-corpus-{id}.jsonl"));
+    std::fs::write(&lines, "3 tbsp sugar\n2 onions, diced\n").unwrap();
+    std::fs::write(
+        &corpus,
+        "{\"input\": \"1 cup flour\", \"name\": \"flour\", \"amounts\": [{\"unit\": \"cup\", \"value\": 1}]}\n{\"input\": \"2 cups flour\", \"name\": \"flour\", \"amounts\": [{\"unit\": \"cup\", \"value\": 2}]}\n",
+    )
+    .unwrap();
+
+    let out = food_cli()
+        .args(["corpus", "shapes", "--lines", lines.to_str().unwrap()])
+        .args(["--corpus", corpus.to_str().unwrap()])
+        .output()
+        .unwrap();
+    for path in [&lines, &corpus] {
+        let _ = std::fs::remove_file(path);
+    }
+
+    assert!(
+        out.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        stdout.starts_with("2 corpus row(s) in 1 shape(s); 1 near-duplicate pair(s)"),
+        "{stdout}"
+    );
+    // "3 tbsp sugar" has the flour rows' shape; only the onion line is new.
+    assert!(stdout.contains("2 onions, diced"), "{stdout}");
+    assert!(!stdout.contains("3 tbsp sugar"), "{stdout}");
+}
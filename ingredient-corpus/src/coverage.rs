@@ -0,0 +1,167 @@
+//! Which parser passes a set of lines exercises: per stage, how many lines
+//! each normalize rewrite, recognizer, segment decision and refine pass fired
+//! on.
+//!
+//! `food-cli corpus lint --report-stages` renders this over the corpus, and
+//! [`label::rank`](crate::label::rank) uses it to find thinly-covered passes.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use ingredient::IngredientParser;
+use ingredient::trace::{PipelineStageNames, StageReport, StageRewrite};
+
+/// Fire counts for one stage's passes: pass name → number of rows it fired on.
+/// A `BTreeMap` keeps the "zero coverage" listing deterministic; ordering for the
+/// main table is taken from the pass universe instead (pipeline order).
+pub type FireCounts = BTreeMap<String, usize>;
+
+/// The tallied result of running the corpus through the traced parser.
+pub struct StageCoverage {
+    pub total_rows: usize,
+    pub normalize: FireCounts,
+    pub recognize: FireCounts,
+    pub segment: FireCounts,
+    pub refine: FireCounts,
+    /// Every pass the parser could have fired, in pipeline order.
+    pub universe: PipelineStageNames,
+}
+
+impl StageCoverage {
+    /// How many rows `pass` fired on in `stage` (a [`fired_passes`] stage
+    /// name); 0 for a pass that never fired or an unknown stage.
+    pub fn rows(&self, stage: &str, pass: &str) -> usize {
+        let counts = match stage {
+            "normalize" => &self.normalize,
+            "recognize" => &self.recognize,
+            "segment" => &self.segment,
+            "refine" => &self.refine,
+            _ => return 0,
+        };
+        counts.get(pass).copied().unwrap_or(0)
+    }
+}
+
+/// Parse each row through the traced path and tally, per stage, how many rows
+/// each pass fired on. Pure over the input rows so it can be unit-tested without
+/// touching the filesystem.
+pub fn report_stages_over(rows: &[String]) -> StageCoverage {
+    report_stages_with(&IngredientParser::new(), rows)
+}
+
+/// [`report_stages_over`] with a configured parser, so its
+/// [registered passes](IngredientParser::with_pass) are counted too.
+pub fn report_stages_with(parser: &IngredientParser, rows: &[String]) -> StageCoverage {
+    let mut cov = StageCoverage {
+        total_rows: rows.len(),
+        normalize: FireCounts::new(),
+        recognize: FireCounts::new(),
+        segment: FireCounts::new(),
+        refine: FireCounts::new(),
+        universe: parser.pipeline_stage_names(),
+    };
+
+    for input in rows {
+        let stages = parser.parse_with_trace(input).trace.stages();
+        let [normalize, recognize, segment, refine] = fired_passes(&stages);
+        for (counts, (_, fired)) in [
+            (&mut cov.normalize, normalize),
+            (&mut cov.recognize, recognize),
+            (&mut cov.segment, segment),
+            (&mut cov.refine, refine),
+        ] {
+            for name in fired {
+                *counts.entry(name.to_string()).or_default() += 1;
+            }
+        }
+    }
+    cov
+}
+
+/// The passes that fired on one parse, per stage (normalize, recognize,
+/// segment, refine), each named once.
+///
+/// A normalize rewrite / refine pass appears in the report only when it changed
+/// the line, so mere presence == it fired. A recognizer appears for every
+/// attempt, so it fired only when it produced output. Segment nodes appear per
+/// clause decision / assembly repair that fired. A label can fire several times
+/// on one line (e.g. two `prep_chain` clause decisions in a multi-clause
+/// modifier); the set names it once, so a rows-per-pass tally never exceeds
+/// 100%.
+pub fn fired_passes(stages: &StageReport) -> [(&'static str, BTreeSet<&str>); 4] {
+    fn names(rewrites: &[StageRewrite]) -> BTreeSet<&str> {
+        rewrites.iter().map(|rw| rw.name.as_str()).collect()
+    }
+    [
+        ("normalize", names(&stages.normalize)),
+        (
+            "recognize",
+            stages
+                .recognizers
+                .iter()
+                .filter(|rec| rec.output.is_some())
+                .map(|rec| rec.name.as_str())
+                .collect(),
+        ),
+        ("segment", names(&stages.segment)),
+        ("refine", names(&stages.refine)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_stages_counts_a_known_pass() {
+        // "chopped walnuts" fires the `extract_adjectives_from_name` refine pass;
+        // "(1 cup walnuts)" matches the `optional_wrapped` recognizer. Assert both
+        // show up with a positive count.
+        let rows = vec![
+            "2 cups chopped walnuts".to_string(),
+            "(1 cup walnuts)".to_string(),
+            "2 cups flour".to_string(),
+        ];
+        let cov = report_stages_over(&rows);
+        assert_eq!(cov.total_rows, 3);
+        assert!(
+            cov.refine
+                .get("extract_adjectives_from_name")
+                .copied()
+                .unwrap_or(0)
+                > 0,
+            "expected extract_adjectives_from_name to fire; got {:?}",
+            cov.refine
+        );
+        assert!(
+            cov.recognize.get("optional_wrapped").copied().unwrap_or(0) > 0,
+            "expected optional_wrapped recognizer to fire; got {:?}",
+            cov.recognize
+        );
+    }
+
+    #[test]
+    fn report_stages_counts_rows_not_trace_nodes() {
+        // A multi-clause line can fire the same segment label more than once
+        // (two prep-chain clauses here). The report is rows-per-pass, so no
+        // count may ever exceed the row total.
+        let rows = vec![
+            "1/2 cup deribbed, seeded, and roughly chopped fresh hot green chiles, such as serrano"
+                .to_string(),
+        ];
+        let cov = report_stages_over(&rows);
+        for (stage, counts) in [
+            ("normalize", &cov.normalize),
+            ("recognize", &cov.recognize),
+            ("segment", &cov.segment),
+            ("refine", &cov.refine),
+        ] {
+            for (name, n) in counts {
+                assert!(
+                    *n <= cov.total_rows,
+                    "{stage} pass {name} counted {n} > {} rows",
+                    cov.total_rows
+                );
+            }
+        }
+    }
+}
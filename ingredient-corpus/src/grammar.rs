@@ -0,0 +1,143 @@
+//! A corpus-seeded line generator for fuzzing.
+//!
+//! Byte-level fuzzing rarely assembles "1 (14-ounce) can tomatoes, drained":
+//! the parser's interesting paths sit behind real units, real names and real
+//! clause shapes. [`Grammar`] harvests the amount, name and modifier spans the
+//! parser carves from every corpus row ([`IngredientParser::decompose`]) and
+//! recombines them through ingredient-line templates — amount-first,
+//! name-first, alternatives, paren equivalents, trailing notes.
+//!
+//! [`Grammar::line`] is driven by a byte slice, one byte per choice, so a
+//! fuzzer's mutations steer the line and a proptest byte vector shrinks to the
+//! simplest line that still fails.
+
+use std::collections::BTreeSet;
+
+use ingredient::{Field, IngredientParser};
+
+use crate::Corpus;
+
+/// Line templates: `{a}` an amount, `{n}` a name, `{m}` a modifier.
+const TEMPLATES: &[&str] = &[
+    "{a} {n}",
+    "{a} {n}, {m}",
+    "{a} {m} {n}",
+    "{a} ({a}) {n}",
+    "{a} {n} ({m})",
+    "{n}, {a}",
+    "{n}",
+    "{a} {n} or {n}",
+    "{a} plus {a} {n}",
+    "{a} {n}, {m}, or {a} {n}",
+];
+
+/// Endings appended after the template: mostly nothing, otherwise a note the
+/// normalize and segment stages handle.
+const SUFFIXES: &[&str] = &[
+    "",
+    "",
+    "",
+    " (optional)",
+    ", to taste",
+    ", for serving",
+    ", divided",
+    " (see note)",
+    "*",
+];
+
+/// The vocabulary harvested from a corpus. Each list is deduplicated and
+/// sorted, so a byte sequence always builds the same line.
+pub struct Grammar {
+    pub amounts: Vec<String>,
+    pub names: Vec<String>,
+    pub modifiers: Vec<String>,
+}
+
+impl Grammar {
+    /// Harvest every row's amount, name and modifier spans.
+    pub fn from_corpus(parser: &IngredientParser, corpus: &Corpus) -> Grammar {
+        let mut amounts = BTreeSet::new();
+        let mut names = BTreeSet::new();
+        let mut modifiers = BTreeSet::new();
+        for row in corpus.rows() {
+            for span in parser.decompose(&row.input).spans {
+                let text = span.text.trim();
+                if text.is_empty() {
+                    continue;
+                }
+                let list = match span.field {
+                    Field::Amount => &mut amounts,
+                    Field::Name => &mut names,
+                    Field::Modifier | Field::Alternative => &mut modifiers,
+                    // The suffixes already supply notes like these.
+                    Field::Usage | Field::Optional => continue,
+                };
+                list.insert(text.to_string());
+            }
+        }
+        Grammar {
+            amounts: amounts.into_iter().collect(),
+            names: names.into_iter().collect(),
+            modifiers: modifiers.into_iter().collect(),
+        }
+    }
+
+    /// Build one line, each byte of `choices` picking the next template,
+    /// filler or suffix. Past the last byte every choice is the first option,
+    /// so any slice — even an empty one — yields a line.
+    pub fn line(&self, choices: &[u8]) -> String {
+        let mut choices = choices.iter().map(|&b| usize::from(b));
+        let mut pick = |n: usize| choices.next().map_or(0, |b| b % n.max(1));
+
+        let template = TEMPLATES[pick(TEMPLATES.len())];
+        let mut line = String::new();
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            line.push_str(&rest[..open]);
+            let list = match rest.get(open + 1..open + 2) {
+                Some("a") => &self.amounts,
+                Some("n") => &self.names,
+                _ => &self.modifiers,
+            };
+            if let Some(word) = list.get(pick(list.len())) {
+                line.push_str(word);
+            }
+            rest = &rest[open + 3..];
+        }
+        line.push_str(rest);
+        line.push_str(SUFFIXES[pick(SUFFIXES.len())]);
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grammar() -> Grammar {
+        Grammar::from_corpus(
+            &IngredientParser::new(),
+            &crate::parse(
+                r#"{"input": "2 cups flour, sifted", "name": "flour"}
+{"input": "1 large onion", "name": "onion"}"#,
+            ),
+        )
+    }
+
+    #[test]
+    fn harvests_each_field_from_the_rows() {
+        let g = grammar();
+        assert_eq!(g.amounts, ["1 large", "2 cups"]);
+        assert_eq!(g.names, ["flour", "onion"]);
+        assert_eq!(g.modifiers, ["sifted"]);
+    }
+
+    #[test]
+    fn bytes_steer_the_template_and_fillers() {
+        let g = grammar();
+        assert_eq!(g.line(&[]), "1 large flour");
+        // template 1, second amount, second name, the modifier, "(optional)".
+        assert_eq!(g.line(&[1, 1, 1, 0, 3]), "2 cups onion, sifted (optional)");
+        assert_eq!(g.line(&[5, 0, 1]), "flour, 2 cups");
+    }
+}
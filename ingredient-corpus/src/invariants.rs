@@ -0,0 +1,185 @@
+//! Parse invariants: properties every parse must keep on *any* input, not just
+//! the labeled rows.
+//!
+//! The corpus ratchet pins what a line parses to; these pin what every parse
+//! must look like, so a fuzzer can hunt for violations with no label in hand.
+//! Each check returns `Err` with a description of the violation rather than
+//! panicking, so the same functions serve the proptest suite
+//! (`ingredient-parser/tests/invariants.rs`) and the `invariants` cargo-fuzz
+//! target, which panics on the first `Err`.
+//!
+//! - [`check_decomposition`]: decomposition spans lie within the source, on char
+//!   boundaries, in order, and don't overlap.
+//! - [`check_bounds`]: a range's lower bound never exceeds its upper bound.
+//! - [`check_scale_round_trip`]: `m.scale(f).scale(1 / f)` is `m` again.
+//! - [`check_normalize_idempotent`]: normalizing twice changes nothing more,
+//!   and parsing the normalized line gives the same ingredient.
+//! - [`check_display_round_trip`]: a measure's `Display` text re-parses to the
+//!   same measure, to the two decimals `Display` prints.
+
+use ingredient::unit::{Measure, MeasureKind, Unit};
+use ingredient::{Decomposition, IngredientParser};
+
+/// Factors [`check_measure`] scales by: recipe-style doublings and halvings,
+/// plus thirds, whose reciprocal is not a terminating decimal.
+pub const SCALE_FACTORS: [f64; 6] = [2.0, 0.5, 3.0, 1.0 / 3.0, 1.5, 0.25];
+
+/// `Display` rounds to two decimals, so a re-parsed value may be off by half
+/// a hundredth.
+const DISPLAY_PRECISION: f64 = 0.005;
+
+/// Every invariant over one line: its decomposition, normalize idempotence, and
+/// each parsed amount ([`check_measure`]).
+pub fn check_line(parser: &IngredientParser, line: &str) -> Result<(), String> {
+    check_decomposition(&parser.decompose(line))?;
+    check_normalize_idempotent(parser, line)?;
+    for m in &parser.from_str(line).amounts {
+        check_measure(parser, m)?;
+    }
+    Ok(())
+}
+
+/// Every invariant over one measure: ordered bounds, a scale round trip by
+/// each of [`SCALE_FACTORS`], and a `Display` round trip.
+pub fn check_measure(parser: &IngredientParser, m: &Measure) -> Result<(), String> {
+    check_bounds(m)?;
+    for factor in SCALE_FACTORS {
+        check_scale_round_trip(m, factor)?;
+    }
+    check_display_round_trip(parser, m)
+}
+
+/// The spans index the source: each range is in bounds, on char boundaries,
+/// holds its `text`, and starts at or after the previous span's end.
+pub fn check_decomposition(decomp: &Decomposition) -> Result<(), String> {
+    let mut end = 0;
+    for span in &decomp.spans {
+        let range = span.range.clone();
+        let Some(text) = decomp.source.get(range.clone()) else {
+            return Err(format!(
+                "{:?} span {range:?} is not a slice of {:?} ({} bytes)",
+                span.field,
+                decomp.source,
+                decomp.source.len()
+            ));
+        };
+        if text != span.text {
+            return Err(format!(
+                "{:?} span {range:?} holds {text:?} but says {:?}",
+                span.field, span.text
+            ));
+        }
+        if range.start < end {
+            return Err(format!(
+                "{:?} span {range:?} overlaps the previous span (ends at {end})",
+                span.field
+            ));
+        }
+        end = range.end;
+    }
+    Ok(())
+}
+
+/// A range's lower bound is at most its upper bound, and neither is NaN.
+pub fn check_bounds(m: &Measure) -> Result<(), String> {
+    let value = m.value();
+    if value.is_nan() {
+        return Err(format!("{m:?} has a NaN value"));
+    }
+    match m.upper_value() {
+        Some(upper) if upper.is_nan() || upper < value => {
+            Err(format!("{m:?} has bounds out of order ({value} > {upper})"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Scaling by `factor` and then by its reciprocal gives back `m` — exactly
+/// when the rational arithmetic doesn't overflow, to f64 rounding when it
+/// does. A scaled value beyond `i64` ("4e18" tripled) saturates, so it is
+/// exempt.
+pub fn check_scale_round_trip(m: &Measure, factor: f64) -> Result<(), String> {
+    let fits = |v: f64| (v * factor).abs() < i64::MAX as f64;
+    if !fits(m.value()) || m.upper_value().is_some_and(|u| !fits(u)) {
+        return Ok(());
+    }
+    let back = m.scale(factor).scale(1.0 / factor);
+    if back == *m {
+        return Ok(());
+    }
+    let close = |a: f64, b: f64| (a - b).abs() <= 1e-9 * a.abs().max(b.abs()).max(1.0);
+    let same = back.unit() == m.unit()
+        && back.qualifier() == m.qualifier()
+        && close(back.value(), m.value())
+        && match (back.upper_value(), m.upper_value()) {
+            (Some(a), Some(b)) => close(a, b),
+            (a, b) => a == b,
+        };
+    if same {
+        Ok(())
+    } else {
+        Err(format!(
+            "{m:?} scaled by {factor} and back is {back:?}, not the original"
+        ))
+    }
+}
+
+/// Parsing the normalized line gives what parsing the raw line does, and the
+/// normalized line is a fixed point of normalize.
+pub fn check_normalize_idempotent(parser: &IngredientParser, line: &str) -> Result<(), String> {
+    let once = parser.normalize(line);
+    let twice = parser.normalize(&once);
+    if twice != once {
+        return Err(format!(
+            "normalize is not idempotent: {line:?} → {once:?} → {twice:?}"
+        ));
+    }
+    let raw = parser.from_str(line);
+    let normalized = parser.from_str(&once);
+    if raw != normalized {
+        return Err(format!(
+            "{line:?} parses to {raw:?} but its normalized form {once:?} parses to {normalized:?}"
+        ));
+    }
+    Ok(())
+}
+
+/// `m`'s `Display` text parses back to one measure with the unit and
+/// qualifier `m` displays with, and values within `Display`'s precision.
+///
+/// Money, free-text units and negative values are exempt: the ingredient
+/// grammar reads neither "$5" nor a bare "3 large" as an amount ("large" is a
+/// unit only ahead of a name, as in "3 large eggs"), and a leading `-` before
+/// a fraction glyph ("-⅒") reads as a range dash, not a sign.
+pub fn check_display_round_trip(parser: &IngredientParser, m: &Measure) -> Result<(), String> {
+    if matches!(m.unit(), Unit::Other(_)) || m.kind() == MeasureKind::Money || m.value() < 0.0 {
+        return Ok(());
+    }
+    let text = m.to_string();
+    let shown = m.denormalize();
+    let reparsed = match parser.parse_amount(&text).as_deref() {
+        Ok([one]) => one.clone(),
+        Ok(many) => {
+            return Err(format!(
+                "{m:?} displays as {text:?}, which parses to {} measures: {many:?}",
+                many.len()
+            ));
+        }
+        Err(e) => return Err(format!("{m:?} displays as {text:?}, which fails: {e}")),
+    };
+    let close = |a: f64, b: f64| (a - b).abs() <= DISPLAY_PRECISION;
+    // `Display` drops a zero lower bound ("up to 2 cups" shows as "2 cups").
+    let bounds = match (shown.upper_value(), reparsed.upper_value()) {
+        (Some(u), None) if shown.value() == 0.0 => close(u, reparsed.value()),
+        (Some(a), Some(b)) => close(a, b) && close(shown.value(), reparsed.value()),
+        (None, None) => close(shown.value(), reparsed.value()),
+        _ => false,
+    };
+    if bounds && reparsed.unit() == shown.unit() && reparsed.qualifier() == m.qualifier() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{m:?} displays as {text:?}, which parses to {reparsed:?}"
+        ))
+    }
+}
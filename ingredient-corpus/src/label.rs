@@ -0,0 +1,483 @@
+//! Labeling: pick the lines worth labeling next from a parse dump, and turn a
+//! reviewed parse into a corpus row.
+//!
+//! [`rank`] orders unlabeled lines by how much a label would teach the corpus:
+//! - the parse's own [`ReviewReason`]s — a fallback, an unparsed digit, or a
+//!   field a competing reading disputes (see
+//!   [`IngredientParser::parse_candidates`]);
+//! - passes that fired on the line but on few corpus rows (from
+//!   [`StageCoverage`]), so thinly-guarded code gets its next guard;
+//! - distance from every existing row, so the corpus grows sideways instead of
+//!   collecting near-copies.
+//!
+//! Each [`Candidate`] carries a [`Draft`] prefilled from the current parse and
+//! the section its nearest existing row lives in. [`Draft::to_row`] only
+//! returns a row the accuracy ratchet accepts: a committed row must match the
+//! parse, and a row that doesn't must say why in `xfail`. [`insert_row`] files
+//! it under its section. Both `food-cli corpus label` and the `food-app` Label
+//! tab drive this module.
+
+use std::collections::{BTreeSet, HashSet};
+
+use crate::coverage::{StageCoverage, fired_passes};
+use crate::{Corpus, CorpusRow, Status};
+use ingredient::section::{LineKind, classify_line};
+use ingredient::unit::Measure;
+use ingredient::{Ingredient, IngredientParser, IngredientUsage, Reading, ReviewReason};
+
+/// A pass that fired on this many corpus rows or fewer counts as thinly
+/// covered.
+pub const RARE_PASS_ROWS: usize = 2;
+
+/// A line whose nearest corpus row shares less than this share of its words
+/// counts as unlike the corpus.
+pub const NOVEL_BELOW: f64 = 0.5;
+
+/// Where a row goes when its nearest neighbour is ungrouped (or the corpus is
+/// empty).
+pub const DEFAULT_SECTION: &str = "labeled";
+
+/// Every usage a label can pick, in declaration order.
+pub const USAGES: [IngredientUsage; 7] = [
+    IngredientUsage::Normal,
+    IngredientUsage::FryingMedium,
+    IngredientUsage::PanGrease,
+    IngredientUsage::Seasoning,
+    IngredientUsage::Dredging,
+    IngredientUsage::Garnish,
+    IngredientUsage::Marinade,
+];
+
+/// Why a line ranks where it does.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Signal {
+    /// The parse flagged itself for review.
+    Review(ReviewReason),
+    /// A pass fired here that fired on at most [`RARE_PASS_ROWS`] corpus rows.
+    RarePass {
+        stage: &'static str,
+        pass: String,
+        rows: usize,
+    },
+    /// The nearest corpus row's word overlap, below [`NOVEL_BELOW`].
+    Novel { similarity: f64 },
+}
+
+impl Signal {
+    /// How much this signal adds to a candidate's score. A flagged parse is
+    /// the strongest evidence of a gap; a disputed field and an unguarded pass
+    /// are next; novelty scales with the distance.
+    pub fn weight(&self) -> f64 {
+        match self {
+            Signal::Review(ReviewReason::FellBack | ReviewReason::UnparsedDigit) => 4.0,
+            Signal::Review(ReviewReason::Uncertain(_)) => 2.0,
+            Signal::RarePass { rows, .. } => 2.0 - *rows as f64 / RARE_PASS_ROWS as f64,
+            Signal::Novel { similarity } => 3.0 * (1.0 - similarity),
+        }
+    }
+}
+
+impl std::fmt::Display for Signal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Signal::Review(reason) => write!(f, "{reason}"),
+            Signal::RarePass { stage, pass, rows } => {
+                write!(f, "{stage}:{pass} fired on {rows} corpus row(s)")
+            }
+            Signal::Novel { similarity } => write!(
+                f,
+                "unlike existing rows ({:.0}% word overlap)",
+                similarity * 100.0
+            ),
+        }
+    }
+}
+
+/// An unlabeled line, its score, and the prefilled label.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub input: String,
+    /// The sum of the signals' weights.
+    pub score: f64,
+    pub signals: Vec<Signal>,
+    pub draft: Draft,
+}
+
+/// An editable corpus row. Text fields hold what a person types: `amounts`
+/// is the corpus JSON array, and an empty `modifier` means none.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Draft {
+    pub input: String,
+    pub name: String,
+    pub amounts: String,
+    pub modifier: String,
+    pub optional: bool,
+    pub usage: IngredientUsage,
+    /// Set to mark the row a known gap; the labels are then the parse wanted.
+    pub xfail: Option<String>,
+    /// The corpus section the row is filed under.
+    pub section: String,
+}
+
+impl Draft {
+    /// A draft holding exactly what the parser produced for `input`.
+    pub fn from_parse(input: &str, ing: &Ingredient, section: &str) -> Self {
+        let amounts: Vec<String> = ing.amounts.iter().map(amount_json).collect();
+        Self {
+            input: input.to_string(),
+            name: ing.name.clone(),
+            amounts: format!("[{}]", amounts.join(", ")),
+            modifier: ing.modifier.clone().unwrap_or_default(),
+            optional: ing.optional,
+            usage: ing.usage,
+            xfail: None,
+            section: section.to_string(),
+        }
+    }
+
+    /// The one-line JSONL row, keys in corpus order (`input, name, amounts,
+    /// modifier, optional, usage, xfail`) with the defaults omitted: empty
+    /// `amounts`, no `modifier`, `optional: false`, `usage: normal`. Checks
+    /// only the row's shape; see [`Draft::to_row`] for the ratchet's rules.
+    pub fn to_json(&self) -> Result<String, String> {
+        // Assemble by hand so key order is stable without the serde_json
+        // `preserve_order` feature. Each `json!` value is valid JSON already.
+        let field = |k: &str, v: serde_json::Value| format!("{}: {v}", serde_json::json!(k));
+        let mut parts = vec![
+            field("input", serde_json::json!(self.input)),
+            field("name", serde_json::json!(self.name.trim())),
+        ];
+        let amounts = self.amounts.trim();
+        let parsed: Vec<serde_json::Value> = if amounts.is_empty() {
+            Vec::new()
+        } else {
+            serde_json::from_str(amounts).map_err(|e| format!("amounts: {e}"))?
+        };
+        if !parsed.is_empty() {
+            // The typed text, not a re-serialization, which would alphabetize
+            // each amount's keys.
+            parts.push(format!("\"amounts\": {amounts}"));
+        }
+        let modifier = self.modifier.trim();
+        if !modifier.is_empty() {
+            parts.push(field("modifier", serde_json::json!(modifier)));
+        }
+        if self.optional {
+            parts.push(field("optional", serde_json::json!(true)));
+        }
+        if self.usage != IngredientUsage::Normal {
+            let usage = serde_json::to_value(self.usage).map_err(|e| e.to_string())?;
+            parts.push(field("usage", usage));
+        }
+        if let Some(reason) = &self.xfail {
+            parts.push(field("xfail", serde_json::json!(reason.trim())));
+        }
+        Ok(format!("{{{}}}", parts.join(", ")))
+    }
+
+    /// The row, once it loads as a [`CorpusRow`] and scores the way its kind
+    /// must: a committed row exactly, an xfail row still failing (an xfail
+    /// that already passes is a mislabel, not a gap).
+    pub fn to_row(&self) -> Result<String, String> {
+        if self.name.trim().is_empty() {
+            return Err("the name is empty".to_string());
+        }
+        if self.xfail.as_ref().is_some_and(|r| r.trim().is_empty()) {
+            return Err("an xfail row needs a reason".to_string());
+        }
+        let json = self.to_json()?;
+        let row: CorpusRow = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+        let scored = crate::score(&row);
+        match scored.status {
+            Status::Exact | Status::Xfail => Ok(json),
+            Status::Regression => {
+                let fields: Vec<&str> = scored.mismatches().map(|d| d.field.as_str()).collect();
+                Err(format!(
+                    "the parse disagrees on {}; mark the row xfail with a reason",
+                    fields.join(", ")
+                ))
+            }
+            Status::Promote => {
+                Err("the parse already matches these labels; drop the xfail".to_string())
+            }
+        }
+    }
+}
+
+/// Serialize one amount as a corpus `{"unit": .., "value": ..}` object, keys
+/// in corpus order (unit, value, upper_value, qualifier), the last two
+/// omitted when absent.
+pub fn amount_json(m: &Measure) -> String {
+    let unit = serde_json::json!(m.unit().to_string());
+    let value = value_json(m.value_as_fraction_str(), m.value());
+    let upper = m
+        .upper_value()
+        .map(|upper| {
+            let upper = value_json(m.upper_value_as_fraction_str(), upper);
+            format!(r#", "upper_value": {upper}"#)
+        })
+        .unwrap_or_default();
+    let qualifier = m
+        .qualifier()
+        .map(|q| format!(r#", "qualifier": {}"#, serde_json::json!(q)))
+        .unwrap_or_default();
+    format!(r#"{{"unit": {unit}, "value": {value}{upper}{qualifier}}}"#)
+}
+
+/// Render a corpus amount value: an exact fraction *string* (`"2/3"`) for a
+/// non-terminating decimal, else a plain JSON number with no trailing `.0`
+/// (`2.0` → `2`, `0.5` → `0.5`) — matching the hand-authored corpus convention
+/// so ⅔ round-trips exactly rather than as `0.666…`.
+fn value_json(frac: Option<String>, val: f64) -> String {
+    match frac {
+        Some(s) => serde_json::json!(s).to_string(),
+        // An integer-valued f64 writes as a bare int; keep `serde_json`'s float
+        // rendering (shortest round-trip) for the rest.
+        None if val.fract() == 0.0 && val.abs() < i64::MAX as f64 => (val as i64).to_string(),
+        None => serde_json::json!(val).to_string(),
+    }
+}
+
+/// The words of a line as a set, lowercased with a plural `s` dropped and
+/// every number folded into one `#` token, so "1 cup flour" and "3 cups flour"
+/// read as the same words.
+pub fn words(line: &str) -> BTreeSet<String> {
+    line.split(|c: char| !c.is_alphanumeric() && !ingredient::fraction::is_vulgar(c))
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            if w.chars()
+                .any(|c| c.is_ascii_digit() || ingredient::fraction::is_vulgar(c))
+            {
+                "#".to_string()
+            } else {
+                let w = w.to_lowercase();
+                match w.strip_suffix('s') {
+                    Some(stem) if stem.len() > 2 && !stem.ends_with('s') => stem.to_string(),
+                    _ => w,
+                }
+            }
+        })
+        .collect()
+}
+
+/// Jaccard overlap of two word sets.
+pub fn similarity(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Rank `lines` for labeling, best first, against the labeled `corpus` and its
+/// pass `coverage` ([`report_stages_over`](crate::coverage::report_stages_over)
+/// of the corpus inputs).
+///
+/// Lines already in the corpus, repeats, and lines that aren't ingredients
+/// (section headers, notes) are dropped; every other line is kept, so a
+/// zero-score line still shows up at the bottom.
+pub fn rank(lines: &[String], corpus: &Corpus, coverage: &StageCoverage) -> Vec<Candidate> {
+    let parser = IngredientParser::new();
+    let labeled: Vec<(BTreeSet<String>, &str)> = corpus
+        .entries
+        .iter()
+        .filter_map(|e| {
+            let row = e.parsed.as_ref().ok()?;
+            let section = corpus.sections.get(e.section).map_or("", String::as_str);
+            Some((words(&row.input), section))
+        })
+        .collect();
+    let mut seen: HashSet<&str> = corpus.rows().map(|r| r.input.as_str()).collect();
+
+    let mut out = Vec::new();
+    for line in lines {
+        let line = line.trim();
+        if classify_line(line) != LineKind::Ingredient || !seen.insert(line) {
+            continue;
+        }
+        let mut signals = Vec::new();
+
+        // The primary reading is what `from_str` returns; its review reasons
+        // include the fields its rivals dispute.
+        let candidates = parser.parse_candidates(line, Reading::ALTERNATIVES.len() + 1);
+        let primary = candidates.iter().find(|c| c.reading == Reading::Primary);
+        let ing = primary.map_or_else(|| parser.from_str(line), |c| c.ingredient.clone());
+        match primary {
+            Some(c) => signals.extend(c.review_reasons().into_iter().map(Signal::Review)),
+            None => signals.extend(
+                ing.parse_notes
+                    .review_reasons()
+                    .into_iter()
+                    .map(Signal::Review),
+            ),
+        }
+
+        let stages = parser.parse_with_trace(line).trace.stages();
+        for (stage, fired) in fired_passes(&stages) {
+            for pass in fired {
+                let rows = coverage.rows(stage, pass);
+                if rows <= RARE_PASS_ROWS {
+                    signals.push(Signal::RarePass {
+                        stage,
+                        pass: pass.to_string(),
+                        rows,
+                    });
+                }
+            }
+        }
+
+        let mine = words(line);
+        let nearest = labeled
+            .iter()
+            .map(|(theirs, section)| (similarity(&mine, theirs), *section))
+            .max_by(|a, b| a.0.total_cmp(&b.0));
+        let (closest, section) = nearest.unwrap_or((0.0, ""));
+        if closest < NOVEL_BELOW {
+            signals.push(Signal::Novel {
+                similarity: closest,
+            });
+        }
+        let section = if section.is_empty() || section == "(ungrouped)" {
+            DEFAULT_SECTION
+        } else {
+            section
+        };
+
+        out.push(Candidate {
+            input: line.to_string(),
+            score: signals.iter().fold(0.0, |sum, s| sum + s.weight()),
+            draft: Draft::from_parse(line, &ing, section),
+            signals,
+        });
+    }
+    out.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.input.cmp(&b.input)));
+    out
+}
+
+/// The corpus text with `row` added as the last row of `section`; a section
+/// the corpus doesn't have yet is opened at the end with a `// --- name ---`
+/// header.
+pub fn insert_row(source: &str, section: &str, row: &str) -> String {
+    let corpus = crate::parse(source);
+    let last = corpus
+        .entries
+        .iter()
+        .filter(|e| corpus.sections.get(e.section).is_some_and(|s| s == section))
+        .map(|e| e.line_no)
+        .max();
+    let mut lines: Vec<String> = source.lines().map(str::to_string).collect();
+    match last {
+        // `line_no` is 1-based, so it is also the index just past the row.
+        Some(line_no) => lines.insert(line_no, row.to_string()),
+        None => {
+            lines.push(format!("// --- {section} ---"));
+            lines.push(row.to_string());
+        }
+    }
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::coverage::report_stages_over;
+
+    const CORPUS: &str = "\
+// header comment
+// --- basics ---
+{\"input\": \"1 cup flour\", \"name\": \"flour\", \"amounts\": [{\"unit\": \"cup\", \"value\": 1}]}
+{\"input\": \"2 cups sugar\", \"name\": \"sugar\", \"amounts\": [{\"unit\": \"cup\", \"value\": 2}]}
+// --- modifiers ---
+{\"input\": \"1 onion, diced\", \"name\": \"onion\", \"amounts\": [{\"unit\": \"whole\", \"value\": 1}], \"modifier\": \"diced\"}
+";
+
+    fn ranked(lines: &[&str]) -> Vec<Candidate> {
+        let corpus = crate::parse(CORPUS);
+        let coverage = report_stages_over(&corpus.inputs());
+        let lines: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        rank(&lines, &corpus, &coverage)
+    }
+
+    #[test]
+    fn flagged_and_novel_lines_rank_first() {
+        let found = ranked(&["3 cups flour", "salt (see note 3)"]);
+        assert_eq!(found[0].input, "salt (see note 3)");
+        assert!(
+            found[0]
+                .signals
+                .contains(&Signal::Review(ReviewReason::UnparsedDigit)),
+            "{:?}",
+            found[0].signals
+        );
+        assert!(found[0].score > found[1].score);
+        // "3 cups flour" has the shape of a labeled row, so it's filed beside it.
+        assert_eq!(found[1].draft.section, "basics");
+    }
+
+    #[test]
+    fn skips_labeled_repeated_and_non_ingredient_lines() {
+        let found = ranked(&["1 cup flour", "For the glaze:", "2 eggs", "2 eggs"]);
+        let inputs: Vec<&str> = found.iter().map(|c| c.input.as_str()).collect();
+        assert_eq!(inputs, ["2 eggs"]);
+    }
+
+    #[test]
+    fn prefilled_draft_is_a_committed_row() {
+        let ing = ingredient::from_str("2/3 cup chopped onion");
+        let draft = Draft::from_parse("2/3 cup chopped onion", &ing, "modifiers");
+        assert_eq!(
+            draft.to_row().unwrap(),
+            r#"{"input": "2/3 cup chopped onion", "name": "onion", "amounts": [{"unit": "cup", "value": "2/3"}], "modifier": "chopped"}"#
+        );
+    }
+
+    #[test]
+    fn an_edited_label_must_be_xfail() {
+        let ing = ingredient::from_str("2 cups flour");
+        let mut draft = Draft::from_parse("2 cups flour", &ing, "basics");
+        draft.name = "bread flour".to_string();
+        let err = draft.to_row().unwrap_err();
+        assert!(err.contains("name"), "{err}");
+
+        draft.xfail = Some("  ".to_string());
+        assert!(draft.to_row().is_err());
+        draft.xfail = Some("wrong flour".to_string());
+        assert!(
+            draft
+                .to_row()
+                .unwrap()
+                .ends_with(r#""xfail": "wrong flour"}"#)
+        );
+
+        // An xfail whose labels already match is refused too.
+        draft.name = "flour".to_string();
+        assert!(draft.to_row().unwrap_err().contains("drop the xfail"));
+    }
+
+    #[test]
+    fn malformed_amounts_are_reported() {
+        let ing = ingredient::from_str("2 cups flour");
+        let mut draft = Draft::from_parse("2 cups flour", &ing, "basics");
+        draft.amounts = "[{\"unit\": \"cup\"".to_string();
+        assert!(draft.to_row().unwrap_err().starts_with("amounts:"));
+    }
+
+    #[test]
+    fn insert_row_files_under_its_section() {
+        let row = r#"{"input": "1 egg", "name": "egg"}"#;
+        let out = insert_row(CORPUS, "basics", row);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[4], row);
+        assert_eq!(lines[5], "// --- modifiers ---");
+
+        let out = insert_row(CORPUS, "labeled", row);
+        assert!(out.ends_with(&format!("// --- labeled ---\n{row}\n")));
+        let corpus = crate::parse(&out);
+        assert_eq!(corpus.rows().count(), 4);
+        assert_eq!(corpus.problems().count(), 0);
+    }
+}
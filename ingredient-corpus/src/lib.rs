@@ -11,11 +11,17 @@
 //! number or an exact fraction string (`"2/3"`, `"1 1/2"`); a truncated decimal
 //! (`0.667`) is a different value and a *quoted* decimal is rejected outright.
 
-use ingredient::{IngredientUsage, unit::Measure};
+use ingredient::IngredientUsage;
+use ingredient::unit::{Measure, Qualifier};
 use serde::Deserialize;
 use serde::de::DeserializeOwned;
 use std::path::Path;
 
+pub mod coverage;
+pub mod grammar;
+pub mod invariants;
+pub mod label;
+
 /// Where the corpus lives relative to the workspace root — for a CLI `--corpus`
 /// default or a GUI path field, which resolve against the process's cwd.
 pub const CORPUS_RELATIVE_PATH: &str = "ingredient-parser/tests/corpus/corpus.jsonl";
@@ -277,6 +283,27 @@ pub fn read(path: &Path) -> std::io::Result<Corpus<CorpusRow>> {
     Ok(parse(&std::fs::read_to_string(path)?))
 }
 
+/// The lines of a line-set file. JSON rows (a corpus, a snapshot, a
+/// `--dump-parsed` harvest) contribute their `input` or `line` key; anything
+/// else is plain text, one ingredient per line. Blank and `//` lines are
+/// skipped.
+pub fn load_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .filter_map(|line| {
+            if !line.starts_with('{') {
+                return Some(line.to_string());
+            }
+            let row: serde_json::Value = serde_json::from_str(line).ok()?;
+            ["input", "line"]
+                .iter()
+                .find_map(|key| row.get(key)?.as_str())
+                .map(str::to_string)
+        })
+        .collect()
+}
+
 /// How one corpus row scored.
 ///
 /// `xfail` NEVER changes how fields are compared — only how a mismatch is
@@ -284,7 +311,7 @@ pub fn read(path: &Path) -> std::io::Result<Corpus<CorpusRow>> {
 /// rows.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Status {
-    /// Committed row, all five fields agreed.
+    /// Committed row, all six fields agreed.
     Exact,
     /// Committed row, something disagreed. The only status that fails CI.
     Regression,
@@ -305,7 +332,7 @@ impl Status {
     }
 }
 
-/// The five fields a corpus row labels, in corpus order.
+/// The six fields a corpus row labels, in corpus order.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LabeledField {
     Name,
@@ -313,6 +340,10 @@ pub enum LabeledField {
     Modifier,
     Optional,
     Usage,
+    /// Each amount's [`Qualifier`] ("about", "scant", …), authored as a
+    /// `qualifier` key on the amount. Scored apart from `Amounts` so a dropped
+    /// "heaping" doesn't read as a wrong quantity.
+    Qualifiers,
 }
 
 impl LabeledField {
@@ -323,6 +354,7 @@ impl LabeledField {
             LabeledField::Modifier => "modifier",
             LabeledField::Optional => "optional",
             LabeledField::Usage => "usage",
+            LabeledField::Qualifiers => "qualifiers",
         }
     }
 }
@@ -341,7 +373,7 @@ pub struct FieldDiff {
 #[derive(Debug, Clone)]
 pub struct Scored {
     pub status: Status,
-    pub fields: [FieldDiff; 5],
+    pub fields: [FieldDiff; 6],
 }
 
 impl Scored {
@@ -370,9 +402,9 @@ pub fn score(row: &CorpusRow) -> Scored {
         ),
         diff(
             LabeledField::Amounts,
-            got.amounts == row.measures(),
-            format!("[{}]", render_parsed(&row.measures())),
-            format!("[{}]", render_parsed(&got.amounts)),
+            unqualified(&got.amounts) == unqualified(&row.measures()),
+            format!("[{}]", render_parsed(&unqualified(&row.measures()))),
+            format!("[{}]", render_parsed(&unqualified(&got.amounts))),
         ),
         diff(
             LabeledField::Modifier,
@@ -392,6 +424,12 @@ pub fn score(row: &CorpusRow) -> Scored {
             format!("{:?}", row.usage),
             format!("{:?}", got.usage),
         ),
+        diff(
+            LabeledField::Qualifiers,
+            qualifiers(&got.amounts) == qualifiers(&row.measures()),
+            render_qualifiers(&row.measures()),
+            render_qualifiers(&got.amounts),
+        ),
     ];
 
     let all_ok = fields.iter().all(|d| d.ok);
@@ -414,7 +452,7 @@ pub struct Tally {
     pub xfail: usize,
     pub promote: usize,
     /// Per-field match counts, in [`LabeledField`] declaration order.
-    pub per_field: [usize; 5],
+    pub per_field: [usize; 6],
 }
 
 impl Tally {
@@ -437,7 +475,7 @@ impl Tally {
         }
     }
 
-    /// Rows where all five fields agreed — `exact + promote`, NOT `exact`: a
+    /// Rows where all six fields agreed — `exact + promote`, NOT `exact`: a
     /// passing xfail row is a match even though its status is `Promote`. Read
     /// this rather than `.exact` for the headline number.
     pub fn matched(&self) -> usize {
@@ -453,15 +491,16 @@ impl Tally {
 /// [`parse_as`] rather than becoming a sixth copy of the line handling.
 pub mod rich {
     use ingredient::rich_text::Chunk;
-    use ingredient::unit::Measure;
+    use ingredient::unit::{Dimension, Measure};
     use serde::Deserialize;
 
     /// One expected chunk, disambiguated by its key: `{"text": …}`,
-    /// `{"measure": [...]}`, or `{"ing": …}`.
+    /// `{"measure": [...]}`, `{"dimension": {...}}`, or `{"ing": …}`.
     #[derive(Debug, Clone, Deserialize)]
     #[serde(untagged)]
     pub enum ExpectedChunk {
         Measure { measure: Vec<Measure> },
+        Dimension { dimension: Dimension },
         Ing { ing: String },
         Text { text: String },
     }
@@ -470,6 +509,7 @@ pub mod rich {
         fn from(e: ExpectedChunk) -> Self {
             match e {
                 ExpectedChunk::Measure { measure } => Chunk::Measure(measure),
+                ExpectedChunk::Dimension { dimension } => Chunk::Dimension(dimension),
                 ExpectedChunk::Ing { ing } => Chunk::Ing(ing),
                 ExpectedChunk::Text { text } => Chunk::Text(text),
             }
@@ -507,6 +547,28 @@ pub(crate) fn render_parsed(amounts: &[Measure]) -> String {
         .join(", ")
 }
 
+/// The amounts with their qualifiers stripped — what the `amounts` field
+/// compares.
+fn unqualified(amounts: &[Measure]) -> Vec<Measure> {
+    amounts.iter().map(Measure::unqualified).collect()
+}
+
+/// The qualifiers the amounts carry, in order — what the `qualifiers` field
+/// compares. Unqualified amounts are skipped, so a missing or extra amount
+/// fails `amounts` alone instead of both fields.
+fn qualifiers(amounts: &[Measure]) -> Vec<Qualifier> {
+    amounts.iter().filter_map(Measure::qualifier).collect()
+}
+
+/// `[about, scant]`, or `[]` for a row with no qualified amount.
+fn render_qualifiers(amounts: &[Measure]) -> String {
+    let words: Vec<&str> = qualifiers(amounts)
+        .into_iter()
+        .map(Qualifier::as_str)
+        .collect();
+    format!("[{}]", words.join(", "))
+}
+
 /// Render amounts the way the CORPUS FILE AUTHORED them: literal unit, EN DASH
 /// range, no suffix for a bare count, and the spelling the file used.
 ///
@@ -538,6 +600,10 @@ pub fn render_authored(amounts: &[AuthoredMeasure]) -> String {
                 }
                 None => value,
             };
+            let qty = match m.qualifier() {
+                Some(q) => format!("{q} {qty}"),
+                None => qty,
+            };
             if unit.is_empty() || unit == "whole" {
                 qty
             } else {
@@ -868,11 +934,22 @@ not valid json
             LabeledField::Modifier,
             LabeledField::Optional,
             LabeledField::Usage,
+            LabeledField::Qualifiers,
         ]
         .iter()
         .map(|f| f.as_str())
         .collect();
-        assert_eq!(labels, ["name", "amounts", "modifier", "optional", "usage"]);
+        assert_eq!(
+            labels,
+            [
+                "name",
+                "amounts",
+                "modifier",
+                "optional",
+                "usage",
+                "qualifiers"
+            ]
+        );
     }
 
     /// `mismatches` yields only the fields that disagreed, in corpus order —
@@ -893,6 +970,25 @@ not valid json
         assert_eq!(name_diff.got, "\"flour\"");
     }
 
+    /// A qualifier is its own field: a dropped "scant" fails `qualifiers`, not
+    /// `amounts`.
+    #[test]
+    fn qualifiers_score_apart_from_amounts() {
+        let labeled = row(
+            r#"{"input":"scant 1 cup flour","name":"flour","amounts":[{"unit":"cup","value":1,"qualifier":"scant"}]}"#,
+        );
+        assert_eq!(score(&labeled).status, Status::Exact);
+
+        let unlabeled = row(
+            r#"{"input":"scant 1 cup flour","name":"flour","amounts":[{"unit":"cup","value":1}]}"#,
+        );
+        let scored = score(&unlabeled);
+        let failed: Vec<&str> = scored.mismatches().map(|d| d.field.as_str()).collect();
+        assert_eq!(failed, ["qualifiers"]);
+        let diff = scored.mismatches().next().unwrap();
+        assert_eq!((diff.want.as_str(), diff.got.as_str()), ("[]", "[scant]"));
+    }
+
     /// The `Xfail` arm of the tally — unreachable from the real corpus, which
     /// has no xfail rows.
     #[test]
@@ -904,4 +1000,13 @@ not valid json
         assert_eq!(tally.regression, 0);
         assert_eq!(tally.matched(), 0);
     }
+
+    #[test]
+    fn load_lines_reads_json_rows_and_plain_text() {
+        let text = "// comment\n{\"input\": \"2 cups flour\", \"name\": \"flour\"}\n\n{\"line\": \"1 egg\"}\n3 tbsp butter\n";
+        assert_eq!(
+            load_lines(text),
+            vec!["2 cups flour", "1 egg", "3 tbsp butter"]
+        );
+    }
 }
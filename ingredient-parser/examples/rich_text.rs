@@ -23,6 +23,7 @@ fn main() {
                 let rendered: Vec<String> = measures.iter().map(ToString::to_string).collect();
                 print!("[{}]", rendered.join(", "));
             }
+            Chunk::Dimension(size) => print!("{{{size}}}"),
             Chunk::Ing(name) => print!("<{name}>"),
         }
     }
@@ -0,0 +1,31 @@
+#![no_main]
+//! The parse invariants (`ingredient_corpus::invariants`) over two inputs per
+//! run: the raw bytes as a line, when they are UTF-8, and a line the
+//! corpus-seeded grammar builds from them. Any violation is a crash.
+use std::sync::LazyLock;
+
+use ingredient::IngredientParser;
+use ingredient_corpus::grammar::Grammar;
+use ingredient_corpus::invariants::check_line;
+use libfuzzer_sys::fuzz_target;
+
+static PARSER: LazyLock<IngredientParser> = LazyLock::new(IngredientParser::new);
+
+static GRAMMAR: LazyLock<Grammar> = LazyLock::new(|| {
+    Grammar::from_corpus(
+        &PARSER,
+        &ingredient_corpus::parse(ingredient_corpus::embedded()),
+    )
+});
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(line) = std::str::from_utf8(data) {
+        if let Err(e) = check_line(&PARSER, line) {
+            panic!("{line:?}: {e}");
+        }
+    }
+    let line = GRAMMAR.line(data);
+    if let Err(e) = check_line(&PARSER, &line) {
+        panic!("{line:?}: {e}");
+    }
+});
@@ -0,0 +1,383 @@
+//! Alternative parses (n-best) with per-field confidence.
+//!
+//! The pipeline commits to one reading of every line, and a few of its
+//! decisions are genuinely ambiguous without a food ontology: whether a size
+//! word is the count unit ("2 large eggs" → `{large:2}` eggs, or 2 "large
+//! eggs"), whether a produce count noun is the unit ("1 garlic clove"), and
+//! whether the head noun of an "X or Y Z" alternative is shared ("salt or
+//! chicken broth" → "salt broth", or "salt"). [`IngredientParser::parse_candidates`]
+//! re-runs the line once per [`Reading`] that flips one of those decisions, keeps
+//! the readings that actually change the result, and scores them.
+//!
+//! Each candidate's [`score`](ParseCandidate::score) is its prior (the
+//! pipeline's own reading dominates; see [`Reading::prior`]) discounted by
+//! parse fidelity, normalized over the candidate set. A field's
+//! [confidence](FieldConfidence) is the share of that weight carried by
+//! candidates that agree with it on the field, so a field no alternative
+//! disputes stays at full confidence while a disputed one drops — and a
+//! fell-back or digit-dropping parse lowers the fields it affects.
+//!
+//! ```
+//! use ingredient::{CandidateField, IngredientParser, Reading};
+//!
+//! let parser = IngredientParser::new();
+//! let candidates = parser.parse_candidates("2 large eggs", 3);
+//! assert_eq!(candidates[0].reading, Reading::Primary);
+//! assert_eq!(candidates[0].ingredient.name, "eggs");
+//! assert_eq!(candidates[1].ingredient.name, "large eggs");
+//! // The name and the amount's unit are disputed; the usage isn't.
+//! let top = &candidates[0];
+//! assert!(top.confidence.get(CandidateField::Amounts) < 1.0);
+//! assert_eq!(top.confidence.get(CandidateField::Usage), 1.0);
+//! assert!(candidates[1].uncertain_fields().contains(&CandidateField::Name));
+//! ```
+
+use serde::Serialize;
+
+use crate::{Ingredient, IngredientParser, ReviewReason};
+
+/// A field at or above this confidence is not worth a reviewer's attention.
+pub const UNCERTAIN_BELOW: f64 = 0.75;
+
+/// One interpretation the pipeline can be asked to take of a line. `Primary`
+/// is the normal parse; each other variant flips a single ambiguous decision.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reading {
+    /// The pipeline's own reading (what [`IngredientParser::from_str`] returns).
+    #[default]
+    Primary,
+    /// A size word after the count stays in the name instead of becoming the
+    /// count unit: "2 large eggs" → 2 "large eggs".
+    SizeInName,
+    /// A trailing produce count noun stays in the name instead of becoming the
+    /// unit: "1 garlic clove" → 1 "garlic clove".
+    ProduceUnitInName,
+    /// An "X or Y Z" alternative keeps the bare left as the name instead of
+    /// grafting the right's head noun onto it: "salt or chicken broth" →
+    /// "salt". A left that only premodifies ("red or white onion") still takes
+    /// the head.
+    NoSharedHead,
+}
+
+impl Reading {
+    /// Every alternative to [`Reading::Primary`], in tie-break order.
+    pub const ALTERNATIVES: [Reading; 3] = [
+        Reading::SizeInName,
+        Reading::ProduceUnitInName,
+        Reading::NoSharedHead,
+    ];
+
+    /// Relative prior weight of the reading, before fidelity discounts. The
+    /// primary reading is right far more often than any flip (the corpus pins
+    /// it), so a flip only ever competes, never wins, on a clean parse; the
+    /// shared-head flip weighs most because its primary is a documented
+    /// over-reconstruction ("salt broth").
+    pub fn prior(self) -> f64 {
+        match self {
+            Reading::Primary => 1.0,
+            Reading::SizeInName => 0.25,
+            Reading::ProduceUnitInName => 0.15,
+            Reading::NoSharedHead => 0.4,
+        }
+    }
+
+    /// A short human description of the reading.
+    pub fn describe(self) -> &'static str {
+        match self {
+            Reading::Primary => "as parsed",
+            Reading::SizeInName => "size word kept in the name",
+            Reading::ProduceUnitInName => "produce count noun kept in the name",
+            Reading::NoSharedHead => "alternative's head noun not shared",
+        }
+    }
+}
+
+/// The parsed fields a confidence is reported for.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CandidateField {
+    Name,
+    Amounts,
+    Modifier,
+    Usage,
+}
+
+impl CandidateField {
+    /// All fields, in report order.
+    pub const ALL: [CandidateField; 4] = [
+        CandidateField::Name,
+        CandidateField::Amounts,
+        CandidateField::Modifier,
+        CandidateField::Usage,
+    ];
+
+    /// Whether `a` and `b` agree on this field.
+    fn agrees(self, a: &Ingredient, b: &Ingredient) -> bool {
+        match self {
+            CandidateField::Name => a.name == b.name,
+            CandidateField::Amounts => a.amounts == b.amounts,
+            CandidateField::Modifier => a.modifier == b.modifier,
+            CandidateField::Usage => a.usage == b.usage,
+        }
+    }
+}
+
+/// Per-field confidence in `[0, 1]` for one candidate.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize)]
+pub struct FieldConfidence {
+    pub name: f64,
+    pub amounts: f64,
+    pub modifier: f64,
+    pub usage: f64,
+}
+
+impl FieldConfidence {
+    /// The confidence for `field`.
+    pub fn get(&self, field: CandidateField) -> f64 {
+        match field {
+            CandidateField::Name => self.name,
+            CandidateField::Amounts => self.amounts,
+            CandidateField::Modifier => self.modifier,
+            CandidateField::Usage => self.usage,
+        }
+    }
+
+    fn set(&mut self, field: CandidateField, value: f64) {
+        match field {
+            CandidateField::Name => self.name = value,
+            CandidateField::Amounts => self.amounts = value,
+            CandidateField::Modifier => self.modifier = value,
+            CandidateField::Usage => self.usage = value,
+        }
+    }
+}
+
+/// One interpretation of a line.
+#[derive(Clone, Debug, Serialize)]
+pub struct ParseCandidate {
+    /// Which reading produced it.
+    pub reading: Reading,
+    /// The parse, with its [`ParseNotes`](crate::ParseNotes).
+    pub ingredient: Ingredient,
+    /// Share of the candidate set's weight; the scores of all candidates of a
+    /// line sum to 1 (before truncation to the requested count).
+    pub score: f64,
+    /// How far each field is to be trusted.
+    pub confidence: FieldConfidence,
+    /// The normalize rewrites, recognizer, segmentation decisions and refine
+    /// passes that shaped this parse, in pipeline order (the `--explain` labels).
+    pub passes: Vec<String>,
+}
+
+impl ParseCandidate {
+    /// Fields below [`UNCERTAIN_BELOW`], in report order.
+    pub fn uncertain_fields(&self) -> Vec<CandidateField> {
+        CandidateField::ALL
+            .into_iter()
+            .filter(|f| self.confidence.get(*f) < UNCERTAIN_BELOW)
+            .collect()
+    }
+
+    /// The parse notes' review reasons followed by one
+    /// [`ReviewReason::Uncertain`] per uncertain field.
+    pub fn review_reasons(&self) -> Vec<ReviewReason> {
+        let mut out = self.ingredient.parse_notes.review_reasons();
+        out.extend(
+            self.uncertain_fields()
+                .into_iter()
+                .map(ReviewReason::Uncertain),
+        );
+        out
+    }
+}
+
+/// Weight multiplier for parse fidelity: a name-only fallback is barely a
+/// reading at all, and a dropped digit means the amounts are likely wrong.
+fn fidelity(ingredient: &Ingredient) -> f64 {
+    let notes = &ingredient.parse_notes;
+    match (notes.fell_back, notes.unparsed_digit) {
+        (true, _) => 0.3,
+        (false, true) => 0.5,
+        (false, false) => 1.0,
+    }
+}
+
+/// How much a field of one parse is to be trusted on its own, before
+/// agreement: a fallback leaves every structural field in doubt, a dropped
+/// digit the amounts.
+fn field_ceiling(ingredient: &Ingredient, field: CandidateField) -> f64 {
+    let notes = &ingredient.parse_notes;
+    match field {
+        CandidateField::Usage => 1.0,
+        CandidateField::Amounts if notes.unparsed_digit => 0.5,
+        _ if notes.fell_back => 0.5,
+        _ => 1.0,
+    }
+}
+
+/// Normalize the candidates' scores to sum to 1 and recompute every field's
+/// confidence from them. Shared with re-rankers that adjust the raw scores.
+pub(crate) fn rescore(candidates: &mut [ParseCandidate]) {
+    let total: f64 = candidates.iter().map(|c| c.score).sum();
+    if total > 0.0 {
+        for c in candidates.iter_mut() {
+            c.score /= total;
+        }
+    }
+    let scores: Vec<f64> = candidates.iter().map(|c| c.score).collect();
+    for i in 0..candidates.len() {
+        let mut confidence = FieldConfidence::default();
+        for field in CandidateField::ALL {
+            let agreement: f64 = candidates
+                .iter()
+                .zip(&scores)
+                .filter(|(other, _)| field.agrees(&candidates[i].ingredient, &other.ingredient))
+                .map(|(_, s)| s)
+                .sum();
+            confidence.set(
+                field,
+                agreement * field_ceiling(&candidates[i].ingredient, field),
+            );
+        }
+        candidates[i].confidence = confidence;
+    }
+}
+
+impl IngredientParser {
+    /// Parse `input` under every [`Reading`] and return the `n` best distinct
+    /// interpretations. The first is always the primary parse (equal to
+    /// [`Self::from_str`]) unless `n` is 0, even when an alternative outscores
+    /// it; the alternatives follow, highest score first. Readings that produce
+    /// the same ingredient as an earlier one are dropped, so an unambiguous
+    /// line yields one candidate at full confidence.
+    ///
+    /// Runs the pipeline traced once per reading, so it is several times the
+    /// cost of [`Self::from_str`]; meant for review surfaces, not bulk parsing.
+    ///
+    /// ```
+    /// use ingredient::{CandidateField, IngredientParser};
+    ///
+    /// let parser = IngredientParser::new();
+    /// let c = parser.parse_candidates("salt or chicken broth", 2);
+    /// assert_eq!(c[0].ingredient.name, "salt broth");
+    /// assert_eq!(c[1].ingredient.name, "salt");
+    /// assert_eq!(c[0].uncertain_fields(), [CandidateField::Name]);
+    ///
+    /// let c = parser.parse_candidates("2 cups flour", 3);
+    /// assert_eq!(c.len(), 1);
+    /// assert_eq!(c[0].score, 1.0);
+    /// ```
+    pub fn parse_candidates(&self, input: &str, n: usize) -> Vec<ParseCandidate> {
+        let mut candidates: Vec<ParseCandidate> = Vec::new();
+        for reading in std::iter::once(Reading::Primary).chain(Reading::ALTERNATIVES) {
+            let parser = IngredientParser {
+                reading,
+                ..self.clone()
+            };
+            let (ingredient, stages) = parser.parse_ingredient_line_with_stages(input);
+            if candidates.iter().any(|c| c.ingredient == ingredient) {
+                continue;
+            }
+            let passes = stages
+                .normalize
+                .iter()
+                .map(|r| r.name.clone())
+                .chain(
+                    stages
+                        .recognizers
+                        .iter()
+                        .filter(|r| r.output.is_some())
+                        .map(|r| r.name.clone()),
+                )
+                .chain(stages.segment.iter().map(|r| r.name.clone()))
+                .chain(stages.refine.iter().map(|r| r.name.clone()))
+                .collect();
+            candidates.push(ParseCandidate {
+                reading,
+                score: reading.prior() * fidelity(&ingredient),
+                ingredient,
+                confidence: FieldConfidence::default(),
+                passes,
+            });
+        }
+
+        rescore(&mut candidates);
+        // The primary is always pushed first (nothing precedes it to dedupe
+        // against); only the alternatives are ranked. Stable: ties keep the
+        // reading order.
+        candidates[1..].sort_by(|a, b| b.score.total_cmp(&a.score));
+        candidates.truncate(n);
+        candidates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("2 large eggs", &["eggs", "large eggs"])]
+    #[case("1 garlic clove, minced", &["garlic", "garlic clove"])]
+    #[case("salt or chicken broth", &["salt broth", "salt"])]
+    #[case("1 cup red or white onion", &["red onion"])]
+    #[case("2 cups flour", &["flour"])]
+    fn candidates_cover_the_ambiguous_readings(#[case] line: &str, #[case] names: &[&str]) {
+        let parser = IngredientParser::new();
+        let got = parser.parse_candidates(line, 5);
+        let got_names: Vec<&str> = got.iter().map(|c| c.ingredient.name.as_str()).collect();
+        assert_eq!(got_names, names, "{line}");
+        assert_eq!(got[0].reading, Reading::Primary);
+        assert_eq!(got[0].ingredient, parser.from_str(line));
+        let total: f64 = got.iter().map(|c| c.score).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn confidence_reflects_agreement_and_fidelity() {
+        let parser = IngredientParser::new();
+        let eggs = parser.parse_candidates("2 large eggs", 2);
+        let (primary, alt) = (&eggs[0], &eggs[1]);
+        // Both readings dispute the name and the amounts; neither the modifier
+        // nor the usage.
+        assert_eq!(primary.confidence.name, primary.score);
+        assert_eq!(primary.confidence.amounts, primary.score);
+        assert_eq!(alt.confidence.name, alt.score);
+        assert!((primary.confidence.modifier - 1.0).abs() < 1e-9);
+        assert_eq!(primary.uncertain_fields(), []);
+        assert_eq!(
+            alt.uncertain_fields(),
+            [CandidateField::Name, CandidateField::Amounts]
+        );
+        assert!(
+            primary
+                .passes
+                .contains(&"extract_size_unit_from_name".to_string())
+        );
+        assert!(
+            !alt.passes
+                .contains(&"extract_size_unit_from_name".to_string())
+        );
+
+        // A dropped digit caps the amounts.
+        let dropped = &parser.parse_candidates("butter (2 sticks minus 1 tablespoon)", 1)[0];
+        assert!(dropped.ingredient.parse_notes.unparsed_digit);
+        assert!(dropped.confidence.amounts <= 0.5);
+        assert_eq!(
+            dropped.review_reasons(),
+            [
+                ReviewReason::UnparsedDigit,
+                ReviewReason::Uncertain(CandidateField::Amounts)
+            ]
+        );
+    }
+
+    #[test]
+    fn truncates_to_n() {
+        let parser = IngredientParser::new();
+        assert!(parser.parse_candidates("2 large eggs", 0).is_empty());
+        assert_eq!(parser.parse_candidates("2 large eggs", 1).len(), 1);
+    }
+}
@@ -3,8 +3,8 @@ use thiserror::Error;
 /// Error types for ingredient parsing operations.
 ///
 /// Note: `from_str` is infallible by design (see lib.rs "Design Decisions"), so
-/// the only variants here are the ones actually produced — by `parse_amount` and
-/// measure arithmetic.
+/// the only variants here are the ones actually produced — by `parse_amount`,
+/// measure arithmetic, unit mappings and pass registration.
 #[derive(Error, Debug, Clone, PartialEq)]
 pub enum IngredientError {
     /// Failed to parse measurement/amount
@@ -16,6 +16,9 @@ pub enum IngredientError {
     /// Failed to parse a unit mapping string ("4 lb = $5", "$5/4lb", …)
     #[error("Failed to parse unit mapping '{input}': {reason}")]
     UnitMappingError { input: String, reason: String },
+    /// A [custom pass](crate::pass::CustomPass) could not be registered
+    #[error("Failed to register pass '{name}': {reason}")]
+    PassError { name: String, reason: String },
 }
 
 /// Result type for ingredient parsing operations
@@ -160,7 +160,14 @@ pub fn fraction_number(input: &str) -> Res<&str, f64> {
         .map(|(next_input, res)| {
             let (whole_number, fractional_part) = res;
             let whole_value = whole_number.map_or(0.0, |(num, _)| num);
-            (next_input, whole_value + fractional_part)
+            // The sign belongs to the whole mixed number: "-1¼" is -1.25, not
+            // -1 + ¼.
+            let value = if whole_value.is_sign_negative() {
+                whole_value - fractional_part
+            } else {
+                whole_value + fractional_part
+            };
+            (next_input, value)
         }),
         |v: &f64| format!("{v}"),
         "no fraction"
@@ -287,6 +294,8 @@ mod tests {
     // The "and" separator works for vulgar glyphs too, not just slash form.
     #[case::one_and_half_vulgar("1 and ½", 1.5)]
     #[case::two_and_third_vulgar("2 and ⅓", 2.0 + 1.0 / 3.0)]
+    #[case::negative_vulgar("-1¼", -1.25)]
+    #[case::negative_slash("-1 1/4", -1.25)]
     fn test_fraction_number_mixed(#[case] input: &str, #[case] expected: f64) {
         assert_eq!(fraction_number(input), Ok(("", expected)));
     }
@@ -1,7 +1,9 @@
 use std::fmt;
 
+use crate::unit::dimension::find_dimensions;
+use crate::unit::{Dimension, Measure, MeasureKind};
 use crate::usage::{IngredientUsage, classify_usage};
-use crate::{ParseNotes, from_str, unit::Measure};
+use crate::{ParseNotes, from_str};
 use serde::{Deserialize, Serialize};
 
 // `PartialEq`/`PartialOrd` are hand-written below (excluding `parse_notes`), so
@@ -19,6 +21,8 @@ use serde::{Deserialize, Serialize};
 /// * `modifier` - Optional preparation instructions (e.g., "sifted", "chopped", "room temperature")
 /// * `optional` - Whether this ingredient is optional (wrapped in parentheses)
 /// * `usage` - The role the line declares (e.g., "oil, for frying" → `FryingMedium`)
+/// * `package` - A count of identically sized packages, when the line gives one
+///   (e.g., "2 (14.5-ounce) cans")
 ///
 /// # Examples
 ///
@@ -58,6 +62,10 @@ pub struct Ingredient {
     /// Required on purpose — no serde default — so stale serialized data fails
     /// loudly instead of silently reading as `Normal`.
     pub usage: IngredientUsage,
+    /// The package structure behind the amounts ("2 (14.5-ounce) cans" → 2 ×
+    /// 14.5 oz cans), when the line counts identically sized packages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<Package>,
     /// Non-failing metadata about *how* this line parsed (confidence, fallback,
     /// unparsed-digit). Runtime-only: `#[serde(skip)]` because it's derived on
     /// every parse and crosses to TypeScript via `WIngredient`, not the core
@@ -85,6 +93,7 @@ impl PartialEq for Ingredient {
             modifier,
             optional,
             usage,
+            package,
             parse_notes: _,
         } = self;
         *name == other.name
@@ -92,6 +101,7 @@ impl PartialEq for Ingredient {
             && *modifier == other.modifier
             && *optional == other.optional
             && *usage == other.usage
+            && *package == other.package
     }
 }
 
@@ -103,14 +113,16 @@ impl PartialOrd for Ingredient {
             modifier,
             optional,
             usage,
+            package,
             parse_notes: _,
         } = self;
-        (name, amounts, modifier, optional, usage).partial_cmp(&(
+        (name, amounts, modifier, optional, usage, package).partial_cmp(&(
             &other.name,
             &other.amounts,
             &other.modifier,
             &other.optional,
             &other.usage,
+            &other.package,
         ))
     }
 }
@@ -144,6 +156,7 @@ impl Ingredient {
             // equivalent line (`Ingredient::new("oil", …, Some("for frying"))`
             // == `from_str("oil, for frying")`).
             usage: classify_usage(name, modifier, None, None),
+            package: None,
             parse_notes: ParseNotes::default(),
         }
     }
@@ -162,6 +175,7 @@ impl Ingredient {
             modifier,
             optional,
             usage: Default::default(),
+            package: None,
             parse_notes: Default::default(),
         }
     }
@@ -186,6 +200,7 @@ impl Ingredient {
             modifier: modifier.map(String::from),
             optional: true,
             usage: classify_usage(name, modifier, None, None),
+            package: None,
             parse_notes: ParseNotes::default(),
         }
     }
@@ -194,7 +209,9 @@ impl Ingredient {
     ///
     /// Measures whose kind does not scale — a pan's length, an oven
     /// temperature, a resting time — pass through untouched; see
-    /// [`Measure::scale`], which owns that rule.
+    /// [`Measure::scale`], which owns that rule. A [`Package`] scales its
+    /// count, not its size: doubled, "2 (14.5-ounce) cans" is 4 cans of 14.5
+    /// oz, not 4 cans of 29 oz.
     ///
     /// `usage` is carried over rather than re-derived: a scaled frying medium
     /// is still a frying medium, and re-running classification here would make
@@ -206,17 +223,162 @@ impl Ingredient {
     ///
     /// let doubled = Ingredient::new("flour", vec![Measure::new("cup", 1.5)], None).scale(2.0);
     /// assert_eq!(doubled.amounts[0].value(), 3.0);
+    ///
+    /// let cans = ingredient::from_str("2 (14.5-ounce) cans tomatoes").scale(2.0);
+    /// assert_eq!(cans.to_string(), "4 can / 14½ oz tomatoes");
     /// ```
     pub fn scale(&self, factor: f64) -> Ingredient {
+        let per_package = self.per_package_sizes();
         Ingredient {
             name: self.name.clone(),
-            amounts: self.amounts.iter().map(|a| a.scale(factor)).collect(),
+            amounts: self
+                .amounts
+                .iter()
+                .map(|a| {
+                    if per_package.contains(&a) {
+                        a.clone()
+                    } else {
+                        a.scale(factor)
+                    }
+                })
+                .collect(),
             modifier: self.modifier.clone(),
             optional: self.optional,
             usage: self.usage,
+            package: self.package.as_ref().map(|p| p.scale(factor)),
             parse_notes: self.parse_notes,
         }
     }
+
+    /// The amounts with every per-package size replaced by the package total:
+    /// what the line adds up to, and what conversion and costing should use.
+    /// Without a package this is just [`Self::amounts`].
+    ///
+    /// ```
+    /// use ingredient::from_str;
+    ///
+    /// let cans = from_str("2 (14.5-ounce) cans tomatoes");
+    /// let totals: Vec<String> = cans.total_amounts().iter().map(|m| m.to_string()).collect();
+    /// assert_eq!(totals, ["2 can", "29 oz"]);
+    /// ```
+    pub fn total_amounts(&self) -> Vec<Measure> {
+        let per_package = self.per_package_sizes();
+        let count = self.package.as_ref().map_or(1.0, |p| p.count);
+        self.amounts
+            .iter()
+            .map(|a| {
+                if per_package.contains(&a) {
+                    a.scale(count)
+                } else {
+                    a.clone()
+                }
+            })
+            .collect()
+    }
+
+    /// Convert the line's [total](Self::total_amounts) to `target` using
+    /// `mappings` (see [`Measure::convert_measure_via_mappings`]): the first
+    /// total that converts wins. A package converts as its total, so "2
+    /// (14.5-ounce) cans" weighs 29 oz, not 14.5.
+    pub fn convert(&self, target: MeasureKind, mappings: &[(Measure, Measure)]) -> Option<Measure> {
+        self.total_amounts()
+            .iter()
+            .find_map(|m| m.convert_measure_via_mappings(target.clone(), mappings))
+    }
+
+    /// The sizes the line gives rather than quantities: a length amount ("1
+    /// (9-inch) pie crust") and any dimension in the modifier ("cut into
+    /// ½-inch-thick slices"). These hold still when the line is
+    /// [scaled](Self::scale).
+    ///
+    /// ```
+    /// use ingredient::from_str;
+    ///
+    /// let carrots = from_str("2 carrots, cut into ½-inch-thick slices");
+    /// let sizes: Vec<String> = carrots.dimensions().iter().map(|d| d.to_string()).collect();
+    /// assert_eq!(sizes, ["½ inch thick"]);
+    /// ```
+    pub fn dimensions(&self) -> Vec<Dimension> {
+        let mut dims: Vec<Dimension> = self
+            .amounts
+            .iter()
+            .filter_map(Dimension::from_measure)
+            .collect();
+        for (_, d) in find_dimensions(self.modifier.as_deref().unwrap_or_default()) {
+            if !dims.contains(&d) {
+                dims.push(d);
+            }
+        }
+        dims
+    }
+
+    /// The amounts that give one package's size rather than a quantity: the
+    /// package's size measures, when the amounts also carry its count ("2
+    /// cans"). A multiplier line without a container ("2 x 200g flour") already
+    /// stores the total, so nothing in it is per-package.
+    fn per_package_sizes(&self) -> Vec<&Measure> {
+        let Some(package) = &self.package else {
+            return vec![];
+        };
+        if !self.amounts.iter().any(|a| package.is_count(a)) {
+            return vec![];
+        }
+        self.amounts
+            .iter()
+            .filter(|a| package.size.contains(a))
+            .collect()
+    }
+}
+
+/// A count of identically sized packages: "2 (14.5-ounce) cans" is 2 cans of
+/// 14.5 oz each, "2 x 400 g" is 2 of 400 g. The parse keeps the line's own
+/// [`Ingredient::amounts`]; this says how they relate.
+///
+/// ```
+/// use ingredient::from_str;
+///
+/// let cans = from_str("3 (15-oz) cans black beans, drained");
+/// let package = cans.package.unwrap();
+/// assert_eq!(package.count, 3.0);
+/// assert_eq!(package.container.as_deref(), Some("can"));
+/// assert_eq!(package.total()[0].to_string(), "45 oz");
+/// ```
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Package {
+    /// How many packages.
+    pub count: f64,
+    /// One package's size, in every unit the line gives it ("(7 oz / 198g)"
+    /// → both).
+    pub size: Vec<Measure>,
+    /// The container noun, singular ("can", "tin", "packet"); `None` for a
+    /// bare multiplier ("2 x 200g flour") or a count of items ("2 (8-ounce)
+    /// swordfish steaks").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+}
+
+impl Package {
+    /// The total across every package, one measure per [size](Self::size).
+    pub fn total(&self) -> Vec<Measure> {
+        self.size.iter().map(|m| m.scale(self.count)).collect()
+    }
+
+    /// The same package, `factor` times as many of it.
+    pub fn scale(&self, factor: f64) -> Package {
+        Package {
+            count: self.count * factor,
+            ..self.clone()
+        }
+    }
+
+    /// Whether `m` is this package's count as an amount: "2 can", or "2 whole"
+    /// when there's no container.
+    fn is_count(&self, m: &Measure) -> bool {
+        let unit = self.container.as_deref().unwrap_or("whole");
+        Measure::new(unit, 1.0).unit() == m.unit()
+            && m.upper_value().is_none()
+            && (m.value() - self.count).abs() < 1e-9
+    }
 }
 
 impl From<&str> for Ingredient {
@@ -331,6 +493,7 @@ mod tests {
             modifier: None,
             optional: false,
             usage: IngredientUsage::Normal,
+            package: None,
             parse_notes: Default::default(),
         };
         assert_eq!(ingredient.to_string(), "2 cups flour");
@@ -342,6 +505,7 @@ mod tests {
             modifier: Some("sifted".to_string()),
             optional: false,
             usage: IngredientUsage::Normal,
+            package: None,
             parse_notes: Default::default(),
         };
         assert_eq!(ingredient.to_string(), "2 cups flour, sifted");
@@ -353,6 +517,7 @@ mod tests {
             modifier: None,
             optional: false,
             usage: IngredientUsage::Normal,
+            package: None,
             parse_notes: Default::default(),
         };
         assert_eq!(ingredient.to_string(), "1 cup / 240 ml water");
@@ -364,6 +529,7 @@ mod tests {
             modifier: Some("to taste".to_string()),
             optional: false,
             usage: IngredientUsage::Normal,
+            package: None,
             parse_notes: Default::default(),
         };
         assert_eq!(ingredient.to_string(), "n/a salt, to taste");
@@ -375,6 +541,7 @@ mod tests {
             modifier: Some("chopped".to_string()),
             optional: true,
             usage: IngredientUsage::Normal,
+            package: None,
             parse_notes: Default::default(),
         };
         assert_eq!(ingredient.to_string(), "½ cup walnuts, chopped (optional)");
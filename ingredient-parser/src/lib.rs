@@ -10,7 +10,8 @@
 //! - Extract ingredient names and modifiers (preparation instructions)
 //! - Handle common recipe notation and edge cases gracefully
 //! - Support for Unicode fractions (½, ¼, etc.) in rich text mode
-//! - Customizable units and adjectives
+//! - Customizable units and adjectives, or a whole loadable vocabulary
+//!   ([`ParserProfile`])
 //!
 //! ## Design Decisions
 //!
@@ -182,19 +183,30 @@
 //! ```
 
 use std::collections::HashSet;
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
 
+pub use crate::candidates::{
+    CandidateField, FieldConfidence, ParseCandidate, Reading, UNCERTAIN_BELOW,
+};
 pub use crate::error::{IngredientError, IngredientResult};
-pub use crate::ingredient::Ingredient;
-pub use crate::usage::{IngredientUsage, classify_usage};
+pub use crate::ingredient::{Ingredient, Package};
+pub use crate::profile::{ParserProfile, ProfileError};
+pub use crate::usage::{IngredientUsage, classify_usage, classify_usage_with};
 use parser::{MeasurementMode, MeasurementParser};
-use unit::Measure;
+use unit::{Measure, Region};
 
+pub mod candidates;
 pub mod error;
 pub mod fraction;
 pub mod ingredient;
+pub mod localize;
 pub(crate) mod parser;
+pub mod pass;
+pub mod profile;
+pub mod rerank;
 pub mod rich_text;
+pub mod section;
+pub mod shape;
 pub mod trace;
 pub mod unit;
 pub mod unit_mapping;
@@ -241,8 +253,8 @@ pub fn from_str(input: &str) -> Ingredient {
     DEFAULT_PARSER.from_str(input)
 }
 
-/// Decompose a line into grammar-stage field spans, using the shared default
-/// parser. See [`IngredientParser::decompose`].
+/// Decompose a line into field spans over the raw line, using the shared
+/// default parser. See [`IngredientParser::decompose`].
 ///
 /// ```
 /// use ingredient::{decompose, Field};
@@ -320,6 +332,10 @@ pub enum ReviewReason {
     /// The line contains a digit that produced no measure — a likely missed
     /// quantity, and the corpus-harvest signal.
     UnparsedDigit,
+    /// A competing reading disputes this field (see
+    /// [`IngredientParser::parse_candidates`]); only reported by
+    /// [`ParseCandidate::review_reasons`].
+    Uncertain(CandidateField),
 }
 
 impl ReviewReason {
@@ -332,6 +348,18 @@ impl ReviewReason {
                 "unparsed_digit",
                 "contains a digit that produced no measure (likely missed quantity)",
             ),
+            ReviewReason::Uncertain(CandidateField::Name) => {
+                ("uncertain_name", "the name has a competing reading")
+            }
+            ReviewReason::Uncertain(CandidateField::Amounts) => {
+                ("uncertain_amounts", "the amounts have a competing reading")
+            }
+            ReviewReason::Uncertain(CandidateField::Modifier) => {
+                ("uncertain_modifier", "the modifier has a competing reading")
+            }
+            ReviewReason::Uncertain(CandidateField::Usage) => {
+                ("uncertain_usage", "the usage has a competing reading")
+            }
         }
     }
 
@@ -401,14 +429,23 @@ impl ParseNotes {
 pub enum Field {
     /// A measurement region (a primary, bracketed, or parenthesized amount).
     Amount,
-    /// The ingredient name, as the grammar carved it (before refine).
+    /// Text that stayed in the ingredient name.
     Name,
-    /// The trailing modifier text (after the first `", "`).
+    /// Modifier text: what followed the name, or a prep word refine moved out
+    /// of it.
     Modifier,
+    /// An alternative ingredient or amount ("or 1 tsp garlic powder").
+    Alternative,
+    /// The phrase that decided the line's [usage](Ingredient::usage) ("for
+    /// frying").
+    Usage,
+    /// What marked the ingredient [optional](Ingredient::optional): an
+    /// "(optional)" note or the parentheses around the whole line.
+    Optional,
 }
 
-/// A grammar-stage byte span: which slice of the normalized input the grammar
-/// assigned to a given [`Field`]. `range` indexes into [`Decomposition::source`].
+/// A byte span of the raw line and the [`Field`] its text became. `range`
+/// indexes into [`Decomposition::source`].
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct FieldSpan {
     /// Which output field this span became.
@@ -419,23 +456,25 @@ pub struct FieldSpan {
     pub text: String,
 }
 
-/// How the grammar carved a line into fields, for the `--explain` view. See
+/// How the parser carved a line into fields, for the `--explain` view and
+/// editors that highlight a line in place. See
 /// [`decompose`](IngredientParser::decompose).
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
 pub struct Decomposition {
-    /// The normalized string the spans index into (what the grammar parsed).
+    /// The raw line the spans index into, as passed to `decompose`.
     pub source: String,
-    /// Grammar-stage field spans, in input order. Empty when a whole-line
-    /// recognizer or the name-only fallback produced the result (no grammar
-    /// carving to show).
+    /// Field spans, in input order and disjoint. Empty when the parse fell
+    /// back to a name-only ingredient (no carving to show).
     pub spans: Vec<FieldSpan>,
 }
 
 /// Customizable ingredient parser with configurable units and adjectives
 ///
 /// This parser allows you to customize which units and adjectives are recognized
-/// during parsing. For parsing recipe instructions (with rich text support),
-/// use [`RichParser`](crate::rich_text::RichParser) instead.
+/// during parsing, either one list at a time ([`with_units`](Self::with_units))
+/// or as a whole [`ParserProfile`] ([`with_profile`](Self::with_profile)).
+/// For parsing recipe instructions (with rich text support), use
+/// [`RichParser`](crate::rich_text::RichParser) instead.
 ///
 /// # Examples
 ///
@@ -455,6 +494,18 @@ pub struct IngredientParser {
     units: HashSet<String>,
     /// Set of recognized adjectives that get moved to modifier field
     adjectives: HashSet<String>,
+    /// The vocabulary the remaining word lists come from (shared, since a
+    /// parser is cloned freely)
+    profile: Arc<ParserProfile>,
+    /// Which interpretation the ambiguous refine decisions take (always
+    /// `Primary` outside [`Self::parse_candidates`])
+    reading: Reading,
+    /// Whose cups, pints and tablespoons the bare unit words mean
+    region: Region,
+    /// Whether to correct unit typos and OCR glyph confusions before parsing
+    spellcheck: bool,
+    /// House-style passes registered with [`Self::with_pass`]
+    passes: pass::CustomPasses,
 }
 
 impl IngredientParser {
@@ -478,21 +529,108 @@ impl IngredientParser {
     ///     .with_units(&["sprig", "sprigs"]);
     /// ```
     pub fn new() -> Self {
+        IngredientParser::default().with_profile(&profile::BUILTIN)
+    }
+
+    /// Replace the parser's vocabulary with `profile` (chainable). The units
+    /// and adjective sets are rebuilt from it, so call [`Self::with_units`]
+    /// afterwards, not before. Pass a loaded profile
+    /// ([`ParserProfile::from_json`] / [`ParserProfile::resolve`]): its `add` /
+    /// `remove` edits are not applied here.
+    ///
+    /// ```
+    /// use ingredient::{IngredientParser, ParserProfile};
+    ///
+    /// let mut profile = ParserProfile::default();
+    /// profile.container_nouns.push("tin".to_string());
+    /// let parser = IngredientParser::new().with_profile(&profile);
+    ///
+    /// let ing = parser.from_str("1 (400g) tin chickpeas");
+    /// assert_eq!(ing.name, "chickpeas");
+    /// assert_eq!(ing.amounts[0].unit().to_str(), "tin");
+    /// ```
+    pub fn with_profile(mut self, profile: &ParserProfile) -> Self {
         // Non-standard units that aren't really convertible for the most part.
         // (See vocab::NON_STANDARD_UNITS for why "whole" is excluded.)
-        let units: HashSet<String> = parser::vocab::NON_STANDARD_UNITS
-            .iter()
-            .map(|&s| s.to_string())
-            .collect();
-
+        self.units = profile.units.iter().cloned().collect();
         // Combine preparation adjectives and purpose phrases
-        let adjectives: HashSet<String> = parser::vocab::DEFAULT_PREPARATION_ADJECTIVES
+        self.adjectives = profile
+            .preparation_adjectives
             .iter()
-            .chain(parser::vocab::DEFAULT_PURPOSE_PHRASES.iter())
-            .map(|&s| s.to_string())
+            .chain(&profile.purpose_phrases)
+            .cloned()
             .collect();
+        self.profile = Arc::new(profile.clone());
+        self
+    }
+
+    /// The vocabulary this parser runs with.
+    pub fn profile(&self) -> &ParserProfile {
+        &self.profile
+    }
+
+    /// Read the bare volume words as `region`'s units (chainable). The default
+    /// is [`Region::US`]; a recipe's region can come from its source's language
+    /// tag ([`Region::from_language_tag`]).
+    ///
+    /// ```
+    /// use ingredient::IngredientParser;
+    /// use ingredient::unit::{Region, Unit};
+    ///
+    /// let uk = IngredientParser::new().with_region(Region::UK);
+    /// let milk = uk.from_str("1 pint milk");
+    /// assert_eq!(*milk.amounts[0].unit(), Unit::ImperialPint);
+    /// // A spelling that names its region keeps it.
+    /// let us = uk.from_str("1 US pint milk");
+    /// assert_eq!(*us.amounts[0].unit(), Unit::Pint);
+    /// ```
+    pub fn with_region(mut self, region: Region) -> Self {
+        self.region = region;
+        self
+    }
+
+    /// The region this parser reads units for.
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    /// Correct misspelled units and OCR glyph confusions in a line's amounts
+    /// before parsing (chainable; off by default). A unit a small edit away
+    /// from a known one ("tablspoon", or a [custom unit](Self::with_units)) is
+    /// respelled, also when glued to its number ("1/2tablspoon"), a leading
+    /// "l"/"O"/"%" is read as 1, 0 or ½ when a unit follows, and a few
+    /// commonly misspelled names right after an amount ("2 cups flower") are
+    /// corrected. Each correction shows up as a normalize rewrite in the
+    /// [stage report](trace::ParseTrace::stages), so they can be audited.
+    ///
+    /// ```
+    /// use ingredient::IngredientParser;
+    ///
+    /// let parser = IngredientParser::new().with_spellcheck(true);
+    /// assert_eq!(parser.from_str("2 tablspoon sugar").to_string(), "2 tbsp sugar");
+    /// assert_eq!(parser.from_str("1% cups flour").to_string(), "1½ cups flour");
+    /// assert_eq!(parser.from_str("2 cups flower").to_string(), "2 cups flour");
+    ///
+    /// let stages = parser.parse_with_trace("l cup milk").trace.stages();
+    /// assert_eq!(stages.normalize[0].name, "fix_ocr_digits");
+    /// assert_eq!(stages.normalize[0].after, "1 cup milk");
+    /// ```
+    pub fn with_spellcheck(mut self, on: bool) -> Self {
+        self.spellcheck = on;
+        self
+    }
+
+    /// Whether this parser corrects typos before parsing.
+    pub fn spellcheck(&self) -> bool {
+        self.spellcheck
+    }
 
-        IngredientParser { units, adjectives }
+    /// A [`MeasurementParser`] over this parser's units, container nouns and
+    /// region.
+    pub(crate) fn measurement_parser(&self, mode: MeasurementMode) -> MeasurementParser<'_> {
+        MeasurementParser::new(&self.units, mode)
+            .with_containers(&self.profile.container_nouns)
+            .with_region(self.region)
     }
 
     /// Add custom units to the parser (chainable)
@@ -531,6 +669,35 @@ impl IngredientParser {
         self.parse_ingredient_line(input)
     }
 
+    /// [`Self::from_str`] for a line under a section header, so the section
+    /// can decide the [usage](Ingredient::usage) when the line doesn't ("For
+    /// the marinade" makes its soy sauce a [`IngredientUsage::Marinade`]).
+    /// Pair with [`section::section_title`] when the sections are inline.
+    ///
+    /// ```
+    /// use ingredient::{IngredientParser, IngredientUsage};
+    ///
+    /// let parser = IngredientParser::new();
+    /// let soy = parser.from_str_in_section("2 tbsp soy sauce", Some("For the marinade"));
+    /// assert_eq!(soy.usage, IngredientUsage::Marinade);
+    /// // A usage the line declares itself still wins.
+    /// let oil = parser.from_str_in_section("oil, for frying", Some("For the marinade"));
+    /// assert_eq!(oil.usage, IngredientUsage::FryingMedium);
+    /// ```
+    pub fn from_str_in_section(&self, input: &str, section: Option<&str>) -> Ingredient {
+        let mut ingredient = self.parse_ingredient_line(input);
+        if section.is_some() && ingredient.usage == IngredientUsage::Normal {
+            ingredient.usage = classify_usage_with(
+                &self.profile,
+                &ingredient.name,
+                ingredient.modifier.as_deref(),
+                Some(input),
+                section,
+            );
+        }
+        ingredient
+    }
+
     /// Parse an ingredient string with debug tracing enabled
     ///
     /// This method returns both the parsed result and a trace of which
@@ -567,6 +734,27 @@ impl IngredientParser {
         self.parse_ingredient_line_with_trace(input)
     }
 
+    /// Parse every line with the pass profiler on, and report where the time
+    /// went: calls, fires and time per stage, normalize rewrite, recognizer
+    /// and refine pass, compile and match counts per regex, and folded stacks
+    /// for a flamegraph. See [`trace::perf`].
+    ///
+    /// ```
+    /// use ingredient::IngredientParser;
+    ///
+    /// let report = IngredientParser::new().profile_passes(["2 cups flour"]);
+    /// let recognize = report.stages.iter().find(|s| s.name == "recognize").unwrap();
+    /// assert_eq!(recognize.calls, 1);
+    /// ```
+    pub fn profile_passes<'a>(
+        &self,
+        lines: impl IntoIterator<Item = &'a str>,
+    ) -> trace::perf::PerfReport {
+        trace::perf::profile(self.pipeline_stage_names(), lines, |line| {
+            self.parse_ingredient_line(line);
+        })
+    }
+
     /// Parse a string containing one or more measurements, e.g. `12 grams` or
     /// `120 grams / 1 cup`.
     ///
@@ -590,7 +778,7 @@ impl IngredientParser {
     /// ```
     #[tracing::instrument(name = "parse_amount", level = "trace", skip_all)]
     pub fn parse_amount(&self, input: &str) -> IngredientResult<Vec<Measure>> {
-        let mp = MeasurementParser::new(&self.units, MeasurementMode::IngredientList);
+        let mp = self.measurement_parser(MeasurementMode::IngredientList);
         match mp.parse_measurement_list(input) {
             Ok((_, measurements)) => Ok(measurements),
             Err(e) => Err(IngredientError::AmountParseError {
@@ -0,0 +1,578 @@
+//! Restating a whole recipe in one measurement system: metric, US customary,
+//! or UK (metric with gas marks).
+//!
+//! [`localize`] walks a [`Recipe`] — parsed ingredient lines plus
+//! [`Rich`] instruction steps — and converts every amount written in the
+//! other system: weights and volumes, oven temperatures, and pan sizes. Each
+//! line comes back as a [`Localized`] pair so callers can show the original
+//! next to (or on hover over) the converted text.
+//!
+//! Converted quantities are rounded to what a cook would write: grams to the
+//! nearest 5 above 100, ovens to the nearest 10 °C or 25 °F, cups to the
+//! nearest eighth, pans to the nearest half centimeter or inch. Amounts
+//! neutral to both systems (counts, pinches, teaspoons and tablespoons, times)
+//! are left alone. When a line already states an amount in the target system
+//! ("1 cup / 120 g flour"), that amount is kept and its counterpart dropped
+//! rather than converted a second time.
+//!
+//! Pan and cut sizes convert as a whole [`Dimension`], in instruction
+//! [`Chunk::Dimension`]s and in an ingredient's modifier ("cut into ½-inch
+//! pieces"). Instruction prose otherwise only converts what
+//! [`RichParser`](crate::rich_text::RichParser) reads as a measure: "350
+//! degrees F" comes through as a bare number and text, and is left as written.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ingredient::{Ingredient, Package};
+use crate::rich_text::{Chunk, Rich};
+use crate::unit::dimension::find_dimensions;
+use crate::unit::measure::{GRAM_TO_OZ, TSP_TO_ML};
+use crate::unit::{
+    Dimension, Measure, MeasureGraph, MeasureKind, Unit, convert_measure_with_graph,
+};
+
+const CM_PER_INCH: f64 = 2.54;
+const OZ_PER_LB: f64 = 16.0;
+
+/// The measurement system a recipe is localized to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum System {
+    /// Grams, milliliters, °C and centimeters.
+    Metric,
+    /// Ounces and pounds, cups, °F and inches.
+    #[serde(rename = "us")]
+    US,
+    /// Metric, with a gas mark after each oven temperature.
+    #[serde(rename = "uk")]
+    UK,
+}
+
+/// Which side of the metric/imperial divide a unit is on.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Family {
+    Metric,
+    Imperial,
+}
+
+impl System {
+    fn family(self) -> Family {
+        match self {
+            System::Metric | System::UK => Family::Metric,
+            System::US => Family::Imperial,
+        }
+    }
+}
+
+/// The family `unit` belongs to; `None` for units both systems share.
+fn family(unit: &Unit) -> Option<Family> {
+    match unit {
+        Unit::Gram
+        | Unit::Kilogram
+        | Unit::Milliliter
+        | Unit::Liter
+        | Unit::MetricCup
+        | Unit::Celsius
+        | Unit::Centimeter
+        | Unit::Millimeter => Some(Family::Metric),
+        Unit::Ounce | Unit::Pound | Unit::Fahrenheit | Unit::Inch => Some(Family::Imperial),
+        u if is_us_volume(u) || is_imperial_volume(u) => Some(Family::Imperial),
+        _ => None,
+    }
+}
+
+/// The US volumes a metric recipe restates (spoons aside).
+fn is_us_volume(unit: &Unit) -> bool {
+    matches!(
+        unit,
+        Unit::Cup | Unit::Pint | Unit::Quart | Unit::Gallon | Unit::FluidOunce
+    )
+}
+
+/// The British imperial volumes, which neither a metric nor a US recipe uses.
+fn is_imperial_volume(unit: &Unit) -> bool {
+    matches!(
+        unit,
+        Unit::ImperialFluidOunce
+            | Unit::ImperialPint
+            | Unit::ImperialQuart
+            | Unit::ImperialGallon
+            | Unit::Gill
+            | Unit::Teacup
+    )
+}
+
+/// A recipe to localize: its parsed ingredient lines and instruction steps.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Recipe {
+    pub ingredients: Vec<Ingredient>,
+    pub instructions: Vec<Rich>,
+}
+
+/// One line as written and as localized.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Localized<T> {
+    pub original: T,
+    pub localized: T,
+}
+
+/// A [`Recipe`] restated in `system`, line by line.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LocalizedRecipe {
+    pub system: System,
+    pub ingredients: Vec<Localized<Ingredient>>,
+    pub instructions: Vec<Localized<Rich>>,
+}
+
+/// Localize `recipe` to `system`. Volumes stay volumes; use
+/// [`localize_with_graphs`] to turn cups into grams.
+///
+/// ```
+/// use ingredient::localize::{Recipe, System, localize};
+/// use ingredient::rich_text::{Chunk, RichParser};
+///
+/// let recipe = Recipe {
+///     ingredients: vec![ingredient::from_str("8 oz butter")],
+///     instructions: vec![RichParser::new(["butter"]).parse("Bake at 350°F.").unwrap()],
+/// };
+/// let uk = localize(&recipe, System::UK);
+/// assert_eq!(uk.ingredients[0].localized.to_string(), "225 g butter");
+/// assert_eq!(uk.ingredients[0].original.to_string(), "8 oz butter");
+/// assert_eq!(
+///     uk.instructions[0].localized[2],
+///     Chunk::Text(" (gas mark 4)".to_string())
+/// );
+/// ```
+pub fn localize(recipe: &Recipe, system: System) -> LocalizedRecipe {
+    localize_with_graphs(recipe, system, &HashMap::new())
+}
+
+/// Like [`localize`], but a metric localization converts an ingredient's cups,
+/// quarts and fluid ounces to grams through its conversion graph, keyed by
+/// lowercase ingredient name. A line that already gives a weight keeps it and
+/// its volume becomes milliliters instead; spoon measures are never weighed.
+pub fn localize_with_graphs(
+    recipe: &Recipe,
+    system: System,
+    graphs: &HashMap<String, MeasureGraph>,
+) -> LocalizedRecipe {
+    LocalizedRecipe {
+        system,
+        ingredients: recipe
+            .ingredients
+            .iter()
+            .map(|ing| Localized {
+                original: ing.clone(),
+                localized: localize_ingredient(ing, system, graphs.get(&ing.name.to_lowercase())),
+            })
+            .collect(),
+        instructions: recipe
+            .instructions
+            .iter()
+            .map(|step| Localized {
+                original: step.clone(),
+                localized: localize_rich(step, system),
+            })
+            .collect(),
+    }
+}
+
+fn localize_ingredient(
+    ing: &Ingredient,
+    system: System,
+    graph: Option<&MeasureGraph>,
+) -> Ingredient {
+    Ingredient {
+        amounts: localize_amounts(&ing.amounts, system, graph),
+        modifier: ing
+            .modifier
+            .as_deref()
+            .map(|modifier| localize_sizes(modifier, system)),
+        package: ing.package.as_ref().map(|p| Package {
+            size: localize_amounts(&p.size, system, None),
+            ..p.clone()
+        }),
+        ..ing.clone()
+    }
+}
+
+/// Localize one line's amounts, dropping the foreign-system ones when the line
+/// already has an amount native to `system`.
+fn localize_amounts(
+    amounts: &[Measure],
+    system: System,
+    graph: Option<&MeasureGraph>,
+) -> Vec<Measure> {
+    let target = system.family();
+    let has_native = amounts.iter().any(|m| family(m.unit()) == Some(target));
+    let has_weight = amounts.iter().any(|m| m.kind() == MeasureKind::Weight);
+    let graph = graph.filter(|_| !has_weight);
+    amounts
+        .iter()
+        .filter(|m| !has_native || family(m.unit()).is_none_or(|f| f == target))
+        .map(|m| {
+            graph
+                .and_then(|g| weigh(m, system, g))
+                .unwrap_or_else(|| localize_measure(m, system))
+        })
+        .collect()
+}
+
+/// A metric weight for a cup/quart/fl oz amount, through `graph`.
+fn weigh(m: &Measure, system: System, graph: &MeasureGraph) -> Option<Measure> {
+    if system.family() != Family::Metric
+        || !(is_us_volume(m.unit()) || is_imperial_volume(m.unit()) || *m.unit() == Unit::MetricCup)
+    {
+        return None;
+    }
+    let grams =
+        convert_measure_with_graph(&m.unqualified(), MeasureKind::Weight, graph)?.normalize();
+    Some(metric(
+        &keep_qualifier(grams, m),
+        Unit::Gram,
+        Unit::Kilogram,
+    ))
+}
+
+/// `m` restated in `system`, or unchanged when it's neutral or already
+/// native.
+pub fn localize_measure(m: &Measure, system: System) -> Measure {
+    match system.family() {
+        Family::Metric => to_metric(m),
+        Family::Imperial => to_us(m),
+    }
+}
+
+fn to_metric(m: &Measure) -> Measure {
+    match m.unit() {
+        Unit::Ounce | Unit::Pound => metric(&m.normalize(), Unit::Gram, Unit::Kilogram),
+        u if is_us_volume(u) || is_imperial_volume(u) => {
+            metric(&milliliters(m), Unit::Milliliter, Unit::Liter)
+        }
+        Unit::Fahrenheit => restate(m, Unit::Celsius, |f| {
+            let c = (f - 32.0) * 5.0 / 9.0;
+            round_to(c, if c >= 120.0 { 10.0 } else { 1.0 })
+        }),
+        Unit::Inch => restate(m, Unit::Centimeter, |i| round_to(i * CM_PER_INCH, 0.5)),
+        _ => m.clone(),
+    }
+}
+
+fn to_us(m: &Measure) -> Measure {
+    match m.unit() {
+        Unit::Gram | Unit::Kilogram => {
+            let grams = m.normalize();
+            if grams.value() / GRAM_TO_OZ >= OZ_PER_LB {
+                restate(&grams, Unit::Pound, |g| {
+                    round_to(g / GRAM_TO_OZ / OZ_PER_LB, 0.125)
+                })
+            } else {
+                restate(&grams, Unit::Ounce, |g| round_to(g / GRAM_TO_OZ, 0.25))
+            }
+        }
+        u if matches!(u, Unit::Milliliter | Unit::Liter | Unit::MetricCup)
+            || is_imperial_volume(u) =>
+        {
+            let ml = milliliters(m);
+            let best =
+                Measure::new_with_upper(Unit::Teaspoon, ml.value() / TSP_TO_ML, None).denormalize();
+            let tsp_per = match best.unit() {
+                Unit::Tablespoon => 3.0,
+                Unit::Cup => 48.0,
+                Unit::Quart => 192.0,
+                _ => 1.0,
+            };
+            restate(&ml, best.unit().clone(), |v| {
+                round_to(v / TSP_TO_ML / tsp_per, 0.125)
+            })
+        }
+        Unit::Celsius => restate(m, Unit::Fahrenheit, |c| {
+            let f = c * 9.0 / 5.0 + 32.0;
+            round_to(f, if f >= 250.0 { 25.0 } else { 1.0 })
+        }),
+        Unit::Centimeter => restate(m, Unit::Inch, |v| round_to(v / CM_PER_INCH, 0.5)),
+        Unit::Millimeter => restate(m, Unit::Inch, |v| round_to(v / 10.0 / CM_PER_INCH, 0.125)),
+        _ => m.clone(),
+    }
+}
+
+/// `d` restated in `system`: a 9x13-inch pan is 23x33 cm in a metric recipe.
+pub fn localize_dimension(d: &Dimension, system: System) -> Dimension {
+    match (system.family(), d.unit()) {
+        (Family::Metric, Unit::Inch) => d.to_metric(),
+        (Family::Imperial, Unit::Centimeter | Unit::Millimeter) => d.to_imperial(),
+        _ => d.clone(),
+    }
+}
+
+/// `text` with each size in it restated in `system`.
+fn localize_sizes(text: &str, system: System) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut at = 0;
+    for (span, d) in find_dimensions(text) {
+        let localized = localize_dimension(&d, system);
+        out.push_str(&text[at..span.start]);
+        if localized == d {
+            out.push_str(&text[span.clone()]);
+        } else {
+            out.push_str(&localized.to_string());
+        }
+        at = span.end;
+    }
+    out.push_str(&text[at..]);
+    out
+}
+
+/// The UK gas mark for an oven temperature, if `m` is one: "4" for 350 °F or
+/// 180 °C, "½" for 250 °F, and "4–5" for a range spanning two marks.
+pub fn gas_mark(m: &Measure) -> Option<String> {
+    let fahrenheit = |v: f64| match m.unit() {
+        Unit::Fahrenheit => Some(v),
+        Unit::Celsius => Some(v * 9.0 / 5.0 + 32.0),
+        _ => None,
+    };
+    let lower = mark(fahrenheit(m.value())?)?;
+    match m.upper_value().and_then(fahrenheit).and_then(mark) {
+        Some(upper) if upper != lower => Some(format!("{lower}–{upper}")),
+        _ => Some(lower.to_string()),
+    }
+}
+
+/// Gas marks by the Fahrenheit temperature each stands for.
+const GAS_MARKS: [(f64, &str); 11] = [
+    (225.0, "¼"),
+    (250.0, "½"),
+    (275.0, "1"),
+    (300.0, "2"),
+    (325.0, "3"),
+    (350.0, "4"),
+    (375.0, "5"),
+    (400.0, "6"),
+    (425.0, "7"),
+    (450.0, "8"),
+    (475.0, "9"),
+];
+
+fn mark(fahrenheit: f64) -> Option<&'static str> {
+    GAS_MARKS
+        .iter()
+        .find(|(f, _)| (fahrenheit - f).abs() <= 12.5)
+        .map(|(_, mark)| *mark)
+}
+
+fn localize_rich(rich: &Rich, system: System) -> Rich {
+    let mut out = Vec::with_capacity(rich.len());
+    for chunk in rich {
+        match chunk {
+            Chunk::Measure(ms) => {
+                let localized = localize_amounts(ms, system, None);
+                let marks: Vec<String> = match system {
+                    System::UK => localized.iter().filter_map(gas_mark).collect(),
+                    _ => vec![],
+                };
+                out.push(Chunk::Measure(localized));
+                if let Some(mark) = marks.first() {
+                    out.push(Chunk::Text(format!(" (gas mark {mark})")));
+                }
+            }
+            Chunk::Dimension(d) => out.push(Chunk::Dimension(localize_dimension(d, system))),
+            chunk => out.push(chunk.clone()),
+        }
+    }
+    out
+}
+
+/// A volume in milliliters, unrounded.
+fn milliliters(m: &Measure) -> Measure {
+    let base = m.normalize();
+    if *base.unit() == Unit::Teaspoon {
+        restate(&base, Unit::Milliliter, |tsp| tsp * TSP_TO_ML)
+    } else {
+        base
+    }
+}
+
+/// A metric weight or volume in its `small` base unit, rounded, and moved to
+/// `large` from 1000 up.
+fn metric(base: &Measure, small: Unit, large: Unit) -> Measure {
+    if base.value() >= 1000.0 {
+        restate(base, large, |v| round_to(v, 50.0) / 1000.0)
+    } else {
+        restate(base, small, |v| {
+            let step = match v {
+                v if v >= 100.0 => 5.0,
+                v if v >= 10.0 => 1.0,
+                _ => 0.1,
+            };
+            round_to(v, step)
+        })
+    }
+}
+
+/// `m`'s bounds mapped through `f` into `unit`, keeping its qualifier.
+fn restate(m: &Measure, unit: Unit, f: impl Fn(f64) -> f64) -> Measure {
+    keep_qualifier(
+        Measure::new_with_upper(unit, f(m.value()), m.upper_value().map(&f)),
+        m,
+    )
+}
+
+fn keep_qualifier(m: Measure, from: &Measure) -> Measure {
+    match from.qualifier() {
+        Some(q) => m.with_qualifier(q),
+        None => m,
+    }
+}
+
+/// `value` to the nearest `step`, but never rounded away to nothing.
+fn round_to(value: f64, step: f64) -> f64 {
+    let rounded = (value / step).round() * step;
+    if rounded == 0.0 && value > 0.0 {
+        step
+    } else {
+        // Strip float noise from the multiplication ("0.30000000000000004").
+        (rounded * 1000.0).round() / 1000.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::rich_text::RichParser;
+    use crate::unit::make_graph;
+    use rstest::rstest;
+
+    fn line(system: System, input: &str) -> String {
+        let ing = crate::from_str(input);
+        localize_ingredient(&ing, system, None).to_string()
+    }
+
+    fn step(system: System, input: &str) -> String {
+        let rich = RichParser::new(Vec::<String>::new()).parse(input).unwrap();
+        localize_rich(&rich, system)
+            .iter()
+            .map(|c| match c {
+                Chunk::Measure(ms) => ms
+                    .iter()
+                    .map(|m| m.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" / "),
+                Chunk::Dimension(d) => d.to_string(),
+                Chunk::Text(t) | Chunk::Ing(t) => t.clone(),
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case::ounces(System::Metric, "8 oz butter", "225 g butter")]
+    #[case::pounds(System::Metric, "2.5 lb potatoes", "1.15 kg potatoes")]
+    #[case::cups(System::Metric, "1 cup milk", "235 ml milk")]
+    #[case::spoons_stay(System::Metric, "1 tbsp oil", "1 tbsp oil")]
+    #[case::grams(System::US, "200 g sugar", "7 oz sugar")]
+    #[case::kilos(System::US, "1 kg flour", "2¼ lb flour")]
+    #[case::milliliters(System::US, "250 ml stock", "1 cup stock")]
+    #[case::small_volume(System::US, "15 ml vinegar", "1 tbsp vinegar")]
+    #[case::range(System::Metric, "2-3 cups stock", "475 - 710 ml stock")]
+    #[case::qualifier(System::Metric, "about 4 oz cheese", "about 115 g cheese")]
+    #[case::imperial_to_metric(System::Metric, "1 imperial pint milk", "570 ml milk")]
+    #[case::imperial_to_us(System::US, "1 imperial pint milk", "2⅜ cups milk")]
+    #[case::metric_cup_to_us(System::US, "1 metric cup milk", "1 cup milk")]
+    #[case::pie_crust(System::Metric, "1 (9-inch) pie crust", "1 / 23 cm pie crust")]
+    #[case::cut_size(
+        System::Metric,
+        "1 lb pork, cut into 2-inch pieces",
+        "455 g pork, cut into 5 cm pieces"
+    )]
+    #[case::metric_cut(
+        System::US,
+        "2 carrots, cut into 1 cm dice",
+        "2 carrots, cut into ⅜ inch dice"
+    )]
+    fn localizes_ingredient_amounts(
+        #[case] system: System,
+        #[case] input: &str,
+        #[case] want: &str,
+    ) {
+        assert_eq!(line(system, input), want, "{input}");
+    }
+
+    #[rstest]
+    #[case::to_metric(System::Metric, "1 cup / 120 g flour", "120 g flour")]
+    #[case::to_us(System::US, "1 cup / 120 g flour", "1 cup flour")]
+    #[case::neutral_kept(System::Metric, "1 tbsp / 15 ml / ½ oz oil", "1 tbsp / 15 ml oil")]
+    fn keeps_the_native_amount_of_a_dual_unit_line(
+        #[case] system: System,
+        #[case] input: &str,
+        #[case] want: &str,
+    ) {
+        assert_eq!(line(system, input), want, "{input}");
+    }
+
+    #[test]
+    fn localizes_package_sizes() {
+        let ing = crate::from_str("2 (14.5-ounce) cans tomatoes");
+        let got = localize_ingredient(&ing, System::Metric, None);
+        assert_eq!(got.package.unwrap().size, [Measure::new("g", 410.0)]);
+    }
+
+    #[rstest]
+    #[case::to_celsius(System::Metric, "Bake at 350°F.", "Bake at 180 celsius.")]
+    #[case::gas_mark(System::UK, "Bake at 425°F.", "Bake at 220 celsius (gas mark 7).")]
+    #[case::celsius_gas_mark(
+        System::UK,
+        "Heat oven to 180°C.",
+        "Heat oven to 180 celsius (gas mark 4)."
+    )]
+    #[case::to_fahrenheit(System::US, "Heat oven to 200°C.", "Heat oven to 400 fahrenheit.")]
+    #[case::pan(System::Metric, "Grease a 9-inch pan.", "Grease a 23 cm pan.")]
+    #[case::two_sided_pan(System::Metric, "Use a 9x13-inch dish.", "Use a 23x33 cm dish.")]
+    #[case::metric_tin(System::US, "Line a 20cm square tin.", "Line a 8 inch square tin.")]
+    #[case::thickness(System::Metric, "Roll ¼-inch-thick.", "Roll 6 mm thick.")]
+    fn localizes_instructions(#[case] system: System, #[case] input: &str, #[case] want: &str) {
+        assert_eq!(step(system, input), want, "{input}");
+    }
+
+    #[rstest]
+    #[case::exact(350.0, "4")]
+    #[case::near(360.0, "4")]
+    #[case::quarter(225.0, "¼")]
+    #[case::too_cool(150.0, "")]
+    fn gas_marks(#[case] fahrenheit: f64, #[case] want: &str) {
+        let got = gas_mark(&Measure::new("°f", fahrenheit)).unwrap_or_default();
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn gas_mark_of_a_range() {
+        let range = Measure::with_range("°f", 350.0, 375.0);
+        assert_eq!(gas_mark(&range).unwrap(), "4–5");
+    }
+
+    #[test]
+    fn weighs_cups_through_a_graph_and_keeps_the_original() {
+        let flour = (Measure::new("cup", 1.0), Measure::new("g", 120.0));
+        let graphs = HashMap::from([("flour".to_string(), make_graph(&[flour]))]);
+        let recipe = Recipe {
+            ingredients: vec![
+                crate::from_str("2 cups flour"),
+                crate::from_str("1 cup flour (4.5 oz)"),
+                crate::from_str("1 cup milk"),
+            ],
+            instructions: vec![],
+        };
+        let got = localize_with_graphs(&recipe, System::Metric, &graphs);
+        let rendered: Vec<String> = got
+            .ingredients
+            .iter()
+            .map(|l| l.localized.to_string())
+            .collect();
+        assert_eq!(
+            rendered,
+            ["240 g flour", "235 ml / 130 g flour", "235 ml milk"]
+        );
+        assert_eq!(got.ingredients[0].original.to_string(), "2 cups flour");
+    }
+}
@@ -0,0 +1,555 @@
+//! Field spans over the raw line, for `--explain` and in-place highlighting.
+//!
+//! [`IngredientParser::decompose`] carves the line the way its parse went: a
+//! recognizer's own split ("Juice of" | "1" | "lemon"), or the clause grammar's
+//! amount, name and modifier spans. The refine passes' moves are then replayed
+//! over the grammar's name span word by word, so a prep word refine moved out
+//! of the name is labeled a modifier and an "or …" split off it an
+//! alternative. Last, every span is taken back through the normalize rewrites
+//! and the optional-note strip to the raw line with [`OffsetMap`]s.
+
+use std::collections::HashSet;
+use std::ops::Range;
+
+use super::ir::{ModifierPart, ParsedIngredient};
+use super::normalize::{lift_inline_descriptive_paren, optional_note_ranges, strip_optional_note};
+use super::offsets::OffsetMap;
+use super::recognize::{RecognizerId, builtin_recognizer, x_of_pivot};
+use crate::usage::Haystack;
+use crate::{Decomposition, Field, FieldSpan, Ingredient, IngredientParser};
+
+/// A field and its byte range in the line being carved.
+type Carve = (Field, Range<usize>);
+
+impl IngredientParser {
+    /// Decompose a line into field spans for the `--explain` decomposition
+    /// view and editors that highlight a line in place.
+    ///
+    /// Each [`FieldSpan`] is a byte range of `raw` itself, whatever the
+    /// normalize rewrites did to the line (a bullet, non-breaking space or
+    /// footnote glyph is left out of the spans). The spans follow the path the
+    /// parse took: a whole-line recognizer's split, or the grammar's carve with
+    /// the refine passes' moves replayed over it. The phrase that decided the
+    /// usage and an "(optional)" note get spans too. `spans` is empty when the
+    /// parse fell back to a name-only ingredient.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ingredient::IngredientParser;
+    /// use ingredient::Field;
+    ///
+    /// let parser = IngredientParser::new();
+    /// let decomp = parser.decompose("2 cups flour, sifted");
+    ///
+    /// assert_eq!(decomp.source, "2 cups flour, sifted");
+    /// assert_eq!(decomp.spans.len(), 3);
+    /// assert_eq!(decomp.spans[0].field, Field::Amount);
+    /// assert_eq!(decomp.spans[0].text, "2 cups");
+    /// assert_eq!(decomp.spans[1].field, Field::Name);
+    /// assert_eq!(decomp.spans[1].text, "flour");
+    /// assert_eq!(decomp.spans[2].field, Field::Modifier);
+    /// assert_eq!(decomp.spans[2].text, "sifted");
+    ///
+    /// // Spans index the raw line, past the bullet and non-breaking space.
+    /// let decomp = parser.decompose("• Juice of 1\u{a0}lemon (optional)");
+    /// let fields: Vec<_> = decomp.spans.iter().map(|s| (s.field, s.text.as_str())).collect();
+    /// assert_eq!(
+    ///     fields,
+    ///     [
+    ///         (Field::Modifier, "Juice of"),
+    ///         (Field::Amount, "1"),
+    ///         (Field::Name, "lemon"),
+    ///         (Field::Optional, "(optional)"),
+    ///     ]
+    /// );
+    /// ```
+    pub fn decompose(&self, raw: &str) -> Decomposition {
+        let normalized = self.normalize_line(raw);
+        let (cleaned, _) = strip_optional_note(&normalized);
+        let mut carves = self.carve_line(&cleaned);
+        if carves.is_empty() {
+            return Decomposition {
+                source: raw.to_string(),
+                spans: Vec::new(),
+            };
+        }
+        if let Some(usage) = self.usage_range(raw, &cleaned, &carves) {
+            carves = overlay(carves, (Field::Usage, usage));
+        }
+
+        let to_raw = OffsetMap::between(raw, &normalized);
+        let cleaned_to_raw = to_raw.then(&OffsetMap::between(&normalized, &cleaned));
+        let mut spans: Vec<Carve> = carves
+            .into_iter()
+            .map(|(field, range)| (field, cleaned_to_raw.range(range)))
+            .collect();
+        spans.extend(
+            optional_note_ranges(&normalized)
+                .into_iter()
+                .map(|range| (Field::Optional, to_raw.range(range))),
+        );
+        Decomposition {
+            source: raw.to_string(),
+            spans: field_spans(raw, spans),
+        }
+    }
+
+    /// The fields of `line` (normalized, note stripped) along the path its
+    /// parse takes. Empty when no path carves it.
+    fn carve_line(&self, line: &str) -> Vec<Carve> {
+        match self.matched_recognizer(line) {
+            Some(name) => self.recognizer_carve(name, line),
+            None => self.grammar_carve(line),
+        }
+    }
+
+    /// A recognizer's split of the line it claimed.
+    fn recognizer_carve(&self, name: &'static str, line: &str) -> Vec<Carve> {
+        let start = line.len() - line.trim_start().len();
+        let trimmed = line.trim();
+        let end = start + trimmed.len();
+        match builtin_recognizer(name) {
+            // "(…)": the parentheses mark it optional; the inside is a line.
+            Some(RecognizerId::OptionalWrapped) => {
+                let mut carves = vec![(Field::Optional, start..start + 1)];
+                carves.extend(shift(
+                    self.grammar_carve(&line[start + 1..end - 1]),
+                    start + 1,
+                ));
+                carves.push((Field::Optional, end - 1..end));
+                carves
+            }
+            Some(RecognizerId::TrailingAmount) => self
+                .split_trailing_amount(line)
+                .map(|(name_end, amount_start, _)| {
+                    vec![
+                        (Field::Name, 0..name_end),
+                        (Field::Amount, amount_start..line.len()),
+                    ]
+                })
+                .unwrap_or_default(),
+            // "Juice of" becomes the modifier; the rest is a line of its own.
+            Some(RecognizerId::XOfConstruction) => {
+                let Some(pivot) = x_of_pivot(trimmed) else {
+                    return Vec::new();
+                };
+                let mut carves = vec![(Field::Modifier, start..start + pivot)];
+                carves.extend(shift(
+                    self.grammar_carve(&line[start + pivot..end]),
+                    start + pivot,
+                ));
+                carves
+            }
+            None => self
+                .passes
+                .named(name)
+                .and_then(|pass| pass.recognize(self, line))
+                .map(|ingredient| locate(line, &ingredient))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The grammar's carve of `line`, with refine's moves replayed. A
+    /// descriptive aside the parse lifts out of the name is a modifier where it
+    /// stands.
+    fn grammar_carve(&self, line: &str) -> Vec<Carve> {
+        let Some((lifted, aside)) = lift_inline_descriptive_paren(line) else {
+            return self.refined_carve(line);
+        };
+        let to_line = OffsetMap::between(line, &lifted);
+        let mut carves: Vec<Carve> = self
+            .refined_carve(&lifted)
+            .into_iter()
+            .map(|(field, range)| (field, to_line.range(range)))
+            .collect();
+        if let Some(at) = line.find(aside.as_str()) {
+            carves.push((Field::Modifier, at..at + aside.len()));
+        }
+        carves.sort_by_key(|(_, range)| range.start);
+        carves
+    }
+
+    fn refined_carve(&self, line: &str) -> Vec<Carve> {
+        let carves = self
+            .segmented_field_spans(line)
+            .into_iter()
+            .map(|span| (span.field, span.range));
+        let Ok((_, mut parsed)) = self.parse_ingredient_ir(line) else {
+            return carves.collect();
+        };
+        self.refine(&mut parsed);
+        carves
+            .flat_map(|(field, range)| match field {
+                Field::Name => replay_refine(line, range, &parsed),
+                Field::Modifier if is_alternative(&line[range.clone()], &parsed) => {
+                    vec![(Field::Alternative, range)]
+                }
+                _ => vec![(field, range)],
+            })
+            .collect()
+    }
+
+    /// Where the phrase that decided the usage sits in `line`, preferring an
+    /// occurrence inside the field it was found in.
+    fn usage_range(&self, raw: &str, line: &str, carves: &[Carve]) -> Option<Range<usize>> {
+        let why = self.explain_usage(raw, None);
+        let hit = why.decided_by()?;
+        let phrase = hit.text.get(hit.range.clone())?;
+        let lower = crate::parser::byte_aligned_lowercase(line)?;
+        let wanted = |field: Field| match hit.haystack {
+            Haystack::Modifier => field == Field::Modifier,
+            Haystack::Name => field == Field::Name,
+            Haystack::RawLine => true,
+            Haystack::Section => false,
+        };
+        let mut found = lower
+            .match_indices(phrase)
+            .map(|(at, _)| at..at + phrase.len());
+        let first = found.clone().next();
+        found
+            .find(|r| {
+                carves
+                    .iter()
+                    .any(|(f, c)| wanted(*f) && c.start <= r.start && r.end <= c.end)
+            })
+            .or(first.filter(|_| hit.haystack != Haystack::Section))
+    }
+}
+
+/// Label each word of the grammar's name span `range` with the field
+/// refine left it in: the name, a prep modifier, an alternative, or (a
+/// unit refine claimed, like "large" or "clove") the amount.
+fn replay_refine(line: &str, range: Range<usize>, parsed: &ParsedIngredient) -> Vec<Carve> {
+    let name: Vec<String> = parsed.name.split_whitespace().map(fold).collect();
+    let part_words = |alternative: bool| -> HashSet<String> {
+        parsed
+            .modifier
+            .iter()
+            .filter(|part| matches!(part, ModifierPart::Alternative(_)) == alternative)
+            .flat_map(|part| part.text().split_whitespace().map(fold))
+            .collect()
+    };
+    let (alternatives, modifiers) = (part_words(true), part_words(false));
+    let units: HashSet<String> = parsed
+        .amounts
+        .iter()
+        .map(|measure| fold(&measure.unit().to_str()))
+        .collect();
+
+    let words = word_ranges(line, range.clone());
+    let mut next_name = 0;
+    let mut fields: Vec<Option<Field>> = words
+        .iter()
+        .map(|word| {
+            let word = fold(&line[word.clone()]);
+            if word.is_empty() {
+                None
+            } else if let Some(k) = name[next_name..].iter().position(|n| same_word(n, &word)) {
+                next_name += k + 1;
+                Some(Field::Name)
+            } else if alternatives.contains(&word) {
+                Some(Field::Alternative)
+            } else if modifiers.contains(&word) {
+                Some(Field::Modifier)
+            } else if units.contains(&word) || units.contains(crate::unit::singular(&word).as_ref())
+            {
+                Some(Field::Amount)
+            } else {
+                None
+            }
+        })
+        .collect();
+    // A connective ("or", "and") goes with the word after it.
+    let mut after = None;
+    for field in fields.iter_mut().rev() {
+        match field {
+            Some(f) => after = Some(*f),
+            None => *field = after,
+        }
+    }
+
+    let mut carves: Vec<Carve> = Vec::new();
+    for (word, field) in words.into_iter().zip(fields) {
+        let field = field
+            .or(carves.last().map(|(f, _)| *f))
+            .unwrap_or(Field::Name);
+        match carves.last_mut() {
+            Some((last, span)) if *last == field => span.end = word.end,
+            _ => carves.push((field, word)),
+        }
+    }
+    carves
+}
+
+/// Offset carves of a sub-line that starts at `by`.
+fn shift(carves: Vec<Carve>, by: usize) -> impl Iterator<Item = Carve> {
+    carves
+        .into_iter()
+        .map(move |(field, range)| (field, range.start + by..range.end + by))
+}
+
+/// Lay `top` over `carves`, cutting away what it covers.
+fn overlay(carves: Vec<Carve>, top: Carve) -> Vec<Carve> {
+    let range = &top.1;
+    let mut out = Vec::with_capacity(carves.len() + 2);
+    for (field, c) in carves {
+        if c.end <= range.start || range.end <= c.start {
+            out.push((field, c));
+            continue;
+        }
+        if c.start < range.start {
+            out.push((field, c.start..range.start));
+        }
+        if range.end < c.end {
+            out.push((field, range.end..c.end));
+        }
+    }
+    out.push(top);
+    out.sort_by_key(|(_, range)| range.start);
+    out
+}
+
+/// The public spans over `raw`: trimmed of spaces and commas, in order, and
+/// disjoint (where two overlap, the earlier one wins). Two spans of a field
+/// with only spaces between them ("2" and the "large" refine made its unit)
+/// are one span.
+fn field_spans(raw: &str, carves: Vec<Carve>) -> Vec<FieldSpan> {
+    let mut carves: Vec<Carve> = carves
+        .into_iter()
+        .filter_map(|(field, range)| Some((field, trim(raw, range)?)))
+        .collect();
+    carves.sort_by_key(|(_, range)| (range.start, range.end));
+    let mut merged: Vec<Carve> = Vec::with_capacity(carves.len());
+    for (field, range) in carves {
+        match merged.last_mut() {
+            Some((_, prev)) if range.start < prev.end => {}
+            Some((last, prev))
+                if *last == field && raw[prev.end..range.start].trim().is_empty() =>
+            {
+                prev.end = range.end;
+            }
+            _ => merged.push((field, range)),
+        }
+    }
+    merged
+        .into_iter()
+        .map(|(field, range)| FieldSpan {
+            field,
+            text: raw[range.clone()].to_string(),
+            range,
+        })
+        .collect()
+}
+
+/// `range` of `text` less leading and trailing spaces and commas; `None` when
+/// nothing is left or it isn't a slice of `text`.
+fn trim(text: &str, range: Range<usize>) -> Option<Range<usize>> {
+    let slice = text.get(range.clone())?;
+    let edge = |c: char| c.is_whitespace() || c == ',';
+    let start = range.start + (slice.len() - slice.trim_start_matches(edge).len());
+    let len = slice.trim_matches(edge).len();
+    (len > 0).then_some(start..start + len)
+}
+
+/// The whitespace-separated words of `line[range]`, as ranges of `line`.
+fn word_ranges(line: &str, range: Range<usize>) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in line[range.clone()].char_indices() {
+        let at = range.start + i;
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                words.push(s..at);
+                start = None;
+            }
+            (false, None) => start = Some(at),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push(s..range.end);
+    }
+    words
+}
+
+/// A word as compared across the line and the parsed fields: lowercase, no
+/// surrounding punctuation.
+fn fold(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+/// Equal, or equal but for a plural "s" (refine may singularize a name).
+fn same_word(a: &str, b: &str) -> bool {
+    a == b || a.trim_end_matches('s') == b.trim_end_matches('s')
+}
+
+/// Whether refine made the grammar's modifier text `text` an alternative.
+fn is_alternative(text: &str, parsed: &ParsedIngredient) -> bool {
+    let text = fold(text);
+    !text.is_empty()
+        && parsed.modifier.iter().any(|part| {
+            let ModifierPart::Alternative(alt) = part else {
+                return false;
+            };
+            let alt = fold(alt);
+            alt.contains(&text) || text.contains(&alt)
+        })
+}
+
+/// Spans for a [registered](crate::pass) recognizer's ingredient, by finding
+/// its name and modifier parts in the line.
+fn locate(line: &str, ingredient: &Ingredient) -> Vec<Carve> {
+    let Some(lower) = crate::parser::byte_aligned_lowercase(line) else {
+        return Vec::new();
+    };
+    let find = |text: &str| {
+        let needle = text.trim().to_lowercase();
+        (!needle.is_empty())
+            .then(|| lower.find(&needle))
+            .flatten()
+            .map(|at| at..at + needle.len())
+    };
+    let mut carves: Vec<Carve> = find(&ingredient.name)
+        .map(|range| (Field::Name, range))
+        .into_iter()
+        .collect();
+    for part in ingredient.modifier.iter().flat_map(|m| m.split(", ")) {
+        carves.extend(find(part).map(|range| (Field::Modifier, range)));
+    }
+    carves.sort_by_key(|(_, range)| range.start);
+    carves
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Field, IngredientParser};
+    use rstest::rstest;
+
+    /// (field, text) pairs expected from `decompose`, in span order.
+    type Expected = &'static [(Field, &'static str)];
+
+    #[rstest]
+    #[case("2 cups flour", &[(Field::Amount, "2 cups"), (Field::Name, "flour")])]
+    #[case(
+        "1 cup / 240ml water",
+        &[(Field::Amount, "1 cup / 240ml"), (Field::Name, "water")]
+    )]
+    #[case(
+        "2¼ cups all-purpose flour, sifted",
+        &[
+            (Field::Amount, "2¼ cups"),
+            (Field::Name, "all-purpose flour"),
+            (Field::Modifier, "sifted"),
+        ]
+    )]
+    // Refine's moves are replayed: the prep words it takes out of the name
+    // are a modifier, the size word it makes the unit part of the amount.
+    #[case(
+        "2 chopped fresh basil",
+        &[(Field::Amount, "2"), (Field::Modifier, "chopped fresh"), (Field::Name, "basil")]
+    )]
+    #[case(
+        "2 large eggs, beaten",
+        &[(Field::Amount, "2 large"), (Field::Name, "eggs"), (Field::Modifier, "beaten")]
+    )]
+    #[case(
+        "1 cup milk or cream",
+        &[(Field::Amount, "1 cup"), (Field::Name, "milk"), (Field::Alternative, "or cream")]
+    )]
+    // Spans index the raw line, past the bullet, nbsp and footnote glyph.
+    #[case("• 2\u{a0}cups flour", &[(Field::Amount, "2\u{a0}cups"), (Field::Name, "flour")])]
+    #[case("rye flour ①, sifted", &[(Field::Name, "rye flour"), (Field::Modifier, "sifted")])]
+    // A lifted aside is a modifier where it was written.
+    #[case(
+        "1 (1½-inch-thick) bone-in pork chop",
+        &[
+            (Field::Amount, "1"),
+            (Field::Modifier, "1½-inch-thick"),
+            (Field::Name, "bone-in pork chop"),
+        ]
+    )]
+    #[case(
+        "2 tbsp oil, for frying",
+        &[(Field::Amount, "2 tbsp"), (Field::Name, "oil"), (Field::Usage, "for frying")]
+    )]
+    #[case(
+        "1 cup parsley, chopped, optional",
+        &[
+            (Field::Amount, "1 cup"),
+            (Field::Name, "parsley"),
+            (Field::Modifier, "chopped"),
+            (Field::Optional, "optional"),
+        ]
+    )]
+    // The trailing_amount recognizer splits at the dash.
+    #[case(
+        "Butter — 2 tablespoons",
+        &[(Field::Name, "Butter"), (Field::Amount, "2 tablespoons")]
+    )]
+    // The optional_wrapped recognizer: the parentheses are the note.
+    #[case(
+        "(1 tsp salt)",
+        &[
+            (Field::Optional, "("),
+            (Field::Amount, "1 tsp"),
+            (Field::Name, "salt"),
+            (Field::Optional, ")"),
+        ]
+    )]
+    #[case("salt", &[(Field::Name, "salt")])]
+    // A multi-clause tail yields one modifier span per clause on the
+    // segmented path (the legacy grammar produced a single span here).
+    #[case(
+        "1 cup flour, sifted, divided",
+        &[
+            (Field::Amount, "1 cup"),
+            (Field::Name, "flour"),
+            (Field::Modifier, "sifted"),
+            (Field::Modifier, "divided"),
+        ]
+    )]
+    fn decompose_carves_fields(#[case] input: &str, #[case] expected: Expected) {
+        let parser = IngredientParser::new();
+        let decomp = parser.decompose(input);
+
+        let got: Vec<(Field, &str)> = decomp
+            .spans
+            .iter()
+            .map(|s| (s.field, s.text.as_str()))
+            .collect();
+        let want: Vec<(Field, &str)> = expected.to_vec();
+        assert_eq!(got, want, "decompose({input:?})");
+
+        // Every span must index back into `source` and match its `text`, and
+        // spans must not overlap.
+        let mut prev_end = 0;
+        for s in &decomp.spans {
+            assert_eq!(&decomp.source[s.range.clone()], s.text, "span text/range");
+            assert!(s.range.start >= prev_end, "spans overlap in {input:?}");
+            prev_end = s.range.end;
+        }
+    }
+
+    #[test]
+    fn recognizer_handled_line_is_carved_by_its_recognizer() {
+        // "Juice of 1 lemon" is produced by the x_of_construction recognizer:
+        // its phrase is the modifier and the rest carves as a line.
+        let parser = IngredientParser::new();
+        let got: Vec<(Field, String)> = parser
+            .decompose("Juice of 1 lemon")
+            .spans
+            .into_iter()
+            .map(|s| (s.field, s.text))
+            .collect();
+        assert_eq!(
+            got,
+            [
+                (Field::Modifier, "Juice of".to_string()),
+                (Field::Amount, "1".to_string()),
+                (Field::Name, "lemon".to_string()),
+            ]
+        );
+    }
+}
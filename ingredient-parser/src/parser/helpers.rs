@@ -145,9 +145,10 @@ fn number_word<'a>(word: &'static str, value: f64, input: &'a str) -> Res<&'a st
     }
 }
 
-/// Try each spelled-out number word from [`vocab::NUMBER_WORDS`], longest first.
-fn try_spelled_number_words(input: &str) -> Res<&str, f64> {
-    for &(word, value) in crate::parser::vocab::NUMBER_WORDS {
+/// Try each `(word, value)` in order (the lists are longest first), e.g.
+/// [`vocab::NUMBER_WORDS`](crate::parser::vocab::NUMBER_WORDS).
+fn first_word_match<'a>(words: &[(&'static str, f64)], input: &'a str) -> Res<&'a str, f64> {
+    for &(word, value) in words {
         if let Ok(result) = number_word(word, value, input) {
             return Ok(result);
         }
@@ -158,14 +159,25 @@ fn try_spelled_number_words(input: &str) -> Res<&str, f64> {
     )))
 }
 
-/// Parse spelled-out text numbers: integer words ("one".."twelve", "dozen") and
-/// the articles "a"/"an" (which mean a quantity of one). Numeric words require a
-/// word boundary so they never match inside a larger word.
+/// Parse a vague count ("a few") as the number it roughly means. The caller
+/// marks the amount approximate.
+pub(crate) fn vague_count(input: &str) -> Res<&str, f64> {
+    context("vague_count", |i| {
+        first_word_match(crate::parser::vocab::VAGUE_COUNTS, i)
+    })
+    .parse(input)
+}
+
+/// Parse spelled-out text numbers: counting phrases ("a couple of", "a
+/// half-dozen"), integer words ("one".."twelve", "dozen") and the articles
+/// "a"/"an" (which mean a quantity of one). Numeric words require a word
+/// boundary so they never match inside a larger word.
 pub(crate) fn text_number(input: &str) -> Res<&str, f64> {
     context(
         "text_number",
         alt((
-            try_spelled_number_words,
+            |i| first_word_match(crate::parser::vocab::COUNTING_PHRASES, i),
+            |i| first_word_match(crate::parser::vocab::NUMBER_WORDS, i),
             |i| tag_no_case("an ").parse(i).map(|(r, _)| (r, 1.0)),
             |i| tag_no_case("a ").parse(i).map(|(r, _)| (r, 1.0)),
         )),
@@ -236,6 +248,11 @@ mod tests {
     #[case::half_a("half a cup", " a cup", 0.5)]
     #[case::two_with_remainder("two eggs", " eggs", 2.0)]
     #[case::ten_with_remainder("ten cloves", " cloves", 10.0)]
+    #[case::couple_of("a couple of eggs", " eggs", 2.0)]
+    #[case::couple("A couple tablespoons", " tablespoons", 2.0)]
+    #[case::a_dozen("a dozen eggs", " eggs", 12.0)]
+    #[case::half_dozen_hyphen("a half-dozen eggs", " eggs", 6.0)]
+    #[case::half_a_dozen("half a dozen eggs", " eggs", 6.0)]
     fn test_text_number_success(
         #[case] input: &str,
         #[case] remaining: &str,
@@ -251,6 +268,7 @@ mod tests {
     #[case::embedded_ten("tenderloin")]
     #[case::embedded_one("oner")]
     #[case::hyphenated("five-spice")]
+    #[case::couple_in_word("couplet")]
     fn test_text_number_fail(#[case] input: &str) {
         assert!(text_number(input).is_err());
     }
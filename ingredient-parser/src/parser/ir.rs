@@ -115,6 +115,8 @@ impl From<ParsedIngredient> for Ingredient {
             optional: parsed.optional,
             // Set authoritatively at the parse funnel (`parse_pipeline_after_normalize`).
             usage: Default::default(),
+            // Likewise, from the finished amounts.
+            package: None,
             parse_notes: Default::default(),
         }
     }
@@ -13,13 +13,11 @@ use nom_language::error::VerboseError;
 
 use crate::parser::Res;
 use crate::traced_parser;
-use crate::unit::Measure;
+use crate::unit::{Measure, MeasureKind};
 
 use super::guards::find_matching_paren;
 use super::{DEFAULT_UNIT, MeasurementParser};
 
-use crate::parser::vocab::CONTAINER_NOUNS;
-
 impl<'a> MeasurementParser<'a> {
     /// Parse measurements enclosed in matching delimiters
     fn parse_delimited_amounts<'b>(
@@ -155,28 +153,27 @@ impl<'a> MeasurementParser<'a> {
         // the input so the parser can return the unconsumed remainder.
         let first_end = after.find(char::is_whitespace).unwrap_or(after.len());
         let first_word = after[..first_end].to_lowercase();
-        let (container, after_rest): (String, &str) =
-            if CONTAINER_NOUNS.contains(&first_word.as_str()) {
-                // "piece ginger" → container "piece", remainder "ginger" (drop a
-                // connecting " of ", mirroring how units consume a trailing "of").
-                let r = after[first_end..].trim_start();
-                let remainder = r.strip_prefix("of ").unwrap_or(r);
-                (first_word, remainder)
+        let (container, after_rest): (String, &str) = if self.containers.contains(&first_word) {
+            // "piece ginger" → container "piece", remainder "ginger" (drop a
+            // connecting " of ", mirroring how units consume a trailing "of").
+            let r = after[first_end..].trim_start();
+            let remainder = r.strip_prefix("of ").unwrap_or(r);
+            (first_word, remainder)
+        } else {
+            // "halibut fillets" → container = trailing "fillets", name "halibut".
+            let last_word = after.rsplit(char::is_whitespace).next().unwrap_or("");
+            let last_lower = last_word.to_lowercase();
+            if self.containers.contains(&last_lower) {
+                let name = after[..after.len() - last_word.len()].trim_end();
+                (last_lower, name)
             } else {
-                // "halibut fillets" → container = trailing "fillets", name "halibut".
-                let last_word = after.rsplit(char::is_whitespace).next().unwrap_or("");
-                let last_lower = last_word.to_lowercase();
-                if CONTAINER_NOUNS.contains(&last_lower.as_str()) {
-                    let name = after[..after.len() - last_word.len()].trim_end();
-                    (last_lower, name)
-                } else {
-                    // No container noun: the count is of whole items that each
-                    // carry the parenthetical size, e.g. "1 (3½ to 4 pound)
-                    // chicken" → [1 whole, 3.5–4 lb] / "chicken" and "2 (8-ounce)
-                    // swordfish steaks, …" → [2 whole, 8 oz] / "swordfish steaks".
-                    (DEFAULT_UNIT.to_string(), after)
-                }
-            };
+                // No container noun: the count is of whole items that each
+                // carry the parenthetical size, e.g. "1 (3½ to 4 pound)
+                // chicken" → [1 whole, 3.5–4 lb] / "chicken" and "2 (8-ounce)
+                // swordfish steaks, …" → [2 whole, 8 oz] / "swordfish steaks".
+                (DEFAULT_UNIT.to_string(), after)
+            }
+        };
 
         // The size must fully parse as a measurement (hyphen → space).
         let inner_norm = inner.replace('-', " ");
@@ -185,6 +182,18 @@ impl<'a> MeasurementParser<'a> {
             _ => return Err(reject()),
         };
 
+        // A length ahead of a unit word describes the cut, not the amount:
+        // "4 (13-millimeter/½-inch) slices pork" is 4 slices, read by the
+        // single-measurement path that skips the parenthetical.
+        if container == DEFAULT_UNIT
+            && inner_measures
+                .iter()
+                .all(|m| m.kind() == MeasureKind::Length)
+            && crate::unit::is_valid(self.units, &after[..first_end])
+        {
+            return Err(reject());
+        }
+
         let mut measures = Vec::with_capacity(1 + inner_measures.len());
         measures.push(Measure::from_parts(container.as_str(), value.0, value.1));
         measures.extend(inner_measures);
@@ -33,7 +33,7 @@ use nom::{Parser, branch::alt, bytes::complete::tag, error::context, multi::sepa
 
 use crate::parser::Res;
 use crate::traced_parser;
-use crate::unit::Measure;
+use crate::unit::{Measure, Region};
 
 use self::guards::optional_period_or_of;
 
@@ -92,16 +92,38 @@ pub(crate) enum MeasurementMode {
 /// Parser for extracting measurements from ingredient strings
 ///
 /// This struct holds configuration for parsing measurements, including
-/// the set of recognized units and which [`MeasurementMode`] is active.
+/// the set of recognized units, the container nouns a parenthesized size may
+/// count ("1 (14-ounce) can"), which [`MeasurementMode`] is active, and whose
+/// cups and pints the bare unit words mean.
 pub(crate) struct MeasurementParser<'a> {
     pub units: &'a HashSet<String>,
+    pub containers: &'a [String],
     pub mode: MeasurementMode,
+    pub region: Region,
 }
 
 impl<'a> MeasurementParser<'a> {
-    /// Create a new measurement parser with the given configuration
+    /// Create a new measurement parser with the given configuration and the
+    /// built-in container nouns.
     pub fn new(units: &'a HashSet<String>, mode: MeasurementMode) -> Self {
-        Self { units, mode }
+        Self {
+            units,
+            containers: &crate::profile::BUILTIN.container_nouns,
+            mode,
+            region: Region::US,
+        }
+    }
+
+    /// Resolve bare unit words for `region` instead of the US (chainable).
+    pub fn with_region(mut self, region: Region) -> Self {
+        self.region = region;
+        self
+    }
+
+    /// Use `containers` instead of the built-in container nouns (chainable).
+    pub fn with_containers(mut self, containers: &'a [String]) -> Self {
+        self.containers = containers;
+        self
     }
 
     /// Parse a list of measurements with different separators
@@ -120,8 +142,6 @@ impl<'a> MeasurementParser<'a> {
             tag(" /"),  // slash, space before only ("175 grams /1¾ cups")
             tag("/ "),  // slash, space after only
             tag(" | "), // pipe with spaces (Bouchon format: metric | volume)
-            tag(" × "), // multiplication sign with spaces (UK format: "1 × 400g tin")
-            tag("× "),  // multiplication sign when leading space was consumed
             tag("/"),   // bare slash
             tag(", "),  // comma with space
             tag(" "),   // just a space
@@ -228,7 +248,8 @@ mod tests {
     #[case::slash("1 cup / 240 ml", 2)]
     #[case::comma("1 cup, 2 tbsp", 2)]
     #[case::pipe("150 grams | 1 cup", 2)]
-    #[case::multiplication_sign("1 × 400 grams", 2)]
+    // "×" is a multiplier, not a separator: "1 × 400 grams" is one measure.
+    #[case::multiplication_sign("1 × 400 grams", 1)]
     fn test_measurement_list_separators(
         units_fx: HashSet<String>,
         #[case] input: &str,
@@ -1,9 +1,14 @@
 //! Number parsing for measurements
 
 use nom::{
-    Parser, branch::alt, bytes::complete::tag, character::complete::space1, combinator::opt,
-    error::context,
+    Parser,
+    branch::alt,
+    bytes::complete::{tag, tag_no_case},
+    character::complete::{space0, space1},
+    combinator::opt,
+    error::{ParseError, context},
 };
+use nom_language::error::VerboseError;
 
 use crate::fraction::{finite_double, fraction_number};
 use crate::parser::{Res, text_number, thousands_number};
@@ -37,9 +42,23 @@ fn double_no_trailing_period(input: &str) -> Res<&str, f64> {
     Ok((remaining, value))
 }
 
+/// A "dozen" counting the number before it: the " dozen" in "2 dozen eggs" or
+/// "1½ dozen". Needs a word boundary after it. (A hyphen can't introduce it:
+/// "2-dozen" already reads as the range 2–12, "dozen" being a number word.)
+fn dozen_suffix(input: &str) -> Res<&str, ()> {
+    let (rest, _) = (space1, tag_no_case("dozen")).parse(input)?;
+    match rest.chars().next() {
+        Some(c) if c.is_alphanumeric() => Err(nom::Err::Error(VerboseError::from_error_kind(
+            input,
+            nom::error::ErrorKind::Tag,
+        ))),
+        _ => Ok((rest, ())),
+    }
+}
+
 impl<'a> MeasurementParser<'a> {
     /// Parse numeric values including fractions, decimals, and text numbers like "one"
-    pub(super) fn parse_number<'b>(&self, input: &'b str) -> Res<&'b str, f64> {
+    pub(crate) fn parse_number<'b>(&self, input: &'b str) -> Res<&'b str, f64> {
         // Choose parsers based on whether we're in rich text mode
         traced_parser!(
             "parse_number",
@@ -74,23 +93,24 @@ impl<'a> MeasurementParser<'a> {
         )
     }
 
-    /// Parse a multiplier expression like "2 x" (meaning multiply the following value by 2)
-    pub(super) fn parse_multiplier<'b>(&self, input: &'b str) -> Res<&'b str, f64> {
-        // Define the format of a multiplier: number + space + "x" + space
-        // Note: We intentionally DON'T include × (multiplication sign) here because
-        // in UK cookbook format "1 × 400g tin" the × is a separator, not a multiplier.
+    /// Parse a multiplier expression like "2 x" or "2×" (meaning multiply the
+    /// following value by 2): "2 x 400g", "2 × 400 g", "2×400 g". A letter "x"
+    /// needs spaces on both sides so it can't split a word; the multiplication
+    /// sign needs none.
+    pub(crate) fn parse_multiplier<'b>(&self, input: &'b str) -> Res<&'b str, f64> {
         let multiplier_format = (
             |a| self.parse_number(a), // The multiplier value
-            space1,                   // Required whitespace
-            tag("x"),                 // The "x" character
-            space1,                   // Required whitespace
+            alt((
+                (space1, tag("x"), space1).map(|_| ()),
+                (space0, tag("×"), space0).map(|_| ()),
+            )),
         );
 
         traced_parser!(
             "parse_multiplier",
             input,
             context("multiplier", multiplier_format).parse(input).map(
-                |(next_input, (multiplier_value, _, _, _))| {
+                |(next_input, (multiplier_value, _))| {
                     // Return just the numeric value
                     (next_input, multiplier_value)
                 },
@@ -107,12 +127,20 @@ impl<'a> MeasurementParser<'a> {
             input,
             context(
                 "value_with_range",
-                alt((
-                    |a| self.parse_upper_bound_only(a), // "up to X" or "at most X"
-                    |a| self.parse_value_with_optional_range(a), // A value possibly with a range
-                )),
+                (
+                    alt((
+                        |a| self.parse_upper_bound_only(a), // "up to X" or "at most X"
+                        |a| self.parse_value_with_optional_range(a), // A value possibly with a range
+                    )),
+                    opt(dozen_suffix),
+                ),
             )
-            .parse(input),
+            .parse(input)
+            .map(|(rest, ((value, upper), dozen))| match dozen {
+                // "2 dozen" / "2-3 dozen": both bounds count dozens.
+                Some(()) => (rest, (value * 12.0, upper.map(|u| u * 12.0))),
+                None => (rest, (value, upper)),
+            }),
             |(val, upper): &(f64, Option<f64>)| match upper {
                 Some(u) => format!("{val}-{u}"),
                 None => format!("{val}"),
@@ -212,11 +240,41 @@ mod tests {
     }
 
     #[rstest]
-    fn test_multiplier(units_fx: HashSet<String>) {
+    #[case::letter("2 x 400g", "400g", 2.0)]
+    #[case::sign_spaced("2 × 400 g", "400 g", 2.0)]
+    #[case::sign_tight("2×400 g", "400 g", 2.0)]
+    #[case::spelled("three x 50g", "50g", 3.0)]
+    fn test_multiplier(
+        units_fx: HashSet<String>,
+        #[case] input: &str,
+        #[case] rest: &str,
+        #[case] expected: f64,
+    ) {
         let parser = MeasurementParser::new(&units_fx, MeasurementMode::IngredientList);
-        let result = parser.parse_multiplier("2 x ");
-        assert!(result.is_ok());
-        let (_, mult) = result.unwrap();
-        assert_eq!(mult, 2.0);
+        assert_eq!(parser.parse_multiplier(input).unwrap(), (rest, expected));
+    }
+
+    #[rstest]
+    // A letter "x" must stand alone: "2x" is not a multiplier.
+    #[case::no_spaces("2x400g")]
+    #[case::no_number("x 400g")]
+    fn test_multiplier_rejects(units_fx: HashSet<String>, #[case] input: &str) {
+        let parser = MeasurementParser::new(&units_fx, MeasurementMode::IngredientList);
+        assert!(parser.parse_multiplier(input).is_err());
+    }
+
+    #[rstest]
+    #[case::dozen("2 dozen eggs", " eggs", (24.0, None))]
+    #[case::fraction("1½ dozen eggs", " eggs", (18.0, None))]
+    #[case::range("2-3 dozen eggs", " eggs", (24.0, Some(36.0)))]
+    #[case::not_a_word("2 dozens", " dozens", (2.0, None))]
+    fn test_dozen_suffix(
+        units_fx: HashSet<String>,
+        #[case] input: &str,
+        #[case] rest: &str,
+        #[case] expected: (f64, Option<f64>),
+    ) {
+        let parser = MeasurementParser::new(&units_fx, MeasurementMode::IngredientList);
+        assert_eq!(parser.parse_value(input).unwrap(), (rest, expected));
     }
 }
@@ -139,7 +139,7 @@ impl<'a> MeasurementParser<'a> {
     ) -> Res<&'b str, Option<Measure>> {
         // Format for a measurement with a range
         let range_format = (
-            // Optional approximation qualifier ("about", "roughly", …, any case)
+            // Optional approximation qualifier ("about", "roughly", …, any case), kept
             nom::combinator::opt(super::single::leading_qualifier),
             |a| self.parse_value(a),                // The lower value
             space0,                                 // Optional whitespace
@@ -155,7 +155,7 @@ impl<'a> MeasurementParser<'a> {
             context("range_with_units", range_format)
                 .parse(input)
                 .map(|(next_input, res)| {
-                    let (_, lower_value, _, lower_unit, upper_val, upper_unit, _) = res;
+                    let (qualifier, lower_value, _, lower_unit, upper_val, upper_unit, _) = res;
 
                     // Both units, when specified, must canonicalize to the same
                     // unit ("1g-2G", "1g-2grams" are fine; "1g-2tbsp" is not).
@@ -173,17 +173,21 @@ impl<'a> MeasurementParser<'a> {
                     }
 
                     // Create the measurement with range
+                    let measure = Measure::from_parts(
+                        // Use the lower unit, or default to "whole" if not specified
+                        lower_unit
+                            .unwrap_or_else(|| DEFAULT_UNIT.to_string())
+                            .to_lowercase()
+                            .as_ref(),
+                        lower_value.0,
+                        Some(upper_val),
+                    );
                     (
                         next_input,
-                        Some(Measure::from_parts(
-                            // Use the lower unit, or default to "whole" if not specified
-                            lower_unit
-                                .unwrap_or_else(|| DEFAULT_UNIT.to_string())
-                                .to_lowercase()
-                                .as_ref(),
-                            lower_value.0,
-                            Some(upper_val),
-                        )),
+                        Some(match qualifier.flatten() {
+                            Some(q) => measure.with_qualifier(q),
+                            None => measure,
+                        }),
                     )
                 }),
             |opt_m: &Option<Measure>| opt_m
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use nom::error::ParseError;
 use nom::{
     Parser,
@@ -9,9 +11,10 @@ use nom::{
 };
 use nom_language::error::VerboseError;
 
+use crate::parser::helpers::vague_count;
 use crate::parser::{Res, parse_unit_text};
 use crate::traced_parser;
-use crate::unit::{self, Measure};
+use crate::unit::{self, Measure, Qualifier, Region, Unit};
 
 use super::guards::{
     find_matching_paren, is_distance_unit, looks_like_step_number, optional_article,
@@ -22,13 +25,20 @@ use super::{DEFAULT_UNIT, MeasurementMode, MeasurementParser};
 impl<'a> MeasurementParser<'a> {
     /// Parse a single measurement like "2 cups" or "about 3 tablespoons".
     ///
+    /// A qualifier before the number ("about", "scant") or between it and the
+    /// unit ("2 generous tablespoons") is kept as the measure's [`Qualifier`];
+    /// a vague count ("a few sprigs") reads as an approximate number.
+    ///
     /// Also handles format: "4 (13-millimeter/½-inch) slices" where a parenthesized
     /// description appears between the number and unit.
-    pub(super) fn parse_single_measurement<'b>(&self, input: &'b str) -> Res<&'b str, Measure> {
+    pub(crate) fn parse_single_measurement<'b>(&self, input: &'b str) -> Res<&'b str, Measure> {
         let measurement_parser = (
             opt(leading_qualifier),
             opt(|a| self.parse_multiplier(a)),
-            |a| self.parse_value(a),
+            alt((
+                |a| vague_count(a).map(|(r, v)| (r, ((v, None), Some(Qualifier::Approximate)))),
+                |a| self.parse_value(a).map(|(r, v)| (r, (v, None))),
+            )),
             space0,
             optional_dash_separator,
             optional_article,
@@ -44,16 +54,17 @@ impl<'a> MeasurementParser<'a> {
                 .parse(input)
                 .and_then(|(next_input, res)| {
                     let (
-                        _estimate_prefix,
+                        leading,
                         multiplier,
-                        value,
+                        (value, vague),
                         _,
                         _dash,
                         _article,
-                        _mid_qualifier,
+                        between,
                         unit,
                         period_consumed,
                     ) = res;
+                    let qualifier = leading.flatten().or(between.flatten()).or(vague);
 
                     // A multiplier ("3 x") scales the whole quantity, so both bounds
                     // of a ranged value must scale: "3 x 100-120 g" is 300-360 g, not
@@ -70,9 +81,14 @@ impl<'a> MeasurementParser<'a> {
                         period_consumed,
                     )?;
 
+                    let measure =
+                        Measure::from_parts(final_unit.as_ref(), final_value, final_upper);
                     Ok((
                         final_next_input,
-                        Measure::from_parts(final_unit.as_ref(), final_value, final_upper),
+                        match qualifier {
+                            Some(q) => measure.with_qualifier(q),
+                            None => measure,
+                        },
                     ))
                 }),
             |m: &Measure| m.to_string(),
@@ -180,9 +196,9 @@ impl<'a> MeasurementParser<'a> {
         // (The early return above already rejected rich-text mode, so a plain
         // `space0` is correct here.)
         //
-        // `opt(unit_only_qualifier)` lets a bare unit carry a discarded
-        // shape/approx qualifier ("Generous pinch of salt" -> 1 pinch, name
-        // "salt") or a size word before a vague unit ("Small handful thyme" ->
+        // `opt(unit_only_qualifier)` lets a bare unit carry a shape/approx
+        // qualifier ("Generous pinch of salt" -> 1 generous pinch, name
+        // "salt") or a discarded size word before a vague unit ("Small handful thyme" ->
         // 1 handful thyme), matching the numbered path in
         // `parse_single_measurement`. It backtracks to nothing when the next word
         // isn't a qualifier, so "pinch of salt" is unaffected.
@@ -198,10 +214,14 @@ impl<'a> MeasurementParser<'a> {
             "parse_unit_only",
             input,
             context("unit_only", unit_only_format).parse(input).map(
-                |(next_input, (_, _, unit, _, _))| {
+                |(next_input, (qualifier, _, unit, _, _))| {
+                    let measure = Measure::from_parts(unit.to_lowercase().as_ref(), 1.0, None);
                     (
                         next_input,
-                        Measure::from_parts(unit.to_lowercase().as_ref(), 1.0, None),
+                        match qualifier.flatten() {
+                            Some(q) => measure.with_qualifier(q),
+                            None => measure,
+                        },
                     )
                 }
             ),
@@ -235,8 +255,11 @@ impl<'a> MeasurementParser<'a> {
         // `parse_unit_text` (a single run of letters) stops at the space in
         // "fl oz", so "18 fl oz water" would lose its unit and fall back to a bare
         // count. Match its spellings explicitly and normalize to canonical "fl oz".
+        if let Some(found) = regional_unit_text(input) {
+            return Ok(found);
+        }
         if let Ok((rest, _)) = fluid_ounce_text(input) {
-            return Ok((rest, "fl oz".to_string()));
+            return Ok((rest, self.regional("fl oz")));
         }
         // Single-letter spoon abbreviations are case-sensitive: lowercase "t" =
         // teaspoon, uppercase "T" = tablespoon (standard cooking shorthand). They
@@ -245,7 +268,7 @@ impl<'a> MeasurementParser<'a> {
         // `Measure::from_parts` lowercase the unit downstream, which would collapse
         // t/T. Mirrors the `fl oz` special-case above.
         if let Some((rest, canon)) = single_letter_spoon(input) {
-            return Ok((rest, canon.to_string()));
+            return Ok((rest, self.regional(canon)));
         }
         self.parse_unit_with(
             input,
@@ -253,6 +276,24 @@ impl<'a> MeasurementParser<'a> {
             "unit",
             "not a valid unit",
         )
+        .map(|(rest, s)| (rest, self.regional(&s)))
+    }
+
+    /// The canonical spelling of the unit `s` means in this parser's region —
+    /// "metric cup" for "cups" in a British recipe — or `s` unchanged.
+    fn regional(&self, s: &str) -> String {
+        if self.region == Region::US {
+            return s.to_string();
+        }
+        let Ok(unit) = Unit::from_str(s) else {
+            return s.to_string();
+        };
+        let resolved = self.region.resolve(unit.clone());
+        if resolved == unit {
+            s.to_string()
+        } else {
+            resolved.to_str().into_owned()
+        }
     }
 
     /// Parse an addon unit (only units in the custom set, not built-in units).
@@ -269,6 +310,30 @@ impl<'a> MeasurementParser<'a> {
     }
 }
 
+/// A unit spelled with its region — "US cups", "imperial pint", "metric cup",
+/// "UK fl oz" — as its canonical name. These bypass region resolution: a
+/// British recipe's "1 US cup" is still a US cup.
+fn regional_unit_text(input: &str) -> Option<(&str, String)> {
+    let word_end = |s: &str| s.find(|c: char| !c.is_alphabetic()).unwrap_or(s.len());
+    let (prefix, rest) = input.split_at(word_end(input));
+    let prefix = prefix.to_lowercase();
+    if !matches!(
+        prefix.as_str(),
+        "us" | "uk" | "imperial" | "metric" | "au" | "australian"
+    ) {
+        return None;
+    }
+    let rest = rest.strip_prefix(' ')?;
+    let (after, candidate) = match fluid_ounce_text(rest) {
+        Ok((after, _)) => (after, format!("{prefix} fl oz")),
+        Err(_) => {
+            let (word, after) = rest.split_at(word_end(rest));
+            (after, format!("{prefix} {}", unit::singular(word)))
+        }
+    };
+    Unit::is_known(&candidate).then_some((after, candidate))
+}
+
 /// Recognize the spellings of the fluid-ounce unit ("fl oz", "fl. oz.", "fluid
 /// ounce(s)", "fluid oz"). Longest forms first so a prefix isn't matched short.
 /// Returns the consumed span; the caller normalizes it to canonical "fl oz".
@@ -344,14 +409,14 @@ fn parse_hyphen_unit_where(input: &str, is_unit: impl Fn(&str) -> bool) -> Optio
 
 /// Consume an amount-shape qualifier ("generous", "scant", "heaping", …) that
 /// sits *between* the number and the unit, as in "2 generous tablespoons". The
-/// qualifier describes how full the measure is; like the leading form it is
-/// discarded (the numeric amount is what's structured). Restricted to shape
-/// qualifiers — "about"/"approximately" never appear in this position.
+/// qualifier describes how full the measure is and is returned like the leading
+/// form's. Restricted to shape qualifiers — "about"/"approximately" never
+/// appear in this position.
 ///
 /// Wrapped in `opt(...)` by the caller, so a non-qualifier word (the real unit)
 /// backtracks and is left for the unit parser.
-fn amount_qualifier_between(input: &str) -> Res<&str, ()> {
-    let (input, _) = alt((
+fn amount_qualifier_between(input: &str) -> Res<&str, Option<Qualifier>> {
+    let (input, word) = alt((
         tag_no_case("generous"),
         tag_no_case("scant"),
         tag_no_case("heaping"),
@@ -360,23 +425,23 @@ fn amount_qualifier_between(input: &str) -> Res<&str, ()> {
         tag_no_case("brimming"),
         // A size word ("small handful", "large pinch", "large bunch", "small
         // head") before a vague/container measure describes the measure, not the
-        // food, so it's discarded like the shape qualifiers above. Gated to those
-        // unit sets so "2 large eggs" keeps "large".
+        // food, so it's discarded: it names no qualifier. Gated to those unit
+        // sets so "2 large eggs" keeps "large".
         size_word_before_discardable_unit,
     ))
     .parse(input)?;
     let (input, _) = space1(input)?;
-    Ok((input, ()))
+    Ok((input, Qualifier::from_word(word)))
 }
 
 /// Consume a qualifier before a bare unit-only amount. This mirrors the numbered
 /// path's leading/size qualifiers, but is deliberately limited to qualifiers
 /// that still leave a recognized addon unit immediately after them.
-fn unit_only_qualifier(input: &str) -> Res<&str, ()> {
+fn unit_only_qualifier(input: &str) -> Res<&str, Option<Qualifier>> {
     alt((leading_qualifier, |input| {
         let (input, _) = size_word_before_discardable_unit(input)?;
         let (input, _) = space1(input)?;
-        Ok((input, ()))
+        Ok((input, None))
     }))
     .parse(input)
 }
@@ -408,17 +473,19 @@ fn size_word_before_discardable_unit(input: &str) -> Res<&str, &str> {
 
 /// Consume a leading approximation/size qualifier ("about", "roughly",
 /// "generous", "scant", …), optionally preceded by an article ("a"/"an"), so
-/// the amount after it still parses. Case-insensitive; the qualifier text is
-/// discarded — except by `rich_text`, which re-emits the consumed span as
-/// prose (the reason this is pub(crate)).
+/// the amount after it still parses. Case-insensitive; returns the
+/// [`Qualifier`] the word expresses ("less than" expresses none and is
+/// dropped). `rich_text` re-emits the consumed span as prose (the reason this
+/// is pub(crate)).
 ///
 /// Wrapped in `opt(...)` by the caller, so a partial match (e.g. consuming "a "
 /// then failing) backtracks and consumes nothing.
-pub(crate) fn leading_qualifier(input: &str) -> Res<&str, ()> {
+pub(crate) fn leading_qualifier(input: &str) -> Res<&str, Option<Qualifier>> {
     let (input, _) = opt(alt((tag_no_case("a "), tag_no_case("an ")))).parse(input)?;
-    let (input, _) = alt((
+    let (input, word) = alt((
         // Multi-word phrases first so the trailing word isn't mistaken for the unit.
         tag_no_case("less than"),
+        tag_no_case("at least"),
         tag_no_case("about"),
         tag_no_case("approximately"),
         tag_no_case("approx"),
@@ -436,7 +503,7 @@ pub(crate) fn leading_qualifier(input: &str) -> Res<&str, ()> {
     ))
     .parse(input)?;
     let (input, _) = space1(input)?;
-    Ok((input, ()))
+    Ok((input, Qualifier::from_word(word)))
 }
 
 #[cfg(test)]
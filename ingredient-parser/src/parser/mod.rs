@@ -49,15 +49,19 @@
 //! load-bearing (see [`refine`]). Always add a corpus row for the fix
 //! (`tests/corpus/corpus.jsonl`).
 
+pub(crate) mod decompose;
 pub(crate) mod helpers;
 pub(crate) mod ir;
 pub(crate) mod measurement;
 pub(crate) mod normalize;
+pub(crate) mod offsets;
+pub(crate) mod package;
 pub(crate) mod paren;
 pub(crate) mod pipeline;
 pub(crate) mod recognize;
 pub(crate) mod refine;
 pub(crate) mod segment;
+pub(crate) mod spellcheck;
 pub(crate) mod stage;
 pub(crate) mod token;
 pub(crate) mod vocab;
@@ -12,6 +12,8 @@
 
 use std::borrow::Cow;
 
+use crate::pass::{CustomPass, CustomPasses};
+
 /// A circled-number glyph (①②③ …) used as a footnote/technique-note marker in
 /// some cookbooks (e.g. Claire Saffitz's *Dessert Person*). They're not part of
 /// the ingredient, so they're stripped during normalization rather than leaking
@@ -49,9 +51,10 @@ fn strip_footnote_markers(input: &str) -> Cow<'_, str> {
 /// Strip a trailing footnote marker — an ASCII asterisk or dagger left at the
 /// end of a line ("shredded zucchini (see note)*" → "shredded zucchini (see
 /// note)"). Anchored to the end so a mid-name asterisk is untouched; the Unicode
-/// circled-digit markers are handled by `strip_footnote_markers`.
+/// circled-digit markers are handled by `strip_footnote_markers`. A spaced run
+/// of markers ("butter * †") goes in one pass.
 fn strip_trailing_footnote_markers(input: &str) -> Cow<'_, str> {
-    crate::lazy_regex!(TRAILING_MARK, r"\s*[*\u{2020}\u{2021}]+\s*$");
+    crate::lazy_regex!(TRAILING_MARK, r"(?:\s*[*\u{2020}\u{2021}])+\s*$");
     TRAILING_MARK.replace(input, "")
 }
 
@@ -60,10 +63,11 @@ fn strip_trailing_footnote_markers(input: &str) -> Cow<'_, str> {
 /// lists in *The Food of Sichuan*) prefix to each ingredient line. Left in place
 /// it lands at the head of the name ("– shiitake mushrooms"). The trailing
 /// whitespace requirement keeps a hyphenated/negative leading token untouched.
+/// A run of bullets ("- • salt") goes in one pass.
 fn strip_leading_bullet(input: &str) -> Cow<'_, str> {
     crate::lazy_regex!(
         LEADING_BULLET,
-        r"^\s*[-\u{2013}\u{2014}\u{2022}\u{00B7}\u{2219}*]\s+"
+        r"^\s*(?:[-\u{2013}\u{2014}\u{2022}\u{00B7}\u{2219}*]\s+)+"
     );
     LEADING_BULLET.replace(input, "")
 }
@@ -307,8 +311,10 @@ crate::define_stage_pipeline! {
     type Rewrite = Rewrite,
     trace: pub(crate) REWRITE_TRACE_NAMES,
     (StripNbsp, "strip_nbsp", strip_nbsp),
-    (StripLeadingBullet, "strip_leading_bullet", strip_leading_bullet),
+    // Footnote markers go first: one ahead of a bullet ("①* salt") would
+    // otherwise shield the bullet from the start-anchored bullet strip.
     (StripFootnoteMarkers, "strip_footnote_markers", strip_footnote_markers),
+    (StripLeadingBullet, "strip_leading_bullet", strip_leading_bullet),
     (
         StripTrailingFootnoteMarkers,
         "strip_trailing_footnote_markers",
@@ -335,7 +341,15 @@ crate::define_stage_pipeline! {
 /// the line emits a before→after node so `--explain` shows which rewrite fired.
 fn apply_rewrite<'a>(acc: Cow<'a, str>, rewrite: &RewriteEntry) -> Cow<'a, str> {
     let RewriteEntry { run, .. } = *rewrite;
-    match run(acc.as_ref()) {
+    let frame = crate::trace::perf::Frame::Pass {
+        stage: "normalize",
+        name: rewrite.id().as_str(),
+    };
+    match crate::trace::perf::timed(
+        frame,
+        || run(acc.as_ref()),
+        |out| matches!(out, Cow::Owned(_)),
+    ) {
         Cow::Owned(rewritten) => {
             crate::trace::trace_on_change(rewrite.id().as_str(), acc.as_ref(), &rewritten, true);
             Cow::Owned(rewritten)
@@ -344,12 +358,45 @@ fn apply_rewrite<'a>(acc: Cow<'a, str>, rewrite: &RewriteEntry) -> Cow<'a, str>
     }
 }
 
-/// Run all pre-parse rewrites on a raw ingredient line, then collapse any
+/// Apply a [registered](crate::pass) rewrite, like [`apply_rewrite`]. Its
+/// result only counts as a change when the text differs: a custom rewrite
+/// might hand back an owned copy of an unchanged line.
+fn apply_custom_rewrite<'a>(acc: Cow<'a, str>, pass: &dyn CustomPass) -> Cow<'a, str> {
+    let frame = crate::trace::perf::Frame::Pass {
+        stage: "normalize",
+        name: pass.name(),
+    };
+    let rewritten = crate::trace::perf::timed(
+        frame,
+        || match pass.rewrite(acc.as_ref()) {
+            Cow::Owned(rewritten) if rewritten != acc.as_ref() => Some(rewritten),
+            _ => None,
+        },
+        Option::is_some,
+    );
+    match rewritten {
+        Some(rewritten) => {
+            crate::trace::trace_on_change(pass.name(), acc.as_ref(), &rewritten, true);
+            Cow::Owned(rewritten)
+        }
+        None => acc,
+    }
+}
+
+/// Run all pre-parse rewrites on a raw ingredient line, the parser's
+/// registered ones each next to its built-in, then collapse any
 /// trailing/doubled whitespace a rewrite may have left behind.
-pub(super) fn normalize_input(input: &str) -> Cow<'_, str> {
+pub(super) fn normalize_input<'a>(input: &'a str, passes: &CustomPasses) -> Cow<'a, str> {
     let mut normalized = Cow::Borrowed(input);
     for rewrite in REWRITES {
+        let label = rewrite.id().as_str();
+        for pass in passes.before(label) {
+            normalized = apply_custom_rewrite(normalized, pass);
+        }
         normalized = apply_rewrite(normalized, rewrite);
+        for pass in passes.after(label) {
+            normalized = apply_custom_rewrite(normalized, pass);
+        }
     }
 
     let has_multiple_spaces = normalized
@@ -368,6 +415,9 @@ pub(super) fn normalize_input(input: &str) -> Cow<'_, str> {
     }
 }
 
+crate::lazy_regex!(OPTIONAL_PAREN, r"(?i)\s*\(optional\)");
+crate::lazy_regex!(OPTIONAL_WORD, r"(?i),?\s+optional\s*$");
+
 /// Strip an "(optional)" note from a line, returning the cleaned line plus
 /// whether the note was present. Handles a parenthesized note anywhere (trailing
 /// "X (optional)" or mid-line "X (optional), chopped") and a trailing word form
@@ -377,9 +427,6 @@ pub(super) fn normalize_input(input: &str) -> Cow<'_, str> {
 /// whole-line "(optional)" (nothing else) is left for the optional-ingredient
 /// path and not treated as a note.
 pub(super) fn strip_optional_note(input: &str) -> (Cow<'_, str>, bool) {
-    crate::lazy_regex!(PAREN, r"(?i)\s*\(optional\)");
-    crate::lazy_regex!(TRAIL_WORD, r"(?i),?\s+optional\s*$");
-
     let trimmed = input.trim();
     // Whole-line "(optional)" → leave for try_parse_optional_ingredient.
     if trimmed.eq_ignore_ascii_case("(optional)") {
@@ -388,22 +435,41 @@ pub(super) fn strip_optional_note(input: &str) -> (Cow<'_, str>, bool) {
 
     // Check both patterns before allocating: the common case is no match, and
     // it must stay allocation-free (this runs on every parsed line).
-    let paren_hit = PAREN.is_match(input);
-    if !paren_hit && !TRAIL_WORD.is_match(input) {
+    let paren_hit = OPTIONAL_PAREN.is_match(input);
+    if !paren_hit && !OPTIONAL_WORD.is_match(input) {
         return (Cow::Borrowed(input), false);
     }
 
     let mut text = if paren_hit {
-        PAREN.replace_all(input, "").into_owned()
+        OPTIONAL_PAREN.replace_all(input, "").into_owned()
     } else {
         input.to_string()
     };
-    if TRAIL_WORD.is_match(&text) {
-        text = TRAIL_WORD.replace(&text, "").into_owned();
+    if OPTIONAL_WORD.is_match(&text) {
+        text = OPTIONAL_WORD.replace(&text, "").into_owned();
     }
     (Cow::Owned(text), true)
 }
 
+/// Where [`strip_optional_note`] finds its notes in `input`: each "(optional)"
+/// and a trailing "optional" word, without the space or comma before them.
+pub(super) fn optional_note_ranges(input: &str) -> Vec<std::ops::Range<usize>> {
+    if !strip_optional_note(input).1 {
+        return Vec::new();
+    }
+    OPTIONAL_PAREN
+        .find_iter(input)
+        .chain(OPTIONAL_WORD.find(input))
+        .map(|m| {
+            let note = m
+                .as_str()
+                .trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+            let start = m.end() - note.len();
+            start..start + note.trim_end().len()
+        })
+        .collect()
+}
+
 /// Detect a *descriptive* parenthetical wedged between name words — a
 /// temperature ("70° to 80°F") or distance ("¼ inch / 6 mm") aside flanked by
 /// alphabetic name text on both sides. Returns the line with that parenthetical
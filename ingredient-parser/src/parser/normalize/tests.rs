@@ -64,6 +64,7 @@ fn test_strip_footnote_markers(#[case] input: &str, #[case] expected: &str) {
 #[case::asterisk("shredded zucchini (see note)*", "shredded zucchini (see note)")]
 #[case::dagger("kosher salt \u{2020}", "kosher salt")]
 #[case::double_dagger("flour\u{2021}", "flour")]
+#[case::spaced_run("butter * \u{2020}", "butter")]
 // A mid-name asterisk is NOT trailing → left alone.
 #[case::mid("2% milk", "2% milk")]
 // No marker → unchanged.
@@ -185,6 +186,7 @@ fn test_lift_leading_piece_dimension(#[case] input: &str, #[case] expected: &str
 #[case::em_dash("— bean sprouts", "bean sprouts")]
 #[case::bullet("• daikon, sliced", "daikon, sliced")]
 #[case::ascii_hyphen("- firm tofu", "firm tofu")]
+#[case::run_of_bullets("- • firm tofu", "firm tofu")]
 // A hyphenated leading token (no trailing space after the dash) is untouched.
 #[case::hyphenated_name("all-purpose flour", "all-purpose flour")]
 fn test_strip_leading_bullet(#[case] input: &str, #[case] expected: &str) {
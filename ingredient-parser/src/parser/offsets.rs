@@ -0,0 +1,311 @@
+//! Byte offsets in a rewritten line, mapped back to the line it came from.
+//!
+//! The normalize rewrites, the optional-note strip and the inline-aside lift
+//! each hand the next stage a different string, so a span the grammar carves
+//! indexes text the author never wrote. An [`OffsetMap`] takes such a span
+//! back. It is built from the two strings alone, by aligning their characters,
+//! so it works for any rewrite (a registered one included) without the
+//! rewrite reporting its edits.
+//!
+//! The alignment keeps the longest common character subsequence. Text only the
+//! rewrite has maps onto what it replaced ("recipe" onto "batch of"), a
+//! deletion is skipped over, and text the rewrite moved ("1½-inch-thick" lifted
+//! to the end of the line) maps back to where it was.
+
+use std::ops::Range;
+
+/// Cells the alignment table may use before a changed middle is mapped as one
+/// replaced block. Real lines need a few thousand.
+const ALIGN_LIMIT: usize = 1 << 18;
+
+/// Shortest unchanged run kept between two edits. Shorter ones are chance
+/// matches inside a replacement (the "c" shared by "batch of" and "recipe").
+const MIN_KEPT_RUN: usize = 3;
+
+/// Maps byte offsets in a rewritten string back to its source.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct OffsetMap {
+    /// `starts[b]`: the source offset of a span that starts at byte `b`.
+    starts: Vec<usize>,
+    /// `ends[b]`: the source offset of a span that ends at byte `b`.
+    ends: Vec<usize>,
+}
+
+/// One stretch of the rewritten string and the source text behind it.
+#[derive(Clone, Debug, PartialEq)]
+enum Piece {
+    /// Copied unchanged from `src`.
+    Same { src: usize, dst: Range<usize> },
+    /// The rewrite replaced `src` with `dst`; either may be empty.
+    Changed {
+        src: Range<usize>,
+        dst: Range<usize>,
+    },
+}
+
+impl OffsetMap {
+    /// The map of a string onto itself.
+    pub(crate) fn identity(len: usize) -> Self {
+        Self {
+            starts: (0..=len).collect(),
+            ends: (0..=len).collect(),
+        }
+    }
+
+    /// The map from `dst` (a rewrite of `src`) back to `src`.
+    pub(crate) fn between(src: &str, dst: &str) -> Self {
+        if src == dst {
+            return Self::identity(src.len());
+        }
+        let mut starts = vec![src.len(); dst.len() + 1];
+        let mut ends = vec![0; dst.len() + 1];
+        for piece in align(src, dst) {
+            match piece {
+                Piece::Same { src, dst } => {
+                    for (k, b) in dst.clone().enumerate() {
+                        starts[b] = src + k;
+                        ends[b + 1] = src + k + 1;
+                    }
+                }
+                Piece::Changed { src, dst } => {
+                    for b in dst {
+                        starts[b] = src.start;
+                        ends[b + 1] = src.end;
+                    }
+                }
+            }
+        }
+        Self { starts, ends }
+    }
+
+    /// Compose with `next`, the map of a later rewrite: the result maps that
+    /// rewrite's offsets back to this map's source.
+    pub(crate) fn then(&self, next: &OffsetMap) -> OffsetMap {
+        let back = |table: &[usize], b: usize| table[b.min(table.len() - 1)];
+        OffsetMap {
+            starts: next.starts.iter().map(|&b| back(&self.starts, b)).collect(),
+            ends: next.ends.iter().map(|&b| back(&self.ends, b)).collect(),
+        }
+    }
+
+    /// The source range behind `range`. Empty when the text there was only
+    /// inserted by the rewrite.
+    pub(crate) fn range(&self, range: Range<usize>) -> Range<usize> {
+        let last = self.starts.len() - 1;
+        let start = self.starts[range.start.min(last)];
+        let end = self.ends[range.end.min(last)];
+        start..end.max(start)
+    }
+}
+
+/// Align `dst` against `src`, as pieces covering `dst` in order.
+fn align(src: &str, dst: &str) -> Vec<Piece> {
+    let a: Vec<(usize, char)> = src.char_indices().collect();
+    let b: Vec<(usize, char)> = dst.char_indices().collect();
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x.1 == y.1).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x.1 == y.1)
+        .count();
+    let at = |chars: &[(usize, char)], text: &str, i: usize| {
+        chars.get(i).map_or(text.len(), |&(offset, _)| offset)
+    };
+
+    // Matched (src, dst) char index pairs, in order.
+    let mut matched: Vec<(usize, usize)> = (0..prefix).map(|i| (i, i)).collect();
+    let (a_mid, b_mid) = (prefix..a.len() - suffix, prefix..b.len() - suffix);
+    if a_mid.len().saturating_mul(b_mid.len()) <= ALIGN_LIMIT {
+        let pairs = lcs(&a[a_mid.clone()], &b[b_mid.clone()]);
+        matched.extend(pairs.into_iter().map(|(i, j)| (i + prefix, j + prefix)));
+    }
+    matched.extend((0..suffix).map(|k| (a_mid.end + k, b_mid.end + k)));
+
+    // Group the matches into unchanged runs, and the gaps between them into
+    // edits, in char indices.
+    let mut runs: Vec<(Range<usize>, Range<usize>)> = Vec::new();
+    for (i, j) in matched {
+        match runs.last_mut() {
+            Some((ra, rb)) if ra.end == i && rb.end == j => {
+                ra.end += 1;
+                rb.end += 1;
+            }
+            _ => runs.push((i..i + 1, j..j + 1)),
+        }
+    }
+    // A short run between two edits is a chance match: fold it into them.
+    let mut i = 0;
+    runs.retain(|(ra, rb)| {
+        i += 1;
+        let edit_before = i > 1 || ra.start > 0 || rb.start > 0;
+        let edit_after = ra.end < a.len() || rb.end < b.len();
+        ra.len() >= MIN_KEPT_RUN || !edit_before || !edit_after
+    });
+
+    let mut pieces = Vec::new();
+    let (mut ia, mut ib) = (0, 0);
+    for (ra, rb) in runs
+        .into_iter()
+        .chain([(a.len()..a.len(), b.len()..b.len())])
+    {
+        if ra.start > ia || rb.start > ib {
+            pieces.push(Piece::Changed {
+                src: at(&a, src, ia)..at(&a, src, ra.start),
+                dst: at(&b, dst, ib)..at(&b, dst, rb.start),
+            });
+        }
+        if !rb.is_empty() {
+            pieces.push(Piece::Same {
+                src: at(&a, src, ra.start),
+                dst: at(&b, dst, rb.start)..at(&b, dst, rb.end),
+            });
+        }
+        (ia, ib) = (ra.end, rb.end);
+    }
+    find_moves(src, dst, pieces)
+}
+
+/// The longest common subsequence of two char runs, as matched index pairs.
+fn lcs(a: &[(usize, char)], b: &[(usize, char)]) -> Vec<(usize, usize)> {
+    let width = b.len() + 1;
+    // `table[i * width + j]`: the LCS length of `a[i..]` and `b[j..]`.
+    let mut table = vec![0u32; (a.len() + 1) * width];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            table[i * width + j] = if a[i].1 == b[j].1 {
+                table[(i + 1) * width + j + 1] + 1
+            } else {
+                table[(i + 1) * width + j].max(table[i * width + j + 1])
+            };
+        }
+    }
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i].1 == b[j].1 {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// Split a moved phrase out of an insertion: when text only `dst` has (less
+/// the ", " a lift joins it with) was deleted verbatim elsewhere in `src`, it
+/// maps back to where it was deleted.
+fn find_moves(src: &str, dst: &str, pieces: Vec<Piece>) -> Vec<Piece> {
+    let deleted: Vec<Range<usize>> = pieces
+        .iter()
+        .filter_map(|piece| match piece {
+            Piece::Changed { src, .. } if !src.is_empty() => Some(src.clone()),
+            _ => None,
+        })
+        .collect();
+    let mut out = Vec::with_capacity(pieces.len());
+    for piece in pieces {
+        let Piece::Changed { src: from, dst: to } = &piece else {
+            out.push(piece);
+            continue;
+        };
+        let inserted = &dst[to.clone()];
+        let core = inserted
+            .trim_start_matches([',', ' '])
+            .trim_end_matches([',', ' ']);
+        let found = (!core.is_empty())
+            .then(|| {
+                deleted.iter().find_map(|range| {
+                    src[range.clone()]
+                        .find(core)
+                        .map(|offset| range.start + offset)
+                })
+            })
+            .flatten();
+        let Some(moved_from) = found else {
+            out.push(piece);
+            continue;
+        };
+        let core_start = to.start + (core.as_ptr() as usize - inserted.as_ptr() as usize);
+        let core_end = core_start + core.len();
+        if core_start > to.start {
+            out.push(Piece::Changed {
+                src: from.start..from.start,
+                dst: to.start..core_start,
+            });
+        }
+        out.push(Piece::Same {
+            src: moved_from,
+            dst: core_start..core_end,
+        });
+        if core_end < to.end {
+            out.push(Piece::Changed {
+                src: from.clone(),
+                dst: core_end..to.end,
+            });
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    /// The source text behind `part` (its first occurrence) in `dst`.
+    fn back<'a>(src: &'a str, dst: &str, part: &str) -> &'a str {
+        let start = dst.find(part).unwrap();
+        &src[OffsetMap::between(src, dst).range(start..start + part.len())]
+    }
+
+    #[rstest]
+    #[case::nbsp("2\u{a0}cups flour", "2 cups flour", "cups flour", "cups flour")]
+    #[case::bullet("• 2 cups flour", "2 cups flour", "2 cups", "2 cups")]
+    #[case::footnote("rye flour ①, sifted", "rye flour , sifted", "sifted", "sifted")]
+    #[case::replaced(
+        "1 batch of Marshmallow Meringue",
+        "1 recipe Marshmallow Meringue",
+        "1 recipe",
+        "1 batch of"
+    )]
+    #[case::moved(
+        "1 (1½-inch-thick) bone-in pork chop",
+        "1 bone-in pork chop, 1½-inch-thick",
+        "1½-inch-thick",
+        "1½-inch-thick"
+    )]
+    #[case::kept_after_move(
+        "1 (1½-inch-thick) bone-in pork chop",
+        "1 bone-in pork chop, 1½-inch-thick",
+        "bone-in pork chop",
+        "bone-in pork chop"
+    )]
+    fn maps_back_to_the_source(
+        #[case] src: &str,
+        #[case] dst: &str,
+        #[case] part: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(back(src, dst, part), expected);
+    }
+
+    #[test]
+    fn inserted_text_maps_to_nothing() {
+        let map = OffsetMap::between("1 lemon", "1 lemon, zested");
+        assert!(map.range(7..15).is_empty());
+    }
+
+    #[test]
+    fn composes_rewrites() {
+        let (raw, mid, last) = ("• 2\u{a0}cups flour", "• 2 cups flour", "2 cups flour");
+        let map = OffsetMap::between(raw, mid).then(&OffsetMap::between(mid, last));
+        assert_eq!(&raw[map.range(0..6)], "2\u{a0}cups");
+        assert_eq!(&raw[map.range(7..12)], "flour");
+    }
+}
@@ -0,0 +1,184 @@
+//! Package structure: recognizing that a line counts identically sized
+//! packages, and how many of what size.
+//!
+//! The measurement grammar already reads every package form into flat
+//! amounts, and the corpus pins those: "2 (14.5-ounce) cans" is `[2 can, 14.5
+//! oz]` and "2 x 200g flour" is `[400 g]`. This runs once the parse is done and
+//! recovers the structure the flat list can't state — count × per-package
+//! size, plus the container noun — as [`Ingredient::package`]. The one amount
+//! rewrite is the multiplier-with-container form, which the grammar reads as a
+//! total and a lone container ("2 x 400g cans" → `[800 g, 1 can]`); it is
+//! restated the way the parenthesized form parses (`[2 can, 400 g]`).
+
+use crate::IngredientParser;
+use crate::ingredient::{Ingredient, Package};
+use crate::parser::MeasurementMode;
+use crate::parser::token;
+use crate::trace;
+use crate::unit::{Measure, MeasureKind, Unit, singular};
+
+/// Whether `m` can be a package's size: a weight or a volume. A length ("4
+/// (½-inch) slices") describes the pieces, not how much they hold.
+fn is_size(m: &Measure) -> bool {
+    matches!(m.kind(), MeasureKind::Weight | MeasureKind::Volume)
+}
+
+impl IngredientParser {
+    /// Set `ingredient.package` from the line's package expression, if it has
+    /// one. `input` is the normalized line the amounts were parsed from.
+    ///
+    /// The re-reads here aren't part of the parse proper, so they stay out of
+    /// any trace being collected.
+    pub(crate) fn attach_package(&self, input: &str, ingredient: &mut Ingredient) {
+        ingredient.package = trace::untraced(|| {
+            self.multiplied_package(input, &mut ingredient.amounts)
+                .or_else(|| self.counted_package(input, &ingredient.amounts))
+        });
+    }
+
+    /// The container noun `m` counts, singular, if its unit is one.
+    fn container_of(&self, m: &Measure) -> Option<String> {
+        let unit = singular(&m.unit().to_str()).into_owned();
+        self.profile
+            .container_nouns
+            .iter()
+            .any(|c| singular(c) == unit)
+            .then_some(unit)
+    }
+
+    /// "2 x 400g cans", "2×400 g", "3 x 100-120 g nuts": a leading multiplier
+    /// and the size it multiplies. A single package with no container ("1 x
+    /// 200g flour") has nothing to structure.
+    fn multiplied_package(&self, input: &str, amounts: &mut Vec<Measure>) -> Option<Package> {
+        let mp = self.measurement_parser(MeasurementMode::IngredientList);
+        let (rest, count) = mp.parse_multiplier(input.trim_start()).ok()?;
+        let (_, size) = mp.parse_single_measurement(rest).ok()?;
+        if !is_size(&size) || count <= 0.0 {
+            return None;
+        }
+        let container_at = amounts.iter().position(|m| self.container_of(m).is_some());
+        let container = container_at.and_then(|i| self.container_of(&amounts[i]));
+        if container.is_none() && count == 1.0 {
+            return None;
+        }
+        if let (Some(i), Some(noun)) = (container_at, &container) {
+            let total_at = amounts.iter().position(|m| {
+                m.unit() == size.unit() && (m.value() - size.value() * count).abs() < 1e-9
+            })?;
+            let restated = [Measure::new(noun, count), size.clone()];
+            let others = amounts
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i && *j != total_at)
+                .map(|(_, m)| m.clone());
+            *amounts = restated.into_iter().chain(others).collect();
+        }
+        Some(Package {
+            count,
+            size: vec![size],
+            container,
+        })
+    }
+
+    /// "2 (14.5-ounce) cans", "2 14-ounce cans", "1 can (15 oz)", "2 (8-ounce)
+    /// swordfish steaks": a count of containers (or of whole items) followed by
+    /// the weight or volume of one.
+    ///
+    /// A size written *between* the count and the noun is always per package.
+    /// One written after the noun often isn't — "2 sticks (226 g) butter" gives
+    /// the total — so there it counts only for a single package or with an
+    /// explicit "each", and never for bare items ("3 eggs (150 g)").
+    fn counted_package(&self, input: &str, amounts: &[Measure]) -> Option<Package> {
+        let (first, rest) = amounts.split_first()?;
+        if first.upper_value().is_some() {
+            return None;
+        }
+        let size: Vec<Measure> = rest.iter().take_while(|m| is_size(m)).cloned().collect();
+        if size.is_empty() {
+            return None;
+        }
+        let container = self.container_of(first);
+        if container.is_none() && *first.unit() != Unit::Whole {
+            return None;
+        }
+
+        let mp = self.measurement_parser(MeasurementMode::IngredientList);
+        let (after_count, _) = mp.parse_number(input.trim_start()).ok()?;
+        let after_count = after_count.trim_start();
+        let size_first = after_count.starts_with('(')
+            || after_count
+                .split_whitespace()
+                .next()
+                .is_some_and(|w| w.starts_with(|c: char| c.is_ascii_digit()) && w.contains('-'));
+        if !size_first {
+            let each = input.split_whitespace().any(|w| token::norm(w) == "each");
+            if container.is_none() || !(first.value() == 1.0 || each) {
+                return None;
+            }
+        }
+        Some(Package {
+            count: first.value(),
+            size,
+            container,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use rstest::rstest;
+
+    fn m(unit: &str, value: f64) -> Measure {
+        Measure::new(unit, value)
+    }
+
+    #[rstest]
+    #[case::paren("2 (14.5-ounce) cans tomatoes", 2.0, &[("oz", 14.5)], Some("can"))]
+    #[case::hyphen("2 14-ounce cans coconut milk", 2.0, &[("oz", 14.0)], Some("can"))]
+    #[case::two_sizes("1 (7 oz / 198g) tube almond paste", 1.0, &[("oz", 7.0), ("g", 198.0)], Some("tube"))]
+    #[case::single_after("1 can (15 oz) chickpeas", 1.0, &[("oz", 15.0)], Some("can"))]
+    #[case::each_after("2 cans (15 oz each) chickpeas", 2.0, &[("oz", 15.0)], Some("can"))]
+    #[case::items("2 (8-ounce) swordfish steaks", 2.0, &[("oz", 8.0)], None)]
+    #[case::multiplier("2 x 400g cans chickpeas", 2.0, &[("g", 400.0)], Some("can"))]
+    #[case::multiplier_sign("2×400 g tins chickpeas", 2.0, &[("g", 400.0)], Some("tin"))]
+    #[case::bare_multiplier("2 x 200g flour", 2.0, &[("g", 200.0)], None)]
+    fn recognizes_packages(
+        #[case] input: &str,
+        #[case] count: f64,
+        #[case] size: &[(&str, f64)],
+        #[case] container: Option<&str>,
+    ) {
+        let got = IngredientParser::new().from_str(input).package.unwrap();
+        assert_eq!(got.count, count, "{input}");
+        let want: Vec<Measure> = size.iter().map(|&(u, v)| m(u, v)).collect();
+        assert_eq!(got.size, want, "{input}");
+        assert_eq!(got.container.as_deref(), container, "{input}");
+    }
+
+    #[rstest]
+    #[case::not_a_container("1 1/2 cups (190 grams) all-purpose flour")]
+    #[case::total_after_noun("2 sticks (226 g) butter")]
+    #[case::items_total("3 eggs (150 g)")]
+    #[case::length("4 (½-inch) slices bread")]
+    #[case::single_multiplier("1 x 200g flour")]
+    #[case::plain("2 cups flour")]
+    fn leaves_other_lines_alone(#[case] input: &str) {
+        assert_eq!(
+            IngredientParser::new().from_str(input).package,
+            None,
+            "{input}"
+        );
+    }
+
+    #[test]
+    fn multiplier_with_container_counts_the_containers() {
+        let got = IngredientParser::new().from_str("2 x 400g cans chickpeas");
+        assert_eq!(got.name, "chickpeas");
+        assert_eq!(got.amounts, [m("can", 2.0), m("g", 400.0)]);
+        // Without a container the multiplier line keeps its total.
+        let flour = IngredientParser::new().from_str("2 x 200g flour");
+        assert_eq!(flour.amounts, [m("g", 400.0)]);
+    }
+}
@@ -79,40 +79,32 @@ pub(crate) enum ParenKind {
 // `replace_all` on. Their DEFINITIONS live here (one home) even though the
 // stripping action stays in normalize.
 
-/// Matches a cross-reference parenthetical whose content is entirely page
-/// references and their connectors. See [`ParenKind::CrossReference`].
-pub(crate) static CROSS_REF: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::new(|| {
-    #[allow(clippy::expect_used)]
-    regex::Regex::new(
-        r"(?i)\s*\(\s*(?:see\s+)?(?:this page|page\s+\d+)(?:[\s,;]*(?:to|or|and)?[\s,;]*(?:see\s+)?(?:this page|page\s+\d+))*\s*\)",
-    )
-    .expect("invalid cross-ref regex")
-});
+crate::lazy_regex!(
+    /// Matches a cross-reference parenthetical whose content is entirely page
+    /// references and their connectors. See [`ParenKind::CrossReference`].
+    pub(crate) CROSS_REF,
+    r"(?i)\s*\(\s*(?:see\s+)?(?:this page|page\s+\d+)(?:[\s,;]*(?:to|or|and)?[\s,;]*(?:see\s+)?(?:this page|page\s+\d+))*\s*\)"
+);
 
-/// Matches a mixed cross-reference + "optional" parenthetical, reduced to
-/// "(optional)" by `split_crossref_optional`.
-pub(crate) static CROSS_REF_OPTIONAL: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::new(
-    || {
-        #[allow(clippy::expect_used)]
-        regex::Regex::new(
-            r"(?i)\(\s*(?:see\s+)?(?:this page|page\s+\d+)(?:[\s,;]*(?:to|or|and)?[\s,;]*(?:see\s+)?(?:this page|page\s+\d+))*[\s,;]+optional\s*\)",
-        )
-        .expect("invalid cross-ref-optional regex")
-    },
+crate::lazy_regex!(
+    /// Matches a mixed cross-reference + "optional" parenthetical, reduced to
+    /// "(optional)" by `split_crossref_optional`.
+    pub(crate) CROSS_REF_OPTIONAL,
+    r"(?i)\(\s*(?:see\s+)?(?:this page|page\s+\d+)(?:[\s,;]*(?:to|or|and)?[\s,;]*(?:see\s+)?(?:this page|page\s+\d+))*[\s,;]+optional\s*\)"
 );
 
-/// Matches a "(see note)" / "(notes)" reference. See [`ParenKind::NoteReference`].
-pub(crate) static NOTE_REF: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::new(|| {
-    #[allow(clippy::expect_used)]
-    regex::Regex::new(r"(?i)\s*\(\s*(?:see\s+)?notes?\s*\)").expect("invalid note-ref regex")
-});
+crate::lazy_regex!(
+    /// Matches a "(see note)" / "(notes)" reference. See [`ParenKind::NoteReference`].
+    pub(crate) NOTE_REF,
+    r"(?i)\s*\(\s*(?:see\s+)?notes?\s*\)"
+);
 
-/// Matches an arithmetic-equivalence parenthetical containing "minus". See
-/// [`ParenKind::MinusEquivalence`].
-pub(crate) static MINUS_PAREN: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::new(|| {
-    #[allow(clippy::expect_used)]
-    regex::Regex::new(r"\s*\([^)]*\bminus\b[^)]*\)").expect("invalid minus-paren regex")
-});
+crate::lazy_regex!(
+    /// Matches an arithmetic-equivalence parenthetical containing "minus". See
+    /// [`ParenKind::MinusEquivalence`].
+    pub(crate) MINUS_PAREN,
+    r"\s*\([^)]*\bminus\b[^)]*\)"
+);
 
 // --- Inner-content predicates (shared with normalize/refine site guards) ------
 
@@ -1,14 +1,17 @@
 use super::ir::{ModifierPart, ParsedIngredient};
+use std::borrow::Cow;
+
 use super::normalize::{lift_inline_descriptive_paren, normalize_input, strip_optional_note};
+use super::spellcheck::correct_line;
 use crate::parser::Res;
 use crate::trace;
 use crate::traced_parser;
-use crate::usage::classify_usage;
+use crate::usage::classify_usage_with;
 use crate::{Ingredient, IngredientParser};
 
 impl IngredientParser {
     pub(crate) fn parse_ingredient_line(&self, input: &str) -> Ingredient {
-        let normalized = normalize_input(input);
+        let normalized = self.normalize_line(input);
         let (mut ingredient, fell_back) =
             self.parse_normalized_ingredient_with_provenance(normalized.as_ref());
         // Attach parse-fidelity notes here at the single funnel, computed from
@@ -21,22 +24,51 @@ impl IngredientParser {
         &self,
         input: &str,
     ) -> trace::ParseWithTrace<Ingredient> {
+        let (result, _fell_back, trace) = self.traced_parse(input);
+        trace::ParseWithTrace {
+            result: Ok(result),
+            trace,
+        }
+    }
+
+    /// [`Self::parse_ingredient_line`] with tracing on: the ingredient (parse
+    /// notes included) and the stage report of how it was reached.
+    pub(crate) fn parse_ingredient_line_with_stages(
+        &self,
+        input: &str,
+    ) -> (Ingredient, trace::StageReport) {
+        let (mut ingredient, fell_back, trace) = self.traced_parse(input);
+        ingredient.parse_notes = crate::ParseNotes::derive(input, &ingredient, fell_back);
+        (ingredient, trace.stages())
+    }
+
+    fn traced_parse(&self, input: &str) -> (Ingredient, bool, trace::ParseTrace) {
         trace::enable_tracing();
         // Open the root span with the *raw* input so the normalize rewrites nest
         // under it as the first stage (the non-trace paths normalize before the
         // span, where tracing is off and it's a no-op). The rest of the pipeline
         // (recognizers, grammar, refine passes) then attaches as later children.
         trace::trace_enter("parse_line", input);
-        let normalized = normalize_input(input);
+        let normalized = self.normalize_line(input);
         let normalized = normalized.as_ref();
-        let (result, _fell_back) = self.parse_pipeline_after_normalize(normalized);
+        let (result, fell_back) = self.parse_pipeline_after_normalize(normalized);
         trace::trace_exit_success(0, &result.name);
-        let trace = trace::disable_tracing(normalized);
+        let mut trace = trace::disable_tracing(normalized);
+        trace.custom_recognizers = self.passes.recognizer_names();
+        (result, fell_back, trace)
+    }
 
-        trace::ParseWithTrace {
-            result: Ok(result),
-            trace,
-        }
+    /// The normalize stage: the pre-parse rewrites, then the typo corrections
+    /// when [spellcheck](IngredientParser::with_spellcheck) is on.
+    pub(super) fn normalize_line<'a>(&self, input: &'a str) -> Cow<'a, str> {
+        trace::perf::stage("normalize", || {
+            let normalized = normalize_input(input, &self.passes);
+            if self.spellcheck {
+                correct_line(normalized, &self.units)
+            } else {
+                normalized
+            }
+        })
     }
 
     /// Parse a normalized line, also reporting whether the parse fell back to a
@@ -72,16 +104,22 @@ impl IngredientParser {
         if is_optional {
             ingredient.optional = true;
         }
+        if !fell_back {
+            trace::perf::stage("package", || self.attach_package(&cleaned, &mut ingredient));
+        }
         // Authoritative usage classification: re-run with the whole line in
         // hand, so purpose phrases the modifier extraction missed still count.
         // Construction-time classification (Ingredient::new, the IR lowering)
         // only sees name+modifier; this is the one place with the full text.
-        ingredient.usage = classify_usage(
-            &ingredient.name,
-            ingredient.modifier.as_deref(),
-            Some(input),
-            None,
-        );
+        ingredient.usage = trace::perf::stage("usage", || {
+            classify_usage_with(
+                &self.profile,
+                &ingredient.name,
+                ingredient.modifier.as_deref(),
+                Some(input),
+                None,
+            )
+        });
         (ingredient, fell_back)
     }
 
@@ -144,62 +182,98 @@ impl IngredientParser {
     /// The `"parse_ingredient"` span name is load-bearing outside this module —
     /// `trace::stages` buckets the grammar stage by it, and a golden snapshot
     /// pins it — so it stays even though the function of that name is gone.
-    fn parse_ingredient_ir<'a>(&self, input: &'a str) -> Res<&'a str, ParsedIngredient> {
-        traced_parser!(
-            "parse_ingredient",
-            input,
-            self.parse_ingredient_segmented(input),
-            |i: &ParsedIngredient| i.name.clone(),
-            "parse failed"
-        )
+    pub(super) fn parse_ingredient_ir<'a>(&self, input: &'a str) -> Res<&'a str, ParsedIngredient> {
+        trace::perf::stage("grammar", || {
+            traced_parser!(
+                "parse_ingredient",
+                input,
+                self.parse_ingredient_segmented(input),
+                |i: &ParsedIngredient| i.name.clone(),
+                "parse failed"
+            )
+        })
     }
 
-    /// Decompose a line into grammar-stage field spans for the `--explain`
-    /// decomposition view.
+    /// The line as the parse stages after normalize see it: the pre-parse
+    /// rewrites (non-breaking spaces, footnote markers, list bullets, …) plus,
+    /// with [spellcheck](IngredientParser::with_spellcheck), the typo
+    /// corrections. Parsing the result gives the same ingredient as parsing
+    /// `raw`.
     ///
-    /// Returns the normalized string the spans index into, plus one
-    /// [`FieldSpan`](crate::FieldSpan) per amount region / name / modifier the
-    /// grammar carved. `spans` is empty when a whole-line recognizer or the
-    /// name-only fallback produced the result (no core-grammar carving to show).
+    /// ```
+    /// use ingredient::IngredientParser;
     ///
-    /// # Example
+    /// let parser = IngredientParser::new();
+    /// assert_eq!(parser.normalize("• 2\u{a0}cups flour ①"), "2 cups flour");
+    /// ```
+    pub fn normalize(&self, raw: &str) -> String {
+        self.normalize_line(raw).into_owned()
+    }
+
+    /// The line's token-shape: its amount pattern, the kinds of its clauses
+    /// and parentheticals, and the recognizer that claimed it. Two lines with
+    /// the same [`signature`](crate::shape::LineShape::signature) exercise the
+    /// same parser paths — see [`shape`](crate::shape).
     ///
     /// ```
     /// use ingredient::IngredientParser;
-    /// use ingredient::Field;
     ///
     /// let parser = IngredientParser::new();
-    /// let decomp = parser.decompose("2 cups flour, sifted");
-    ///
-    /// assert_eq!(decomp.source, "2 cups flour, sifted");
-    /// assert_eq!(decomp.spans.len(), 3);
-    /// assert_eq!(decomp.spans[0].field, Field::Amount);
-    /// assert_eq!(decomp.spans[0].text, "2 cups");
-    /// assert_eq!(decomp.spans[1].field, Field::Name);
-    /// assert_eq!(decomp.spans[1].text, "flour");
-    /// assert_eq!(decomp.spans[2].field, Field::Modifier);
-    /// assert_eq!(decomp.spans[2].text, "sifted");
+    /// let shape = parser.shape("2 cups flour, sifted");
+    /// assert_eq!(shape.amounts, "N volume");
+    /// assert_eq!(shape.clauses, ["head_candidate", "prep_chain"]);
+    /// assert_eq!(shape, parser.shape("3 tbsp sugar, melted"));
     /// ```
-    pub fn decompose(&self, raw: &str) -> crate::Decomposition {
-        let normalized = normalize_input(raw);
-        let (cleaned, _optional) = strip_optional_note(normalized.as_ref());
-        // Only the core grammar carves fields into spans; a whole-line
-        // recognizer produces the result without the field grammar running.
-        let spans = if self.run_recognizers(cleaned.as_ref()).is_some() {
-            Vec::new()
-        } else {
-            self.grammar_field_spans(cleaned.as_ref())
+    pub fn shape(&self, raw: &str) -> crate::shape::LineShape {
+        let normalized = self.normalize_line(raw);
+        let (cleaned, optional) = strip_optional_note(normalized.as_ref());
+        let recognizer = self.matched_recognizer(cleaned.as_ref());
+        // A recognizer's line never reaches the clause grammar, so it has no
+        // clauses to report.
+        let (clauses, mut parens) = match recognizer {
+            Some(_) => (Vec::new(), Vec::new()),
+            None => self.clause_shape(cleaned.as_ref()),
         };
-        crate::Decomposition {
-            source: cleaned.into_owned(),
-            spans,
+        if optional {
+            parens.insert(0, "optional");
+        }
+        crate::shape::LineShape {
+            amounts: crate::shape::amount_pattern(&self.parse_ingredient_line(raw).amounts),
+            clauses,
+            parens,
+            recognizer,
         }
     }
 
-    /// Grammar-stage field spans, derived from the clause byte ranges. Empty
-    /// vec if the parse fails.
-    fn grammar_field_spans(&self, input: &str) -> Vec<crate::FieldSpan> {
-        self.segmented_field_spans(input)
+    /// Why the line got its [usage](crate::Ingredient::usage): the phrase
+    /// hits [`classify_usage`](crate::classify_usage) saw, over the same
+    /// name, modifier and normalized line the parse classifies. With a
+    /// `section`, it's the classification
+    /// [`from_str_in_section`](IngredientParser::from_str_in_section) makes.
+    ///
+    /// ```
+    /// use ingredient::IngredientParser;
+    /// use ingredient::usage::{Haystack, IngredientUsage};
+    ///
+    /// let why = IngredientParser::new().explain_usage("oil, for frying", None);
+    /// assert_eq!(why.usage, IngredientUsage::FryingMedium);
+    /// let hit = why.decided_by().unwrap();
+    /// assert_eq!((hit.phrase.as_str(), hit.haystack), ("for frying", Haystack::Modifier));
+    /// ```
+    pub fn explain_usage(
+        &self,
+        raw: &str,
+        section: Option<&str>,
+    ) -> crate::usage::UsageExplanation {
+        let ingredient = self.parse_ingredient_line(raw);
+        let normalized = self.normalize_line(raw);
+        crate::usage::explain_usage_with(
+            &self.profile,
+            &ingredient.name,
+            ingredient.modifier.as_deref(),
+            Some(normalized.as_ref()),
+            section,
+        )
     }
 }
 
@@ -207,79 +281,3 @@ impl IngredientParser {
 fn fallback_ingredient(input: &str) -> Ingredient {
     Ingredient::from_parser_parts(input.trim(), vec![], None, false)
 }
-
-#[cfg(test)]
-mod decompose_tests {
-    use crate::{Field, IngredientParser};
-    use rstest::rstest;
-
-    /// (field, text) pairs expected from `decompose`, in span order.
-    type Expected = &'static [(Field, &'static str)];
-
-    #[rstest]
-    #[case("2 cups flour", &[(Field::Amount, "2 cups"), (Field::Name, "flour")])]
-    #[case(
-        "1 cup / 240ml water",
-        &[(Field::Amount, "1 cup / 240ml"), (Field::Name, "water")]
-    )]
-    #[case(
-        "2¼ cups all-purpose flour, sifted",
-        &[
-            (Field::Amount, "2¼ cups"),
-            (Field::Name, "all-purpose flour"),
-            (Field::Modifier, "sifted"),
-        ]
-    )]
-    // Grammar-stage carve: prep adjectives stay in the name span here; refine
-    // moves them later (the --explain stage view shows that separately).
-    #[case(
-        "2 chopped fresh basil",
-        &[(Field::Amount, "2"), (Field::Name, "chopped fresh basil")]
-    )]
-    #[case("salt", &[(Field::Name, "salt")])]
-    // A multi-clause tail yields one modifier span per clause on the
-    // segmented path (the legacy grammar produced a single span here).
-    #[case(
-        "1 cup flour, sifted, divided",
-        &[
-            (Field::Amount, "1 cup"),
-            (Field::Name, "flour"),
-            (Field::Modifier, "sifted"),
-            (Field::Modifier, "divided"),
-        ]
-    )]
-    fn decompose_carves_fields(#[case] input: &str, #[case] expected: Expected) {
-        let parser = IngredientParser::new();
-        let decomp = parser.decompose(input);
-
-        let got: Vec<(Field, &str)> = decomp
-            .spans
-            .iter()
-            .map(|s| (s.field, s.text.as_str()))
-            .collect();
-        let want: Vec<(Field, &str)> = expected.to_vec();
-        assert_eq!(got, want, "decompose({input:?})");
-
-        // Every span must index back into `source` and match its `text`, and
-        // spans must not overlap.
-        let mut prev_end = 0;
-        for s in &decomp.spans {
-            assert_eq!(&decomp.source[s.range.clone()], s.text, "span text/range");
-            assert!(s.range.start >= prev_end, "spans overlap in {input:?}");
-            prev_end = s.range.end;
-        }
-    }
-
-    #[test]
-    fn recognizer_handled_line_has_no_grammar_spans() {
-        // "Juice of 1 lemon" is produced by the x_of_construction recognizer,
-        // not the field grammar — so there are no grammar-stage spans to show.
-        let parser = IngredientParser::new();
-        let decomp = parser.decompose("Juice of 1 lemon");
-        assert!(
-            decomp.spans.is_empty(),
-            "recognizer result should yield no spans, got {:?}",
-            decomp.spans
-        );
-    }
-}
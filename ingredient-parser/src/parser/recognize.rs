@@ -6,22 +6,59 @@
 //! recognizer returns `Some(Ingredient)` when it matches and `None` to fall
 //! through to the next recognizer / the core parse.
 
-use crate::parser::{MeasurementMode, MeasurementParser};
+use crate::parser::MeasurementMode;
 use crate::unit;
 use crate::{Ingredient, IngredientParser};
 
 impl IngredientParser {
-    /// Try each whole-line special-form recognizer in order, returning the first
-    /// that matches (or `None` to fall through to the core parse).
+    /// Try each whole-line special-form recognizer in order, the parser's
+    /// [registered](crate::pass) ones each next to its built-in, returning the
+    /// first that matches (or `None` to fall through to the core parse).
     pub(super) fn run_recognizers(&self, input: &str) -> Option<Ingredient> {
-        RECOGNIZERS.iter().find_map(|recognizer| {
-            let result = (recognizer.run)(self, input);
-            crate::trace::trace_attempt(recognizer.id().as_str(), input, result, |ingredient| {
-                ingredient.name.clone()
+        crate::trace::perf::stage("recognize", || {
+            self.recognizer_order(input, |name, run| {
+                let frame = crate::trace::perf::Frame::Pass {
+                    stage: "recognize",
+                    name,
+                };
+                let result = crate::trace::perf::timed(frame, run, Option::is_some);
+                crate::trace::trace_attempt(name, input, result, |ingredient| {
+                    ingredient.name.clone()
+                })
             })
         })
     }
 
+    /// The name of the first recognizer that claims `input`, untraced — the
+    /// same first-match-wins order as [`Self::run_recognizers`].
+    pub(crate) fn matched_recognizer(&self, input: &str) -> Option<&'static str> {
+        self.recognizer_order(input, |name, run| run().map(|_| name))
+    }
+
+    /// Offer each recognizer, built-in and registered, in pipeline order to
+    /// `attempt` (its name and a thunk running it on `input`) until one
+    /// returns `Some`.
+    fn recognizer_order<T>(
+        &self,
+        input: &str,
+        mut attempt: impl FnMut(&'static str, &dyn Fn() -> Option<Ingredient>) -> Option<T>,
+    ) -> Option<T> {
+        RECOGNIZERS.iter().find_map(|recognizer| {
+            let label = recognizer.id().as_str();
+            for pass in self.passes.before(label) {
+                if let Some(found) = attempt(pass.name(), &|| pass.recognize(self, input)) {
+                    return Some(found);
+                }
+            }
+            if let Some(found) = attempt(label, &|| (recognizer.run)(self, input)) {
+                return Some(found);
+            }
+            self.passes
+                .after(label)
+                .find_map(|pass| attempt(pass.name(), &|| pass.recognize(self, input)))
+        })
+    }
+
     /// Try to parse an optional ingredient format: "(amount ingredient, modifier)"
     ///
     /// When an entire ingredient line is wrapped in parentheses, it indicates
@@ -48,15 +85,29 @@ impl IngredientParser {
     /// This handles professional/European cookbook formats where the amount
     /// comes at the end after an em-dash, en-dash, or double hyphen.
     pub(super) fn try_parse_trailing_amount_format(&self, input: &str) -> Option<Ingredient> {
+        let (name_end, _, amounts) = self.split_trailing_amount(input)?;
+        Some(Ingredient::from_parser_parts(
+            input[..name_end].trim(),
+            amounts,
+            None,
+            false,
+        ))
+    }
+
+    /// Where a "Name — AMOUNT" line splits: the end of the name, the start of
+    /// the amount, and the amounts parsed from it.
+    pub(super) fn split_trailing_amount(
+        &self,
+        input: &str,
+    ) -> Option<(usize, usize, Vec<unit::Measure>)> {
         let separators = [" — ", " – ", " -- "];
-        let mp = MeasurementParser::new(&self.units, MeasurementMode::IngredientList);
+        let mp = self.measurement_parser(MeasurementMode::IngredientList);
 
         for sep in separators {
             let Some(pos) = input.rfind(sep) else {
                 continue;
             };
 
-            let name_part = &input[..pos];
             let amount_part = &input[pos + sep.len()..];
 
             let Ok((remaining, amounts)) = mp.parse_measurement_list(amount_part) else {
@@ -70,12 +121,7 @@ impl IngredientParser {
                 continue;
             }
 
-            return Some(Ingredient::from_parser_parts(
-                name_part.trim(),
-                amounts,
-                None,
-                false,
-            ));
+            return Some((pos, pos + sep.len(), amounts));
         }
 
         None
@@ -89,37 +135,8 @@ impl IngredientParser {
     /// "seeds scraped from", ...) moves into the modifier.
     pub(super) fn try_parse_x_of_construction(&self, input: &str) -> Option<Ingredient> {
         let trimmed = input.trim();
-
-        // Find the leading "… of " / "… from " clause whose pivot is immediately
-        // followed by a number (e.g. "Seeds scraped from 1 …"). Uses the EARLIEST
-        // qualifying pivot across both separators.
-        let lower = crate::parser::byte_aligned_lowercase(trimmed)?;
-        let pivot_end = [" of ", " from "]
-            .iter()
-            .filter_map(|sep| {
-                lower.find(sep).and_then(|pos| {
-                    let after = pos + sep.len();
-                    // A number must follow the separator: a digit/vulgar fraction
-                    // or a spelled-out count ("one lemon"). This keeps normal
-                    // names with "of"/"from" (e.g. "cream of tartar", "heart of
-                    // palm") from being captured.
-                    let tail = &trimmed[after..];
-                    let starts_number = tail
-                        .chars()
-                        .next()
-                        .is_some_and(|c| c.is_ascii_digit() || crate::fraction::is_vulgar(c))
-                        || crate::parser::text_number(tail).is_ok();
-                    starts_number.then_some(after)
-                })
-            })
-            .min()?;
-
+        let pivot_end = x_of_pivot(trimmed)?;
         let phrase = trimmed[..pivot_end].trim();
-        // Guard against a bare leading pivot ("of 1 lemon") with no descriptor.
-        if phrase.is_empty() || phrase.split_whitespace().count() > 5 {
-            return None;
-        }
-
         let rest = trimmed[pivot_end..].trim_start();
         let mut parsed = self.parse_core_ingredient(rest)?;
 
@@ -164,6 +181,49 @@ crate::define_stage_pipeline! {
     ),
 }
 
+/// The built-in recognizer labeled `name`; `None` for a registered one.
+pub(super) fn builtin_recognizer(name: &str) -> Option<RecognizerId> {
+    RECOGNIZERS
+        .iter()
+        .map(|recognizer| recognizer.id())
+        .find(|id| id.as_str() == name)
+}
+
+/// The end of the leading "… of " / "… from " phrase of an "X of N item" line
+/// (`line` trimmed), when the line has that shape.
+pub(super) fn x_of_pivot(line: &str) -> Option<usize> {
+    // Find the leading "… of " / "… from " clause whose pivot is immediately
+    // followed by a number (e.g. "Seeds scraped from 1 …"). Uses the EARLIEST
+    // qualifying pivot across both separators.
+    let lower = crate::parser::byte_aligned_lowercase(line)?;
+    let pivot_end = [" of ", " from "]
+        .iter()
+        .filter_map(|sep| {
+            lower.find(sep).and_then(|pos| {
+                let after = pos + sep.len();
+                // A number must follow the separator: a digit/vulgar fraction
+                // or a spelled-out count ("one lemon"). This keeps normal
+                // names with "of"/"from" (e.g. "cream of tartar", "heart of
+                // palm") from being captured.
+                let tail = &line[after..];
+                let starts_number = tail
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.is_ascii_digit() || crate::fraction::is_vulgar(c))
+                    || crate::parser::text_number(tail).is_ok();
+                starts_number.then_some(after)
+            })
+        })
+        .min()?;
+
+    let phrase = line[..pivot_end].trim();
+    // Guard against a bare leading pivot ("of 1 lemon") with no descriptor.
+    if phrase.is_empty() || phrase.split_whitespace().count() > 5 {
+        return None;
+    }
+    Some(pivot_end)
+}
+
 fn is_temperature_unit(unit: &unit::Unit) -> bool {
     matches!(unit, unit::Unit::Fahrenheit | unit::Unit::Celsius)
 }
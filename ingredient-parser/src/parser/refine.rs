@@ -35,7 +35,7 @@ use super::ir::{ModifierPart, ParsedIngredient};
 use super::normalize::collapse_whitespace;
 use crate::parser::{MeasurementMode, MeasurementParser};
 use crate::unit::{self, Measure};
-use crate::{Ingredient, IngredientParser};
+use crate::{Ingredient, IngredientParser, Reading};
 
 impl IngredientParser {
     /// Run the ordered refinement passes on the parsed IR, then lower it to the
@@ -51,21 +51,63 @@ impl IngredientParser {
     /// inline-descriptive-paren path) can do so through the IR before lowering,
     /// rather than hand-joining the public modifier string.
     pub(super) fn refine(&self, parsed: &mut ParsedIngredient) {
-        for pass in REFINE_PIPELINE {
-            self.run_refine_pass(pass, parsed);
-        }
+        crate::trace::perf::stage("refine", || {
+            for pass in REFINE_PIPELINE {
+                let label = pass.id().as_str();
+                for custom in self.passes.before(label) {
+                    self.run_custom_refine_pass(custom, parsed);
+                }
+                if !self.reading.skips(pass.id()) {
+                    self.run_refine_pass(pass, parsed);
+                }
+                for custom in self.passes.after(label) {
+                    self.run_custom_refine_pass(custom, parsed);
+                }
+            }
+        });
     }
 
     fn run_refine_pass(&self, pass: &RefinePass, parsed: &mut ParsedIngredient) {
         let RefinePass { run, .. } = *pass;
-        if !crate::trace::is_tracing_enabled() {
-            run(self, parsed);
+        self.run_refine_step(pass.id().as_str(), parsed, |parsed| run(self, parsed));
+    }
+
+    /// Run a [registered](crate::pass) refine pass, traced like a built-in.
+    fn run_custom_refine_pass(
+        &self,
+        pass: &dyn crate::pass::CustomPass,
+        parsed: &mut ParsedIngredient,
+    ) {
+        self.run_refine_step(pass.name(), parsed, |parsed| {
+            pass.refine(&mut crate::pass::Refining::new(parsed));
+        });
+    }
+
+    /// Run one refine pass, timing it when profiling and recording it in the
+    /// trace when it changed the ingredient.
+    fn run_refine_step(
+        &self,
+        name: &'static str,
+        parsed: &mut ParsedIngredient,
+        run: impl FnOnce(&mut ParsedIngredient),
+    ) {
+        let tracing = crate::trace::is_tracing_enabled();
+        if !tracing && !crate::trace::perf::is_profiling() {
+            run(parsed);
             return;
         }
         let before = parsed.clone();
-        run(self, parsed);
+        crate::trace::perf::enter(crate::trace::perf::Frame::Pass {
+            stage: "refine",
+            name,
+        });
+        run(parsed);
+        crate::trace::perf::exit(|| *parsed != before);
+        if !tracing {
+            return;
+        }
         crate::trace::trace_on_change(
-            pass.id().as_str(),
+            name,
             &before.name,
             &format!(
                 "{} | {}",
@@ -89,6 +131,20 @@ impl IngredientParser {
 
 type Pass = fn(&IngredientParser, &mut ParsedIngredient);
 
+impl Reading {
+    /// Whether this reading leaves `pass` out of the refine pipeline.
+    fn skips(self, pass: PassId) -> bool {
+        matches!(
+            (self, pass),
+            (Reading::SizeInName, PassId::ExtractSizeUnitFromName)
+                | (
+                    Reading::ProduceUnitInName,
+                    PassId::ExtractPostfixProduceUnit
+                )
+        )
+    }
+}
+
 crate::define_stage_pipeline! {
     // Every pass is an `Extract*` step (the lone non-`Extract` variant,
     // `CollapseName`, was removed as dead), so the shared prefix is intrinsic to
@@ -92,9 +92,10 @@ impl IngredientParser {
     /// [`Self::extract_alternative_from_name`]), so any "or" remaining here is a
     /// plain ingredient/adjective alternative sharing the primary's amount.
     pub(super) fn extract_word_alternative_from_name(&self, parsed: &mut ParsedIngredient) {
+        let share_head = self.reading != Reading::NoSharedHead;
         Self::apply_alternative_split(
             parsed,
-            split_word_alternative(&parsed.name, &self.adjectives),
+            split_word_alternative(&parsed.name, &self.adjectives, share_head),
         );
     }
 
@@ -133,25 +134,32 @@ impl IngredientParser {
     /// "canola oil") and keep the whole list as an "or …" alternative modifier.
     ///
     /// Gated narrowly (requires a comma *and* an "or", plus a final word in
-    /// [`vocab::SHARED_HEAD_NOUNS`]) so lists of complete ingredients —
+    /// the profile's [`shared_head_nouns`]) so lists of complete ingredients —
     /// "salt, pepper, or paprika", "flour, sugar, or baking soda" — never get a
     /// nonsense head grafted on.
+    ///
+    /// [`shared_head_nouns`]: crate::profile::ParserProfile::shared_head_nouns
     pub(in crate::parser) fn recover_shared_head_from_alternatives(
         &self,
         parsed: &mut ParsedIngredient,
     ) {
+        if self.reading == Reading::NoSharedHead {
+            return;
+        }
         // Name must be a single bare token that isn't already the head noun.
         let mut name_words = parsed.name.split_whitespace();
         let (Some(name_word), None) = (name_words.next(), name_words.next()) else {
             return;
         };
-        if crate::parser::vocab::SHARED_HEAD_NOUNS.contains(&name_word.to_lowercase().as_str()) {
+        let heads = &self.profile().shared_head_nouns;
+        if heads.contains(&name_word.to_lowercase()) {
             return;
         }
         let Some(modifier) = parsed.modifier_string() else {
             return;
         };
-        let Some(head) = distributable_head(&modifier, SharedHeadContext::CommaOrList) else {
+        let Some(head) = distributable_head(&modifier, SharedHeadContext::CommaOrList { heads })
+        else {
             return;
         };
         parsed.name = graft(name_word, head, GraftMode::AppendTrailingHead);
@@ -164,9 +172,9 @@ impl IngredientParser {
 /// the two shapes intentionally consult different vocab lists.
 enum SharedHeadContext<'a> {
     /// A comma+or alternatives list stranded in the modifier
-    /// ("vegetable, or melted coconut oil"), gated on
-    /// [`vocab::SHARED_HEAD_NOUNS`].
-    CommaOrList,
+    /// ("vegetable, or melted coconut oil"), gated on the profile's shared
+    /// head nouns ([`vocab::SHARED_HEAD_NOUNS`] by default).
+    CommaOrList { heads: &'a [String] },
     /// An inline "A or B `<head>`" right side ("white onion"), gated on
     /// [`vocab::DISTRIBUTABLE_HEAD_NOUNS`]. Carries the parser's adjective set
     /// for the "not led by a prep adjective" guard.
@@ -181,17 +189,17 @@ enum SharedHeadContext<'a> {
 ///
 /// - [`SharedHeadContext::CommaOrList`]: `right` must read as a comma-separated
 ///   alternatives list joined by "or" (both signals a shared head, not a
-///   standalone alternative), and its final token must be in
-///   [`vocab::SHARED_HEAD_NOUNS`]. Casing note: the last token is trimmed of
-///   surrounding punctuation but *not* lowercased for the graft — only the vocab
-///   lookup lowercases — so the grafted head preserves the source casing.
+///   standalone alternative), and its final token must be one of its `heads`.
+///   Casing note: the last token is trimmed of surrounding punctuation but
+///   *not* lowercased for the graft — only the vocab lookup lowercases — so
+///   the grafted head preserves the source casing.
 /// - [`SharedHeadContext::InlineOr`]: `right` must read as
 ///   "`<premodifier> <head noun>`" (at least two tokens, not led by a prep
 ///   adjective, free of stopwords) and its trailing head noun must be in
 ///   [`vocab::DISTRIBUTABLE_HEAD_NOUNS`].
 fn distributable_head<'a>(right: &'a str, ctx: SharedHeadContext) -> Option<&'a str> {
     match ctx {
-        SharedHeadContext::CommaOrList => {
+        SharedHeadContext::CommaOrList { heads } => {
             // The modifier must read as a comma-separated alternatives list
             // joined by "or" — both signals that the trailing noun is a shared
             // head, not a standalone alternative ("flour or oil" stays two
@@ -208,9 +216,7 @@ fn distributable_head<'a>(right: &'a str, ctx: SharedHeadContext) -> Option<&'a
                 .split_whitespace()
                 .next_back()
                 .map(|t| t.trim_matches(|c: char| !c.is_alphanumeric()))?;
-            crate::parser::vocab::SHARED_HEAD_NOUNS
-                .contains(&head.to_lowercase().as_str())
-                .then_some(head)
+            heads.contains(&head.to_lowercase()).then_some(head)
         }
         SharedHeadContext::InlineOr { adjectives } => {
             let right_tokens: Vec<&str> = right.split_whitespace().collect();
@@ -313,10 +319,14 @@ fn extract_alternative(name: &str) -> (String, Option<String>) {
 /// the alternative.
 /// Known limitation: a single-token *noun* on the left with a distinct
 /// multi-word alternative ("salt or chicken broth") over-reconstructs to "salt
-/// broth" — rare, not in the corpus, and the alternative stays correct.
+/// broth" — rare, not in the corpus, and the alternative stays correct. With
+/// `share_head` off (the [`Reading::NoSharedHead`] candidate) an open-ended left
+/// stays bare, which is that line's other reading; a known premodifier ("red")
+/// still takes the head, since it can't be a name on its own.
 pub(super) fn split_word_alternative(
     name: &str,
     adjectives: &std::collections::HashSet<String>,
+    share_head: bool,
 ) -> (String, Option<String>) {
     // First word-boundary " or ", case-insensitive. Matching on the original
     // `name` (not a lowercased copy) keeps the byte offsets valid for slicing.
@@ -340,7 +350,8 @@ pub(super) fn split_word_alternative(
 
     let primary = match graft_decision(left, &left_tokens, right, &right_tokens, adjectives) {
         // A single left adjective replaces `right`'s leading adjective, keeping
-        // the trailing head noun: "red or white onion" -> "red onion".
+        // the trailing head noun: "red or white onion" -> "red onion". Taken
+        // with or without `share_head`: a bare premodifier is never a name.
         Graft::ReplaceLeadingAdjective => graft(
             left,
             &right_tokens[1..].join(" "),
@@ -348,8 +359,10 @@ pub(super) fn split_word_alternative(
         ),
         // An open-ended left distributes onto the right's trailing head noun:
         // "chicken or vegetable stock" -> "chicken stock".
-        Graft::AppendTrailingHead(head) => graft(left, head, GraftMode::AppendTrailingHead),
-        Graft::None => left.to_string(),
+        Graft::AppendTrailingHead(head) if share_head => {
+            graft(left, head, GraftMode::AppendTrailingHead)
+        }
+        Graft::AppendTrailingHead(_) | Graft::None => left.to_string(),
     };
 
     (primary, Some(format!("or {right}")))
@@ -457,7 +470,12 @@ mod helper_tests {
     #[case::final_not_curated("sugar, or baking soda", None)]
     fn test_distributable_head_comma_or_list(#[case] right: &str, #[case] expected: Option<&str>) {
         assert_eq!(
-            distributable_head(right, SharedHeadContext::CommaOrList),
+            distributable_head(
+                right,
+                SharedHeadContext::CommaOrList {
+                    heads: &crate::profile::BUILTIN.shared_head_nouns
+                }
+            ),
             expected,
             "right: {right}"
         );
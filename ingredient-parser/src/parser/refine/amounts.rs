@@ -9,8 +9,8 @@ impl IngredientParser {
             return;
         };
 
-        let (secondary_amounts, cleaned_modifier) =
-            extract_secondary_amounts(&modifier, &self.units);
+        let mp = self.measurement_parser(MeasurementMode::IngredientList);
+        let (secondary_amounts, cleaned_modifier) = extract_secondary_amounts(&modifier, &mp);
         // Only rewrite the modifier when an amount was actually hoisted; otherwise
         // leave the typed parts untouched (the cleaned string equals the original).
         if secondary_amounts.is_empty() {
@@ -30,18 +30,16 @@ impl IngredientParser {
 // This site keeps its own regexes because it needs the *match spans* (to excise
 // and clean the modifier) and its own distance-aside rejection — the classifier
 // yields only a kind, not a span. See `parser::paren` module docs.
-fn extract_secondary_amounts(
-    modifier: &str,
-    units: &std::collections::HashSet<String>,
-) -> (Vec<Measure>, String) {
+fn extract_secondary_amounts(modifier: &str, mp: &MeasurementParser<'_>) -> (Vec<Measure>, String) {
     crate::lazy_regex!(
         SECONDARY_AMOUNT_PATTERN,
-        r"\((?:from\s+)?(?:about|approximately|roughly|around)\s+([^)]+)\)"
+        r"\((?:from\s+)?((?:about|approximately|roughly|around)\s+[^)]+)\)"
     );
     crate::lazy_regex!(TRAILING_MEASURE_PATTERN, r"\(([^)]+)\)\s*$");
 
-    // The approximation aside wins (it strips the "about" off the amount text);
-    // otherwise fall back to a bare trailing measure parenthetical.
+    // The approximation aside wins; its "about" stays on the amount text, where
+    // the measurement parser reads it as the qualifier. Otherwise fall back to a
+    // bare trailing measure parenthetical.
     let Some(caps) = SECONDARY_AMOUNT_PATTERN
         .captures(modifier)
         .or_else(|| TRAILING_MEASURE_PATTERN.captures(modifier))
@@ -57,7 +55,6 @@ fn extract_secondary_amounts(
     };
     let amount_text = amount_match.as_str().trim();
 
-    let mp = MeasurementParser::new(units, MeasurementMode::IngredientList);
     let Ok((remaining, measures)) = mp.parse_measurement_list(amount_text) else {
         return (vec![], modifier.to_string());
     };
@@ -66,9 +63,8 @@ fn extract_secondary_amounts(
     // long (about 3-inch) strips") describes shape, not a secondary quantity.
     // Leave it in the modifier rather than hoisting a spurious inch amount.
     let is_distance = |m: &Measure| match m.unit() {
-        unit::Unit::Inch => true,
         unit::Unit::Other(s) => crate::parser::is_distance_unit(s),
-        _ => false,
+        _ => m.kind() == unit::MeasureKind::Length,
     };
     if measures.iter().any(is_distance) {
         return (vec![], modifier.to_string());
@@ -39,7 +39,7 @@ impl IngredientParser {
         else {
             return;
         };
-        let mp = MeasurementParser::new(&self.units, MeasurementMode::IngredientList);
+        let mp = self.measurement_parser(MeasurementMode::IngredientList);
         let Ok((remaining, measures)) = mp.parse_measurement_list(rest) else {
             return;
         };
@@ -354,7 +354,7 @@ fn test_split_word_alternative(
 ) {
     let parser = IngredientParser::new();
     let (got_name, got_alternative) =
-        alternatives::split_word_alternative(name, &parser.adjectives);
+        alternatives::split_word_alternative(name, &parser.adjectives, true);
     assert_eq!(got_name, want_name, "name: {name}");
     assert_eq!(got_alternative.as_deref(), want_alternative, "name: {name}");
 }
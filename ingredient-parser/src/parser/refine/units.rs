@@ -3,12 +3,12 @@ use super::*;
 impl IngredientParser {
     /// Postfix produce count-units: "1 medium garlic clove" -> name "garlic",
     /// amount `{clove:1}`, with leading descriptors ("medium") moved to the
-    /// modifier. Only fires for the curated [`vocab::POSTFIX_PRODUCE_UNITS`]
+    /// modifier. Only fires for the profile's curated [`postfix_produce_units`]
     /// pairs and only when the count is a plain whole number (or absent), so
     /// weights/volumes and idioms like "cinnamon stick" / "wood ear mushroom"
     /// are untouched.
     ///
-    /// [`vocab::POSTFIX_PRODUCE_UNITS`]: crate::parser::vocab::POSTFIX_PRODUCE_UNITS
+    /// [`postfix_produce_units`]: crate::profile::ParserProfile::postfix_produce_units
     pub(super) fn extract_postfix_produce_unit(&self, parsed: &mut ParsedIngredient) {
         // The count must be a plain whole number (the default count unit) or
         // there must be no amount at all; a real volume/weight lead means the
@@ -22,23 +22,31 @@ impl IngredientParser {
         }
 
         let name_lower = parsed.name.to_lowercase();
-        for (food, units) in crate::parser::vocab::POSTFIX_PRODUCE_UNITS {
-            for unit_word in *units {
+        for (food, units) in &self.profile().postfix_produce_units {
+            for unit_word in units {
                 let suffix = format!("{food} {unit_word}");
                 if name_lower != suffix && !name_lower.ends_with(&format!(" {suffix}")) {
                     continue;
                 }
-                // `suffix` is ASCII produce, so lowercasing preserved byte
-                // lengths and this offset is a valid char boundary in `name`.
-                let food_start = parsed.name.len() - suffix.len();
+                // Lowercasing preserves byte lengths for ASCII produce, so this
+                // offset is a char boundary in `name`; a profile entry whose
+                // case mapping changes length just doesn't match.
+                let Some(prefix) = parsed
+                    .name
+                    .len()
+                    .checked_sub(suffix.len())
+                    .and_then(|start| parsed.name.get(..start))
+                else {
+                    continue;
+                };
+                let prefix = prefix.trim().to_string();
                 let count = whole_idx.map(|i| parsed.amounts[i].value()).unwrap_or(1.0);
                 let measure = Measure::new(unit_word, count);
                 match whole_idx {
                     Some(i) => parsed.amounts[i] = measure,
                     None => parsed.amounts.push(measure),
                 }
-                let prefix = parsed.name[..food_start].trim().to_string();
-                parsed.name = (*food).to_string();
+                parsed.name = food.clone();
                 if !prefix.is_empty() {
                     parsed.modifier.insert(0, ModifierPart::Prep(prefix));
                 }
@@ -55,13 +63,13 @@ impl IngredientParser {
     ///
     /// Fires only when a real `Unit::Whole` count is present (so "medium heat", a
     /// no-count "medium onion", and "2 cups large onion" are all untouched), the
-    /// name begins with a [`vocab::SIZE_UNIT_WORDS`] token, and a head noun follows
+    /// name begins with one of the profile's [`size_unit_words`], and a head noun follows
     /// that isn't a connector or another size word (so the "medium or large …"
     /// range — kept whole by [`split_word_alternative`] — is left alone). Runs after
     /// [`Self::extract_postfix_produce_unit`] so a produce count unit ("1 medium
     /// garlic clove" -> `{clove:1}`) wins and this pass then skips it.
     ///
-    /// [`vocab::SIZE_UNIT_WORDS`]: crate::parser::vocab::SIZE_UNIT_WORDS
+    /// [`size_unit_words`]: crate::profile::ParserProfile::size_unit_words
     pub(super) fn extract_size_unit_from_name(&self, parsed: &mut ParsedIngredient) {
         // Require an explicit whole count — the load-bearing gate. No count means
         // there is no portion to size ("medium heat" has no amount).
@@ -75,14 +83,14 @@ impl IngredientParser {
 
         let name = parsed.name.trim();
         let name_lower = name.to_lowercase();
-        // SIZE_UNIT_WORDS is ordered longest-first, so "extra large"/"extra-large"
+        // The size words are ordered longest-first, so "extra large"/"extra-large"
         // win over "large"; the trailing-whitespace check rejects "larger"/"jumbos".
         let mut matched: Option<&str> = None;
-        for w in crate::parser::vocab::SIZE_UNIT_WORDS {
-            if let Some(rest) = name_lower.strip_prefix(*w)
+        for w in &self.profile().size_unit_words {
+            if let Some(rest) = name_lower.strip_prefix(w.as_str())
                 && rest.starts_with(char::is_whitespace)
             {
-                matched = Some(*w);
+                matched = Some(w);
                 break;
             }
         }
@@ -90,8 +98,11 @@ impl IngredientParser {
             return;
         };
 
-        // `size` is ASCII, so its byte length indexes `name` (original case) too.
-        let rest = name[size.len()..].trim();
+        // `size` is ASCII, so its byte length indexes `name` (original case)
+        // too; a non-ASCII profile entry that changed length is skipped.
+        let Some(rest) = name.get(size.len()..).map(str::trim) else {
+            return;
+        };
         if rest.is_empty() {
             return;
         }
@@ -107,7 +118,7 @@ impl IngredientParser {
 
         // Only the two "extra large" spellings canonicalize to "extra large".
         // Match them exactly rather than on an "extra" prefix, so a future
-        // SIZE_UNIT_WORDS entry like "extra small" is never mis-mapped to large.
+        // size-word entry like "extra small" is never mis-mapped to large.
         let unit_str = match size {
             "extra large" | "extra-large" => "extra large",
             other => other,
@@ -123,14 +123,16 @@ impl<'a> Clause<'a> {
 pub(crate) struct Segmenter<'p> {
     adjectives: &'p std::collections::HashSet<String>,
     units: &'p std::collections::HashSet<String>,
+    containers: &'p [String],
 }
 
 impl IngredientParser {
-    /// A [`Segmenter`] borrowing this parser's adjective/unit vocab.
+    /// A [`Segmenter`] borrowing this parser's adjective/unit/container vocab.
     pub(crate) fn segmenter(&self) -> Segmenter<'_> {
         Segmenter {
             adjectives: &self.adjectives,
             units: &self.units,
+            containers: &self.profile().container_nouns,
         }
     }
 }
@@ -216,7 +218,8 @@ fn is_minus_measure(seg: &Segmenter<'_>, text: &str) -> bool {
     else {
         return false;
     };
-    let mp = MeasurementParser::new(seg.units, MeasurementMode::IngredientList);
+    let mp = MeasurementParser::new(seg.units, MeasurementMode::IngredientList)
+        .with_containers(seg.containers);
     match mp.parse_measurement_list(rest) {
         Ok((_, measures)) => !measures.is_empty(),
         Err(_) => false,
@@ -374,7 +377,7 @@ impl IngredientParser {
         &self,
         input: &'a str,
     ) -> Res<&'a str, ParsedIngredient> {
-        let mp = MeasurementParser::new(&self.units, MeasurementMode::IngredientList);
+        let mp = self.measurement_parser(MeasurementMode::IngredientList);
         let (rest, (primary, _, bracketed, _)) = (
             opt(|a| mp.parse_measurement_list(a)),
             space0,
@@ -575,7 +578,7 @@ impl IngredientParser {
     pub(crate) fn segmented_field_spans(&self, input: &str) -> Vec<crate::FieldSpan> {
         use crate::{Field, FieldSpan};
 
-        let mp = MeasurementParser::new(&self.units, MeasurementMode::IngredientList);
+        let mp = self.measurement_parser(MeasurementMode::IngredientList);
         let Ok((rest, _)) = (
             opt(|a| mp.parse_measurement_list(a)),
             space0,
@@ -590,8 +593,16 @@ impl IngredientParser {
             return Vec::new();
         };
 
-        // `rest` is a suffix slice of `input`; offset clause/carve ranges by it.
-        let base = rest.as_ptr() as usize - input.as_ptr() as usize;
+        // `rest` is a suffix of `input`; offset clause/carve ranges by where it
+        // starts. Located by length, not pointer: a measurement parser can
+        // return a remainder it didn't slice from `input` (as for "0T").
+        let Some(base) = input
+            .len()
+            .checked_sub(rest.len())
+            .filter(|&b| input.get(b..) == Some(rest))
+        else {
+            return Vec::new();
+        };
         let span_of = |range: Range<usize>, field: Field| -> Option<FieldSpan> {
             let slice = &input[range.clone()];
             let trimmed = slice.trim();
@@ -659,6 +670,37 @@ fn trace_clauses(source: &str, clauses: &[Clause<'_>]) {
     }
 }
 
+impl IngredientParser {
+    /// The clause kinds and top-level paren kinds of the post-amount text, in
+    /// source order, by their stable labels — what [`LineShape`](crate::shape::LineShape)
+    /// records. Empty when the amounts grammar fails outright.
+    pub(crate) fn clause_shape(&self, input: &str) -> (Vec<&'static str>, Vec<&'static str>) {
+        let mp = self.measurement_parser(MeasurementMode::IngredientList);
+        let Ok((rest, _)) = (
+            opt(|a| mp.parse_measurement_list(a)),
+            space0,
+            opt(|a| mp.parse_bracketed_amounts(a)),
+            space0,
+        )
+            .parse(input)
+        else {
+            return (Vec::new(), Vec::new());
+        };
+        let mut clauses = Vec::new();
+        let mut parens = Vec::new();
+        for clause in self.segmenter().segment(rest) {
+            if clause.text(rest).trim().is_empty() {
+                continue;
+            }
+            if !clause.stripped.is_empty() {
+                clauses.push(clause.kind.as_str());
+            }
+            parens.extend(clause.parens.iter().map(|p| paren_kind_label(p.kind)));
+        }
+        (clauses, parens)
+    }
+}
+
 /// Stable lowercase label for a [`ParenKind`] (trace preview text).
 fn paren_kind_label(kind: ParenKind) -> &'static str {
     match kind {
@@ -888,7 +930,7 @@ mod tests {
 
     /// The pre-repair IR, so a test can replay the repairs in any order.
     fn unrepaired(parser: &IngredientParser, line: &str) -> ParsedIngredient {
-        let mp = MeasurementParser::new(&parser.units, MeasurementMode::IngredientList);
+        let mp = parser.measurement_parser(MeasurementMode::IngredientList);
         let (rest, (primary, _, bracketed, _)) = (
             opt(|a| mp.parse_measurement_list(a)),
             space0,
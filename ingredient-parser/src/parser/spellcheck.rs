@@ -0,0 +1,324 @@
+//! Opt-in typo and OCR correction for the amount part of a line
+//! ([`IngredientParser::with_spellcheck`](crate::IngredientParser::with_spellcheck)).
+//!
+//! Scanned cookbooks and hand-typed lines misspell units ("2 tablspoon
+//! sugar"), glue them to the number ("1/2tablspoon", "l/2c"), confuse glyphs
+//! ("l cup milk", "1% cups flour" for "1½") and misspell the name ("2 cups
+//! flower"). The exact lookups read those as a count and a name, so these
+//! corrections run after the [`normalize`](super::normalize) rewrites and
+//! before the grammar. Each one that fires is traced like a normalize rewrite,
+//! so the stage report shows exactly what was changed.
+//!
+//! Corrections only touch the amount and the word right after it: a glyph fix
+//! needs a unit right after the token, a unit fix needs a number right before
+//! it, and a name fix needs an amount before it and nothing after it but
+//! punctuation, so "2% milk", "2 pinto beans" and "1 cup flower petals" are
+//! left alone. Name fixes come from a short list of known misspellings, not
+//! edit distance: there is no dictionary of ingredient names to measure
+//! against. Being opt-in, these labels are not part of
+//! [`pipeline_stage_names`](crate::trace::pipeline_stage_names).
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use super::vocab::COMMON_NAME_MISSPELLINGS;
+use crate::unit::{self, singular};
+
+/// A correction: the line, and the parser's addon units.
+type Correction = for<'a> fn(&'a str, &HashSet<String>) -> Cow<'a, str>;
+
+crate::define_stage_pipeline! {
+    enum CorrectionId,
+    struct CorrectionEntry,
+    const CORRECTIONS: &[CorrectionEntry],
+    type Correction = Correction,
+    trace: none,
+    (SplitGluedUnit, "split_glued_unit", split_glued_unit),
+    (FixOcrDigits, "fix_ocr_digits", fix_ocr_digits),
+    (FixOcrHalf, "fix_ocr_half", fix_ocr_half),
+    (CorrectUnitSpelling, "correct_unit_spelling", correct_unit_spelling),
+    (CorrectNameSpelling, "correct_name_spelling", correct_name_spelling),
+}
+
+/// Run every correction on a normalized line, tracing the ones that change it.
+pub(crate) fn correct_line<'a>(line: Cow<'a, str>, units: &HashSet<String>) -> Cow<'a, str> {
+    let mut line = line;
+    for entry in CORRECTIONS {
+        let CorrectionEntry { run, .. } = *entry;
+        if let Cow::Owned(corrected) = run(line.as_ref(), units) {
+            crate::trace::trace_on_change(entry.id().as_str(), line.as_ref(), &corrected, true);
+            line = Cow::Owned(corrected);
+        }
+    }
+    line
+}
+
+/// "1/2tablspoon sugar" → "1/2 tablspoon sugar", "l/2c flour" → "l/2 c
+/// flour": a leading quantity with a unit glued on, split so the corrections
+/// after this one see the two apart. Only when one of them needs correcting;
+/// "1/2c" and "100g" already parse.
+fn split_glued_unit<'a>(line: &'a str, units: &HashSet<String>) -> Cow<'a, str> {
+    let Some(first) = line.split_whitespace().next() else {
+        return Cow::Borrowed(line);
+    };
+    let is_amount_char =
+        |c: char| c.is_ascii_digit() || crate::fraction::is_vulgar(c) || matches!(c, '/' | '.');
+    let is_ocr_char = |c: char| matches!(c, 'l' | 'I' | 'O' | 'o' | '%');
+    // Try the longest alphabetic tail first: "1Og" is "1O" + "g", not "1" + "Og".
+    for (at, _) in first.char_indices().skip(1) {
+        let (head, tail) = first.split_at(at);
+        if !tail.chars().all(char::is_alphabetic)
+            || !head.chars().all(|c| is_amount_char(c) || is_ocr_char(c))
+            || !head
+                .chars()
+                .any(|c| c.is_ascii_digit() || crate::fraction::is_vulgar(c))
+        {
+            continue;
+        }
+        let ocr = head.chars().any(is_ocr_char);
+        let valid = unit::is_valid(units, tail);
+        if (valid && ocr) || (!valid && closest_unit(tail, units).is_some()) {
+            return Cow::Owned(format!("{head} {tail}{}", &line[first.len()..]));
+        }
+    }
+    Cow::Borrowed(line)
+}
+
+/// "l cup milk" → "1 cup milk", "1O0 g butter" → "100 g butter": a leading
+/// quantity with "l"/"I" read for 1 and "O"/"o" for 0, when a unit follows.
+fn fix_ocr_digits<'a>(line: &'a str, units: &HashSet<String>) -> Cow<'a, str> {
+    let Some((first, next)) = leading_pair(line) else {
+        return Cow::Borrowed(line);
+    };
+    let fixable = first.chars().any(|c| matches!(c, 'l' | 'I' | 'O' | 'o'))
+        && first
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, 'l' | 'I' | 'O' | 'o' | '/' | '.'));
+    if !fixable || !is_unit_like(next, units) {
+        return Cow::Borrowed(line);
+    }
+    let fixed: String = first
+        .chars()
+        .map(|c| match c {
+            'l' | 'I' => '1',
+            'O' | 'o' => '0',
+            c => c,
+        })
+        .collect();
+    if !fixed.starts_with(|c: char| c.is_ascii_digit()) {
+        return Cow::Borrowed(line);
+    }
+    Cow::Owned(format!("{fixed}{}", &line[first.len()..]))
+}
+
+/// "% cup sugar" → "½ cup sugar", "1% cups flour" → "1½ cups flour": OCR's
+/// usual reading of the ½ glyph, when a unit follows.
+fn fix_ocr_half<'a>(line: &'a str, units: &HashSet<String>) -> Cow<'a, str> {
+    let Some((first, next)) = leading_pair(line) else {
+        return Cow::Borrowed(line);
+    };
+    let Some(whole) = first.strip_suffix('%') else {
+        return Cow::Borrowed(line);
+    };
+    if !whole.chars().all(|c| c.is_ascii_digit()) || !is_unit_like(next, units) {
+        return Cow::Borrowed(line);
+    }
+    Cow::Owned(format!("{whole}½{}", &line[first.len()..]))
+}
+
+/// "2 tablspoon sugar" → "2 tablespoon sugar": a word after a number that
+/// isn't a unit but is a small edit away from exactly one.
+fn correct_unit_spelling<'a>(line: &'a str, units: &HashSet<String>) -> Cow<'a, str> {
+    let mut out = String::new();
+    let mut at = 0;
+    let mut prev_is_number = false;
+    for m in TOKEN.find_iter(line) {
+        let token = m.as_str();
+        let word = token.trim_end_matches(['.', ',', ';', ':', ')']);
+        if prev_is_number
+            && !unit::is_valid(units, word)
+            && let Some(spelling) = closest_unit(word, units)
+        {
+            out.push_str(&line[at..m.start()]);
+            out.push_str(spelling);
+            at = m.start() + word.len();
+        }
+        prev_is_number = token
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_digit() || crate::fraction::is_vulgar(c));
+    }
+    if at == 0 {
+        return Cow::Borrowed(line);
+    }
+    out.push_str(&line[at..]);
+    Cow::Owned(out)
+}
+
+/// "2 cups flower" → "2 cups flour": a name from
+/// [`COMMON_NAME_MISSPELLINGS`] right after a number and unit, with nothing
+/// but punctuation or a parenthetical after it.
+fn correct_name_spelling<'a>(line: &'a str, units: &HashSet<String>) -> Cow<'a, str> {
+    let tokens: Vec<_> = TOKEN.find_iter(line).collect();
+    let [number, unit_token, name, rest @ ..] = tokens.as_slice() else {
+        return Cow::Borrowed(line);
+    };
+    let starts_with_number = number
+        .as_str()
+        .starts_with(|c: char| c.is_ascii_digit() || crate::fraction::is_vulgar(c));
+    let unit_word = unit_token.as_str().trim_end_matches(['.', ',']);
+    let word = name.as_str().trim_end_matches(['.', ',', ';', ':']);
+    let ends_name = word.len() < name.as_str().len()
+        || rest.first().is_none_or(|t| t.as_str().starts_with('('));
+    if !starts_with_number || !unit::is_valid(units, unit_word) || !ends_name {
+        return Cow::Borrowed(line);
+    }
+    let lower = word.to_lowercase();
+    let Some((_, meant)) = COMMON_NAME_MISSPELLINGS
+        .iter()
+        .find(|(typo, _)| *typo == lower)
+    else {
+        return Cow::Borrowed(line);
+    };
+    Cow::Owned(format!(
+        "{}{meant}{}",
+        &line[..name.start()],
+        &line[name.start() + word.len()..]
+    ))
+}
+
+crate::lazy_regex!(TOKEN, r"\S+");
+
+/// The first two whitespace-separated tokens, the second without trailing
+/// punctuation ("tbsp." → "tbsp").
+fn leading_pair(line: &str) -> Option<(&str, &str)> {
+    let mut tokens = line.split_whitespace();
+    let first = tokens.next()?;
+    let next = tokens.next()?.trim_end_matches(['.', ',']);
+    Some((first, next))
+}
+
+/// A unit as written, or one [`closest_unit`] would correct it to.
+fn is_unit_like(word: &str, units: &HashSet<String>) -> bool {
+    unit::is_valid(units, word) || closest_unit(word, units).is_some()
+}
+
+/// The unit spelling `word` is most likely a typo of, if exactly one unit is
+/// closest and close enough: one edit for words of 6–8 letters, two from 9.
+/// Shorter words are never corrected ("pinto" is not "pint").
+fn closest_unit<'u>(word: &str, units: &'u HashSet<String>) -> Option<&'u str> {
+    let len = word.chars().count();
+    let max = match len {
+        0..6 => return None,
+        6..9 => 1,
+        _ => 2,
+    };
+    let word = word.to_lowercase();
+    let mut best: Option<(usize, &str)> = None;
+    let mut tied = false;
+    let builtin = unit::spellings().map(|s| -> &'u str { s });
+    for candidate in builtin.chain(units.iter().map(String::as_str)) {
+        if candidate.len() < 4 || candidate.contains(' ') {
+            continue;
+        }
+        let d = edit_distance(&word, candidate);
+        match best {
+            _ if d > max => {}
+            Some((b, c)) if d == b => tied |= singular(c) != singular(candidate),
+            Some((b, _)) if d > b => {}
+            _ => {
+                best = Some((d, candidate));
+                tied = false;
+            }
+        }
+    }
+    best.filter(|_| !tied).map(|(_, spelling)| spelling)
+}
+
+/// Levenshtein distance over chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let next = (row[j + 1] + 1)
+                .min(row[j] + 1)
+                .min(diag + usize::from(ca != *cb));
+            diag = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use rstest::rstest;
+
+    fn correct(line: &str) -> String {
+        correct_line(Cow::Borrowed(line), &HashSet::new()).into_owned()
+    }
+
+    #[rstest]
+    #[case::tablespoon("2 tablspoon sugar", "2 tablespoon sugar")]
+    #[case::teaspoon("1 teaspon salt", "1 teaspoon salt")]
+    #[case::plural("2 tablepoons butter", "2 tablespoons butter")]
+    #[case::punctuation("1 teaspon, heaped", "1 teaspoon, heaped")]
+    #[case::ocr_one("l cup milk", "1 cup milk")]
+    #[case::ocr_capital_i("I cup rice", "1 cup rice")]
+    #[case::ocr_zero("1O0 g butter", "100 g butter")]
+    #[case::ocr_half("% cup sugar", "½ cup sugar")]
+    #[case::ocr_mixed_half("1% cups flour", "1½ cups flour")]
+    #[case::ocr_and_typo("l tablspoon oil", "1 tablespoon oil")]
+    #[case::glued_typo("1/2tablspoon sugar", "1/2 tablespoon sugar")]
+    #[case::glued_ocr("l/2c flour", "1/2 c flour")]
+    #[case::glued_ocr_half("1%c sugar", "1½ c sugar")]
+    #[case::glued_ocr_zero("1Og butter", "10 g butter")]
+    #[case::name("2 cups flower", "2 cups flour")]
+    #[case::name_before_comma("1 cup flower, sifted", "1 cup flour, sifted")]
+    #[case::name_before_paren("1 cup suger (packed)", "1 cup sugar (packed)")]
+    #[case::name_and_unit("2 tablspoon suger", "2 tablespoon sugar")]
+    fn corrects(#[case] line: &str, #[case] want: &str) {
+        assert_eq!(correct(line), want);
+    }
+
+    #[rstest]
+    #[case::percent_milk("2% milk")]
+    #[case::short_word("2 pinto beans")]
+    #[case::not_after_number("tablspoon of sugar")]
+    #[case::real_unit("2 cups flour")]
+    #[case::name_letter("l'oignon")]
+    #[case::plain_word("lo mein noodles")]
+    #[case::glued_valid("1/2c flour")]
+    #[case::glued_weight("100g butter")]
+    #[case::ordinal("2nd batch")]
+    #[case::name_goes_on("1 cup flower petals")]
+    #[case::name_without_unit("2 flower pots")]
+    #[case::name_without_amount("cups flower")]
+    fn leaves_alone(#[case] line: &str) {
+        assert_eq!(correct(line), line);
+    }
+
+    #[test]
+    fn corrects_to_addon_units() {
+        let units: HashSet<String> = ["handful".to_string()].into();
+        let got = correct_line(Cow::Borrowed("2 handfull basil"), &units);
+        assert_eq!(got, "2 handful basil");
+    }
+
+    #[test]
+    fn corrections_table_is_well_formed() {
+        crate::assert_stage_pipeline!(CORRECTIONS);
+    }
+
+    #[test]
+    fn edit_distance_counts_single_char_edits() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("teaspon", "teaspoon"), 1);
+        assert_eq!(edit_distance("", "cup"), 3);
+    }
+}
@@ -5,22 +5,76 @@
 
 /// Declare a lazy-compiled regex. Pass a string literal pattern, or a block
 /// expression that evaluates to a `regex::Regex` (for patterns built with `format!`).
+/// The static is a `LazyRegex`, so a [pass profile](crate::trace::perf) can
+/// count its compile and its matches.
 #[macro_export]
 macro_rules! lazy_regex {
-    ($name:ident, $pat:literal) => {
-        static $name: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::new(|| {
-            #[allow(clippy::expect_used)]
-            regex::Regex::new($pat).expect(concat!("invalid regex: ", $pat))
-        });
+    ($(#[$meta:meta])* $vis:vis $name:ident, $pat:literal) => {
+        $(#[$meta])*
+        $vis static $name: $crate::parser::stage::LazyRegex = $crate::parser::stage::LazyRegex::new(
+            concat!(module_path!(), "::", stringify!($name)),
+            std::sync::LazyLock::new(|| {
+                $crate::parser::stage::LazyRegex::compile(
+                    concat!(module_path!(), "::", stringify!($name)),
+                    || {
+                        #[allow(clippy::expect_used)]
+                        regex::Regex::new($pat).expect(concat!("invalid regex: ", $pat))
+                    },
+                )
+            }),
+        );
     };
-    ($name:ident, { $($body:tt)* }) => {
-        static $name: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::new(|| {
-            #[allow(clippy::expect_used)]
-            { $($body)* }
-        });
+    ($(#[$meta:meta])* $vis:vis $name:ident, { $($body:tt)* }) => {
+        $(#[$meta])*
+        $vis static $name: $crate::parser::stage::LazyRegex = $crate::parser::stage::LazyRegex::new(
+            concat!(module_path!(), "::", stringify!($name)),
+            std::sync::LazyLock::new(|| {
+                $crate::parser::stage::LazyRegex::compile(
+                    concat!(module_path!(), "::", stringify!($name)),
+                    || {
+                        #[allow(clippy::expect_used)]
+                        { $($body)* }
+                    },
+                )
+            }),
+        );
     };
 }
 
+/// A regex compiled on first use, declared with
+/// [`lazy_regex!`](crate::lazy_regex). Derefs to the [`regex::Regex`]; each
+/// deref counts as one match in a running [pass profile](crate::trace::perf).
+pub(crate) struct LazyRegex {
+    name: &'static str,
+    regex: std::sync::LazyLock<regex::Regex>,
+}
+
+impl LazyRegex {
+    pub(crate) const fn new(name: &'static str, regex: std::sync::LazyLock<regex::Regex>) -> Self {
+        Self { name, regex }
+    }
+
+    /// Build the regex, recording the compile in a running profile.
+    pub(crate) fn compile(
+        name: &'static str,
+        build: impl FnOnce() -> regex::Regex,
+    ) -> regex::Regex {
+        let started = std::time::Instant::now();
+        let regex = build();
+        crate::trace::perf::regex_compiled(name, started.elapsed());
+        regex
+    }
+}
+
+impl std::ops::Deref for LazyRegex {
+    type Target = regex::Regex;
+
+    fn deref(&self) -> &regex::Regex {
+        crate::trace::perf::regex_used(self.name);
+        &self.regex
+    }
+}
+
 /// From one row list, generate Id + as_str + Entry + TABLE. Each row is
 /// `(Variant, "trace_label", run_fn)`. Set `trace: none` or
 /// `trace: pub(crate) TRACE_NAMES` to optionally emit a label slice for tracing.
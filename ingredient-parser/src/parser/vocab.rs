@@ -32,6 +32,41 @@ pub(crate) const NUMBER_WORDS: &[(&str, f64)] = &[
     ("half", 0.5),
 ];
 
+/// Multi-word counting phrases parsed as amounts ("a couple of eggs" → 2,
+/// "a half-dozen eggs" → 6). Tried before [`NUMBER_WORDS`] and the bare
+/// articles, so "a half-dozen" isn't read as "a" (1) followed by a name; longest
+/// first, so "a couple of" swallows its "of". A digit count scales by a
+/// following "dozen" separately ("2 dozen" → 24, in `measurement::number`).
+pub(crate) const COUNTING_PHRASES: &[(&str, f64)] = &[
+    ("a half-dozen", 6.0),
+    ("a half dozen", 6.0),
+    ("half a dozen", 6.0),
+    ("half-dozen", 6.0),
+    ("half dozen", 6.0),
+    ("a couple of", 2.0),
+    ("a couple", 2.0),
+    ("couple of", 2.0),
+    ("a dozen", 12.0),
+];
+
+/// Counts that only gesture at a number ("a few sprigs"). Read as the number
+/// given and marked [`Qualifier::Approximate`](crate::unit::Qualifier).
+pub(crate) const VAGUE_COUNTS: &[(&str, f64)] = &[("a few", 3.0)];
+
+/// Ingredient names commonly misspelled or swapped for a homophone, and what
+/// was meant ("2 cups flower"). Corrected only by the opt-in spellcheck, and
+/// only as the whole name right after an amount, so "1 cup flower petals" is
+/// left alone. Consumed by `spellcheck::correct_name_spelling`.
+pub(crate) const COMMON_NAME_MISSPELLINGS: &[(&str, &str)] = &[
+    ("flower", "flour"),
+    ("suger", "sugar"),
+    ("buter", "butter"),
+    ("vanila", "vanilla"),
+    ("cinamon", "cinnamon"),
+    ("tumeric", "turmeric"),
+    ("parmesean", "parmesan"),
+];
+
 /// Stopwords that signal a modifier clause is prose, not a shared head noun. Union
 /// of the lists used in `refine::recover` and `refine::alternatives`.
 pub(crate) const MODIFIER_STOPWORDS: &[&str] = &[
@@ -167,6 +202,72 @@ pub(crate) const MARINADE_PHRASES: &[&str] = &["for the marinade", "for marinati
 /// "For the marinade", "Brine").
 pub(crate) const MARINADE_SECTION_WORDS: &[&str] = &["marinade", "marinating", "brine"];
 
+/// Recipe components an ingredient-list header names on its own ("Sauce",
+/// "FILLING", "Cream Cheese Frosting"). A capitalized line of at most three
+/// words ending in one of these is a section header, not an ingredient.
+pub(crate) const SECTION_HEADER_WORDS: &[&str] = &[
+    "assembly",
+    "base",
+    "batter",
+    "brine",
+    "crumble",
+    "crust",
+    "custard",
+    "dough",
+    "dressing",
+    "filling",
+    "frosting",
+    "ganache",
+    "garnish",
+    "garnishes",
+    "glaze",
+    "icing",
+    "marinade",
+    "pastry",
+    "rub",
+    "sauce",
+    "streusel",
+    "syrup",
+    "topping",
+    "toppings",
+];
+
+/// Verbs that make "To serve" / "To finish" a section header.
+pub(crate) const SECTION_HEADER_VERBS: &[&str] =
+    &["assemble", "decorate", "finish", "garnish", "serve"];
+
+/// Whole-line headings in a pasted list that are neither an ingredient nor a
+/// recipe component: the list's own title and the recipe's other parts.
+pub(crate) const NON_INGREDIENT_HEADINGS: &[&str] = &[
+    "directions",
+    "equipment",
+    "ingredient list",
+    "ingredients",
+    "instructions",
+    "method",
+    "notes",
+    "preparation",
+    "steps",
+    "you will need",
+    "you'll need",
+];
+
+/// Leading words of yield and timing lines ("Serves 4", "Prep time: 10 min").
+pub(crate) const NON_INGREDIENT_PREFIXES: &[&str] = &[
+    "active time",
+    "cook time",
+    "makes",
+    "prep time",
+    "serves",
+    "servings",
+    "total time",
+    "yield",
+    "yields",
+];
+
+/// Leading words of a note line ("Note: any short pasta works").
+pub(crate) const NOTE_PREFIXES: &[&str] = &["cook's note", "note", "notes", "tip", "tips"];
+
 /// Non-standard units that aren't really convertible, seeded into the parser's
 /// unit set. Note: "whole" is deliberately NOT included — it's the built-in
 /// `Unit::Whole`, and listing it here would parse "whole wheat flour" as having
@@ -504,6 +605,8 @@ pub(crate) const CONTAINER_NOUNS: &[&str] = &[
     "tubes",
     "envelope",
     "envelopes",
+    "tin",
+    "tins",
 ];
 
 /// Clause boundaries that end a recovered head noun. When
@@ -745,6 +848,11 @@ mod tests {
             ("SEASONING_PHRASES", SEASONING_PHRASES),
             ("MARINADE_PHRASES", MARINADE_PHRASES),
             ("MARINADE_SECTION_WORDS", MARINADE_SECTION_WORDS),
+            ("SECTION_HEADER_WORDS", SECTION_HEADER_WORDS),
+            ("SECTION_HEADER_VERBS", SECTION_HEADER_VERBS),
+            ("NON_INGREDIENT_HEADINGS", NON_INGREDIENT_HEADINGS),
+            ("NON_INGREDIENT_PREFIXES", NON_INGREDIENT_PREFIXES),
+            ("NOTE_PREFIXES", NOTE_PREFIXES),
             ("NON_STANDARD_UNITS", NON_STANDARD_UNITS),
             ("VAGUE_UNITS", VAGUE_UNITS),
             ("SIZE_QUALIFIABLE_UNITS", SIZE_QUALIFIABLE_UNITS),
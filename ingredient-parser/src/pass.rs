@@ -0,0 +1,369 @@
+//! House-style passes plugged into an [`IngredientParser`]'s pipeline.
+//!
+//! The normalize rewrites, whole-line recognizers and refine passes are fixed
+//! tables (see `parser/stage.rs`). A [`CustomPass`] adds one more to a
+//! parser's pipeline, next to a built-in pass of the stage it joins: a rewrite
+//! for a CMS that writes "2c.", a recognizer for a house line shape, a refine
+//! pass that turns an "[opt]" tag into [`Ingredient::optional`].
+//!
+//! Registered passes are traced like built-ins (they appear in
+//! [`StageReport`](crate::trace::StageReport)s and `--explain`), profiled by
+//! [`IngredientParser::profile_passes`], and listed by
+//! [`IngredientParser::pipeline_stage_names`], so coverage tooling counts them.
+//!
+//! ```
+//! use std::borrow::Cow;
+//! use ingredient::IngredientParser;
+//! use ingredient::pass::{CustomPass, Position, Refining};
+//!
+//! /// "2c. flour" → "2 cups flour"
+//! struct CmsCups;
+//!
+//! impl CustomPass for CmsCups {
+//!     fn name(&self) -> &'static str {
+//!         "expand_cms_cups"
+//!     }
+//!     fn position(&self) -> Position {
+//!         Position::After("strip_nbsp")
+//!     }
+//!     fn rewrite<'a>(&self, line: &'a str) -> Cow<'a, str> {
+//!         match line.split_once("c. ") {
+//!             Some((n, rest)) if n.chars().all(|c| c.is_ascii_digit()) => {
+//!                 Cow::Owned(format!("{n} cups {rest}"))
+//!             }
+//!             _ => Cow::Borrowed(line),
+//!         }
+//!     }
+//! }
+//!
+//! /// A trailing "[opt]" tag marks the ingredient optional.
+//! struct CmsOptional;
+//!
+//! impl CustomPass for CmsOptional {
+//!     fn name(&self) -> &'static str {
+//!         "cms_optional_tag"
+//!     }
+//!     fn position(&self) -> Position {
+//!         Position::Before("extract_adjectives_from_name")
+//!     }
+//!     fn refine(&self, ingredient: &mut Refining<'_>) {
+//!         if ingredient.modifier().as_deref() == Some("[opt]") {
+//!             ingredient.set_modifier(None);
+//!             ingredient.set_optional(true);
+//!         }
+//!     }
+//! }
+//!
+//! let parser = IngredientParser::new()
+//!     .with_pass(CmsCups)?
+//!     .with_pass(CmsOptional)?;
+//! let capers = parser.from_str("2c. capers [opt]");
+//! assert_eq!(capers.to_string(), "2 cups capers (optional)");
+//! assert!(capers.optional);
+//!
+//! let stages = parser.parse_with_trace("2c. capers [opt]").trace.stages();
+//! assert_eq!(stages.normalize[0].name, "expand_cms_cups");
+//! assert!(stages.refine.iter().any(|r| r.name == "cms_optional_tag"));
+//! # Ok::<(), ingredient::IngredientError>(())
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::error::{IngredientError, IngredientResult};
+use crate::parser::ir::{ModifierPart, ParsedIngredient};
+use crate::parser::normalize::REWRITE_TRACE_NAMES;
+use crate::parser::recognize::RECOGNIZER_TRACE_NAMES;
+use crate::parser::refine::REFINE_TRACE_NAMES;
+use crate::trace::PipelineStageNames;
+use crate::unit::Measure;
+use crate::{Ingredient, IngredientParser};
+
+/// The stage a custom pass joins, set by the built-in pass it is
+/// [positioned](Position) next to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    /// A rewrite of the raw line, before anything parses it.
+    Normalize,
+    /// A whole-line recognizer: claims the line outright, or passes.
+    Recognize,
+    /// A pass over the parsed name, amounts and modifier.
+    Refine,
+}
+
+impl Stage {
+    /// The stage's name in traces and profiles: `normalize`, `recognize` or
+    /// `refine`.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Stage::Normalize => "normalize",
+            Stage::Recognize => "recognize",
+            Stage::Refine => "refine",
+        }
+    }
+}
+
+/// Where a custom pass runs: just before or just after a built-in pass, named
+/// by its trace label (see [`crate::trace::pipeline_stage_names`]). Passes
+/// registered at the same spot run in registration order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Position {
+    Before(&'static str),
+    After(&'static str),
+}
+
+impl Position {
+    /// The built-in pass this position is relative to.
+    pub const fn anchor(self) -> &'static str {
+        match self {
+            Position::Before(label) | Position::After(label) => label,
+        }
+    }
+
+    /// The stage of the anchor; `None` when no normalize rewrite, recognizer
+    /// or refine pass has that label.
+    pub fn stage(self) -> Option<Stage> {
+        let anchor = self.anchor();
+        [
+            (REWRITE_TRACE_NAMES, Stage::Normalize),
+            (RECOGNIZER_TRACE_NAMES, Stage::Recognize),
+            (REFINE_TRACE_NAMES, Stage::Refine),
+        ]
+        .into_iter()
+        .find_map(|(labels, stage)| labels.contains(&anchor).then_some(stage))
+    }
+}
+
+/// A pass registered with [`IngredientParser::with_pass`].
+///
+/// Only the hook for the pass's [stage](Position::stage) is called; the
+/// others keep their do-nothing defaults.
+pub trait CustomPass: Send + Sync + 'static {
+    /// The pass's label in traces, profiles and
+    /// [`pipeline_stage_names`](IngredientParser::pipeline_stage_names).
+    /// Must not collide with a built-in or another registered pass.
+    fn name(&self) -> &'static str;
+
+    /// Which built-in pass it runs next to.
+    fn position(&self) -> Position;
+
+    /// [`Stage::Normalize`]: rewrite the line. Return a borrow when nothing
+    /// changes.
+    fn rewrite<'a>(&self, line: &'a str) -> Cow<'a, str> {
+        Cow::Borrowed(line)
+    }
+
+    /// [`Stage::Recognize`]: the finished ingredient when the line has this
+    /// recognizer's shape, else `None` to let the pipeline continue. `parser`
+    /// can parse the parts of the line.
+    fn recognize(&self, _parser: &IngredientParser, _line: &str) -> Option<Ingredient> {
+        None
+    }
+
+    /// [`Stage::Refine`]: adjust the parsed ingredient in place.
+    fn refine(&self, _ingredient: &mut Refining<'_>) {}
+}
+
+/// A refine pass's view of the ingredient being parsed.
+pub struct Refining<'a> {
+    parsed: &'a mut ParsedIngredient,
+}
+
+impl<'a> Refining<'a> {
+    pub(crate) fn new(parsed: &'a mut ParsedIngredient) -> Self {
+        Self { parsed }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.parsed.name
+    }
+
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.parsed.name = name.into();
+    }
+
+    pub fn amounts(&self) -> &[Measure] {
+        &self.parsed.amounts
+    }
+
+    pub fn amounts_mut(&mut self) -> &mut Vec<Measure> {
+        &mut self.parsed.amounts
+    }
+
+    /// The modifier so far, joined as it will be on the [`Ingredient`].
+    pub fn modifier(&self) -> Option<String> {
+        self.parsed.modifier_string()
+    }
+
+    /// Replace the modifier with `text`, or clear it.
+    pub fn set_modifier(&mut self, text: Option<String>) {
+        self.parsed.modifier = text.map(ModifierPart::Raw).into_iter().collect();
+    }
+
+    /// Append `text` to the modifier.
+    pub fn push_modifier(&mut self, text: impl Into<String>) {
+        self.parsed.modifier.push(ModifierPart::Raw(text.into()));
+    }
+
+    pub fn optional(&self) -> bool {
+        self.parsed.optional
+    }
+
+    pub fn set_optional(&mut self, optional: bool) {
+        self.parsed.optional = optional;
+    }
+}
+
+/// One registered pass and the stage its position resolved to.
+#[derive(Clone)]
+struct Registered {
+    stage: Stage,
+    position: Position,
+    pass: Arc<dyn CustomPass>,
+}
+
+/// A parser's registered passes, in registration order. Shared, since a parser
+/// is cloned freely.
+#[derive(Clone, Default)]
+pub(crate) struct CustomPasses {
+    passes: Arc<Vec<Registered>>,
+}
+
+impl CustomPasses {
+    fn at(&self, position: Position) -> impl Iterator<Item = &dyn CustomPass> {
+        self.passes
+            .iter()
+            .filter(move |r| r.position == position)
+            .map(|r| r.pass.as_ref())
+    }
+
+    /// The passes to run just before the built-in `label`.
+    pub(crate) fn before(&self, label: &'static str) -> impl Iterator<Item = &dyn CustomPass> {
+        self.at(Position::Before(label))
+    }
+
+    /// The passes to run just after the built-in `label`.
+    pub(crate) fn after(&self, label: &'static str) -> impl Iterator<Item = &dyn CustomPass> {
+        self.at(Position::After(label))
+    }
+
+    /// `builtin` (a stage's labels in order) with this stage's passes merged
+    /// in where they run.
+    fn merged(&self, stage: Stage, builtin: &[&'static str]) -> Vec<&'static str> {
+        let mut names = Vec::with_capacity(builtin.len());
+        for &label in builtin {
+            names.extend(self.before(label).map(CustomPass::name));
+            names.push(label);
+            names.extend(self.after(label).map(CustomPass::name));
+        }
+        debug_assert!(
+            self.passes
+                .iter()
+                .filter(|r| r.stage == stage)
+                .all(|r| names.contains(&r.pass.name()))
+        );
+        names
+    }
+
+    /// The registered recognizer called `name`.
+    pub(crate) fn named(&self, name: &str) -> Option<&dyn CustomPass> {
+        self.passes
+            .iter()
+            .find(|r| r.stage == Stage::Recognize && r.pass.name() == name)
+            .map(|r| r.pass.as_ref())
+    }
+
+    /// The registered recognizers' names, for bucketing a trace's stages.
+    pub(crate) fn recognizer_names(&self) -> Vec<String> {
+        self.passes
+            .iter()
+            .filter(|r| r.stage == Stage::Recognize)
+            .map(|r| r.pass.name().to_string())
+            .collect()
+    }
+}
+
+impl fmt::Debug for CustomPasses {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.passes.iter().map(|r| (r.pass.name(), r.position)))
+            .finish()
+    }
+}
+
+/// Passes compare by name and position: the pass itself is opaque.
+impl PartialEq for CustomPasses {
+    fn eq(&self, other: &Self) -> bool {
+        self.passes.len() == other.passes.len()
+            && self
+                .passes
+                .iter()
+                .zip(other.passes.iter())
+                .all(|(a, b)| a.pass.name() == b.pass.name() && a.position == b.position)
+    }
+}
+
+impl IngredientParser {
+    /// Register a [`CustomPass`] (chainable). Fails when the pass's position
+    /// names no normalize rewrite, recognizer or refine pass, or its name is
+    /// empty or already taken by a built-in or registered pass. See the
+    /// [module docs](crate::pass) for an example.
+    pub fn with_pass(mut self, pass: impl CustomPass) -> IngredientResult<Self> {
+        let name = pass.name();
+        let position = pass.position();
+        let fail = |reason: String| IngredientError::PassError {
+            name: name.to_string(),
+            reason,
+        };
+        let Some(stage) = position.stage() else {
+            return Err(fail(format!(
+                "{:?} is not a normalize rewrite, recognizer or refine pass",
+                position.anchor()
+            )));
+        };
+        if name.is_empty() {
+            return Err(fail("empty name".to_string()));
+        }
+        let names = self.pipeline_stage_names();
+        let taken = [
+            &names.normalize,
+            &names.recognizers,
+            &names.segment,
+            &names.refine,
+        ]
+        .into_iter()
+        .any(|labels| labels.contains(&name));
+        if taken {
+            return Err(fail(
+                "a pass with this name is already in the pipeline".to_string(),
+            ));
+        }
+        Arc::make_mut(&mut self.passes.passes).push(Registered {
+            stage,
+            position,
+            pass: Arc::new(pass),
+        });
+        Ok(self)
+    }
+
+    /// [`crate::trace::pipeline_stage_names`] for this parser: the built-in
+    /// labels with its [registered passes](Self::with_pass) merged in, in the
+    /// order they run.
+    ///
+    /// ```
+    /// use ingredient::IngredientParser;
+    ///
+    /// let names = IngredientParser::new().pipeline_stage_names();
+    /// assert_eq!(names, ingredient::trace::pipeline_stage_names());
+    /// ```
+    pub fn pipeline_stage_names(&self) -> PipelineStageNames {
+        let builtin = crate::trace::pipeline_stage_names();
+        PipelineStageNames {
+            normalize: self.passes.merged(Stage::Normalize, &builtin.normalize),
+            recognizers: self.passes.merged(Stage::Recognize, &builtin.recognizers),
+            segment: builtin.segment,
+            refine: self.passes.merged(Stage::Refine, &builtin.refine),
+        }
+    }
+}
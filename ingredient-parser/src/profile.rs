@@ -0,0 +1,503 @@
+//! Loadable parser vocabulary.
+//!
+//! Every word list that steers [`IngredientParser`](crate::IngredientParser) —
+//! the extra units, the preparation adjectives and purpose phrases moved into
+//! the modifier, the postfix produce units ("garlic clove"), the size words
+//! consumed as count units, the container nouns after a parenthesized size, the
+//! shared head nouns of "X, Y, or Z oil" lists, and the usage-classification
+//! phrases — lives in a [`ParserProfile`]. The built-in lists (`parser::vocab`)
+//! are simply the [`Default`] profile; a domain that needs more or fewer
+//! entries loads its own with [`ParserProfile::from_json`] (or any serde format,
+//! then [`ParserProfile::resolve`]) and applies it with
+//! [`IngredientParser::with_profile`](crate::IngredientParser::with_profile).
+//!
+//! A profile file names only what it changes. Omitted lists keep the built-in
+//! entries, a list given in full replaces them, and the `add` / `remove` tables
+//! edit them in place:
+//!
+//! ```
+//! use ingredient::{IngredientParser, profile::ParserProfile};
+//!
+//! let profile = ParserProfile::from_json(r#"{
+//!     "name": "charcuterie",
+//!     "add": {
+//!         "units": ["casing", "casings"],
+//!         "preparation_adjectives": ["cured"]
+//!     },
+//!     "remove": { "preparation_adjectives": ["fresh"] }
+//! }"#).unwrap();
+//! let parser = IngredientParser::new().with_profile(&profile);
+//!
+//! let ing = parser.from_str("2 casings cured pork belly");
+//! assert_eq!(ing.name, "pork belly");
+//! assert_eq!(ing.modifier.as_deref(), Some("cured"));
+//! // "fresh" is no longer stripped from the name.
+//! assert_eq!(parser.from_str("fresh cheese").name, "fresh cheese");
+//! ```
+//!
+//! The lists are cross-checked on load (see [`ParserProfile::validate`]): the
+//! built-in tests pin the same relationships, e.g. every postfix produce unit
+//! must also be a recognized unit.
+//!
+//! Two built-in behaviours stay on the compiled-in lists: the pre-parse
+//! "2-inch piece (20 g) ginger" rewrite and the classification of text inside
+//! parentheses, both of which only ever see the common containers.
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::parser::vocab;
+
+/// Why a profile could not be loaded.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ProfileError {
+    /// The document isn't a well-formed profile (syntax error, unknown key,
+    /// wrong type).
+    #[error("invalid profile document: {0}")]
+    Parse(String),
+    /// The lists parsed but contradict each other; one message per problem.
+    #[error("profile {name:?} is inconsistent: {}", problems.join("; "))]
+    Invalid { name: String, problems: Vec<String> },
+}
+
+/// The vocabulary one [`IngredientParser`](crate::IngredientParser) runs with.
+/// See the [module docs](self) for the file format.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ParserProfile {
+    /// A label for messages (`"default"` for the built-in profile).
+    pub name: String,
+    /// Units beyond the convertible ones ("clove", "bunch", "can", …). Plurals
+    /// are matched exactly, so list both forms.
+    pub units: Vec<String>,
+    /// Preparation words moved from the name into the modifier ("chopped",
+    /// "firmly packed"). Longer entries win over shorter ones they contain.
+    pub preparation_adjectives: Vec<String>,
+    /// Purpose phrases moved into the modifier ("for garnish").
+    pub purpose_phrases: Vec<String>,
+    /// Food → the trailing count units it takes ("garlic" → "clove", …), so
+    /// "1 garlic clove" is one clove of garlic.
+    pub postfix_produce_units: BTreeMap<String, Vec<String>>,
+    /// Size words consumed as the count unit ("3 medium carrots"), multi-word
+    /// entries first.
+    pub size_unit_words: Vec<String>,
+    /// Nouns that may follow a parenthesized size ("1 (14-ounce) can").
+    pub container_nouns: Vec<String>,
+    /// Head nouns an "X, Y, or Z <noun>" list shares ("oil", "stock").
+    pub shared_head_nouns: Vec<String>,
+    /// Usage phrases, in classification precedence order (garnish first).
+    pub garnish_phrases: Vec<String>,
+    pub frying_phrases: Vec<String>,
+    pub pan_grease_phrases: Vec<String>,
+    pub dredging_phrases: Vec<String>,
+    pub seasoning_phrases: Vec<String>,
+    pub marinade_phrases: Vec<String>,
+    /// Section-title words marking a whole section as marinade ("brine").
+    pub marinade_section_words: Vec<String>,
+    /// Entries appended to the lists above by [`Self::resolve`].
+    #[serde(skip_serializing_if = "ProfileEdit::is_empty")]
+    pub add: ProfileEdit,
+    /// Entries removed from the lists above by [`Self::resolve`].
+    #[serde(skip_serializing_if = "ProfileEdit::is_empty")]
+    pub remove: ProfileEdit,
+}
+
+/// Additions to or removals from a [`ParserProfile`]'s lists; same keys, all
+/// empty by default.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProfileEdit {
+    pub units: Vec<String>,
+    pub preparation_adjectives: Vec<String>,
+    pub purpose_phrases: Vec<String>,
+    /// Per food: units to add (a new food is inserted) or remove (a food left
+    /// with no units is dropped).
+    pub postfix_produce_units: BTreeMap<String, Vec<String>>,
+    pub size_unit_words: Vec<String>,
+    pub container_nouns: Vec<String>,
+    pub shared_head_nouns: Vec<String>,
+    pub garnish_phrases: Vec<String>,
+    pub frying_phrases: Vec<String>,
+    pub pan_grease_phrases: Vec<String>,
+    pub dredging_phrases: Vec<String>,
+    pub seasoning_phrases: Vec<String>,
+    pub marinade_phrases: Vec<String>,
+    pub marinade_section_words: Vec<String>,
+}
+
+fn owned(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| s.to_string()).collect()
+}
+
+/// The built-in profile, shared by the free [`classify_usage`](crate::classify_usage)
+/// and anything else that runs without a parser instance.
+pub(crate) static BUILTIN: LazyLock<ParserProfile> = LazyLock::new(ParserProfile::default);
+
+impl Default for ParserProfile {
+    /// The built-in vocabulary.
+    fn default() -> Self {
+        ParserProfile {
+            name: "default".to_string(),
+            units: owned(vocab::NON_STANDARD_UNITS),
+            preparation_adjectives: owned(vocab::DEFAULT_PREPARATION_ADJECTIVES),
+            purpose_phrases: owned(vocab::DEFAULT_PURPOSE_PHRASES),
+            postfix_produce_units: vocab::POSTFIX_PRODUCE_UNITS
+                .iter()
+                .map(|(food, units)| (food.to_string(), owned(units)))
+                .collect(),
+            size_unit_words: owned(vocab::SIZE_UNIT_WORDS),
+            container_nouns: owned(vocab::CONTAINER_NOUNS),
+            shared_head_nouns: owned(vocab::SHARED_HEAD_NOUNS),
+            garnish_phrases: owned(vocab::GARNISH_PHRASES),
+            frying_phrases: owned(vocab::FRYING_PHRASES),
+            pan_grease_phrases: owned(vocab::PAN_GREASE_PHRASES),
+            dredging_phrases: owned(vocab::DREDGING_PHRASES),
+            seasoning_phrases: owned(vocab::SEASONING_PHRASES),
+            marinade_phrases: owned(vocab::MARINADE_PHRASES),
+            marinade_section_words: owned(vocab::MARINADE_SECTION_WORDS),
+            add: ProfileEdit::default(),
+            remove: ProfileEdit::default(),
+        }
+    }
+}
+
+impl ProfileEdit {
+    /// Whether the edit changes nothing.
+    pub fn is_empty(&self) -> bool {
+        *self == ProfileEdit::default()
+    }
+}
+
+impl ParserProfile {
+    /// Parse a JSON profile, then [`resolve`](Self::resolve) it.
+    pub fn from_json(json: &str) -> Result<Self, ProfileError> {
+        serde_json::from_str::<ParserProfile>(json)
+            .map_err(|e| ProfileError::Parse(e.to_string()))?
+            .resolve()
+    }
+
+    /// Finish loading a deserialized profile: apply `add` then `remove`
+    /// (entries are lowercased; additions already present are skipped), put
+    /// multi-word size words ahead of single words, and [`validate`](Self::validate).
+    /// Every loader ends here, whatever the file format.
+    pub fn resolve(mut self) -> Result<Self, ProfileError> {
+        let add = std::mem::take(&mut self.add);
+        let remove = std::mem::take(&mut self.remove);
+        let edits = add.lists().into_iter().zip(remove.lists());
+        for (list, ((_, added), (_, removed))) in self.lists_mut().into_iter().zip(edits) {
+            for entry in added {
+                let entry = entry.to_lowercase();
+                if !list.contains(&entry) {
+                    list.push(entry);
+                }
+            }
+            list.retain(|e| !removed.iter().any(|r| r.eq_ignore_ascii_case(e)));
+        }
+        for (food, units) in add.postfix_produce_units {
+            let slot = self
+                .postfix_produce_units
+                .entry(food.to_lowercase())
+                .or_default();
+            for unit in units {
+                let unit = unit.to_lowercase();
+                if !slot.contains(&unit) {
+                    slot.push(unit);
+                }
+            }
+        }
+        for (food, units) in remove.postfix_produce_units {
+            let food = food.to_lowercase();
+            if let Some(slot) = self.postfix_produce_units.get_mut(&food) {
+                slot.retain(|u| !units.iter().any(|r| r.eq_ignore_ascii_case(u)));
+                if slot.is_empty() {
+                    self.postfix_produce_units.remove(&food);
+                }
+            }
+        }
+        // Stable sort: the size pass takes the first match, so "extra large"
+        // must be tried before "large"; added entries find their place.
+        self.size_unit_words
+            .sort_by_key(|w| std::cmp::Reverse(w.split(['-', ' ']).count()));
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Check the lists against each other, reporting every problem at once:
+    ///
+    /// - entries are non-empty, lowercase and not repeated within a list;
+    /// - postfix produce units, and the built-in vague ("pinch") and
+    ///   size-qualifiable ("bunch") measures, are all recognized `units`;
+    /// - each multi-word size word precedes the single words it contains;
+    /// - no single-word preparation adjective is one of the built-in adverbs
+    ///   ("finely", "very") that are folded into a following adjective.
+    pub fn validate(&self) -> Result<(), ProfileError> {
+        let mut problems = Vec::new();
+        let lists = self.lists();
+        for (name, list) in &lists {
+            let mut seen = HashSet::new();
+            for entry in *list {
+                if entry.trim().is_empty() {
+                    problems.push(format!("{name} has an empty entry"));
+                } else if *entry != entry.to_lowercase() {
+                    problems.push(format!("{name} entry {entry:?} is not lowercase"));
+                } else if !seen.insert(entry) {
+                    problems.push(format!("{name} has duplicate entry {entry:?}"));
+                }
+            }
+        }
+        let unit_set: HashSet<&str> = self.units.iter().map(String::as_str).collect();
+        for (food, units) in &self.postfix_produce_units {
+            for unit in units.iter().filter(|u| !unit_set.contains(u.as_str())) {
+                problems.push(format!(
+                    "postfix_produce_units[{food:?}] unit {unit:?} is not in units"
+                ));
+            }
+        }
+        for unit in vocab::VAGUE_UNITS
+            .iter()
+            .chain(vocab::SIZE_QUALIFIABLE_UNITS)
+            .filter(|u| !unit_set.contains(**u))
+        {
+            problems.push(format!(
+                "unit {unit:?} can't be removed: the size-qualified measure parse needs it"
+            ));
+        }
+        for (i, entry) in self.size_unit_words.iter().enumerate() {
+            for word in entry.split(['-', ' ']).filter(|w| w != entry) {
+                if let Some(j) = self.size_unit_words.iter().position(|e| e == word)
+                    && j < i
+                {
+                    problems.push(format!(
+                        "size_unit_words: {entry:?} must come before {word:?}"
+                    ));
+                }
+            }
+        }
+        for adj in &self.preparation_adjectives {
+            if vocab::INTENSIFIER_ADVERBS
+                .iter()
+                .chain(vocab::MANNER_ADVERBS)
+                .any(|a| a == adj)
+            {
+                problems.push(format!(
+                    "preparation_adjectives entry {adj:?} is an adverb the parser folds into the adjective after it"
+                ));
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ProfileError::Invalid {
+                name: self.name.clone(),
+                problems,
+            })
+        }
+    }
+
+    /// The usage rules in precedence order: the first phrase hit wins.
+    pub(crate) fn usage_rules(&self) -> [(&[String], crate::IngredientUsage); 6] {
+        use crate::IngredientUsage as U;
+        [
+            (&self.garnish_phrases, U::Garnish),
+            (&self.frying_phrases, U::FryingMedium),
+            (&self.pan_grease_phrases, U::PanGrease),
+            (&self.dredging_phrases, U::Dredging),
+            (&self.seasoning_phrases, U::Seasoning),
+            (&self.marinade_phrases, U::Marinade),
+        ]
+    }
+
+    fn lists(&self) -> [(&'static str, &Vec<String>); 13] {
+        [
+            ("units", &self.units),
+            ("preparation_adjectives", &self.preparation_adjectives),
+            ("purpose_phrases", &self.purpose_phrases),
+            ("size_unit_words", &self.size_unit_words),
+            ("container_nouns", &self.container_nouns),
+            ("shared_head_nouns", &self.shared_head_nouns),
+            ("garnish_phrases", &self.garnish_phrases),
+            ("frying_phrases", &self.frying_phrases),
+            ("pan_grease_phrases", &self.pan_grease_phrases),
+            ("dredging_phrases", &self.dredging_phrases),
+            ("seasoning_phrases", &self.seasoning_phrases),
+            ("marinade_phrases", &self.marinade_phrases),
+            ("marinade_section_words", &self.marinade_section_words),
+        ]
+    }
+
+    fn lists_mut(&mut self) -> [&mut Vec<String>; 13] {
+        [
+            &mut self.units,
+            &mut self.preparation_adjectives,
+            &mut self.purpose_phrases,
+            &mut self.size_unit_words,
+            &mut self.container_nouns,
+            &mut self.shared_head_nouns,
+            &mut self.garnish_phrases,
+            &mut self.frying_phrases,
+            &mut self.pan_grease_phrases,
+            &mut self.dredging_phrases,
+            &mut self.seasoning_phrases,
+            &mut self.marinade_phrases,
+            &mut self.marinade_section_words,
+        ]
+    }
+}
+
+impl ProfileEdit {
+    /// The plain lists, in [`ParserProfile::lists`] order.
+    fn lists(&self) -> [(&'static str, &Vec<String>); 13] {
+        [
+            ("units", &self.units),
+            ("preparation_adjectives", &self.preparation_adjectives),
+            ("purpose_phrases", &self.purpose_phrases),
+            ("size_unit_words", &self.size_unit_words),
+            ("container_nouns", &self.container_nouns),
+            ("shared_head_nouns", &self.shared_head_nouns),
+            ("garnish_phrases", &self.garnish_phrases),
+            ("frying_phrases", &self.frying_phrases),
+            ("pan_grease_phrases", &self.pan_grease_phrases),
+            ("dredging_phrases", &self.dredging_phrases),
+            ("seasoning_phrases", &self.seasoning_phrases),
+            ("marinade_phrases", &self.marinade_phrases),
+            ("marinade_section_words", &self.marinade_section_words),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::{IngredientParser, IngredientUsage};
+
+    /// The validation problems `json` is rejected with (a parse error shows up
+    /// as the only "problem", failing the caller's assertions).
+    fn problems(json: &str) -> Vec<String> {
+        match ParserProfile::from_json(json).unwrap_err() {
+            ProfileError::Invalid { problems, .. } => problems,
+            other => vec![other.to_string()],
+        }
+    }
+
+    #[test]
+    fn builtin_profile_is_valid_and_round_trips() {
+        let builtin = ParserProfile::default();
+        builtin.validate().unwrap();
+        let json = serde_json::to_string(&builtin).unwrap();
+        assert!(!json.contains("\"add\""), "empty edits aren't serialized");
+        assert_eq!(ParserProfile::from_json(&json).unwrap(), builtin);
+        // An empty document is the built-in profile.
+        assert_eq!(ParserProfile::from_json("{}").unwrap(), builtin);
+    }
+
+    #[test]
+    fn edits_add_and_remove_entries() {
+        let p = ParserProfile::from_json(
+            r#"{
+                "add": {
+                    "units": ["Knob", "knobs", "clove"],
+                    "size_unit_words": ["extra small"],
+                    "postfix_produce_units": { "ginger": ["knob", "knobs"] }
+                },
+                "remove": {
+                    "units": ["sprig"],
+                    "postfix_produce_units": { "corn": ["ear", "ears"] }
+                }
+            }"#,
+        )
+        .unwrap();
+        let base = ParserProfile::default();
+        assert!(
+            p.units
+                .ends_with(&["knob".to_string(), "knobs".to_string()])
+        );
+        assert_eq!(
+            p.units.len(),
+            base.units.len() + 1,
+            "clove was already there, sprig removed"
+        );
+        assert!(!p.units.contains(&"sprig".to_string()));
+        assert_eq!(p.postfix_produce_units["ginger"], ["knob", "knobs"]);
+        assert!(!p.postfix_produce_units.contains_key("corn"));
+        // Multi-word entries move ahead of single words.
+        let pos = |w: &str| p.size_unit_words.iter().position(|e| e == w).unwrap();
+        assert!(pos("extra small") < pos("small"));
+        assert!(p.add.is_empty() && p.remove.is_empty());
+    }
+
+    #[test]
+    fn validation_reports_every_problem() {
+        let got = problems(
+            r#"{
+                "name": "broken",
+                "garnish_phrases": ["For Garnish", "to finish", "to finish"],
+                "add": { "postfix_produce_units": { "leek": ["knob"] } },
+                "remove": { "units": ["pinch"] }
+            }"#,
+        );
+        assert_eq!(got.len(), 4, "{got:#?}");
+        assert!(got[0].contains("\"For Garnish\" is not lowercase"));
+        assert!(got[1].contains("duplicate entry \"to finish\""));
+        assert!(got[2].contains("\"knob\" is not in units"));
+        assert!(got[3].contains("\"pinch\""));
+
+        let got = problems(r#"{ "preparation_adjectives": ["finely", "chopped"] }"#);
+        assert!(got[0].contains("\"finely\" is an adverb"), "{got:#?}");
+    }
+
+    #[test]
+    fn malformed_documents_are_parse_errors() {
+        let err = ParserProfile::from_json(r#"{ "adjectives": [] }"#).unwrap_err();
+        assert!(matches!(err, ProfileError::Parse(ref m) if m.contains("unknown field")));
+        assert!(matches!(
+            ParserProfile::from_json(r#"{ "units": "cup" }"#),
+            Err(ProfileError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn with_profile_changes_parsing() {
+        let p = ParserProfile::from_json(
+            r#"{
+                "add": {
+                    "units": ["knob", "knobs"],
+                    "postfix_produce_units": { "ginger": ["knob"] },
+                    "shared_head_nouns": ["vinaigrette"],
+                    "garnish_phrases": ["to finish"]
+                },
+                "remove": { "size_unit_words": ["medium"] }
+            }"#,
+        )
+        .unwrap();
+        let default = IngredientParser::new();
+        let custom = IngredientParser::new().with_profile(&p);
+
+        let ing = custom.from_str("1 ginger knob, peeled");
+        assert_eq!(ing.name, "ginger");
+        assert_eq!(ing.amounts[0].unit().to_str(), "knob");
+        assert_eq!(
+            default.from_str("1 ginger knob, peeled").name,
+            "ginger knob"
+        );
+
+        assert_eq!(custom.from_str("3 medium carrots").name, "medium carrots");
+        assert_eq!(default.from_str("3 medium carrots").name, "carrots");
+
+        assert_eq!(
+            custom.from_str("flaky salt, to finish").usage,
+            IngredientUsage::Garnish
+        );
+        assert_eq!(
+            default.from_str("flaky salt, to finish").usage,
+            IngredientUsage::Normal
+        );
+
+        let line = "sherry, red wine, or balsamic vinaigrette";
+        assert_eq!(custom.from_str(line).name, "sherry vinaigrette");
+        assert_eq!(default.from_str(line).name, "sherry");
+    }
+}
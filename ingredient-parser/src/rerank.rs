@@ -0,0 +1,605 @@
+//! A learned token tagger, trained offline from labeled lines, that re-ranks
+//! candidate parses or labels a line the rule pipeline gave up on.
+//!
+//! The rules stay the parser: nothing here runs unless a caller loads a
+//! [`TokenTagger`] and asks for it, and [`IngredientParser::from_str`] is
+//! untouched by the `rerank` feature. The tagger is an averaged perceptron
+//! over the whitespace token stream the refine passes use, assigning each
+//! token one [`TokenLabel`] from local features (the word, its shape, whether
+//! it is a known unit or preparation adjective, punctuation and parenthesis
+//! context, its neighbours, and the previous token's label), decoded greedily
+//! left to right. Training visits lines in the order given and decoding breaks
+//! ties by label order, so the same lines always produce the same model and
+//! the same model always produces the same labels.
+//!
+//! Two uses, both opt-in:
+//! - [`TokenTagger::rerank`] weights each [`ParseCandidate`] by how well its
+//!   name agrees with the tokens the tagger calls [`TokenLabel::Name`], then
+//!   re-normalizes scores and confidences.
+//! - [`TokenTagger::label_fallback`] builds an ingredient from the labels when
+//!   the rule parse [fell back](crate::ParseNotes::fell_back) to a name-only
+//!   result, and returns the rule parse unchanged otherwise.
+//!
+//! ```
+//! use ingredient::IngredientParser;
+//! use ingredient::rerank::{LabeledLine, TokenLabel, TokenTagger};
+//!
+//! let parser = IngredientParser::new();
+//! let lines = [LabeledLine {
+//!     input: "2 cups flour, sifted".to_string(),
+//!     name: "flour".to_string(),
+//!     modifier: Some("sifted".to_string()),
+//!     amount_units: vec!["cups".to_string()],
+//! }];
+//! let (tagger, report) = TokenTagger::train(&parser, &lines, 5);
+//! assert_eq!(report.accuracy(), 1.0);
+//! let labels: Vec<TokenLabel> = tagger
+//!     .tag(&parser, "2 cups flour, sifted")
+//!     .into_iter()
+//!     .map(|(_, label)| label)
+//!     .collect();
+//! assert_eq!(
+//!     labels,
+//!     [TokenLabel::Amount, TokenLabel::Amount, TokenLabel::Name, TokenLabel::Modifier]
+//! );
+//! ```
+#![cfg(feature = "rerank")]
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::candidates::{ParseCandidate, rescore};
+use crate::parser::token;
+use crate::{Ingredient, IngredientParser, unit};
+
+/// Model format version written by [`TokenTagger::to_json`]; a file with any
+/// other version is rejected rather than silently mis-read.
+pub const MODEL_VERSION: u32 = 1;
+
+/// Weights with every magnitude below this are dropped from the saved model.
+const PRUNE_BELOW: f32 = 1e-3;
+
+/// What a token contributes to the parsed ingredient.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenLabel {
+    /// Part of a quantity or unit ("2", "½", "cups").
+    Amount,
+    /// Part of the ingredient name.
+    Name,
+    /// Part of the preparation modifier.
+    Modifier,
+    /// Connective or filler the parse drops ("of", "or", "-").
+    Other,
+}
+
+impl TokenLabel {
+    /// Every label, in decoding tie-break order.
+    pub const ALL: [TokenLabel; 4] = [
+        TokenLabel::Amount,
+        TokenLabel::Name,
+        TokenLabel::Modifier,
+        TokenLabel::Other,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// One training example: a line and the fields a correct parse has. Owned and
+/// corpus-agnostic so any labeled source can feed [`TokenTagger::train`].
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct LabeledLine {
+    pub input: String,
+    pub name: String,
+    pub modifier: Option<String>,
+    /// Units of the labeled amounts, as written in the measures.
+    pub amount_units: Vec<String>,
+}
+
+/// Token-level accuracy of a tagger over a set of [`LabeledLine`]s.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize)]
+pub struct TagAccuracy {
+    /// Lines whose gold labels could be derived (and were scored).
+    pub lines: usize,
+    /// Lines skipped because their labeled name doesn't align with the input
+    /// tokens (a reconstructed or reworded name).
+    pub skipped: usize,
+    pub tokens: usize,
+    pub correct: usize,
+}
+
+impl TagAccuracy {
+    /// Fraction of tokens labeled correctly (1.0 when nothing was scored).
+    pub fn accuracy(&self) -> f64 {
+        if self.tokens == 0 {
+            1.0
+        } else {
+            self.correct as f64 / self.tokens as f64
+        }
+    }
+}
+
+/// A trained token tagger: one weight per (feature, label).
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct TokenTagger {
+    version: u32,
+    /// Feature → weight per label, in [`TokenLabel::ALL`] order.
+    weights: BTreeMap<String, [f32; 4]>,
+}
+
+/// Errors loading a saved model.
+#[derive(Debug, thiserror::Error)]
+pub enum ModelError {
+    #[error("invalid model: {0}")]
+    Parse(String),
+    #[error("model version {found} is not supported (expected {MODEL_VERSION})")]
+    Version { found: u32 },
+}
+
+/// A tokenized line: the raw words and their per-token features.
+struct Tokens<'a> {
+    words: Vec<&'a str>,
+    /// Features that don't depend on the previous label.
+    features: Vec<Vec<String>>,
+}
+
+fn tokenize<'a>(parser: &IngredientParser, input: &'a str) -> Tokens<'a> {
+    let words: Vec<&str> = token::offsets(input).map(|(_, w)| w).collect();
+    let norms: Vec<String> = words.iter().map(|w| token::norm(w)).collect();
+    let mut features = Vec::with_capacity(words.len());
+    let mut depth = 0usize;
+    for (i, (word, norm)) in words.iter().zip(&norms).enumerate() {
+        depth += word.matches('(').count();
+        let mut f = vec![
+            "bias".to_string(),
+            format!("w={norm}"),
+            format!("suf={}", suffix(norm)),
+            format!("shape={}", shape(word)),
+            format!("prev={}", i.checked_sub(1).map_or("<s>", |p| &norms[p])),
+            format!("next={}", norms.get(i + 1).map_or("</s>", String::as_str)),
+        ];
+        if word.chars().any(char::is_numeric) {
+            f.push("num".to_string());
+        }
+        if !norm.is_empty() && unit::is_valid(parser.units(), norm) {
+            f.push("unit".to_string());
+        }
+        if parser.adjectives.contains(norm.as_str()) {
+            f.push("adj".to_string());
+        }
+        if word.ends_with(',') {
+            f.push("comma".to_string());
+        }
+        if i > 0 && words[i - 1].ends_with(',') {
+            f.push("after_comma".to_string());
+        }
+        if depth > 0 {
+            f.push("paren".to_string());
+        }
+        if i == 0 {
+            f.push("first".to_string());
+        }
+        if i + 1 == words.len() {
+            f.push("last".to_string());
+        }
+        depth = depth.saturating_sub(word.matches(')').count());
+        features.push(f);
+    }
+    Tokens { words, features }
+}
+
+/// Last three characters of a normalized word.
+fn suffix(norm: &str) -> String {
+    let n = norm.chars().count();
+    norm.chars().skip(n.saturating_sub(3)).collect()
+}
+
+/// Word shape with repeats collapsed: "1/2" → "d/d", "Flour," → "Xx,".
+fn shape(word: &str) -> String {
+    let mut out = String::new();
+    for c in word.chars() {
+        let s = if c.is_numeric() {
+            'd'
+        } else if c.is_uppercase() {
+            'X'
+        } else if c.is_alphabetic() {
+            'x'
+        } else {
+            c
+        };
+        if !out.ends_with(s) {
+            out.push(s);
+        }
+    }
+    out
+}
+
+fn prev_label_feature(prev: Option<TokenLabel>) -> String {
+    format!("prev_label={prev:?}")
+}
+
+/// Gold labels for `line`, or `None` if its name doesn't align: the name's
+/// words must all appear, in order, among the input tokens.
+fn gold_labels(
+    parser: &IngredientParser,
+    line: &LabeledLine,
+    words: &[&str],
+) -> Option<Vec<TokenLabel>> {
+    let name: Vec<String> = words_of(&line.name);
+    let mut modifier: HashMap<String, usize> = HashMap::new();
+    for w in words_of(line.modifier.as_deref().unwrap_or_default()) {
+        *modifier.entry(w).or_default() += 1;
+    }
+    let units: HashSet<String> = line
+        .amount_units
+        .iter()
+        .map(|u| unit::singular(&u.to_lowercase()).into_owned())
+        .collect();
+
+    let mut next_name = 0;
+    let mut labels = Vec::with_capacity(words.len());
+    for word in words {
+        let norm = token::norm(word);
+        let label = if norm.is_empty() {
+            TokenLabel::Other
+        } else if name.get(next_name) == Some(&norm) {
+            next_name += 1;
+            TokenLabel::Name
+        } else if let Some(n) = modifier.get_mut(&norm).filter(|n| **n > 0) {
+            *n -= 1;
+            TokenLabel::Modifier
+        } else if word.chars().any(char::is_numeric)
+            || units.contains(unit::singular(&norm).as_ref())
+            || unit::is_valid(parser.units(), &norm)
+        {
+            TokenLabel::Amount
+        } else {
+            TokenLabel::Other
+        };
+        labels.push(label);
+    }
+    (next_name == name.len() && !name.is_empty()).then_some(labels)
+}
+
+fn words_of(s: &str) -> Vec<String> {
+    s.split_whitespace()
+        .map(token::norm)
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Training-time weights: the current and the step-weighted running sum, for
+/// averaging.
+#[derive(Default)]
+struct Averaged {
+    current: HashMap<String, [f64; 4]>,
+    summed: HashMap<String, [f64; 4]>,
+    step: f64,
+}
+
+impl Averaged {
+    fn update(&mut self, features: &[String], gold: TokenLabel, guess: TokenLabel) {
+        for f in features {
+            for (label, delta) in [(gold, 1.0), (guess, -1.0)] {
+                self.current.entry(f.clone()).or_default()[label.index()] += delta;
+                self.summed.entry(f.clone()).or_default()[label.index()] += self.step * delta;
+            }
+        }
+    }
+
+    /// The averaged weights, `w - u/c`, pruned of near-zero entries.
+    fn finish(self) -> BTreeMap<String, [f32; 4]> {
+        let step = self.step.max(1.0);
+        self.current
+            .into_iter()
+            .filter_map(|(f, w)| {
+                let u = self.summed.get(&f).copied().unwrap_or_default();
+                let avg: [f32; 4] = std::array::from_fn(|l| (w[l] - u[l] / step) as f32);
+                avg.iter()
+                    .any(|x| x.abs() >= PRUNE_BELOW)
+                    .then_some((f, avg))
+            })
+            .collect()
+    }
+}
+
+fn best_label<'w>(
+    weight: impl Fn(&str) -> Option<&'w [f64; 4]>,
+    features: &[String],
+) -> TokenLabel {
+    let mut scores = [0.0; 4];
+    for f in features {
+        if let Some(w) = weight(f) {
+            for (s, x) in scores.iter_mut().zip(w) {
+                *s += x;
+            }
+        }
+    }
+    // First maximum wins: ties break by `TokenLabel::ALL` order.
+    let mut best = TokenLabel::ALL[0];
+    for label in TokenLabel::ALL {
+        if scores[label.index()] > scores[best.index()] {
+            best = label;
+        }
+    }
+    best
+}
+
+impl TokenTagger {
+    /// Train a tagger on `lines` for `epochs` passes. Lines whose name doesn't
+    /// align with their tokens are skipped. Returns the tagger and its
+    /// accuracy on the (aligned) training lines.
+    pub fn train(
+        parser: &IngredientParser,
+        lines: &[LabeledLine],
+        epochs: usize,
+    ) -> (TokenTagger, TagAccuracy) {
+        let examples: Vec<(Tokens, Vec<TokenLabel>)> = lines
+            .iter()
+            .filter_map(|line| {
+                let tokens = tokenize(parser, &line.input);
+                let gold = gold_labels(parser, line, &tokens.words)?;
+                Some((tokens, gold))
+            })
+            .collect();
+
+        let mut model = Averaged::default();
+        for _ in 0..epochs {
+            for (tokens, gold) in &examples {
+                let mut prev = None;
+                for (base, &label) in tokens.features.iter().zip(gold) {
+                    model.step += 1.0;
+                    let mut features = base.clone();
+                    features.push(prev_label_feature(prev));
+                    let guess = best_label(|f| model.current.get(f), &features);
+                    if guess != label {
+                        model.update(&features, label, guess);
+                    }
+                    prev = Some(guess);
+                }
+            }
+        }
+
+        let tagger = TokenTagger {
+            version: MODEL_VERSION,
+            weights: model.finish(),
+        };
+        let report = tagger.evaluate(parser, lines);
+        (tagger, report)
+    }
+
+    /// Label every whitespace token of `input`, in order.
+    pub fn tag<'a>(&self, parser: &IngredientParser, input: &'a str) -> Vec<(&'a str, TokenLabel)> {
+        let tokens = tokenize(parser, input);
+        let mut prev = None;
+        let mut out = Vec::with_capacity(tokens.words.len());
+        for (word, base) in tokens.words.iter().zip(&tokens.features) {
+            let mut features = base.clone();
+            features.push(prev_label_feature(prev));
+            let scores: HashMap<&str, [f64; 4]> = features
+                .iter()
+                .filter_map(|f| {
+                    let w = self.weights.get(f)?;
+                    Some((f.as_str(), w.map(f64::from)))
+                })
+                .collect();
+            let label = best_label(|f| scores.get(f), &features);
+            out.push((*word, label));
+            prev = Some(label);
+        }
+        out
+    }
+
+    /// Token-level accuracy on `lines`, skipping lines that don't align.
+    pub fn evaluate(&self, parser: &IngredientParser, lines: &[LabeledLine]) -> TagAccuracy {
+        let mut report = TagAccuracy::default();
+        for line in lines {
+            let words: Vec<&str> = token::offsets(&line.input).map(|(_, w)| w).collect();
+            let Some(gold) = gold_labels(parser, line, &words) else {
+                report.skipped += 1;
+                continue;
+            };
+            report.lines += 1;
+            report.tokens += gold.len();
+            report.correct += self
+                .tag(parser, &line.input)
+                .iter()
+                .zip(&gold)
+                .filter(|((_, guess), gold)| guess == *gold)
+                .count();
+        }
+        report
+    }
+
+    /// Re-rank `candidates` (from [`IngredientParser::parse_candidates`] on
+    /// the same `input`) by agreement with the tagger: each score is scaled by
+    /// `0.5 + 0.5 × overlap` between the candidate's name words and the words
+    /// tagged [`TokenLabel::Name`], then scores and field confidences are
+    /// re-normalized and the candidates re-sorted (stably, so ties keep the
+    /// rules' order).
+    pub fn rerank(
+        &self,
+        parser: &IngredientParser,
+        input: &str,
+        mut candidates: Vec<ParseCandidate>,
+    ) -> Vec<ParseCandidate> {
+        let tagged: HashSet<String> = self
+            .tag(parser, input)
+            .into_iter()
+            .filter(|(_, label)| *label == TokenLabel::Name)
+            .map(|(w, _)| token::norm(w))
+            .filter(|w| !w.is_empty())
+            .collect();
+        for c in &mut candidates {
+            let name: HashSet<String> = words_of(&c.ingredient.name).into_iter().collect();
+            c.score *= 0.5 + 0.5 * jaccard(&tagged, &name);
+        }
+        rescore(&mut candidates);
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        candidates
+    }
+
+    /// The rule parse of `input`, unless it fell back to a name-only result:
+    /// then an ingredient assembled from the tagger's labels (name from
+    /// [`TokenLabel::Name`] tokens, modifier from [`TokenLabel::Modifier`],
+    /// amounts parsed from [`TokenLabel::Amount`]). The parse notes are the
+    /// rule parse's, so a labeled fallback still shows up for review.
+    pub fn label_fallback(&self, parser: &IngredientParser, input: &str) -> Ingredient {
+        let parsed = parser.from_str(input);
+        if !parsed.parse_notes.fell_back {
+            return parsed;
+        }
+        let tagged = self.tag(parser, input);
+        let join = |want: TokenLabel| {
+            tagged
+                .iter()
+                .filter(|(_, label)| *label == want)
+                .map(|(w, _)| w.trim_matches(|c: char| matches!(c, ',' | ';' | '(' | ')')))
+                .filter(|w| !w.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let name = join(TokenLabel::Name);
+        if name.is_empty() {
+            return parsed;
+        }
+        let modifier = join(TokenLabel::Modifier);
+        let amounts = parser
+            .parse_amount(&join(TokenLabel::Amount))
+            .unwrap_or_default();
+        let mut labeled = Ingredient::new(
+            &name,
+            amounts,
+            Some(modifier.as_str()).filter(|m| !m.is_empty()),
+        );
+        labeled.optional = parsed.optional;
+        labeled.parse_notes = parsed.parse_notes;
+        labeled
+    }
+
+    /// Serialize the model to compact JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Load a model written by [`Self::to_json`].
+    pub fn from_json(json: &str) -> Result<TokenTagger, ModelError> {
+        let tagger: TokenTagger =
+            serde_json::from_str(json).map_err(|e| ModelError::Parse(e.to_string()))?;
+        if tagger.version != MODEL_VERSION {
+            return Err(ModelError::Version {
+                found: tagger.version,
+            });
+        }
+        Ok(tagger)
+    }
+}
+
+/// Jaccard overlap of two word sets; two empty sets agree completely.
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn line(input: &str, name: &str, modifier: Option<&str>, units: &[&str]) -> LabeledLine {
+        LabeledLine {
+            input: input.to_string(),
+            name: name.to_string(),
+            modifier: modifier.map(str::to_string),
+            amount_units: units.iter().map(|u| u.to_string()).collect(),
+        }
+    }
+
+    fn lines() -> Vec<LabeledLine> {
+        vec![
+            line("2 cups flour, sifted", "flour", Some("sifted"), &["cups"]),
+            line("1 onion, diced", "onion", Some("diced"), &["whole"]),
+            line(
+                "3 tablespoons butter, melted",
+                "butter",
+                Some("melted"),
+                &["tbsp"],
+            ),
+            line("1 cup chicken stock", "chicken stock", None, &["cup"]),
+            line("salt or chicken broth", "salt broth", None, &[]),
+            line("2 large eggs", "eggs", None, &["large"]),
+            line("2 multivitamins", "multivitamins", None, &["whole"]),
+        ]
+    }
+
+    #[test]
+    fn gold_labels_align_or_skip() {
+        let parser = IngredientParser::new();
+        let l = line(
+            "1 cup chicken stock, warmed",
+            "chicken stock",
+            Some("warmed"),
+            &["cup"],
+        );
+        let words: Vec<&str> = l.input.split_whitespace().collect();
+        use TokenLabel::*;
+        assert_eq!(
+            gold_labels(&parser, &l, &words).unwrap(),
+            [Amount, Amount, Name, Name, Modifier]
+        );
+        let reworded = line("2 cups flour", "all-purpose flour", None, &["cups"]);
+        let words: Vec<&str> = reworded.input.split_whitespace().collect();
+        assert_eq!(gold_labels(&parser, &reworded, &words), None);
+    }
+
+    #[test]
+    fn training_is_deterministic_and_round_trips() {
+        let parser = IngredientParser::new();
+        let (a, report) = TokenTagger::train(&parser, &lines(), 10);
+        let (b, _) = TokenTagger::train(&parser, &lines(), 10);
+        assert_eq!(a, b);
+        assert_eq!((report.lines, report.skipped), (7, 0));
+        assert!(report.accuracy() > 0.9, "{report:?}");
+        assert_eq!(TokenTagger::from_json(&a.to_json()).unwrap(), a);
+        assert!(matches!(
+            TokenTagger::from_json(r#"{"version":0,"weights":{}}"#),
+            Err(ModelError::Version { found: 0 })
+        ));
+    }
+
+    #[test]
+    fn rerank_keeps_every_candidate_and_normalizes() {
+        let parser = IngredientParser::new();
+        let (tagger, _) = TokenTagger::train(&parser, &lines(), 10);
+        let before = parser.parse_candidates("salt or chicken broth", 3);
+        let after = tagger.rerank(&parser, "salt or chicken broth", before.clone());
+        assert_eq!(after.len(), before.len());
+        let total: f64 = after.iter().map(|c| c.score).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert_eq!(after[0].ingredient.name, "salt broth");
+    }
+
+    #[test]
+    fn label_fallback_only_touches_fallbacks() {
+        let parser = IngredientParser::new();
+        let (tagger, _) = TokenTagger::train(&parser, &lines(), 10);
+        let input = "2 cups flour, sifted";
+        assert_eq!(
+            tagger.label_fallback(&parser, input),
+            parser.from_str(input)
+        );
+
+        // "1+1" defeats the grammar, so the rules fall back to the whole line.
+        let input = "1+1 multivitamins";
+        assert!(parser.from_str(input).parse_notes.fell_back);
+        let labeled = tagger.label_fallback(&parser, input);
+        assert_eq!(labeled.name, "multivitamins");
+        assert!(labeled.parse_notes.fell_back);
+    }
+}
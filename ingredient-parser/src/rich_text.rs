@@ -1,23 +1,41 @@
 use std::collections::HashSet;
+use std::ops::Range;
 
 use crate::{
     IngredientParser, Res,
     parser::measurement::single::leading_qualifier,
     parser::{MeasurementMode, MeasurementParser},
-    unit::Measure,
+    unit::{Dimension, Measure, Region, dimension::dimension_prefix},
 };
-use nom::{Parser, branch::alt, character::complete::satisfy, error::context, multi::many0};
+use nom::{
+    Parser,
+    branch::alt,
+    character::complete::satisfy,
+    error::{ParseError, context},
+    multi::many0,
+};
+use nom_language::error::VerboseError;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "kind", content = "value")]
 pub enum Chunk {
     Measure(Vec<Measure>),
+    /// A size rather than an amount: "9x13-inch", "½-inch-thick".
+    Dimension(Dimension),
     Text(String),
     Ing(String),
 }
 pub type Rich = Vec<Chunk>;
 
+/// A [`Chunk`] and the byte range of the input it was read from, for
+/// highlighting an instruction in place. See [`RichParser::parse_spanned`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpannedChunk {
+    pub chunk: Chunk,
+    pub range: Range<usize>,
+}
+
 /// Error returned by [`RichParser::parse`] when the underlying nom grammar fails
 /// to consume the input.
 ///
@@ -233,9 +251,13 @@ fn extract_ingredients(r: Rich, ingredient_names: &[String]) -> Rich {
         .collect()
 }
 
-fn amounts_chunk<'a>(units: &HashSet<String>, input: &'a str) -> Res<&'a str, Vec<Chunk>> {
+fn amounts_chunk<'a>(
+    units: &HashSet<String>,
+    region: Region,
+    input: &'a str,
+) -> Res<&'a str, Vec<Chunk>> {
     // Always use rich text mode (true) for instruction parsing
-    let mp = MeasurementParser::new(units, MeasurementMode::RichText);
+    let mp = MeasurementParser::new(units, MeasurementMode::RichText).with_region(region);
     let (next_input, measures) =
         context("amounts_chunk", |a| mp.parse_measurement_list(a)).parse(input)?;
 
@@ -247,7 +269,7 @@ fn amounts_chunk<'a>(units: &HashSet<String>, input: &'a str) -> Res<&'a str, Ve
     // "...foamy, about 3 minutes. Continue" → "...foamy, 3 minutesContinue").
     let consumed = &input[..input.len() - next_input.len()];
     let leading_len = match leading_qualifier(input) {
-        Ok((rest, ())) => input.len() - rest.len(),
+        Ok((rest, _)) => input.len() - rest.len(),
         Err(_) => 0,
     };
 
@@ -255,6 +277,13 @@ fn amounts_chunk<'a>(units: &HashSet<String>, input: &'a str) -> Res<&'a str, Ve
     if leading_len > 0 {
         chunks.push(Chunk::Text(input[..leading_len].to_string()));
     }
+    // The qualifier is in the re-emitted prose; keeping it on the measure too
+    // would render "about about 15 minutes".
+    let measures = if leading_len > 0 {
+        measures.iter().map(Measure::unqualified).collect()
+    } else {
+        measures
+    };
     chunks.push(Chunk::Measure(measures));
     let trailing = trailing_boundary(consumed);
     if !trailing.is_empty() {
@@ -285,6 +314,53 @@ fn trailing_boundary(consumed: &str) -> &'static str {
     }
 }
 
+/// A pan or cut size ("9x13-inch pan", "½-inch-thick slices"), tried before
+/// the measure grammar, which would read "9 x 13 inch" as 117 inches.
+fn dimension_chunk(input: &str) -> Res<&str, Vec<Chunk>> {
+    match dimension_prefix(input) {
+        Some((len, d)) => Ok((&input[len..], vec![Chunk::Dimension(d)])),
+        None => Err(nom::Err::Error(VerboseError::from_error_kind(
+            input,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }
+}
+
+/// Where the measure or dimension of one parsed group sits: the group's range
+/// less the prose `amounts_chunk` re-emits as text around it.
+fn measured_range(chunks: &[Chunk], range: Range<usize>) -> Option<Range<usize>> {
+    let at = chunks
+        .iter()
+        .position(|c| matches!(c, Chunk::Measure(_) | Chunk::Dimension(_)))?;
+    let len = |chunks: &[Chunk]| chunks.iter().map(text_len).sum::<usize>();
+    Some(range.start + len(&chunks[..at])..range.end - len(&chunks[at + 1..]))
+}
+
+fn text_len(chunk: &Chunk) -> usize {
+    match chunk {
+        Chunk::Text(s) | Chunk::Ing(s) => s.len(),
+        Chunk::Measure(_) | Chunk::Dimension(_) => 0,
+    }
+}
+
+/// Lay `rich` back over the input. Text and ingredient chunks are verbatim, so
+/// their lengths place them; each measure or dimension takes the next range in
+/// `measured`.
+fn place(rich: Rich, measured: Vec<Range<usize>>) -> Vec<SpannedChunk> {
+    let mut measured = measured.into_iter();
+    let mut at = 0;
+    rich.into_iter()
+        .map(|chunk| {
+            let range = match &chunk {
+                Chunk::Text(_) | Chunk::Ing(_) => at..at + text_len(&chunk),
+                Chunk::Measure(_) | Chunk::Dimension(_) => measured.next().unwrap_or(at..at),
+            };
+            at = range.end;
+            SpannedChunk { chunk, range }
+        })
+        .collect()
+}
+
 fn text_chunk(input: &str) -> Res<&str, Vec<Chunk>> {
     parse_rich_char(input).map(|(next_input, res)| (next_input, vec![Chunk::Text(res)]))
 }
@@ -348,21 +424,45 @@ impl RichParser {
         }
     }
 
-    #[tracing::instrument(level = "trace", skip_all)]
+    /// Read cups, pints and tablespoons as `region`'s (chainable); see
+    /// [`IngredientParser::with_region`].
+    pub fn with_region(mut self, region: Region) -> Self {
+        self.ip = self.ip.with_region(region);
+        self
+    }
+
     pub fn parse(&self, input: &str) -> Result<Rich, RichParseError> {
+        let spanned = self.parse_spanned(input)?;
+        Ok(spanned.into_iter().map(|s| s.chunk).collect())
+    }
+
+    /// [`parse`](Self::parse), with the byte range of `input` each chunk was
+    /// read from. A measure's range covers the amount as written ("2 cups"),
+    /// not the prose around it that stays text.
+    ///
+    /// # Example
+    /// ```
+    /// use ingredient::rich_text::{Chunk, RichParser};
+    ///
+    /// let input = "Add 2 cups flour";
+    /// let chunks = RichParser::new(["flour"]).parse_spanned(input).unwrap();
+    /// assert!(matches!(chunks[1].chunk, Chunk::Measure(_)));
+    /// assert_eq!(&input[chunks[1].range.clone()], "2 cups");
+    /// assert_eq!(chunks[3].chunk, Chunk::Ing("flour".to_string()));
+    /// assert_eq!(&input[chunks[3].range.clone()], "flour");
+    /// ```
+    #[tracing::instrument(level = "trace", skip_all)]
+    pub fn parse_spanned(&self, input: &str) -> Result<Vec<SpannedChunk>, RichParseError> {
         let units = self.ip.units();
-        match context(
-            "amts",
-            many0(alt((|a| amounts_chunk(units, a), text_chunk))),
-        )
-        .parse(input)
-        {
-            Ok((_, res)) => {
-                let flat: Rich = res.into_iter().flatten().collect();
-                Ok(extract_ingredients(
-                    condense_text(flat),
-                    &self.ingredient_names,
-                ))
+        match context("amts", many0(|a| self.chunk_group(units, input.len(), a))).parse(input) {
+            Ok((_, groups)) => {
+                let measured: Vec<Range<usize>> = groups
+                    .iter()
+                    .filter_map(|(chunks, range)| measured_range(chunks, range.clone()))
+                    .collect();
+                let flat: Rich = groups.into_iter().flat_map(|(chunks, _)| chunks).collect();
+                let rich = extract_ingredients(condense_text(flat), &self.ingredient_names);
+                Ok(place(rich, measured))
             }
             Err(e) => Err(RichParseError::Parse {
                 input: input.to_string(),
@@ -370,6 +470,24 @@ impl RichParser {
             }),
         }
     }
+
+    /// One dimension, measure or text character, and its range in an input
+    /// `len` bytes long that `input` is the tail of.
+    fn chunk_group<'a>(
+        &self,
+        units: &HashSet<String>,
+        len: usize,
+        input: &'a str,
+    ) -> Res<&'a str, (Vec<Chunk>, Range<usize>)> {
+        let start = len - input.len();
+        alt((
+            dimension_chunk,
+            |a| amounts_chunk(units, self.ip.region(), a),
+            text_chunk,
+        ))
+        .parse(input)
+        .map(|(rest, chunks)| (rest, (chunks, start..len - rest.len())))
+    }
 }
 
 #[cfg(test)]
@@ -522,6 +640,7 @@ mod tests {
                     .map(ToString::to_string)
                     .collect::<Vec<_>>()
                     .join(" "),
+                Chunk::Dimension(d) => d.to_string(),
             })
             .collect();
         assert!(
@@ -553,6 +672,30 @@ mod tests {
         assert!(!result.is_empty());
     }
 
+    /// Every chunk's range slices its own text out of the input (a measure's,
+    /// the amount as written), and the ranges tile the input in order.
+    #[rstest]
+    #[case("Add 2 cups flour and stir", &["Add ", "2 cups", " ", "flour", " and stir"])]
+    #[case(
+        "Bake until golden, about 15 minutes. Cool",
+        &["Bake until golden, about ", "15 minutes", ". Cool"]
+    )]
+    #[case("Line a 9x13-inch pan", &["Line a ", "9x13-inch", " pan"])]
+    #[case("Whisk 12 eggs", &["Whisk ", "12", " ", "eggs"])]
+    fn test_parse_spanned_ranges(#[case] input: &str, #[case] expected: &[&str]) {
+        let parser = RichParser::new(["flour", "egg"]);
+        let spanned = parser.parse_spanned(input).unwrap();
+        let got: Vec<&str> = spanned.iter().map(|s| &input[s.range.clone()]).collect();
+        assert_eq!(got, expected, "input={input:?}");
+        for s in &spanned {
+            if let Chunk::Text(t) | Chunk::Ing(t) = &s.chunk {
+                assert_eq!(&input[s.range.clone()], t);
+            }
+        }
+        let chunks: Rich = spanned.into_iter().map(|s| s.chunk).collect();
+        assert_eq!(chunks, parser.parse(input).unwrap());
+    }
+
     // ============================================================================
     // Condense Text Tests
     // ============================================================================
@@ -0,0 +1,232 @@
+//! Sorting the lines of a raw ingredient list: ingredients, the section
+//! headers that split it into components, notes, and everything else.
+//!
+//! Pasted text, EPUB chapters and HTML fallbacks hand over an ingredient list
+//! as flat lines, with the component structure written inline:
+//!
+//! ```text
+//! For the crust:
+//! 1 ¼ cups flour
+//! FILLING
+//! 2 lb apples
+//! Note: any tart apple works.
+//! ```
+//!
+//! [`classify_line`] tells those lines apart, so a header isn't parsed as a
+//! name-only ingredient, and [`section_title`] gives the header's name to pass
+//! on to [`IngredientParser::from_str_in_section`](crate::IngredientParser::from_str_in_section).
+//!
+//! The rules are shape-based and conservative: a header has no digits and at
+//! most six words, and is either phrased as one ("For the marinade", "To
+//! serve"), ends in a colon, is a markdown heading, or is an upper-case
+//! component name ("SAUCE", "CREAM CHEESE FROSTING"). "Fish Sauce", "Pizza
+//! Dough" and "Soy sauce" stay ingredients.
+
+use serde::{Deserialize, Serialize};
+
+use crate::parser::vocab::{
+    NON_INGREDIENT_HEADINGS, NON_INGREDIENT_PREFIXES, NOTE_PREFIXES, SECTION_HEADER_VERBS,
+    SECTION_HEADER_WORDS,
+};
+
+/// The most words a section header has ("For the lemon cream cheese frosting").
+const MAX_HEADER_WORDS: usize = 6;
+
+/// Prose this long ending in a period is a note, not an ingredient.
+const MIN_NOTE_WORDS: usize = 12;
+
+/// What a line of an ingredient list is.
+///
+/// Serialized in `snake_case` (`"section_header"`, …).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineKind {
+    /// An ingredient to parse.
+    Ingredient,
+    /// The start of a recipe component ("For the crust:", "FILLING").
+    SectionHeader,
+    /// A remark about the ingredients ("Note: any tart apple works.").
+    Note,
+    /// Anything else: blank or decorative lines, the list's own "Ingredients"
+    /// title, yield and timing lines.
+    NonIngredient,
+}
+
+/// Classify one line of an ingredient list.
+///
+/// ```
+/// use ingredient::section::{classify_line, LineKind};
+///
+/// assert_eq!(classify_line("For the crust:"), LineKind::SectionHeader);
+/// assert_eq!(classify_line("FILLING"), LineKind::SectionHeader);
+/// assert_eq!(classify_line("SAUCE"), LineKind::SectionHeader);
+/// assert_eq!(classify_line("Fish Sauce"), LineKind::Ingredient);
+/// assert_eq!(classify_line("2 cups flour"), LineKind::Ingredient);
+/// assert_eq!(classify_line("Soy sauce"), LineKind::Ingredient);
+/// assert_eq!(classify_line("Note: any tart apple works."), LineKind::Note);
+/// assert_eq!(classify_line("Serves 4"), LineKind::NonIngredient);
+/// ```
+pub fn classify_line(line: &str) -> LineKind {
+    let text = strip_decoration(line);
+    if text.is_empty() {
+        return LineKind::NonIngredient;
+    }
+    let lower = text.to_lowercase();
+    if NON_INGREDIENT_HEADINGS.contains(&lower.as_str())
+        || NON_INGREDIENT_PREFIXES
+            .iter()
+            .any(|p| starts_with_word(&lower, p))
+    {
+        return LineKind::NonIngredient;
+    }
+    if is_note(line.trim(), text, &lower) {
+        return LineKind::Note;
+    }
+    let marked = has_trailing_colon(line) || line.trim_start().starts_with('#');
+    if is_header(text, &lower, marked) {
+        return LineKind::SectionHeader;
+    }
+    LineKind::Ingredient
+}
+
+/// A section header's name, without its colon or markup ("## For the crust:"
+/// → "For the crust"); `None` when the line isn't a header.
+///
+/// ```
+/// use ingredient::section::section_title;
+///
+/// assert_eq!(section_title("**For the crust:**").as_deref(), Some("For the crust"));
+/// assert_eq!(section_title("2 cups flour"), None);
+/// ```
+pub fn section_title(line: &str) -> Option<String> {
+    (classify_line(line) == LineKind::SectionHeader).then(|| strip_decoration(line).to_string())
+}
+
+/// The line without surrounding markdown, bullets and rules, or a trailing
+/// colon.
+fn strip_decoration(line: &str) -> &str {
+    const DECORATION: &[char] = &['#', '*', '_', '-', '=', '•', '·', '~', ':'];
+    line.trim()
+        .trim_matches(|c: char| DECORATION.contains(&c) || c.is_whitespace())
+}
+
+/// Whether the line's text ends in a colon, inside any closing markup
+/// ("**Crust:**").
+fn has_trailing_colon(line: &str) -> bool {
+    line.trim().trim_end_matches(['*', '_', ' ']).ends_with(':')
+}
+
+/// `text` is `prefix`, or starts with it followed by a non-letter.
+fn starts_with_word(text: &str, prefix: &str) -> bool {
+    text.strip_prefix(prefix)
+        .is_some_and(|rest| !rest.starts_with(char::is_alphabetic))
+}
+
+/// A "Note:"/"Tip:" line, a "*"/"†" footnote, or a long sentence of prose.
+fn is_note(trimmed: &str, text: &str, lower: &str) -> bool {
+    if NOTE_PREFIXES.iter().any(|p| {
+        lower
+            .strip_prefix(p)
+            .is_some_and(|rest| rest.starts_with([':', '.', ' ', '-']) && rest.len() > 1)
+    }) {
+        return true;
+    }
+    let footnote = trimmed.starts_with('†')
+        || (trimmed.starts_with('*')
+            && trimmed[1..].starts_with(char::is_alphabetic)
+            && !trimmed.ends_with('*'));
+    if footnote {
+        return true;
+    }
+    let starts_with_amount = text
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_digit() || crate::fraction::is_vulgar(c));
+    !starts_with_amount && text.ends_with('.') && text.split_whitespace().count() >= MIN_NOTE_WORDS
+}
+
+/// A short digit-free line phrased as a header, marked as one by a trailing
+/// colon or markdown heading, or naming a recipe component in upper case.
+/// Title case alone isn't enough: "Fish Sauce" and "Pizza Dough" are
+/// ingredients.
+fn is_header(text: &str, lower: &str, marked: bool) -> bool {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.len() > MAX_HEADER_WORDS
+        || text
+            .chars()
+            .any(|c| c.is_ascii_digit() || crate::fraction::is_vulgar(c))
+    {
+        return false;
+    }
+    if marked || starts_with_word(lower, "for") {
+        return true;
+    }
+    let lower_words: Vec<&str> = lower.split_whitespace().collect();
+    if let [first, verb] = lower_words.as_slice()
+        && *first == "to"
+        && SECTION_HEADER_VERBS.contains(verb)
+    {
+        return true;
+    }
+    let upper = text.chars().any(char::is_alphabetic) && !text.chars().any(char::is_lowercase);
+    words.len() <= 3
+        && upper
+        && lower_words
+            .last()
+            .is_some_and(|w| SECTION_HEADER_WORDS.contains(w))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::for_the("For the crust:", LineKind::SectionHeader)]
+    #[case::for_the_no_colon("For the marinade", LineKind::SectionHeader)]
+    #[case::upper("FILLING", LineKind::SectionHeader)]
+    #[case::component_word("SAUCE", LineKind::SectionHeader)]
+    #[case::upper_title("CREAM CHEESE FROSTING", LineKind::SectionHeader)]
+    #[case::title_case_colon("Cream Cheese Frosting:", LineKind::SectionHeader)]
+    #[case::colon("Spice mix:", LineKind::SectionHeader)]
+    #[case::markdown("## Topping", LineKind::SectionHeader)]
+    #[case::bold_colon("**Dough:**", LineKind::SectionHeader)]
+    #[case::to_serve("To serve", LineKind::SectionHeader)]
+    #[case::amount("2 cups flour", LineKind::Ingredient)]
+    #[case::lowercase_component("Soy sauce", LineKind::Ingredient)]
+    #[case::bare_ingredient("Kosher salt", LineKind::Ingredient)]
+    #[case::purpose("Oil, for frying", LineKind::Ingredient)]
+    #[case::to_taste("Salt, to taste", LineKind::Ingredient)]
+    #[case::fish_sauce("Fish Sauce", LineKind::Ingredient)]
+    #[case::puff_pastry("Puff Pastry", LineKind::Ingredient)]
+    #[case::maple_syrup("Maple Syrup", LineKind::Ingredient)]
+    #[case::pizza_dough("Pizza Dough", LineKind::Ingredient)]
+    #[case::ranch_dressing("Ranch Dressing", LineKind::Ingredient)]
+    #[case::title_case_component("Sauce", LineKind::Ingredient)]
+    #[case::long_title("Hot Fudge Sauce With Toasted Nuts", LineKind::Ingredient)]
+    #[case::bullet("* 2 eggs", LineKind::Ingredient)]
+    #[case::note("Note: any tart apple works.", LineKind::Note)]
+    #[case::tip("Tip - chill the dough overnight", LineKind::Note)]
+    #[case::footnote("*Available at Asian markets", LineKind::Note)]
+    #[case::dagger("† or use store-bought", LineKind::Note)]
+    #[case::prose(
+        "You can swap the maple syrup for honey if that is what you have on hand.",
+        LineKind::Note
+    )]
+    #[case::blank("  ", LineKind::NonIngredient)]
+    #[case::rule("---", LineKind::NonIngredient)]
+    #[case::list_title("Ingredients:", LineKind::NonIngredient)]
+    #[case::serves("Serves 4", LineKind::NonIngredient)]
+    #[case::timing("Prep time: 10 minutes", LineKind::NonIngredient)]
+    fn classifies(#[case] line: &str, #[case] want: LineKind) {
+        assert_eq!(classify_line(line), want, "{line:?}");
+    }
+
+    #[rstest]
+    #[case("For the crust:", "For the crust")]
+    #[case("## FILLING", "FILLING")]
+    #[case("**Glaze:**", "Glaze")]
+    fn titles(#[case] line: &str, #[case] want: &str) {
+        assert_eq!(section_title(line).as_deref(), Some(want));
+    }
+}
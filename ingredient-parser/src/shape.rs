@@ -0,0 +1,161 @@
+//! Token-shape signatures: which parser paths a line exercises, independent
+//! of the words in it.
+//!
+//! "2 cups flour, sifted" and "3 tbsp sugar, melted" read differently but
+//! parse the same way: one whole-number volume, a head clause, a prep chain.
+//! [`IngredientParser::shape`](crate::IngredientParser::shape) reduces a line
+//! to that [`LineShape`], built from the parser's own judgements — the clause
+//! classifier, the parenthetical classifier and the recognizer table — so
+//! grouping lines by [`LineShape::signature`] shows which constructions a set
+//! of lines covers, and which it covers many times over.
+
+use crate::unit::{Measure, MeasureKind, Unit};
+
+/// A line's token-shape. Equal shapes mean the same amount pattern, clause
+/// kinds, paren kinds and recognizer.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct LineShape {
+    /// The parsed amounts' pattern ([`amount_pattern`]), e.g. `"N volume"` or
+    /// `"F-F weight + N count"`; `"none"` without amounts.
+    pub amounts: String,
+    /// The post-amount clauses' kinds in order (`"head_candidate"`,
+    /// `"prep_chain"`, `"purpose"`, …). Empty for a recognizer's line.
+    pub clauses: Vec<&'static str>,
+    /// The top-level parentheticals' kinds in order (`"amount"`, `"alias"`,
+    /// `"cross_reference"`, …), plus `"optional"` for a stripped
+    /// "(optional)" note.
+    pub parens: Vec<&'static str>,
+    /// The whole-line recognizer that claimed the line, if one did.
+    pub recognizer: Option<&'static str>,
+}
+
+impl LineShape {
+    /// The shape as one comparable string:
+    /// `amounts | clauses | parens | recognizer`, `-` for an empty part.
+    ///
+    /// ```
+    /// use ingredient::IngredientParser;
+    ///
+    /// let shape = IngredientParser::new().shape("1 cup (120g) flour, sifted (optional)");
+    /// assert_eq!(
+    ///     shape.signature(),
+    ///     "N volume + N weight | head_candidate, prep_chain | optional | -"
+    /// );
+    /// ```
+    pub fn signature(&self) -> String {
+        fn part(items: &[&str]) -> String {
+            if items.is_empty() {
+                "-".to_string()
+            } else {
+                items.join(", ")
+            }
+        }
+        format!(
+            "{} | {} | {} | {}",
+            self.amounts,
+            part(&self.clauses),
+            part(&self.parens),
+            self.recognizer.unwrap_or("-")
+        )
+    }
+}
+
+impl std::fmt::Display for LineShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.signature())
+    }
+}
+
+/// The shape of a list of amounts: per measure, `N` for a whole number or `F`
+/// for a fraction (`N-F` for a range), a `~` prefix for a qualifier, and the
+/// unit's class; joined with `" + "`.
+///
+/// ```
+/// use ingredient::shape::amount_pattern;
+/// use ingredient::unit::Measure;
+///
+/// assert_eq!(amount_pattern(&[Measure::new("cup", 1.5)]), "F volume");
+/// assert_eq!(amount_pattern(&[Measure::with_range("whole", 2.0, 3.0)]), "N-N count");
+/// assert_eq!(amount_pattern(&[]), "none");
+/// ```
+pub fn amount_pattern(amounts: &[Measure]) -> String {
+    if amounts.is_empty() {
+        return "none".to_string();
+    }
+    let value = |v: f64| if v.fract() == 0.0 { "N" } else { "F" };
+    amounts
+        .iter()
+        .map(|m| {
+            let qualifier = if m.qualifier().is_some() { "~" } else { "" };
+            let upper = m
+                .upper_value()
+                .map(|u| format!("-{}", value(u)))
+                .unwrap_or_default();
+            format!("{qualifier}{}{upper} {}", value(m.value()), unit_class(m))
+        })
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+/// A unit's class: its measure kind, `count` for a bare count, and `unit` for
+/// every other non-convertible unit (containers, produce units, vague units).
+fn unit_class(m: &Measure) -> &'static str {
+    match m.kind() {
+        MeasureKind::Weight => "weight",
+        MeasureKind::Volume => "volume",
+        MeasureKind::Length => "length",
+        MeasureKind::Temperature => "temperature",
+        MeasureKind::Time => "time",
+        MeasureKind::Money | MeasureKind::Calories | MeasureKind::Nutrient(_) => "other",
+        MeasureKind::Other(_) if *m.unit() == Unit::Whole => "count",
+        MeasureKind::Other(_) => "unit",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IngredientParser;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::plain("2 cups flour", "N volume | head_candidate | - | -")]
+    #[case::count_and_prep("2 onions, diced", "N count | head_candidate, prep_chain | - | -")]
+    #[case::range("1-2 tsp salt", "N-N volume | head_candidate | - | -")]
+    #[case::purpose(
+        "Oil, for brushing the bread",
+        "none | head_candidate, purpose | - | -"
+    )]
+    #[case::alternative(
+        "1 cup milk, or cream",
+        "N volume | head_candidate, alternative | - | -"
+    )]
+    #[case::paren_amount(
+        "1 (14-ounce) can tomatoes",
+        "N unit + N weight | head_candidate | - | -"
+    )]
+    #[case::alias_paren(
+        "1 cup scallions (green onions), sliced",
+        "N volume | head_candidate, prep_chain | alias | -"
+    )]
+    #[case::recognizer("juice of 1 lemon", "N count | - | - | x_of_construction")]
+    #[case::wrapped("(1 cup walnuts)", "N volume | - | - | optional_wrapped")]
+    fn signatures(#[case] line: &str, #[case] want: &str) {
+        assert_eq!(
+            IngredientParser::new().shape(line).signature(),
+            want,
+            "{line:?}"
+        );
+    }
+
+    #[test]
+    fn words_do_not_change_the_shape() {
+        let parser = IngredientParser::new();
+        assert_eq!(
+            parser.shape("2 cups flour, sifted"),
+            parser.shape("3 tablespoons sugar, melted")
+        );
+        let weight = parser.shape("4 ounces butter, softened");
+        assert_eq!(weight.amounts, "N weight");
+        assert_eq!(weight.clauses, ["head_candidate", "prep_chain"]);
+    }
+}
@@ -7,9 +7,10 @@ use std::time::Instant;
 /// Collects trace information during parsing
 #[derive(Debug)]
 pub(crate) struct TraceCollector {
-    /// Stack of nodes being built (parent -> child relationship)
-    stack: Vec<TraceNode>,
-    /// Baseline instant for converting to unix time
+    /// Stack of nodes being built (parent -> child relationship), each with
+    /// the instant it was entered
+    stack: Vec<(TraceNode, Instant)>,
+    /// When tracing started; node start offsets count from here
     baseline_instant: Instant,
     /// Unix timestamp (microseconds) when tracing started
     baseline_unix_micros: u64,
@@ -33,39 +34,43 @@ impl TraceCollector {
 
     /// Enter a new parser context
     pub(crate) fn enter(&mut self, name: &str, input: &str) {
-        let node = TraceNode::new(name, input);
-        self.stack.push(node);
+        let now = Instant::now();
+        let mut node = TraceNode::new(name, input);
+        node.start_micros = Some(micros(now.duration_since(self.baseline_instant)));
+        self.stack.push((node, now));
     }
 
     /// Exit the current parser context with success
     pub(crate) fn exit_success(&mut self, consumed: usize, output_preview: &str) {
-        if let Some(mut node) = self.stack.pop() {
+        if let Some((mut node, started)) = self.stack.pop() {
             node.success(consumed, output_preview);
-            self.attach_to_parent(node);
+            node.duration_micros = Some(micros(started.elapsed()));
+            self.attach_to_parent(node, started);
         }
     }
 
     /// Exit the current parser context with failure
     pub(crate) fn exit_failure(&mut self, error: &str) {
-        if let Some(mut node) = self.stack.pop() {
+        if let Some((mut node, started)) = self.stack.pop() {
             node.failure(error);
-            self.attach_to_parent(node);
+            node.duration_micros = Some(micros(started.elapsed()));
+            self.attach_to_parent(node, started);
         }
     }
 
     /// Attach a completed node to its parent (or keep as root)
-    fn attach_to_parent(&mut self, node: TraceNode) {
-        if let Some(parent) = self.stack.last_mut() {
+    fn attach_to_parent(&mut self, node: TraceNode, started: Instant) {
+        if let Some((parent, _)) = self.stack.last_mut() {
             parent.add_child(node);
         } else {
             // This is the root node, push it back
-            self.stack.push(node);
+            self.stack.push((node, started));
         }
     }
 
     /// Finish tracing and return the root trace
     pub(crate) fn finish(mut self, input: &str) -> ParseTrace {
-        let root = if let Some(node) = self.stack.pop() {
+        let root = if let Some((node, _)) = self.stack.pop() {
             node
         } else {
             TraceNode::new("parse_ingredient", input)
@@ -74,12 +79,17 @@ impl TraceCollector {
         ParseTrace {
             input: input.to_string(),
             root,
-            baseline_instant: Some(self.baseline_instant),
             baseline_unix_micros: self.baseline_unix_micros,
+            custom_recognizers: Vec::new(),
         }
     }
 }
 
+/// A duration in whole microseconds, saturating.
+fn micros(d: std::time::Duration) -> u64 {
+    u64::try_from(d.as_micros()).unwrap_or(u64::MAX)
+}
+
 impl Default for TraceCollector {
     fn default() -> Self {
         Self::new()
@@ -137,6 +147,15 @@ pub(crate) fn trace_exit_failure(error: &str) {
     });
 }
 
+/// Run `f` with tracing suspended, so follow-up checks that reuse traced
+/// parsers don't add nodes to the trace being collected.
+pub(crate) fn untraced<T>(f: impl FnOnce() -> T) -> T {
+    let saved = TRACE_COLLECTOR.with(|tc| tc.borrow_mut().take());
+    let out = f();
+    TRACE_COLLECTOR.with(|tc| *tc.borrow_mut() = saved);
+    out
+}
+
 /// Check if tracing is currently enabled for this thread
 ///
 /// Use this to avoid expensive formatting operations when tracing is disabled.
@@ -0,0 +1,274 @@
+//! Structural diff of two parse traces.
+//!
+//! A trace saved before a parser change (`food-cli parse-ingredient
+//! --trace-out`) and one taken after it usually share most of their tree: the
+//! same normalize rewrites, the same `alt()` attempts, until one combinator
+//! answers differently and everything downstream follows. [`TraceDiff`] aligns
+//! the two trees node by node — children matched by name with a longest common
+//! subsequence, so an extra or missing attempt doesn't shift every sibling
+//! after it — and [`TraceDiff::first_divergence`] walks down to the earliest
+//! node where the parses part ways.
+//!
+//! Timings are ignored: two runs never take the same microseconds.
+
+use std::fmt::Write as _;
+
+use super::format::outcome_symbol;
+use super::{ParseTrace, TraceNode, TraceOutcome};
+
+/// What one side of a [`DiffNode`] saw.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeState {
+    /// Input at this parse point (truncated, as in [`TraceNode::input`])
+    pub input: String,
+    /// Outcome of the parser on this side
+    pub outcome: TraceOutcome,
+}
+
+/// How a [`DiffNode`] differs between the two traces, ignoring its children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeChange {
+    /// Same input, same outcome
+    Same,
+    /// Present in both, with a different input or outcome
+    Changed,
+    /// Only in the `before` trace
+    Removed,
+    /// Only in the `after` trace
+    Added,
+}
+
+/// One aligned node: the parser name and what each trace saw there.
+#[derive(Debug, Clone)]
+pub struct DiffNode {
+    /// Name of the parser/combinator
+    pub name: String,
+    /// The node in the `before` trace, if it has one here
+    pub before: Option<NodeState>,
+    /// The node in the `after` trace, if it has one here
+    pub after: Option<NodeState>,
+    /// Aligned children, in trace order
+    pub children: Vec<DiffNode>,
+}
+
+impl DiffNode {
+    /// How this node itself differs; see [`NodeChange`].
+    pub fn change(&self) -> NodeChange {
+        match (&self.before, &self.after) {
+            (Some(b), Some(a)) if b == a => NodeChange::Same,
+            (Some(_), Some(_)) => NodeChange::Changed,
+            (Some(_), None) => NodeChange::Removed,
+            // An aligned node always has at least one side.
+            (None, _) => NodeChange::Added,
+        }
+    }
+
+    /// Whether this node and its whole subtree are the same on both sides.
+    pub fn is_identical(&self) -> bool {
+        self.change() == NodeChange::Same && self.children.iter().all(DiffNode::is_identical)
+    }
+
+    /// Number of nodes in this subtree, this one included.
+    fn len(&self) -> usize {
+        1 + self.children.iter().map(DiffNode::len).sum::<usize>()
+    }
+}
+
+/// Two traces aligned node by node; built by [`ParseTrace::diff`].
+#[derive(Debug, Clone)]
+pub struct TraceDiff {
+    /// Input of the `before` trace
+    pub before_input: String,
+    /// Input of the `after` trace
+    pub after_input: String,
+    /// Aligned roots
+    pub root: DiffNode,
+}
+
+impl TraceDiff {
+    /// Whether the two trees match everywhere but in timing.
+    pub fn is_identical(&self) -> bool {
+        self.root.is_identical()
+    }
+
+    /// The path from the root to the first node where the parses part ways,
+    /// or `None` when the trees match.
+    ///
+    /// A parent's outcome changes *because* a child's did, so the walk descends
+    /// into the first divergent child for as long as there is one; the last
+    /// node of the path is the earliest, innermost difference.
+    pub fn first_divergence(&self) -> Option<Vec<&DiffNode>> {
+        if self.root.is_identical() {
+            return None;
+        }
+        let mut path = vec![&self.root];
+        let mut node = &self.root;
+        while matches!(node.change(), NodeChange::Same | NodeChange::Changed)
+            && let Some(child) = node.children.iter().find(|c| !c.is_identical())
+        {
+            path.push(child);
+            node = child;
+        }
+        Some(path)
+    }
+
+    /// Render the aligned tree. Each line starts with a marker — ` ` same,
+    /// `~` changed, `-` removed, `+` added — and an identical subtree shows as
+    /// its root alone with a count of the nodes folded under it.
+    pub fn format(&self, colored: bool) -> String {
+        let mut out = String::new();
+        if self.before_input != self.after_input {
+            let _ = writeln!(out, "- input \"{}\"", self.before_input);
+            let _ = writeln!(out, "+ input \"{}\"", self.after_input);
+        }
+        let first = self
+            .first_divergence()
+            .and_then(|path| path.last().copied());
+        format_node(&self.root, &mut out, "", true, first, colored);
+        out
+    }
+}
+
+/// Align `before` and `after`; see [`ParseTrace::diff`].
+pub(super) fn diff(before: &ParseTrace, after: &ParseTrace) -> TraceDiff {
+    TraceDiff {
+        before_input: before.input.clone(),
+        after_input: after.input.clone(),
+        root: diff_node(Some(&before.root), Some(&after.root)),
+    }
+}
+
+fn state(node: &TraceNode) -> NodeState {
+    NodeState {
+        input: node.input.clone(),
+        outcome: node.outcome.clone(),
+    }
+}
+
+fn diff_node(before: Option<&TraceNode>, after: Option<&TraceNode>) -> DiffNode {
+    let name = before.or(after).map(|n| n.name.clone()).unwrap_or_default();
+    let no_children: &[TraceNode] = &[];
+    let b = before.map_or(no_children, |n| &n.children);
+    let a = after.map_or(no_children, |n| &n.children);
+    let children = align(b, a)
+        .into_iter()
+        .map(|(i, j)| diff_node(i.map(|i| &b[i]), j.map(|j| &a[j])))
+        .collect();
+    DiffNode {
+        name,
+        before: before.map(state),
+        after: after.map(state),
+        children,
+    }
+}
+
+/// Pair up two child lists by name: a longest common subsequence of names,
+/// with the unmatched nodes of each side in between, `before`'s first.
+fn align(before: &[TraceNode], after: &[TraceNode]) -> Vec<(Option<usize>, Option<usize>)> {
+    let (n, m) = (before.len(), after.len());
+    // lcs[i][j]: LCS length of before[i..] and after[j..]
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if before[i].name == after[j].name {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut pairs = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && before[i].name == after[j].name {
+            pairs.push((Some(i), Some(j)));
+            i += 1;
+            j += 1;
+        } else if j == m || (i < n && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            pairs.push((Some(i), None));
+            i += 1;
+        } else {
+            pairs.push((None, Some(j)));
+            j += 1;
+        }
+    }
+    pairs
+}
+
+fn format_node(
+    node: &DiffNode,
+    out: &mut String,
+    prefix: &str,
+    is_last: bool,
+    first: Option<&DiffNode>,
+    colored: bool,
+) {
+    let connector = if is_last { "└─ " } else { "├─ " };
+    let change = node.change();
+    let (marker, color) = match change {
+        NodeChange::Same => (' ', ""),
+        NodeChange::Changed => ('~', "\x1b[33m"),
+        NodeChange::Removed => ('-', "\x1b[31m"),
+        NodeChange::Added => ('+', "\x1b[32m"),
+    };
+    let body = match (&node.before, &node.after) {
+        (Some(b), Some(a)) if b.input != a.input => format!(
+            "\"{}\" {} ⇒ \"{}\" {}",
+            b.input,
+            outcome_symbol(&b.outcome, colored),
+            a.input,
+            outcome_symbol(&a.outcome, colored)
+        ),
+        (Some(b), Some(a)) if b.outcome != a.outcome => format!(
+            "\"{}\" {} ⇒ {}",
+            b.input,
+            outcome_symbol(&b.outcome, colored),
+            outcome_symbol(&a.outcome, colored)
+        ),
+        (Some(s), _) | (None, Some(s)) => {
+            format!("\"{}\" {}", s.input, outcome_symbol(&s.outcome, colored))
+        }
+        (None, None) => String::new(),
+    };
+    let expand = node.children.is_empty() || !node.is_identical();
+    let mut line = if colored && change != NodeChange::Same {
+        format!(
+            "{color}{marker}\x1b[0m {prefix}{connector}\x1b[1m{}\x1b[0m {body}",
+            node.name
+        )
+    } else {
+        format!("{marker} {prefix}{connector}{} {body}", node.name)
+    };
+    if !expand {
+        let hidden = node.len() - 1;
+        let _ = write!(line, "  ({hidden} identical below)");
+    }
+    if first.is_some_and(|f| std::ptr::eq(f, node)) {
+        line.push_str(if colored {
+            "  \x1b[1;33m◀ first divergence\x1b[0m"
+        } else {
+            "  ◀ first divergence"
+        });
+    }
+    let _ = writeln!(out, "{line}");
+    if !expand {
+        return;
+    }
+
+    let child_prefix = if is_last {
+        format!("{prefix}   ")
+    } else {
+        format!("{prefix}│  ")
+    };
+    let child_count = node.children.len();
+    for (idx, child) in node.children.iter().enumerate() {
+        format_node(
+            child,
+            out,
+            &child_prefix,
+            idx == child_count - 1,
+            first,
+            colored,
+        );
+    }
+}
@@ -16,24 +16,7 @@ fn format_node(node: &TraceNode, output: &mut String, prefix: &str, is_last: boo
     // Determine the connector
     let connector = if is_last { "└─ " } else { "├─ " };
 
-    // Format the outcome symbol
-    let outcome_symbol = match &node.outcome {
-        TraceOutcome::Success { output_preview, .. } => {
-            if colored {
-                format!("\x1b[32m✓\x1b[0m → {output_preview}")
-            } else {
-                format!("✓ → {output_preview}")
-            }
-        }
-        TraceOutcome::Failure { .. } => {
-            if colored {
-                "\x1b[31m✗\x1b[0m".to_string()
-            } else {
-                "✗".to_string()
-            }
-        }
-        TraceOutcome::Incomplete => "...".to_string(),
-    };
+    let outcome_symbol = outcome_symbol(&node.outcome, colored);
 
     // Write this node
     if colored {
@@ -62,6 +45,27 @@ fn format_node(node: &TraceNode, output: &mut String, prefix: &str, is_last: boo
     }
 }
 
+/// `✓ → preview`, `✗` or `...` for an outcome; shared with the diff renderer.
+pub(super) fn outcome_symbol(outcome: &TraceOutcome, colored: bool) -> String {
+    match outcome {
+        TraceOutcome::Success { output_preview, .. } => {
+            if colored {
+                format!("\x1b[32m✓\x1b[0m → {output_preview}")
+            } else {
+                format!("✓ → {output_preview}")
+            }
+        }
+        TraceOutcome::Failure { .. } => {
+            if colored {
+                "\x1b[31m✗\x1b[0m".to_string()
+            } else {
+                "✗".to_string()
+            }
+        }
+        TraceOutcome::Incomplete => "...".to_string(),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Compact stage view (`--explain`)
 //
@@ -78,16 +78,10 @@ fn collect_spans(
     let span_id = random_hex(16);
 
     // Calculate start time in unix microseconds
-    let start_time =
-        if let (Some(baseline), Some(node_start)) = (trace.baseline_instant, node.start_time) {
-            let offset = node_start.duration_since(baseline).as_micros() as u64;
-            trace.baseline_unix_micros + offset
-        } else {
-            trace.baseline_unix_micros + *span_counter
-        };
+    let start_time = trace.baseline_unix_micros + node.start_micros.unwrap_or(*span_counter);
 
     // Calculate duration
-    let duration = node.duration_micros().unwrap_or(1);
+    let duration = node.duration_micros.unwrap_or(1);
 
     // Build references (parent relationship)
     let references: Vec<serde_json::Value> = parent_span_id
@@ -14,15 +14,20 @@
 //! ```
 
 mod collector;
+mod diff;
 mod format;
 mod jaeger;
+pub mod otlp;
+pub mod perf;
 mod stages;
 
 pub use collector::is_tracing_enabled;
+pub use diff::{DiffNode, NodeChange, NodeState, TraceDiff};
 pub use stages::{GrammarOutcome, RecognizerAttempt, StageReport, StageRewrite};
 // Thread-local span-stack mutators: in-crate only (the `traced_parser!` macro and
 // the pipeline/recognize/refine phases). Not part of the public API — the public
 // entry point is `IngredientParser::parse_with_trace` → `ParseTrace`.
+pub(crate) use collector::untraced;
 pub(crate) use collector::{disable_tracing, enable_tracing};
 pub(crate) use collector::{trace_enter, trace_exit_failure, trace_exit_success};
 
@@ -33,17 +38,17 @@ pub(crate) use collector::{trace_enter, trace_exit_failure, trace_exit_success};
 /// --report-stages` to detect rules with zero corpus coverage). It is *not* a
 /// per-parse result — for that, parse with [`ParseTrace::stages`], which reports
 /// only the stages that actually fired on a given line.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PipelineStageNames {
     /// Every normalize rewrite label, in pipeline order.
-    pub normalize: &'static [&'static str],
+    pub normalize: Vec<&'static str>,
     /// Every whole-line recognizer label, in attempt order.
-    pub recognizers: &'static [&'static str],
+    pub recognizers: Vec<&'static str>,
     /// Every segment-stage label (clause kinds in classifier order, then the
     /// assembly repairs), in emit order.
-    pub segment: &'static [&'static str],
+    pub segment: Vec<&'static str>,
     /// Every refine pass label, in pipeline order.
-    pub refine: &'static [&'static str],
+    pub refine: Vec<&'static str>,
 }
 
 /// The full label universe of the parser's three ordered stage pipelines.
@@ -53,13 +58,17 @@ pub struct PipelineStageNames {
 /// corpus) can compare it against the rules that actually fire. It carries no
 /// per-line state; use [`IngredientParser::parse_with_trace`] for that.
 ///
+/// The built-in passes only; a parser's
+/// [`pipeline_stage_names`](crate::IngredientParser::pipeline_stage_names)
+/// adds its [registered passes](crate::pass).
+///
 /// [`IngredientParser::parse_with_trace`]: crate::IngredientParser::parse_with_trace
 pub fn pipeline_stage_names() -> PipelineStageNames {
     PipelineStageNames {
-        normalize: crate::parser::normalize::REWRITE_TRACE_NAMES,
-        recognizers: crate::parser::recognize::RECOGNIZER_TRACE_NAMES,
-        segment: crate::parser::segment::SEGMENT_TRACE_NAMES,
-        refine: crate::parser::refine::REFINE_TRACE_NAMES,
+        normalize: crate::parser::normalize::REWRITE_TRACE_NAMES.to_vec(),
+        recognizers: crate::parser::recognize::RECOGNIZER_TRACE_NAMES.to_vec(),
+        segment: crate::parser::segment::SEGMENT_TRACE_NAMES.to_vec(),
+        refine: crate::parser::refine::REFINE_TRACE_NAMES.to_vec(),
     }
 }
 
@@ -91,35 +100,45 @@ pub(crate) fn trace_attempt<T>(
 }
 
 use std::fmt;
-use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
 
 /// A node in the parse trace tree
-#[derive(Debug, Clone)]
+///
+/// Timings are plain microsecond offsets, not `Instant`s, so a trace can be
+/// saved with serde and compared against one from another run or parser
+/// version (see [`ParseTrace::diff`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraceNode {
     /// Name of the parser/combinator
     pub name: String,
     /// Input at this parse point (truncated for display)
     pub input: String,
     /// Child parser attempts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub children: Vec<TraceNode>,
     /// Outcome of this parser
     pub outcome: TraceOutcome,
-    /// When this parser started (for timing)
-    pub start_time: Option<Instant>,
-    /// When this parser finished (for timing)
-    pub end_time: Option<Instant>,
+    /// When this parser started, in microseconds after the trace began
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_micros: Option<u64>,
+    /// How long this parser ran, in microseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_micros: Option<u64>,
 }
 
 impl TraceNode {
-    /// Create a new trace node (always captures timing)
+    /// Create a new trace node. Timing is left empty: the collector behind
+    /// [`parse_with_trace`](crate::IngredientParser::parse_with_trace) fills
+    /// it in against the trace's start.
     pub fn new(name: impl Into<String>, input: &str) -> Self {
         Self {
             name: name.into(),
             input: crate::util::truncate_str(input, 40),
             children: Vec::new(),
             outcome: TraceOutcome::Incomplete,
-            start_time: Some(Instant::now()),
-            end_time: None,
+            start_micros: None,
+            duration_micros: None,
         }
     }
 
@@ -129,7 +148,6 @@ impl TraceNode {
             consumed,
             output_preview: output_preview.into(),
         };
-        self.end_time = Some(Instant::now());
     }
 
     /// Mark this node as failed
@@ -137,15 +155,6 @@ impl TraceNode {
         self.outcome = TraceOutcome::Failure {
             error: error.into(),
         };
-        self.end_time = Some(Instant::now());
-    }
-
-    /// Get duration in microseconds if timing is available
-    pub(crate) fn duration_micros(&self) -> Option<u64> {
-        match (self.start_time, self.end_time) {
-            (Some(start), Some(end)) => Some(end.duration_since(start).as_micros() as u64),
-            _ => None,
-        }
     }
 
     /// Add a child node
@@ -155,7 +164,8 @@ impl TraceNode {
 }
 
 /// Outcome of a parse attempt
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
 pub enum TraceOutcome {
     /// Parser succeeded
     Success {
@@ -174,16 +184,23 @@ pub enum TraceOutcome {
 }
 
 /// Full parse trace for an ingredient
-#[derive(Debug, Clone)]
+///
+/// Serializes to JSON with serde (`food-cli parse-ingredient --trace-out`),
+/// so a trace saved before a parser change can be [diffed](Self::diff)
+/// against one taken after.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParseTrace {
     /// Original input string
     pub input: String,
     /// Root of the trace tree
     pub root: TraceNode,
-    /// Baseline instant for converting node times to unix time
-    pub baseline_instant: Option<Instant>,
-    /// Unix timestamp (microseconds) when tracing started
+    /// Unix timestamp (microseconds) when tracing started; node
+    /// [`start_micros`](TraceNode::start_micros) offsets count from here
     pub baseline_unix_micros: u64,
+    /// The parser's [registered](crate::pass) recognizers, so
+    /// [`stages`](Self::stages) can tell their attempts from rewrites
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_recognizers: Vec<String>,
 }
 
 impl ParseTrace {
@@ -193,11 +210,11 @@ impl ParseTrace {
         Self {
             input: input.to_string(),
             root: TraceNode::new("parse_ingredient", input),
-            baseline_instant: None,
             baseline_unix_micros: SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .map(|d| d.as_micros() as u64)
                 .unwrap_or(0),
+            custom_recognizers: Vec::new(),
         }
     }
 
@@ -226,13 +243,28 @@ impl ParseTrace {
     /// [`format_stages`](Self::format_stages)) — normalize rewrites, recognizer
     /// attempts, grammar outcome, refine passes, and the result preview.
     pub fn stages(&self) -> StageReport {
-        stages::build_report(&self.root)
+        stages::build_report(&self.root, &self.custom_recognizers)
     }
 
     /// Export trace to Jaeger-compatible JSON format
     pub fn to_jaeger_json(&self) -> String {
         jaeger::to_jaeger_json(self)
     }
+
+    /// Export trace as OTLP/JSON, a trace of its own seeded by the input; see
+    /// [`otlp::OtlpTrace`] to put it in a larger trace instead.
+    #[cfg(feature = "otlp")]
+    pub fn to_otlp_json(&self) -> String {
+        let mut export = otlp::OtlpTrace::new("ingredient-parser", &self.input);
+        export.add_parse_trace(self, None);
+        export.to_json_string()
+    }
+
+    /// Align this trace (before) with `other` (after) node by node, to find
+    /// where two parses of the same line part ways. See [`TraceDiff`].
+    pub fn diff(&self, other: &ParseTrace) -> TraceDiff {
+        diff::diff(self, other)
+    }
 }
 
 /// Result of parsing with trace
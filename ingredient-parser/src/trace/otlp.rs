@@ -0,0 +1,363 @@
+//! OpenTelemetry export: parse traces as OTLP/JSON spans.
+//!
+//! [`ParseTrace::to_jaeger_json`] writes one self-contained Jaeger file per
+//! parse, with random IDs. An [`OtlpTrace`] instead collects spans from
+//! anywhere in an import — the HTTP fetch, each EPUB chunk extraction, the
+//! parse of every ingredient line — under one trace ID, and serializes them as
+//! an OTLP/JSON `ExportTraceServiceRequest`: the body an OpenTelemetry
+//! collector accepts at `POST /v1/traces`, and the file format its `file`
+//! receiver reads.
+//!
+//! [`OtlpTrace::add_parse_trace`] maps a [`TraceNode`] tree to spans carrying
+//! `parser.stage` (normalize, recognize, grammar, segment, refine),
+//! `parser.pass`, `parser.input`, `parser.consumed_bytes` and
+//! `parser.output`; a failed node gets an error status with its message.
+//!
+//! IDs are an FNV-1a hash of the seed passed to [`OtlpTrace::new`], so the
+//! same seed always yields the same trace and span IDs, whatever the Rust
+//! release; callers seed with something unique to the import (its source and
+//! start time).
+//!
+//! ```
+//! use ingredient::IngredientParser;
+//! use ingredient::trace::otlp::OtlpTrace;
+//!
+//! let trace = IngredientParser::new().parse_with_trace("2 cups flour").trace;
+//! let mut export = OtlpTrace::new("my-app", "2 cups flour");
+//! export.add_parse_trace(&trace, None);
+//! let json = export.to_json();
+//! let spans = &json["resourceSpans"][0]["scopeSpans"][0]["spans"];
+//! assert_eq!(spans[0]["name"], trace.root.name);
+//! ```
+#![cfg(feature = "otlp")]
+
+use std::fmt;
+
+use serde_json::{Value, json};
+
+use super::stages::{child_stages, is_segment_node};
+use super::{ParseTrace, TraceNode, TraceOutcome};
+
+/// A 16-byte OpenTelemetry trace ID; displays as 32 lowercase hex digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraceId(pub [u8; 16]);
+
+/// An 8-byte OpenTelemetry span ID; displays as 16 lowercase hex digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpanId(pub [u8; 8]);
+
+impl fmt::Display for TraceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{b:02x}"))
+    }
+}
+
+impl fmt::Display for SpanId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{b:02x}"))
+    }
+}
+
+/// What a span represents, as OTLP's `SpanKind` numbers it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpanKind {
+    /// Work inside the process (parsing, extraction)
+    #[default]
+    Internal,
+    /// Handling an incoming request
+    Server,
+    /// An outgoing request (an HTTP fetch, an LLM call)
+    Client,
+}
+
+impl SpanKind {
+    fn code(self) -> u8 {
+        match self {
+            SpanKind::Internal => 1,
+            SpanKind::Server => 2,
+            SpanKind::Client => 3,
+        }
+    }
+}
+
+/// A span attribute value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValue {
+    String(String),
+    Int(i64),
+    Double(f64),
+    Bool(bool),
+}
+
+impl From<&str> for AttributeValue {
+    fn from(s: &str) -> Self {
+        AttributeValue::String(s.to_string())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(s: String) -> Self {
+        AttributeValue::String(s)
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(n: i64) -> Self {
+        AttributeValue::Int(n)
+    }
+}
+
+impl From<f64> for AttributeValue {
+    fn from(n: f64) -> Self {
+        AttributeValue::Double(n)
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(b: bool) -> Self {
+        AttributeValue::Bool(b)
+    }
+}
+
+impl AttributeValue {
+    /// OTLP/JSON's `AnyValue`: 64-bit integers are strings, per the protobuf
+    /// JSON mapping.
+    fn to_json(&self) -> Value {
+        match self {
+            AttributeValue::String(s) => json!({ "stringValue": s }),
+            AttributeValue::Int(n) => json!({ "intValue": n.to_string() }),
+            AttributeValue::Double(n) => json!({ "doubleValue": n }),
+            AttributeValue::Bool(b) => json!({ "boolValue": b }),
+        }
+    }
+}
+
+/// How a span ended.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum SpanStatus {
+    /// Not set: the span neither claims success nor failure
+    #[default]
+    Unset,
+    Ok,
+    /// Failed, with a message
+    Error(String),
+}
+
+/// One finished span.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtlpSpan {
+    pub span_id: SpanId,
+    /// `None` for the trace's root span
+    pub parent_span_id: Option<SpanId>,
+    pub name: String,
+    pub kind: SpanKind,
+    pub start_unix_nanos: u64,
+    pub end_unix_nanos: u64,
+    pub attributes: Vec<(String, AttributeValue)>,
+    pub status: SpanStatus,
+}
+
+/// The spans of one trace, ready to serialize as OTLP/JSON.
+#[derive(Debug, Clone)]
+pub struct OtlpTrace {
+    service_name: String,
+    trace_id: TraceId,
+    spans: Vec<OtlpSpan>,
+    /// Span IDs handed out so far.
+    issued: u64,
+}
+
+impl OtlpTrace {
+    /// An empty trace for `service_name` (the `service.name` resource
+    /// attribute), with its IDs derived from `seed`.
+    pub fn new(service_name: impl Into<String>, seed: &str) -> Self {
+        let mut id = [0; 16];
+        id[..8].copy_from_slice(&hash_nonzero(&[seed.as_bytes(), &[0]]).to_be_bytes());
+        id[8..].copy_from_slice(&hash_nonzero(&[seed.as_bytes(), &[1]]).to_be_bytes());
+        Self {
+            service_name: service_name.into(),
+            trace_id: TraceId(id),
+            spans: Vec::new(),
+            issued: 0,
+        }
+    }
+
+    pub fn trace_id(&self) -> TraceId {
+        self.trace_id
+    }
+
+    /// The spans added so far, in the order they were added.
+    pub fn spans(&self) -> &[OtlpSpan] {
+        &self.spans
+    }
+
+    /// A span ID not yet used in this trace, for a span that will be
+    /// [pushed](Self::push) once it ends. Reserving the ID up front lets its
+    /// children name it as their parent before it is finished.
+    pub fn next_span_id(&mut self) -> SpanId {
+        self.issued += 1;
+        SpanId(hash_nonzero(&[&self.trace_id.0, &self.issued.to_le_bytes()]).to_be_bytes())
+    }
+
+    /// Add a finished span.
+    pub fn push(&mut self, span: OtlpSpan) {
+        self.spans.push(span);
+    }
+
+    /// Add every node of `trace` as a span, the root under `parent`. Returns
+    /// the root's span ID.
+    ///
+    /// A node without timing (a hand-built trace) starts where its parent
+    /// does and lasts no time.
+    pub fn add_parse_trace(&mut self, trace: &ParseTrace, parent: Option<SpanId>) -> SpanId {
+        let base = trace.baseline_unix_micros;
+        let root = &trace.root;
+        let (root_id, root_start) = self.add_node(root, parent, None, base, base);
+        for (child, stage) in root
+            .children
+            .iter()
+            .zip(child_stages(root, &trace.custom_recognizers))
+        {
+            self.add_subtree(child, root_id, stage, root_start, base);
+        }
+        root_id
+    }
+
+    fn add_subtree(
+        &mut self,
+        node: &TraceNode,
+        parent: SpanId,
+        stage: &'static str,
+        parent_start: u64,
+        base: u64,
+    ) {
+        let (id, start) = self.add_node(node, Some(parent), Some(stage), base, parent_start);
+        for child in &node.children {
+            let stage = if stage == "grammar" && is_segment_node(&child.name) {
+                "segment"
+            } else {
+                stage
+            };
+            self.add_subtree(child, id, stage, start, base);
+        }
+    }
+
+    /// Push `node`'s own span; returns its ID and start (unix microseconds).
+    /// `base` is the trace's start, which node offsets count from;
+    /// `fallback_start` is used when the node has no timing.
+    fn add_node(
+        &mut self,
+        node: &TraceNode,
+        parent: Option<SpanId>,
+        stage: Option<&'static str>,
+        base: u64,
+        fallback_start: u64,
+    ) -> (SpanId, u64) {
+        let start = node
+            .start_micros
+            .map_or(fallback_start, |offset| base.saturating_add(offset));
+        let end = start.saturating_add(node.duration_micros.unwrap_or(0));
+
+        let mut attributes: Vec<(String, AttributeValue)> = Vec::new();
+        if let Some(stage) = stage {
+            attributes.push(("parser.stage".into(), stage.into()));
+        }
+        attributes.push(("parser.pass".into(), node.name.as_str().into()));
+        attributes.push(("parser.input".into(), node.input.as_str().into()));
+        let status = match &node.outcome {
+            TraceOutcome::Success {
+                consumed,
+                output_preview,
+            } => {
+                let consumed = i64::try_from(*consumed).unwrap_or(i64::MAX);
+                attributes.push(("parser.consumed_bytes".into(), consumed.into()));
+                attributes.push(("parser.output".into(), output_preview.as_str().into()));
+                SpanStatus::Ok
+            }
+            TraceOutcome::Failure { error } => SpanStatus::Error(error.clone()),
+            TraceOutcome::Incomplete => SpanStatus::Unset,
+        };
+
+        let span_id = self.next_span_id();
+        self.push(OtlpSpan {
+            span_id,
+            parent_span_id: parent,
+            name: node.name.clone(),
+            kind: SpanKind::Internal,
+            start_unix_nanos: start.saturating_mul(1000),
+            end_unix_nanos: end.saturating_mul(1000),
+            attributes,
+            status,
+        });
+        (span_id, start)
+    }
+
+    /// The trace as an OTLP/JSON `ExportTraceServiceRequest`.
+    pub fn to_json(&self) -> Value {
+        let spans: Vec<Value> = self
+            .spans
+            .iter()
+            .map(|s| {
+                let mut span = json!({
+                    "traceId": self.trace_id.to_string(),
+                    "spanId": s.span_id.to_string(),
+                    "name": s.name,
+                    "kind": s.kind.code(),
+                    "startTimeUnixNano": s.start_unix_nanos.to_string(),
+                    "endTimeUnixNano": s.end_unix_nanos.to_string(),
+                    "attributes": attributes_json(&s.attributes),
+                });
+                if let Some(parent) = s.parent_span_id {
+                    span["parentSpanId"] = json!(parent.to_string());
+                }
+                match &s.status {
+                    SpanStatus::Unset => {}
+                    SpanStatus::Ok => span["status"] = json!({ "code": 1 }),
+                    SpanStatus::Error(message) => {
+                        span["status"] = json!({ "code": 2, "message": message });
+                    }
+                }
+                span
+            })
+            .collect();
+        json!({
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": attributes_json(&[(
+                        "service.name".to_string(),
+                        self.service_name.as_str().into(),
+                    )]),
+                },
+                "scopeSpans": [{
+                    "scope": { "name": "ingredient", "version": env!("CARGO_PKG_VERSION") },
+                    "spans": spans,
+                }],
+            }],
+        })
+    }
+
+    /// [`to_json`](Self::to_json), pretty-printed.
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string_pretty(&self.to_json()).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+fn attributes_json(attributes: &[(String, AttributeValue)]) -> Vec<Value> {
+    attributes
+        .iter()
+        .map(|(key, value)| json!({ "key": key, "value": value.to_json() }))
+        .collect()
+}
+
+/// FNV-1a over `parts` in order, never zero: OTLP treats an all-zero ID as
+/// invalid. Spelled out rather than using `DefaultHasher`, whose algorithm may
+/// change between Rust releases.
+fn hash_nonzero(parts: &[&[u8]]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    let mut hash = OFFSET;
+    for &byte in parts.iter().flat_map(|p| p.iter()) {
+        hash = (hash ^ u64::from(byte)).wrapping_mul(PRIME);
+    }
+    hash.max(1)
+}
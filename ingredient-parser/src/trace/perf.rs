@@ -0,0 +1,360 @@
+//! Pass-level profiling: where parse time goes across a whole line set.
+//!
+//! A [`ParseTrace`](super::ParseTrace) times one line, and lists only the
+//! rewrites and passes that *changed* it. A profile from
+//! [`IngredientParser::profile_passes`](crate::IngredientParser::profile_passes)
+//! times every stage, normalize rewrite, recognizer and refine pass on every
+//! line — the checks that don't fire are most of the cost — and sums them into
+//! a [`PerfReport`]: calls, fires and time per pass, compile and use counts
+//! per lazily-compiled regex, and [folded stacks](PerfReport::folded) for
+//! `flamegraph.pl` / `inferno-flamegraph`.
+//!
+//! Like tracing, the profiler is thread-local and off unless a profile is
+//! being taken; with it off, each hook is one thread-local check.
+//!
+//! ```
+//! use ingredient::IngredientParser;
+//!
+//! let report = IngredientParser::new().profile_passes(["2 cups flour", "salt, to taste"]);
+//! assert_eq!(report.lines, 2);
+//! let nbsp = report.passes.iter().find(|p| p.name == "strip_nbsp").unwrap();
+//! assert_eq!((nbsp.calls, nbsp.fired), (2, 0));
+//! assert!(report.folded().starts_with("parse_line"));
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// A timed span of the pipeline: a stage, or one pass within a stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Frame {
+    /// A whole stage (`normalize`, `recognize`, `grammar`, `refine`, …) or the
+    /// `parse_line` root.
+    Stage(&'static str),
+    /// One named pass of a stage: a normalize rewrite, a recognizer, a refine
+    /// pass.
+    Pass {
+        stage: &'static str,
+        name: &'static str,
+    },
+}
+
+impl Frame {
+    fn label(self) -> &'static str {
+        match self {
+            Frame::Stage(name) | Frame::Pass { name, .. } => name,
+        }
+    }
+}
+
+/// One normalize rewrite, recognizer or refine pass, summed over the profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassProfile {
+    /// `normalize`, `recognize` or `refine`
+    pub stage: &'static str,
+    /// The pass's trace label (e.g. `strip_nbsp`, `x_of_construction`)
+    pub name: &'static str,
+    /// Times the pass ran
+    pub calls: u64,
+    /// Times it changed the line (a rewrite, a refine pass) or claimed it (a
+    /// recognizer)
+    pub fired: u64,
+    /// Time inside the pass, nested stages included
+    pub total_nanos: u64,
+    /// Time inside the pass itself, nested stages excluded (a recognizer that
+    /// re-enters the grammar spends most of its time there)
+    pub self_nanos: u64,
+}
+
+/// One pipeline stage, summed over the profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageProfile {
+    pub name: &'static str,
+    /// Times the stage was entered (a recognizer re-entering the grammar
+    /// counts again)
+    pub calls: u64,
+    pub total_nanos: u64,
+    /// Time not spent in a pass or a nested stage
+    pub self_nanos: u64,
+}
+
+/// One lazily-compiled regex, summed over the profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegexProfile {
+    /// The static's path, e.g. `parser::normalize::TRAILING_MARK`
+    pub name: &'static str,
+    /// Compiles during the profile: 1 the first time a regex is used in the
+    /// process, 0 after that
+    pub compiles: u64,
+    pub compile_nanos: u64,
+    /// Uses: each access to the regex for an `is_match`, `find`,
+    /// `replace_all`, …, whether or not it matched
+    pub uses: u64,
+}
+
+/// Where the time went parsing a line set; see the [module docs](self).
+#[derive(Debug, Clone, Default)]
+pub struct PerfReport {
+    /// Lines parsed
+    pub lines: usize,
+    /// Wall time of all parses, profiling overhead included
+    pub total_nanos: u64,
+    /// Most total time first
+    pub stages: Vec<StageProfile>,
+    /// Every normalize rewrite, recognizer and refine pass, most total time
+    /// first; passes that never ran are listed with zero calls
+    pub passes: Vec<PassProfile>,
+    /// Every regex used during the profile, most uses first
+    pub regexes: Vec<RegexProfile>,
+    /// `(stack, self nanos)`: each stack is its frames root first, joined by
+    /// `;`. Sorted by stack.
+    pub stacks: Vec<(String, u64)>,
+}
+
+impl PerfReport {
+    /// The stacks in folded format (`parse_line;normalize;strip_nbsp 1234`,
+    /// one per line), the input `flamegraph.pl` and `inferno-flamegraph`
+    /// read. Weights are nanoseconds of self time.
+    pub fn folded(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|(stack, nanos)| format!("{stack} {nanos}\n"))
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct FrameStats {
+    calls: u64,
+    fired: u64,
+    total_nanos: u64,
+    self_nanos: u64,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct RegexStats {
+    compiles: u64,
+    compile_nanos: u64,
+    uses: u64,
+}
+
+/// An entered frame: what it is, when it was entered, and the time its
+/// children have taken so far.
+struct Open {
+    frame: Frame,
+    entered: Instant,
+    child_nanos: u64,
+}
+
+#[derive(Default)]
+struct Profiler {
+    open: Vec<Open>,
+    frames: HashMap<Frame, FrameStats>,
+    regexes: HashMap<&'static str, RegexStats>,
+    /// Self time per stack of frame labels, root first
+    stacks: HashMap<Vec<&'static str>, u64>,
+}
+
+impl Profiler {
+    fn exit(&mut self, exited: Instant, fired: bool) {
+        let Some(open) = self.open.pop() else {
+            return;
+        };
+        let total = nanos(exited.duration_since(open.entered));
+        let own = total.saturating_sub(open.child_nanos);
+        if let Some(parent) = self.open.last_mut() {
+            parent.child_nanos += total;
+        }
+
+        let stats = self.frames.entry(open.frame).or_default();
+        stats.calls += 1;
+        stats.fired += u64::from(fired);
+        stats.total_nanos += total;
+        stats.self_nanos += own;
+
+        let mut stack: Vec<&'static str> = self.open.iter().map(|o| o.frame.label()).collect();
+        stack.push(open.frame.label());
+        *self.stacks.entry(stack).or_default() += own;
+    }
+}
+
+thread_local! {
+    static PROFILER: RefCell<Option<Profiler>> = const { RefCell::new(None) };
+}
+
+/// Whether a profile is being taken on this thread.
+pub(crate) fn is_profiling() -> bool {
+    PROFILER.with(|p| p.borrow().is_some())
+}
+
+/// Enter `frame` (if profiling).
+pub(crate) fn enter(frame: Frame) {
+    PROFILER.with(|p| {
+        if let Some(profiler) = p.borrow_mut().as_mut() {
+            profiler.open.push(Open {
+                frame,
+                entered: Instant::now(),
+                child_nanos: 0,
+            });
+        }
+    });
+}
+
+/// Exit the innermost frame (if profiling). `fired` is asked after the clock
+/// stops, so a before/after comparison isn't billed to the pass.
+pub(crate) fn exit(fired: impl FnOnce() -> bool) {
+    if !is_profiling() {
+        return;
+    }
+    let exited = Instant::now();
+    let fired = fired();
+    PROFILER.with(|p| {
+        if let Some(profiler) = p.borrow_mut().as_mut() {
+            profiler.exit(exited, fired);
+        }
+    });
+}
+
+/// Run `f` inside `frame`; `fired` says whether its result counts as the pass
+/// firing.
+pub(crate) fn timed<T>(frame: Frame, f: impl FnOnce() -> T, fired: impl FnOnce(&T) -> bool) -> T {
+    if !is_profiling() {
+        return f();
+    }
+    enter(frame);
+    let out = f();
+    exit(|| fired(&out));
+    out
+}
+
+/// Run `f` inside the stage frame `name`.
+pub(crate) fn stage<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+    timed(Frame::Stage(name), f, |_| false)
+}
+
+/// Count one use of the regex `name` (if profiling).
+pub(crate) fn regex_used(name: &'static str) {
+    PROFILER.with(|p| {
+        if let Some(profiler) = p.borrow_mut().as_mut() {
+            profiler.regexes.entry(name).or_default().uses += 1;
+        }
+    });
+}
+
+/// Record the regex `name` compiling in `took` (if profiling).
+pub(crate) fn regex_compiled(name: &'static str, took: Duration) {
+    PROFILER.with(|p| {
+        if let Some(profiler) = p.borrow_mut().as_mut() {
+            let stats = profiler.regexes.entry(name).or_default();
+            stats.compiles += 1;
+            stats.compile_nanos += nanos(took);
+        }
+    });
+}
+
+/// Parse every line with `parse` under the profiler and report, listing every
+/// pass in `names` even if it never ran.
+pub(crate) fn profile<'a>(
+    names: super::PipelineStageNames,
+    lines: impl IntoIterator<Item = &'a str>,
+    mut parse: impl FnMut(&str),
+) -> PerfReport {
+    PROFILER.with(|p| *p.borrow_mut() = Some(Profiler::default()));
+    let started = Instant::now();
+    let mut count = 0;
+    for line in lines {
+        timed(Frame::Stage("parse_line"), || parse(line), |_| false);
+        count += 1;
+    }
+    let total_nanos = nanos(started.elapsed());
+    let profiler = PROFILER.with(|p| p.borrow_mut().take()).unwrap_or_default();
+    build_report(profiler, &names, count, total_nanos)
+}
+
+fn build_report(
+    profiler: Profiler,
+    names: &super::PipelineStageNames,
+    lines: usize,
+    total_nanos: u64,
+) -> PerfReport {
+    let mut stages = Vec::new();
+    let mut passes = Vec::new();
+    for (frame, s) in &profiler.frames {
+        match *frame {
+            Frame::Stage(name) => stages.push(StageProfile {
+                name,
+                calls: s.calls,
+                total_nanos: s.total_nanos,
+                self_nanos: s.self_nanos,
+            }),
+            Frame::Pass { stage, name } => passes.push(PassProfile {
+                stage,
+                name,
+                calls: s.calls,
+                fired: s.fired,
+                total_nanos: s.total_nanos,
+                self_nanos: s.self_nanos,
+            }),
+        }
+    }
+    // List the passes that never ran, so a profile shows the whole pipeline.
+    for (stage, labels) in [
+        ("normalize", &names.normalize),
+        ("recognize", &names.recognizers),
+        ("refine", &names.refine),
+    ] {
+        for &name in labels {
+            if !passes.iter().any(|p| p.stage == stage && p.name == name) {
+                passes.push(PassProfile {
+                    stage,
+                    name,
+                    calls: 0,
+                    fired: 0,
+                    total_nanos: 0,
+                    self_nanos: 0,
+                });
+            }
+        }
+    }
+    stages.sort_by(|a, b| b.total_nanos.cmp(&a.total_nanos).then(a.name.cmp(b.name)));
+    passes.sort_by(|a, b| {
+        b.total_nanos
+            .cmp(&a.total_nanos)
+            .then(a.stage.cmp(b.stage))
+            .then(a.name.cmp(b.name))
+    });
+
+    let mut regexes: Vec<RegexProfile> = profiler
+        .regexes
+        .into_iter()
+        .map(|(name, s)| RegexProfile {
+            name: name.strip_prefix("ingredient::").unwrap_or(name),
+            compiles: s.compiles,
+            compile_nanos: s.compile_nanos,
+            uses: s.uses,
+        })
+        .collect();
+    regexes.sort_by(|a, b| b.uses.cmp(&a.uses).then(a.name.cmp(b.name)));
+
+    let mut stacks: Vec<(String, u64)> = profiler
+        .stacks
+        .into_iter()
+        .map(|(frames, nanos)| (frames.join(";"), nanos))
+        .collect();
+    stacks.sort();
+
+    PerfReport {
+        lines,
+        total_nanos,
+        stages,
+        passes,
+        regexes,
+        stacks,
+    }
+}
+
+/// A duration in whole nanoseconds, saturating.
+fn nanos(d: Duration) -> u64 {
+    u64::try_from(d.as_nanos()).unwrap_or(u64::MAX)
+}
@@ -73,8 +73,14 @@ impl StageReport {
     }
 }
 
-fn is_core_node(name: &str) -> bool {
-    name == GRAMMAR_NAME || RECOGNIZER_TRACE_NAMES.contains(&name)
+/// Whether `name` is a recognizer: a built-in, or one of `custom` (the
+/// parser's [registered](crate::pass) recognizers).
+fn is_recognizer(name: &str, custom: &[String]) -> bool {
+    RECOGNIZER_TRACE_NAMES.contains(&name) || custom.iter().any(|c| c == name)
+}
+
+fn is_core_node(name: &str, custom: &[String]) -> bool {
+    name == GRAMMAR_NAME || is_recognizer(name, custom)
 }
 
 fn success_preview(node: &TraceNode) -> Option<&str> {
@@ -87,12 +93,12 @@ fn success_preview(node: &TraceNode) -> Option<&str> {
 /// Find the grammar node among the core children, whether it's a direct child
 /// (no recognizer matched) or nested under a successful recognizer (e.g.
 /// `x_of_construction` re-parses its rewritten line through the grammar).
-fn find_grammar(core: &[TraceNode]) -> Option<&TraceNode> {
+fn find_grammar<'a>(core: &'a [TraceNode], custom: &[String]) -> Option<&'a TraceNode> {
     for c in core {
         if c.name == GRAMMAR_NAME {
             return Some(c);
         }
-        if RECOGNIZER_TRACE_NAMES.contains(&c.name.as_str())
+        if is_recognizer(&c.name, custom)
             && let Some(g) = c.children.iter().find(|g| g.name == GRAMMAR_NAME)
         {
             return Some(g);
@@ -109,11 +115,40 @@ fn rewrite_from(node: &TraceNode) -> StageRewrite {
     }
 }
 
-/// Bucket a trace root's direct children into pipeline stages.
-pub(super) fn build_report(root: &TraceNode) -> StageReport {
+/// The pipeline stage of each of a trace root's direct children, by the same
+/// bucketing as [`build_report`]: `normalize`, `recognize`, `grammar` or
+/// `refine`. (`segment` decisions nest inside the grammar node; see
+/// [`SEGMENT_TRACE_NAMES`].)
+#[cfg(feature = "otlp")]
+pub(super) fn child_stages(root: &TraceNode, custom: &[String]) -> Vec<&'static str> {
+    let children = &root.children;
+    let first_core = children.iter().position(|c| is_core_node(&c.name, custom));
+    let last_core = children.iter().rposition(|c| is_core_node(&c.name, custom));
+    children
+        .iter()
+        .enumerate()
+        .map(|(idx, c)| match (first_core, last_core) {
+            (Some(i), _) if idx < i => "normalize",
+            (Some(_), Some(j)) if idx > j => "refine",
+            (Some(_), _) if c.name == GRAMMAR_NAME => "grammar",
+            (Some(_), _) => "recognize",
+            (None, _) => "normalize",
+        })
+        .collect()
+}
+
+/// Whether a node nested in the grammar is a segmentation decision.
+#[cfg(feature = "otlp")]
+pub(super) fn is_segment_node(name: &str) -> bool {
+    SEGMENT_TRACE_NAMES.contains(&name)
+}
+
+/// Bucket a trace root's direct children into pipeline stages. `custom` names
+/// the parser's registered recognizers.
+pub(super) fn build_report(root: &TraceNode, custom: &[String]) -> StageReport {
     let children = &root.children;
-    let first_core = children.iter().position(|c| is_core_node(&c.name));
-    let last_core = children.iter().rposition(|c| is_core_node(&c.name));
+    let first_core = children.iter().position(|c| is_core_node(&c.name, custom));
+    let last_core = children.iter().rposition(|c| is_core_node(&c.name, custom));
 
     // normalize — every node before the first core (recognizer/grammar) node.
     let normalize_nodes = match first_core {
@@ -130,13 +165,13 @@ pub(super) fn build_report(root: &TraceNode) -> StageReport {
             let core = &children[i..=j];
             let recognizers = core
                 .iter()
-                .filter(|c| RECOGNIZER_TRACE_NAMES.contains(&c.name.as_str()))
+                .filter(|c| is_recognizer(&c.name, custom))
                 .map(|c| RecognizerAttempt {
                     name: c.name.clone(),
                     output: success_preview(c).map(str::to_string),
                 })
                 .collect();
-            let grammar_node = find_grammar(core);
+            let grammar_node = find_grammar(core, custom);
             let grammar = match grammar_node {
                 Some(g) => match success_preview(g) {
                     Some(p) => GrammarOutcome::Parsed(p.to_string()),
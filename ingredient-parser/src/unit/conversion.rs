@@ -319,7 +319,42 @@ pub fn find_connected_components(graph: &MeasureGraph) -> Vec<Vec<String>> {
 pub struct ConversionStep {
     pub from_unit: Unit,
     pub to_unit: Unit,
+    /// The edge's lower bound ([`EdgeFactor::lower`]).
     pub factor: f64,
+    /// The edge's upper bound; equal to `factor` for a point mapping.
+    pub factor_upper: f64,
+    /// The amount after this hop, in `to_unit`, unrounded: the lower bound…
+    pub value: f64,
+    /// …and the upper bound (equal to `value` unless the amount or an edge so
+    /// far is a range).
+    pub upper_value: f64,
+}
+
+impl ConversionStep {
+    /// Index of the mapping in `mappings` (as passed to [`make_graph`]) that
+    /// supplied this hop's edge: the last one joining its two nodes, since a
+    /// later conflicting mapping replaces an earlier one. `None` for the hops
+    /// the engine owns — the teaspoon↔milliliter bridge and the pinch/dash
+    /// rescale.
+    ///
+    /// ```
+    /// use ingredient::unit::{convert_measure_with_graph_explained, make_graph, Measure, MeasureKind};
+    ///
+    /// let mappings = [(Measure::new("cup", 1.0), Measure::new("g", 120.0))];
+    /// let graph = make_graph(&mappings);
+    /// let (_, steps) =
+    ///     convert_measure_with_graph_explained(&Measure::new("ml", 100.0), MeasureKind::Weight, &graph)
+    ///         .unwrap();
+    /// let sources: Vec<_> = steps.iter().map(|s| s.mapping_index(&mappings)).collect();
+    /// assert_eq!(sources, [None, Some(0)]);
+    /// ```
+    pub fn mapping_index(&self, mappings: &[(Measure, Measure)]) -> Option<usize> {
+        mappings.iter().rposition(|(a, b)| {
+            let a = a.normalize().unit().normalize();
+            let b = b.normalize().unit().normalize();
+            (a == self.from_unit && b == self.to_unit) || (a == self.to_unit && b == self.from_unit)
+        })
+    }
 }
 
 /// A tiny volumetric unit's size in teaspoons, if `unit` is one.
@@ -343,6 +378,9 @@ fn tiny_volume_in_tsp(unit: &Unit) -> Option<f64> {
 /// from the source unit to a unit of the target kind. The conversion factor
 /// is computed by multiplying all edge weights along the path.
 ///
+/// A qualified measure ("heaping tbsp", "about 2 lb") converts as the range
+/// its [`Qualifier`](super::Qualifier) reads as, so the result is a range.
+///
 /// Use this when converting multiple measures to avoid rebuilding the graph
 /// each time. Build the graph once with [`make_graph`] and reuse it.
 ///
@@ -380,7 +418,15 @@ pub fn convert_measure_with_graph_explained(
     target: MeasureKind,
     graph: &MeasureGraph,
 ) -> Option<(Measure, Vec<ConversionStep>)> {
-    let input = measure.normalize();
+    // A qualified amount converts as the range its qualifier reads as (see
+    // `Measure::interpreted`); only an "at least" floor carries through as-is.
+    let interpreted = measure.interpreted();
+    let carried = interpreted.qualifier();
+    let keep_qualifier = |m: Measure| match carried {
+        Some(q) => m.with_qualifier(q),
+        None => m,
+    };
+    let input = interpreted.normalize();
     // Normalize BOTH endpoints the exact way `make_graph` normalizes its nodes
     // (`unit().normalize()` — lowercase + singularize + promote known aliases), so
     // the node lookups below can't miss. Without this on the target, a descriptor
@@ -414,6 +460,9 @@ pub fn convert_measure_with_graph_explained(
             from_unit: unit_a,
             to_unit: Unit::Teaspoon,
             factor: tsp,
+            factor_upper: tsp,
+            value: input.value() * tsp,
+            upper_value: input.upper_value().unwrap_or(input.value()) * tsp,
         });
         input = Measure::new_with_upper(
             Unit::Teaspoon,
@@ -442,7 +491,7 @@ pub fn convert_measure_with_graph_explained(
         let resolved = Measure::new_with_upper(unit_b, lo, hi);
         // `steps` is empty unless a pinch/dash rescale landed us on the target
         // node, in which case that one synthetic hop IS the whole path.
-        return Some((resolved.denormalize(), steps));
+        return Some((keep_qualifier(resolved.denormalize()), steps));
     }
 
     let n_a = graph.node_indices().find(|i| graph[*i] == unit_a)?;
@@ -464,29 +513,29 @@ pub fn convert_measure_with_graph_explained(
     };
     let mut factor_lo: f64 = 1.0;
     let mut factor_hi: f64 = 1.0;
+    let input_val = input.value();
+    let input_upper = input.upper_value();
     steps.reserve(path.len().saturating_sub(1));
     for x in 0..path.len() - 1 {
         let (n_from, n_to) = (*path.get(x)?, *path.get(x + 1)?);
         let edge = graph.find_edge(n_from, n_to)?;
         let weight = *graph.edge_weight(edge)?;
+        factor_lo *= weight.lower;
+        factor_hi *= weight.upper;
         steps.push(ConversionStep {
             from_unit: graph[n_from].clone(),
             to_unit: graph[n_to].clone(),
-            // Report the lower bound: point edges have lower == upper, and the
-            // only ranged edges (sub-recipe yields) never appear in ingredient
-            // explain paths, so a single display factor stays faithful.
             factor: weight.lower,
+            factor_upper: weight.upper,
+            value: input_val * factor_lo,
+            upper_value: input_upper.unwrap_or(input_val) * factor_hi,
         });
-        factor_lo *= weight.lower;
-        factor_hi *= weight.upper;
     }
 
     // Result range = input range × factor interval (all bounds positive):
     // lower = input_lo × factor_lo, upper = input_hi × factor_hi. Suppress an
     // upper that rounds equal to the lower so a point conversion of a point
     // amount never fabricates a range.
-    let input_val = input.value();
-    let input_upper = input.upper_value();
     let lower = round_sig(input_val * factor_lo, RESULT_SIG_FIGS);
     let upper = round_sig(
         input_upper.unwrap_or(input_val) * factor_hi,
@@ -494,7 +543,7 @@ pub fn convert_measure_with_graph_explained(
     );
     let result = Measure::new_with_upper(unit_b, lower, (upper > lower).then_some(upper));
     debug!("{:?} -> {:?} ({} hops)", input, result, path.len());
-    Some((result.denormalize(), steps))
+    Some((keep_qualifier(result.denormalize()), steps))
 }
 
 /// Unit node used by [`make_graph`] for a display unit string (e.g. `"cup"` →
@@ -877,6 +926,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn explain_steps_carry_both_bounds_and_their_mapping() {
+        // A ranged sub-recipe yield: 1 batch = $6–8. The step keeps both bounds,
+        // and names the later of two conflicting mappings as its source.
+        let mappings = vec![
+            (Measure::new("batch", 1.0), Measure::new("$", 5.0)),
+            (
+                Measure::new("batch", 1.0),
+                Measure::with_range("$", 6.0, 8.0),
+            ),
+        ];
+        let graph = make_graph(&mappings);
+        let (_, steps) = convert_measure_with_graph_explained(
+            &Measure::new("batch", 2.0),
+            MeasureKind::Money,
+            &graph,
+        )
+        .unwrap();
+
+        assert_eq!(steps.len(), 1);
+        assert_eq!((steps[0].factor, steps[0].factor_upper), (600.0, 800.0));
+        assert_eq!((steps[0].value, steps[0].upper_value), (1200.0, 1600.0));
+        assert_eq!(steps[0].mapping_index(&mappings), Some(1));
+    }
+
     #[test]
     fn pinch_converts_to_volume_and_carries_a_range() {
         let mappings = vec![(Measure::new("cup", 1.0), Measure::new("g", 480.0))];
@@ -8,11 +8,11 @@ use std::str::FromStr;
 use std::sync::LazyLock;
 
 pub fn is_valid(units: &HashSet<String>, s: &str) -> bool {
-    // A built-in unit, or a registered addon unit. Checked via `Unit::is_known`
+    // A built-in unit, or a registered addon unit. Checked via `Unit::known`
     // rather than `from_str(..) != Other`, which allocated a throwaway
     // `Unit::Other(String)` on every miss — this runs for every unit token on
     // every parse.
-    Unit::is_known(&singular(s)) || is_addon_unit(units, s)
+    Unit::known(s).is_some() || is_addon_unit(units, s)
 }
 
 /// Check if a string matches an addon unit (from the custom units set)
@@ -40,6 +40,23 @@ pub enum Unit {
     Quart,
     Gallon,
     FluidOunce,
+    /// US pint (2 cups).
+    Pint,
+    // imperial and other regional volumes: see `Region`
+    ImperialFluidOunce,
+    ImperialPint,
+    ImperialQuart,
+    ImperialGallon,
+    /// 250 ml: the cup in Australian, New Zealand and modern British recipes.
+    MetricCup,
+    /// 20 ml.
+    AustralianTablespoon,
+    /// 10 ml.
+    Dessertspoon,
+    /// Imperial gill, ¼ imperial pint.
+    Gill,
+    /// ⅓ imperial pint, the older British teacup.
+    Teacup,
     Ounce,
     Pound,
     Cent,
@@ -55,6 +72,8 @@ pub enum Unit {
     Celsius,
     //distance
     Inch,
+    Centimeter,
+    Millimeter,
     Whole,
     // https://stackoverflow.com/a/77723851
     #[serde(untagged)]
@@ -98,6 +117,16 @@ impl Unit {
             Unit::Quart => Cow::Borrowed("quart"),
             Unit::Gallon => Cow::Borrowed("gallon"),
             Unit::FluidOunce => Cow::Borrowed("fl oz"),
+            Unit::Pint => Cow::Borrowed("pint"),
+            Unit::ImperialFluidOunce => Cow::Borrowed("imperial fl oz"),
+            Unit::ImperialPint => Cow::Borrowed("imperial pint"),
+            Unit::ImperialQuart => Cow::Borrowed("imperial quart"),
+            Unit::ImperialGallon => Cow::Borrowed("imperial gallon"),
+            Unit::MetricCup => Cow::Borrowed("metric cup"),
+            Unit::AustralianTablespoon => Cow::Borrowed("au tbsp"),
+            Unit::Dessertspoon => Cow::Borrowed("dessertspoon"),
+            Unit::Gill => Cow::Borrowed("gill"),
+            Unit::Teacup => Cow::Borrowed("teacup"),
             Unit::Ounce => Cow::Borrowed("oz"),
             Unit::Pound => Cow::Borrowed("lb"),
             Unit::Cent => Cow::Borrowed("cent"),
@@ -110,6 +139,8 @@ impl Unit {
             Unit::Fahrenheit => Cow::Borrowed("fahrenheit"),
             Unit::Celsius => Cow::Borrowed("celsius"),
             Unit::Inch => Cow::Borrowed("\""),
+            Unit::Centimeter => Cow::Borrowed("cm"),
+            Unit::Millimeter => Cow::Borrowed("mm"),
             Unit::Whole => Cow::Borrowed("whole"),
             Unit::Other(s) => Cow::Owned(singular(s).into_owned()),
         }
@@ -132,7 +163,17 @@ impl Unit {
             | Unit::Cup
             | Unit::Quart
             | Unit::Gallon
-            | Unit::FluidOunce => MeasureKind::Volume,
+            | Unit::FluidOunce
+            | Unit::Pint
+            | Unit::ImperialFluidOunce
+            | Unit::ImperialPint
+            | Unit::ImperialQuart
+            | Unit::ImperialGallon
+            | Unit::MetricCup
+            | Unit::AustralianTablespoon
+            | Unit::Dessertspoon
+            | Unit::Gill
+            | Unit::Teacup => MeasureKind::Volume,
 
             // Money units
             Unit::Cent | Unit::Dollar => MeasureKind::Money,
@@ -147,7 +188,7 @@ impl Unit {
             Unit::KCal => MeasureKind::Calories,
 
             // Length units
-            Unit::Inch => MeasureKind::Length,
+            Unit::Inch | Unit::Centimeter | Unit::Millimeter => MeasureKind::Length,
 
             // Other/custom units
             Unit::Whole => MeasureKind::Other("whole".to_string()),
@@ -184,6 +225,32 @@ static UNIT_MAPPINGS: &[(&str, Unit)] = &[
     ("gal", Unit::Gallon),
     ("fl oz", Unit::FluidOunce),
     ("fluid oz", Unit::FluidOunce),
+    ("pint", Unit::Pint),
+    ("pt", Unit::Pint),
+    // Region-qualified spellings name one variant whatever the parser's
+    // `Region`; the bare words above are resolved through it.
+    ("us cup", Unit::Cup),
+    ("us pint", Unit::Pint),
+    ("us quart", Unit::Quart),
+    ("us gallon", Unit::Gallon),
+    ("us fl oz", Unit::FluidOunce),
+    ("imperial fl oz", Unit::ImperialFluidOunce),
+    ("uk fl oz", Unit::ImperialFluidOunce),
+    ("imperial pint", Unit::ImperialPint),
+    ("uk pint", Unit::ImperialPint),
+    ("imperial quart", Unit::ImperialQuart),
+    ("uk quart", Unit::ImperialQuart),
+    ("imperial gallon", Unit::ImperialGallon),
+    ("uk gallon", Unit::ImperialGallon),
+    ("metric cup", Unit::MetricCup),
+    ("au tbsp", Unit::AustralianTablespoon),
+    ("australian tablespoon", Unit::AustralianTablespoon),
+    ("dessertspoon", Unit::Dessertspoon),
+    ("dsp", Unit::Dessertspoon),
+    ("dessertspoonful", Unit::Dessertspoon),
+    ("gill", Unit::Gill),
+    ("teacup", Unit::Teacup),
+    ("teacupful", Unit::Teacup),
     ("oz", Unit::Ounce),
     ("ounce", Unit::Ounce),
     ("lb", Unit::Pound),
@@ -215,6 +282,12 @@ static UNIT_MAPPINGS: &[(&str, Unit)] = &[
     ("\"", Unit::Inch),
     //distance
     ("inch", Unit::Inch),
+    ("cm", Unit::Centimeter),
+    ("centimeter", Unit::Centimeter),
+    ("centimetre", Unit::Centimeter),
+    ("mm", Unit::Millimeter),
+    ("millimeter", Unit::Millimeter),
+    ("millimetre", Unit::Millimeter),
     ("whole", Unit::Whole),
     ("each", Unit::Whole),
 ];
@@ -223,6 +296,25 @@ static UNIT_MAPPINGS: &[(&str, Unit)] = &[
 static UNIT_MAP: LazyLock<HashMap<&'static str, Unit>> =
     LazyLock::new(|| UNIT_MAPPINGS.iter().map(|(s, u)| (*s, u.clone())).collect());
 
+/// Every built-in unit spelling with its regular plural ("teaspoon",
+/// "teaspoons", "tsp", "tsps", …): the dictionary typo correction matches
+/// against.
+pub(crate) fn spellings() -> impl Iterator<Item = &'static str> {
+    static SPELLINGS: LazyLock<Vec<String>> = LazyLock::new(|| {
+        UNIT_MAPPINGS
+            .iter()
+            .flat_map(|(s, _)| {
+                let plural = match s.as_bytes().last() {
+                    Some(b's' | b'h' | b'x') => format!("{s}es"),
+                    _ => format!("{s}s"),
+                };
+                [s.to_string(), plural]
+            })
+            .collect()
+    });
+    SPELLINGS.iter().map(String::as_str)
+}
+
 impl Unit {
     /// True if `s` (expected already singularized) maps to a built-in unit.
     /// Pure lookup — unlike `from_str`, it never allocates an `Other(String)`
@@ -230,6 +322,24 @@ impl Unit {
     pub(crate) fn is_known(s: &str) -> bool {
         UNIT_MAP.contains_key(s)
     }
+
+    /// The built-in unit `s` spells, singular or plural. A unit whose name
+    /// ends in "s" ("celsius") is tried as written when its singular misses.
+    fn known(s: &str) -> Option<&'static Unit> {
+        UNIT_MAP.get(&*singular(s)).or_else(|| {
+            if !s.ends_with(['s', 'S']) {
+                return None;
+            }
+            // Borrow unless there's something to lowercase, as `singular`
+            // does: this retry runs for every plural miss ("eggs").
+            let lowered = if s.bytes().any(|b| b.is_ascii_uppercase()) {
+                Cow::Owned(s.to_lowercase())
+            } else {
+                Cow::Borrowed(s)
+            };
+            UNIT_MAP.get(&*lowered)
+        })
+    }
 }
 
 impl FromStr for Unit {
@@ -239,9 +349,8 @@ impl FromStr for Unit {
         // `singular` lowercases internally, borrowing without allocation when the
         // input is already lowercase ASCII (the common case off a recipe line),
         // so there's no need for an unconditional `to_lowercase()` here.
-        let s_norm = singular(s);
         // O(1) lookup using HashMap
-        if let Some(unit) = UNIT_MAP.get(&*s_norm) {
+        if let Some(unit) = Unit::known(s) {
             return Ok(unit.clone());
         }
         Ok(Unit::Other(s.to_string()))
@@ -289,6 +398,13 @@ pub fn singular(s: &str) -> Cow<'_, str> {
 mod tests {
     use super::*;
 
+    /// Singularizing would read "celsius" as "celsiu", which isn't a unit.
+    #[test]
+    fn unit_ending_in_s_is_known() {
+        assert_eq!(Unit::from_str("celsius"), Ok(Unit::Celsius));
+        assert!(is_valid(&HashSet::new(), "Celsius"));
+    }
+
     #[test]
     fn test_singular_plural_suffixes() {
         // Bare trailing "s"
@@ -0,0 +1,480 @@
+//! Sizes rather than amounts: the "9x13-inch" of a pan, the "2-inch" of a
+//! piece, the "½-inch-thick" of a slice. A [`Dimension`] never scales with the
+//! recipe, converts between inches and centimeters as a whole, and for pans
+//! knows its base area so a scaled recipe can be matched to a pan.
+
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::measure::{deserialize_unit, serialize_unit};
+use super::{Measure, MeasureKind, Unit};
+use crate::util::format_quantity;
+
+const CM_PER_INCH: f64 = 2.54;
+
+/// How far a scaled pan's area may stray from a standard pan's and still be
+/// offered as a match.
+const PAN_AREA_TOLERANCE: f64 = 0.2;
+
+/// The word after a dimension saying which extent it is ("½-inch-thick") or,
+/// for a pan given by one side, its shape ("8-inch square").
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Aspect {
+    Thick,
+    Long,
+    Wide,
+    Deep,
+    Tall,
+    Round,
+    Square,
+}
+
+impl Aspect {
+    const ALL: [Aspect; 7] = [
+        Aspect::Thick,
+        Aspect::Long,
+        Aspect::Wide,
+        Aspect::Deep,
+        Aspect::Tall,
+        Aspect::Round,
+        Aspect::Square,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Aspect::Thick => "thick",
+            Aspect::Long => "long",
+            Aspect::Wide => "wide",
+            Aspect::Deep => "deep",
+            Aspect::Tall => "tall",
+            Aspect::Round => "round",
+            Aspect::Square => "square",
+        }
+    }
+}
+
+impl FromStr for Aspect {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Aspect::ALL
+            .into_iter()
+            .find(|a| a.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown aspect: {s}"))
+    }
+}
+
+/// One or more lengths in a single unit: a cut size ("2-inch"), a thickness
+/// ("½-inch-thick") or a pan ("9x13-inch", "23cm round").
+///
+/// ```
+/// use ingredient::unit::Dimension;
+///
+/// let pan = Dimension::parse("9x13-inch").unwrap();
+/// assert_eq!(pan.to_string(), "9x13 inch");
+/// assert_eq!(pan.to_metric().to_string(), "23x33 cm");
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Dimension {
+    sides: Vec<f64>,
+    #[serde(
+        serialize_with = "serialize_unit",
+        deserialize_with = "deserialize_unit"
+    )]
+    unit: Unit,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    aspect: Option<Aspect>,
+}
+
+/// A standard pan: its sides in the pan's own unit, and the shape when it is
+/// given by one side.
+type Pan = (&'static [f64], Option<Aspect>);
+
+const IMPERIAL_PANS: &[Pan] = &[
+    (&[8.0], Some(Aspect::Square)),
+    (&[9.0], Some(Aspect::Square)),
+    (&[8.0], Some(Aspect::Round)),
+    (&[9.0], Some(Aspect::Round)),
+    (&[10.0], Some(Aspect::Round)),
+    (&[9.0, 5.0], None),
+    (&[11.0, 7.0], None),
+    (&[9.0, 13.0], None),
+    (&[10.0, 15.0], None),
+    (&[13.0, 18.0], None),
+];
+
+const METRIC_PANS: &[Pan] = &[
+    (&[20.0], Some(Aspect::Square)),
+    (&[23.0], Some(Aspect::Square)),
+    (&[20.0], Some(Aspect::Round)),
+    (&[23.0], Some(Aspect::Round)),
+    (&[25.0], Some(Aspect::Round)),
+    (&[23.0, 13.0], None),
+    (&[28.0, 18.0], None),
+    (&[23.0, 33.0], None),
+    (&[25.0, 38.0], None),
+    (&[33.0, 46.0], None),
+];
+
+impl Dimension {
+    /// A dimension of `sides` in `unit`; `None` unless `unit` is a length and
+    /// there is at least one side.
+    pub fn new(sides: Vec<f64>, unit: Unit) -> Option<Dimension> {
+        (unit.kind() == MeasureKind::Length && !sides.is_empty()).then_some(Dimension {
+            sides,
+            unit,
+            aspect: None,
+        })
+    }
+
+    /// Say which extent this is, or the pan's shape (chainable).
+    pub fn with_aspect(mut self, aspect: Aspect) -> Dimension {
+        self.aspect = Some(aspect);
+        self
+    }
+
+    /// The one-sided dimension a parsed length amount gives ("1 (9-inch) pie
+    /// crust" carries `9 "`); `None` for other kinds and for ranges.
+    pub fn from_measure(m: &Measure) -> Option<Dimension> {
+        if m.upper_value().is_some() {
+            return None;
+        }
+        Dimension::new(vec![m.value()], m.unit().clone())
+    }
+
+    /// Read a whole string as a dimension: "9x13-inch", "20 x 30 cm",
+    /// "½-inch-thick", "8\" square". `None` when anything is left over.
+    pub fn parse(s: &str) -> Option<Dimension> {
+        let s = s.trim();
+        match dimension_prefix(s) {
+            Some((len, d)) if len == s.len() => Some(d),
+            _ => None,
+        }
+    }
+
+    pub fn sides(&self) -> &[f64] {
+        &self.sides
+    }
+
+    pub fn unit(&self) -> &Unit {
+        &self.unit
+    }
+
+    pub fn aspect(&self) -> Option<Aspect> {
+        self.aspect
+    }
+
+    /// The same dimension in `unit`, unrounded; `None` if `unit` isn't a
+    /// length this knows the size of.
+    pub fn to_unit(&self, unit: Unit) -> Option<Dimension> {
+        let from = cm_per(&self.unit)?;
+        let to = cm_per(&unit)?;
+        Some(Dimension {
+            sides: self.sides.iter().map(|s| s * from / to).collect(),
+            unit,
+            aspect: self.aspect,
+        })
+    }
+
+    /// In centimeters (millimeters below 1 cm), rounded the way a metric
+    /// recipe states sizes: to the half centimeter from 2 cm up.
+    pub fn to_metric(&self) -> Dimension {
+        let Some(cm) = self.to_unit(Unit::Centimeter) else {
+            return self.clone();
+        };
+        if cm.sides.iter().all(|s| *s < 1.0) {
+            let mm = cm.sides.iter().map(|s| (s * 10.0).round().max(1.0));
+            return Dimension {
+                sides: mm.collect(),
+                unit: Unit::Millimeter,
+                aspect: self.aspect,
+            };
+        }
+        Dimension {
+            sides: cm.sides.iter().map(|s| round_side(*s, 0.1)).collect(),
+            ..cm
+        }
+    }
+
+    /// In inches, rounded to the half inch from 2 inches up and to the eighth
+    /// below, the way American recipes give sizes.
+    pub fn to_imperial(&self) -> Dimension {
+        let Some(inch) = self.to_unit(Unit::Inch) else {
+            return self.clone();
+        };
+        Dimension {
+            sides: inch.sides.iter().map(|s| round_side(*s, 0.125)).collect(),
+            ..inch
+        }
+    }
+
+    /// The base area in square centimeters: the product of the first two sides,
+    /// or for a one-sided pan the circle or square it names. `None` when the
+    /// dimension doesn't describe an area (a "2-inch" piece).
+    pub fn area_cm2(&self) -> Option<f64> {
+        let cm = cm_per(&self.unit)?;
+        match (self.sides.as_slice(), self.aspect) {
+            ([a, b, ..], _) => Some(a * cm * b * cm),
+            ([d], Some(Aspect::Round)) => Some(std::f64::consts::PI * (d * cm / 2.0).powi(2)),
+            ([d], Some(Aspect::Square)) => Some((d * cm).powi(2)),
+            _ => None,
+        }
+    }
+
+    /// The standard pan, in this dimension's system, whose area is closest to
+    /// this pan's scaled by `factor` (a doubled 8-inch square wants a 9x13);
+    /// `None` when no standard pan is within 20%.
+    ///
+    /// ```
+    /// use ingredient::unit::Dimension;
+    ///
+    /// let pan = Dimension::parse("8-inch square").unwrap();
+    /// assert_eq!(pan.matching_pan(2.0).unwrap().to_string(), "9x13 inch");
+    /// ```
+    pub fn matching_pan(&self, factor: f64) -> Option<Dimension> {
+        let want = self.area_cm2()? * factor;
+        let (pans, unit) = if self.unit == Unit::Inch {
+            (IMPERIAL_PANS, Unit::Inch)
+        } else {
+            (METRIC_PANS, Unit::Centimeter)
+        };
+        pans.iter()
+            .filter_map(|(sides, aspect)| {
+                let pan = Dimension {
+                    sides: sides.to_vec(),
+                    unit: unit.clone(),
+                    aspect: *aspect,
+                };
+                let off = (pan.area_cm2()? - want).abs() / want;
+                (off <= PAN_AREA_TOLERANCE).then_some((off, pan))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, pan)| pan)
+    }
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sides: Vec<String> = self.sides.iter().map(|s| format_quantity(*s)).collect();
+        let unit = match &self.unit {
+            Unit::Inch => "inch".into(),
+            u => u.to_str(),
+        };
+        write!(f, "{} {unit}", sides.join("x"))?;
+        if let Some(aspect) = self.aspect {
+            write!(f, " {}", aspect.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+/// Every dimension in `text` with its byte span, e.g. the "2-inch" in "cut
+/// into 2-inch pieces". Used to read sizes out of modifiers and prose.
+pub fn find_dimensions(text: &str) -> Vec<(Range<usize>, Dimension)> {
+    let mut found = vec![];
+    let mut at = 0;
+    while let Some(m) = DIMENSION.find_at(text, at) {
+        let start = m.start();
+        let glued = text[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || matches!(c, '.' | '/' | ','));
+        match dimension_prefix(&text[start..]) {
+            Some((len, d)) if !glued => {
+                found.push((start..start + len, d));
+                at = start + len;
+            }
+            _ => at = start + text[start..].chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    found
+}
+
+/// The dimension at the very start of `input` and how many bytes it spans.
+pub(crate) fn dimension_prefix(input: &str) -> Option<(usize, Dimension)> {
+    let m = DIMENSION_AT.find(input)?;
+    let span = m.as_str();
+    let mut sides = vec![];
+    let mut unit = None;
+    for caps in SIDE.captures_iter(span) {
+        sides.push(number(caps.get(1)?.as_str())?);
+        if let Some(u) = caps.get(2) {
+            unit = Some(length_unit(u.as_str()));
+        }
+    }
+    let mut d = Dimension::new(sides, unit?)?;
+    if let Some(word) = ASPECT.captures(span).and_then(|c| c.get(1)) {
+        d = d.with_aspect(word.as_str().parse().ok()?);
+    }
+    Some((m.end(), d))
+}
+
+fn length_unit(s: &str) -> Unit {
+    match s.to_lowercase().chars().next() {
+        Some('c') => Unit::Centimeter,
+        Some('m') => Unit::Millimeter,
+        _ => Unit::Inch,
+    }
+}
+
+/// A number as recipes write sizes: "2", "1.5", "½", "1½", "1 1/2".
+fn number(s: &str) -> Option<f64> {
+    let s = s.trim();
+    // Sizes are read out of already-parsed text; keep the fraction parser out
+    // of the parse trace.
+    let frac = crate::trace::untraced(|| crate::fraction::fraction_number(s));
+    match frac {
+        Ok((rest, v)) if rest.trim().is_empty() => Some(v),
+        _ => s.parse().ok().filter(|v: &f64| v.is_finite() && *v > 0.0),
+    }
+}
+
+fn cm_per(unit: &Unit) -> Option<f64> {
+    match unit {
+        Unit::Inch => Some(CM_PER_INCH),
+        Unit::Centimeter => Some(1.0),
+        Unit::Millimeter => Some(0.1),
+        _ => None,
+    }
+}
+
+/// Round to the half unit from 2 up, otherwise to `fine` (never to zero).
+fn round_side(v: f64, fine: f64) -> f64 {
+    let step = if v >= 2.0 { 0.5 } else { fine };
+    ((v / step).round() * step).max(step)
+}
+
+const NUM: &str =
+    r"(?:\d+\s+\d+/\d+|\d+/\d+|\d+(?:\.\d+)?\s?[¼½¾⅐⅑⅒⅓⅔⅕⅖⅗⅘⅙⅚⅛⅜⅝⅞]?|[¼½¾⅐⅑⅒⅓⅔⅕⅖⅗⅘⅙⅚⅛⅜⅝⅞])";
+// "in" is left out: "2 in a bowl" is far more common than "2 in" for inches.
+const UNIT: &str = r#"(?:(?i:inches|inch|centimeters|centimeter|centimetres|centimetre|cm|millimeters|millimeter|millimetres|millimetre|mm)\b|["”″])"#;
+const SEP: &str = r"\s*-?\s*(?:[xX×]|by)\s*-?\s*";
+const ASPECT_WORDS: &str = r"(?i:thick|long|wide|deep|tall|round|square)\b";
+
+fn dimension_pattern() -> String {
+    format!(
+        r"(?:{NUM}(?:\s*-?\s*{UNIT})?{SEP}){{0,2}}{NUM}\s*-?\s*{UNIT}(?:\s*-?\s*{ASPECT_WORDS})?"
+    )
+}
+
+crate::lazy_regex!(DIMENSION, {
+    regex::Regex::new(&dimension_pattern()).expect("invalid dimension regex")
+});
+// Anchored, so the rich-text grammar can try it at every position cheaply.
+crate::lazy_regex!(DIMENSION_AT, {
+    regex::Regex::new(&format!("^{}", dimension_pattern())).expect("invalid dimension regex")
+});
+crate::lazy_regex!(SIDE, {
+    regex::Regex::new(&format!(r"({NUM})(?:\s*-?\s*({UNIT}))?")).expect("invalid side regex")
+});
+crate::lazy_regex!(ASPECT, {
+    regex::Regex::new(&format!(r"({ASPECT_WORDS})$")).expect("invalid aspect regex")
+});
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::pan("9x13-inch", "9x13 inch")]
+    #[case::spaced("9 x 13 inch", "9x13 inch")]
+    #[case::quotes("9\" x 13\"", "9x13 inch")]
+    #[case::by("9-by-13-inch", "9x13 inch")]
+    #[case::metric_pan("20 x 30 cm", "20x30 cm")]
+    #[case::piece("2-inch", "2 inch")]
+    #[case::thick("½-inch-thick", "½ inch thick")]
+    #[case::mixed("1 1/2 inches long", "1½ inch long")]
+    #[case::round("23cm round", "23 cm round")]
+    #[case::mm("13-millimeter", "13 mm")]
+    fn parses(#[case] input: &str, #[case] want: &str) {
+        assert_eq!(Dimension::parse(input).unwrap().to_string(), want);
+    }
+
+    #[rstest]
+    #[case::no_unit("9x13")]
+    #[case::in_word("2 in")]
+    #[case::volume("2 cups")]
+    fn rejects(#[case] input: &str) {
+        assert_eq!(Dimension::parse(input), None);
+    }
+
+    #[rstest]
+    #[case::pan("9x13-inch", "23x33 cm")]
+    #[case::thin("¼-inch-thick", "6 mm thick")]
+    #[case::half("½-inch", "1.3 cm")]
+    fn converts_to_metric(#[case] input: &str, #[case] want: &str) {
+        let d = Dimension::parse(input).unwrap();
+        assert_eq!(d.to_metric().to_string(), want);
+    }
+
+    #[rstest]
+    #[case::tin("20cm square", "8 inch square")]
+    #[case::pan("33 x 23 cm", "13x9 inch")]
+    #[case::slice("13 mm", "½ inch")]
+    fn converts_to_imperial(#[case] input: &str, #[case] want: &str) {
+        let d = Dimension::parse(input).unwrap();
+        assert_eq!(d.to_imperial().to_string(), want);
+    }
+
+    #[test]
+    fn area_needs_two_sides_or_a_shape() {
+        let area = |s: &str| Dimension::parse(s).unwrap().area_cm2();
+        assert!((area("9x13-inch").unwrap() - 754.8).abs() < 0.1);
+        assert!((area("20 cm square").unwrap() - 400.0).abs() < 1e-9);
+        assert!((area("20 cm round").unwrap() - 314.16).abs() < 0.01);
+        assert_eq!(area("2-inch"), None);
+    }
+
+    #[rstest]
+    #[case::doubled_square("8-inch square", 2.0, Some("9x13 inch"))]
+    #[case::halved_pan("9x13-inch", 0.5, Some("9 inch round"))]
+    #[case::metric("23 x 33 cm", 0.5, Some("20 cm square"))]
+    #[case::same("9-inch round", 1.0, Some("9 inch round"))]
+    #[case::nothing_close("13x18-inch", 3.0, None)]
+    #[case::no_area("2-inch", 2.0, None)]
+    fn matches_pans(#[case] input: &str, #[case] factor: f64, #[case] want: Option<&str>) {
+        let pan = Dimension::parse(input).unwrap().matching_pan(factor);
+        assert_eq!(pan.map(|p| p.to_string()).as_deref(), want);
+    }
+
+    #[test]
+    fn finds_dimensions_in_prose() {
+        let text = "Grease a 9x13-inch pan and cut into ½-inch-thick slices, 2 in a row.";
+        let found: Vec<(&str, String)> = find_dimensions(text)
+            .into_iter()
+            .map(|(r, d)| (&text[r], d.to_string()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("9x13-inch", "9x13 inch".to_string()),
+                ("½-inch-thick", "½ inch thick".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trips_through_serde() {
+        let d = Dimension::parse("½-inch-thick").unwrap();
+        let json = serde_json::to_string(&d).unwrap();
+        assert_eq!(json, r#"{"sides":[0.5],"unit":"\"","aspect":"thick"}"#);
+        assert_eq!(serde_json::from_str::<Dimension>(&json).unwrap(), d);
+    }
+
+    #[test]
+    fn from_measure_takes_single_lengths() {
+        assert_eq!(
+            Dimension::from_measure(&Measure::new("inch", 9.0))
+                .unwrap()
+                .to_string(),
+            "9 inch"
+        );
+        assert_eq!(Dimension::from_measure(&Measure::new("cup", 1.0)), None);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::unit::singular;
-use crate::unit::{Unit, kind::MeasureKind};
+use crate::unit::{Qualifier, Unit, kind::MeasureKind};
 use crate::util::{format_quantity, num_without_zeroes};
 use crate::{IngredientError, IngredientResult};
 use num_rational::Rational64;
@@ -142,15 +142,18 @@ pub struct Measure {
         deserialize_with = "deserialize_rational_opt"
     )]
     upper_value: Option<Rational64>,
+    /// "about", "scant", "heaping", … — how loosely the quantity is meant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    qualifier: Option<Qualifier>,
 }
 
 /// Serialize Unit as its canonical string form (e.g., "cup", "g", "$")
-fn serialize_unit<S: Serializer>(unit: &Unit, s: S) -> Result<S::Ok, S::Error> {
+pub(super) fn serialize_unit<S: Serializer>(unit: &Unit, s: S) -> Result<S::Ok, S::Error> {
     s.serialize_str(&unit.to_str())
 }
 
 /// Deserialize Unit from a string
-fn deserialize_unit<'de, D: Deserializer<'de>>(d: D) -> Result<Unit, D::Error> {
+pub(super) fn deserialize_unit<'de, D: Deserializer<'de>>(d: D) -> Result<Unit, D::Error> {
     let s = String::deserialize(d)?;
     // `Unit::from_str` is infallible (the fallback arm is unreachable);
     // `normalize` singularizes unknown units.
@@ -290,7 +293,11 @@ const G_TO_K: f64 = 1000.0;
 const CUP_TO_QUART: f64 = 4.0;
 const QUART_TO_GALLON: f64 = 4.0;
 const TSP_TO_CUP: f64 = 48.0;
-const GRAM_TO_OZ: f64 = 28.3495;
+// Regional volumes are defined in milliliters (the imperial gallon is exactly
+// 4.54609 l); the US pint is 2 cups, like the other US units.
+const ML_PER_IMPERIAL_GALLON: f64 = 4546.09;
+const ML_PER_IMPERIAL_PINT: f64 = ML_PER_IMPERIAL_GALLON / 8.0;
+pub(crate) const GRAM_TO_OZ: f64 = 28.3495;
 const OZ_TO_LB: f64 = 16.0;
 const CENTS_TO_DOLLAR: f64 = 100.0;
 const SEC_TO_MIN: f64 = 60.0;
@@ -354,6 +361,56 @@ static NORMALIZATION_RULES: &[NormalizationRule] = &[
         factor: TSP_TO_FL_OZ,
     },
     // Money: normalize to cents
+    NormalizationRule {
+        from: Unit::Pint,
+        to_base: Unit::Teaspoon,
+        factor: TSP_TO_CUP * 2.0,
+    },
+    NormalizationRule {
+        from: Unit::ImperialFluidOunce,
+        to_base: Unit::Milliliter,
+        factor: ML_PER_IMPERIAL_PINT / 20.0,
+    },
+    NormalizationRule {
+        from: Unit::ImperialPint,
+        to_base: Unit::Milliliter,
+        factor: ML_PER_IMPERIAL_PINT,
+    },
+    NormalizationRule {
+        from: Unit::ImperialQuart,
+        to_base: Unit::Milliliter,
+        factor: ML_PER_IMPERIAL_PINT * 2.0,
+    },
+    NormalizationRule {
+        from: Unit::ImperialGallon,
+        to_base: Unit::Milliliter,
+        factor: ML_PER_IMPERIAL_GALLON,
+    },
+    NormalizationRule {
+        from: Unit::MetricCup,
+        to_base: Unit::Milliliter,
+        factor: 250.0,
+    },
+    NormalizationRule {
+        from: Unit::AustralianTablespoon,
+        to_base: Unit::Milliliter,
+        factor: 20.0,
+    },
+    NormalizationRule {
+        from: Unit::Dessertspoon,
+        to_base: Unit::Milliliter,
+        factor: 10.0,
+    },
+    NormalizationRule {
+        from: Unit::Gill,
+        to_base: Unit::Milliliter,
+        factor: ML_PER_IMPERIAL_PINT / 4.0,
+    },
+    NormalizationRule {
+        from: Unit::Teacup,
+        to_base: Unit::Milliliter,
+        factor: ML_PER_IMPERIAL_PINT / 3.0,
+    },
     NormalizationRule {
         from: Unit::Dollar,
         to_base: Unit::Cent,
@@ -436,6 +493,7 @@ impl Measure {
             unit,
             value: to_rational(value),
             upper_value: upper_value.map(to_rational),
+            qualifier: None,
         }
     }
     pub fn unit(&self) -> &Unit {
@@ -449,6 +507,62 @@ impl Measure {
     pub fn upper_value(&self) -> Option<f64> {
         self.upper_value.map(to_f64)
     }
+    /// How loosely the quantity is meant ("about", "scant", …), if the recipe
+    /// said.
+    pub fn qualifier(&self) -> Option<Qualifier> {
+        self.qualifier
+    }
+    /// This measure with `qualifier` attached.
+    ///
+    /// # Example
+    /// ```
+    /// use ingredient::unit::{Measure, Qualifier};
+    /// let m = Measure::new("cup", 0.5).with_qualifier(Qualifier::Scant);
+    /// assert_eq!(m.to_string(), "scant ½ cup");
+    /// ```
+    pub fn with_qualifier(mut self, qualifier: Qualifier) -> Measure {
+        self.qualifier = Some(qualifier);
+        self
+    }
+    /// This measure without its qualifier — the bare quantity as written.
+    pub fn unqualified(&self) -> Measure {
+        Measure {
+            qualifier: None,
+            ..self.clone()
+        }
+    }
+    /// The qualifier's numeric reading applied: the stated quantity multiplied
+    /// by [`Qualifier::factor`], as a range. "1 heaping tbsp" is `1–1.25 tbsp`
+    /// and "about 2 lb" is `1.8–2.2 lb`. The result is unqualified, except for
+    /// [`Qualifier::AtLeast`], whose open top no range can state.
+    ///
+    /// # Example
+    /// ```
+    /// use ingredient::unit::{Measure, Qualifier};
+    /// let heaping = Measure::new("tbsp", 1.0).with_qualifier(Qualifier::Heaping);
+    /// assert_eq!(heaping.interpreted(), Measure::with_range("tbsp", 1.0, 1.25));
+    /// ```
+    pub fn interpreted(&self) -> Measure {
+        let Some(q) = self.qualifier else {
+            return self.clone();
+        };
+        let f = q.factor();
+        if f.lower == f.upper {
+            return self.clone();
+        }
+        // Rational multiply so "about 2 lb" is exactly 1.8–2.2, not 2.2000000000000002.
+        let mul = |a: Rational64, factor: f64| -> Rational64 {
+            a.checked_mul(&to_rational(factor))
+                .unwrap_or_else(|| to_rational(to_f64(a) * factor))
+        };
+        let hi = self.upper_value.unwrap_or(self.value);
+        Measure {
+            unit: self.unit.clone(),
+            value: mul(self.value, f.lower),
+            upper_value: Some(mul(hi, f.upper)),
+            qualifier: None,
+        }
+    }
 
     /// The exact `"N/D"` (or `"W N/D"` mixed) fraction string for the primary
     /// value, but *only* when its `f64` view is a non-terminating decimal
@@ -480,6 +594,7 @@ impl Measure {
                 unit: Unit::Other(singular(x).into_owned()),
                 value: self.value,
                 upper_value: self.upper_value,
+                qualifier: self.qualifier,
             };
         }
 
@@ -489,6 +604,7 @@ impl Measure {
                 unit: rule.to_base.clone(),
                 value: to_rational(self.value() * rule.factor),
                 upper_value: self.upper_value().map(|x| to_rational(x * rule.factor)),
+                qualifier: self.qualifier,
             };
         }
 
@@ -536,6 +652,7 @@ impl Measure {
                 (None, Some(b)) => Some(checked_add(left.value, b)),
                 (Some(a), None) => Some(checked_add(a, right.value)),
             },
+            qualifier: left.qualifier.or(right.qualifier),
         })
     }
     /// Scale this measure by `factor`, keeping the value exact.
@@ -596,6 +713,7 @@ impl Measure {
             unit: self.unit.clone(),
             value,
             upper_value,
+            qualifier: self.qualifier,
         }
     }
 
@@ -634,15 +752,19 @@ impl Measure {
     ///
     /// This is the low-level constructor used by `new` and `with_range`.
     pub(crate) fn from_parts(unit: &str, value: f64, upper_value: Option<f64>) -> Measure {
-        let normalized_unit = singular(unit);
-        let unit =
-            Unit::from_str(&normalized_unit).unwrap_or(Unit::Other(normalized_unit.into_owned()));
+        // `from_str` singularizes itself; singularizing first would turn
+        // "celsius" into "celsiu".
+        let unit = match Unit::from_str(unit) {
+            Ok(Unit::Other(_)) | Err(()) => Unit::Other(singular(unit).into_owned()),
+            Ok(known) => known,
+        };
 
         let (value, upper_value) = ordered_bounds(value, upper_value);
         Measure {
             unit,
             value: to_rational(value),
             upper_value: upper_value.map(to_rational),
+            qualifier: None,
         }
     }
     /// Get the kind/category of this measurement (weight, volume, time, etc.).
@@ -673,6 +795,8 @@ impl Measure {
                 _ => (Unit::Day, SEC_TO_DAY),
             },
             Unit::Inch => (Unit::Inch, 1.0),
+            Unit::Centimeter => (Unit::Centimeter, 1.0),
+            Unit::Millimeter => (Unit::Millimeter, 1.0),
             Unit::Other(o) => (Unit::Other(o.clone()), 1.0),
             Unit::Kilogram
             | Unit::Liter
@@ -681,6 +805,16 @@ impl Measure {
             | Unit::Quart
             | Unit::Gallon
             | Unit::FluidOunce
+            | Unit::Pint
+            | Unit::ImperialFluidOunce
+            | Unit::ImperialPint
+            | Unit::ImperialQuart
+            | Unit::ImperialGallon
+            | Unit::MetricCup
+            | Unit::AustralianTablespoon
+            | Unit::Dessertspoon
+            | Unit::Gill
+            | Unit::Teacup
             | Unit::Ounce
             | Unit::Pound
             | Unit::Dollar
@@ -695,6 +829,7 @@ impl Measure {
             unit: u,
             value: to_rational(self.value() / f),
             upper_value: self.upper_value().map(|x| to_rational(x / f)),
+            qualifier: self.qualifier,
         }
     }
 
@@ -712,11 +847,24 @@ impl Measure {
     ///
     /// For example, `Measure::new("cup", 2.0).unit_as_string()` returns `"cups"`.
     pub fn unit_as_string(&self) -> String {
-        let unit_str = self.unit().to_str();
-        let base = singular(&unit_str);
+        // `to_str` is already singular; singularizing again would turn
+        // "celsius" into "celsiu".
+        let base = self.unit().to_str();
         if matches!(
             self.unit(),
-            Unit::Cup | Unit::Second | Unit::Minute | Unit::Hour | Unit::Day
+            Unit::Cup
+                | Unit::Pint
+                | Unit::ImperialPint
+                | Unit::ImperialQuart
+                | Unit::ImperialGallon
+                | Unit::MetricCup
+                | Unit::Dessertspoon
+                | Unit::Gill
+                | Unit::Teacup
+                | Unit::Second
+                | Unit::Minute
+                | Unit::Hour
+                | Unit::Day
         ) && (self.value() > 1.0 || self.upper_value().unwrap_or(0.0) > 1.0)
         {
             let mut s = base.into_owned();
@@ -730,6 +878,9 @@ impl Measure {
 
 impl fmt::Display for Measure {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(q) = self.qualifier {
+            write!(f, "{q} ")?;
+        }
         let measure = self.denormalize();
         let value = measure.value();
         // Money renders symbol-first ("$5", "$2 - $4") instead of with a trailing
@@ -1,7 +1,7 @@
 pub(crate) mod core;
-pub(crate) use core::is_addon_unit;
 pub use core::singular;
 pub use core::{Unit, is_valid};
+pub(crate) use core::{is_addon_unit, spellings};
 
 pub mod kind;
 pub use kind::MeasureKind;
@@ -14,3 +14,12 @@ pub use conversion::{
 
 pub(crate) mod measure;
 pub use measure::{Measure, MeasureGraph, make_graph, print_graph};
+
+pub mod qualifier;
+pub use qualifier::Qualifier;
+
+pub mod region;
+pub use region::Region;
+
+pub mod dimension;
+pub use dimension::{Aspect, Dimension};
@@ -0,0 +1,153 @@
+//! Amount qualifiers: the "about", "scant" and "heaping" that say how exact a
+//! quantity is.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::conversion::EdgeFactor;
+
+/// How loosely a [`Measure`](super::Measure)'s quantity is meant.
+///
+/// Each qualifier has a fixed numeric reading, [`factor`](Self::factor): an
+/// interval the stated quantity is multiplied by, the same shape as a ranged
+/// conversion-graph edge. Conversion applies it (a heaping tablespoon at 15 g
+/// per tablespoon is 15–18.75 g); scaling and formatting carry the qualifier
+/// along unchanged ("scant ½ cup" doubled is "scant 1 cup").
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Qualifier {
+    /// "about", "approximately", "roughly", "around", "a few": ±10%.
+    Approximate,
+    /// "scant": up to 10% under.
+    Scant,
+    /// "heaping", "heaped", "rounded", "brimming": up to 25% over.
+    Heaping,
+    /// "generous", "healthy", "good": up to 10% over.
+    Generous,
+    /// "at least": the quantity is a floor with no stated ceiling, so there is
+    /// no range to widen to — the factor is 1 and the qualifier survives
+    /// conversion instead.
+    AtLeast,
+}
+
+impl Qualifier {
+    pub const ALL: [Qualifier; 5] = [
+        Qualifier::Approximate,
+        Qualifier::Scant,
+        Qualifier::Heaping,
+        Qualifier::Generous,
+        Qualifier::AtLeast,
+    ];
+
+    /// The interval a qualified quantity spans, relative to the stated value.
+    pub fn factor(self) -> EdgeFactor {
+        match self {
+            Qualifier::Approximate => EdgeFactor {
+                lower: 0.9,
+                upper: 1.1,
+            },
+            Qualifier::Scant => EdgeFactor {
+                lower: 0.9,
+                upper: 1.0,
+            },
+            Qualifier::Heaping => EdgeFactor {
+                lower: 1.0,
+                upper: 1.25,
+            },
+            Qualifier::Generous => EdgeFactor {
+                lower: 1.0,
+                upper: 1.1,
+            },
+            Qualifier::AtLeast => EdgeFactor::point(1.0),
+        }
+    }
+
+    /// The canonical word, as [`Display`](fmt::Display) writes it before the
+    /// quantity.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Qualifier::Approximate => "about",
+            Qualifier::Scant => "scant",
+            Qualifier::Heaping => "heaping",
+            Qualifier::Generous => "generous",
+            Qualifier::AtLeast => "at least",
+        }
+    }
+
+    /// The qualifier a recipe word (or phrase) expresses, if any. Case-insensitive.
+    pub fn from_word(word: &str) -> Option<Qualifier> {
+        match word.trim().to_lowercase().as_str() {
+            "about" | "approximately" | "approx" | "roughly" | "around" => {
+                Some(Qualifier::Approximate)
+            }
+            "scant" => Some(Qualifier::Scant),
+            "heaping" | "heaped" | "rounded" | "brimming" => Some(Qualifier::Heaping),
+            "generous" | "healthy" | "good" => Some(Qualifier::Generous),
+            "at least" => Some(Qualifier::AtLeast),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Qualifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Qualifier {
+    type Err = String;
+
+    /// Accepts the serde names (`"at_least"`) as well as any recipe word
+    /// [`from_word`](Self::from_word) knows.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Qualifier::ALL
+            .into_iter()
+            .find(|q| serde_json::to_value(q).is_ok_and(|v| v == s))
+            .or_else(|| Qualifier::from_word(s))
+            .ok_or_else(|| format!("unknown qualifier: {s}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::about("about", Qualifier::Approximate)]
+    #[case::approx_caps("Approximately", Qualifier::Approximate)]
+    #[case::heaped("heaped", Qualifier::Heaping)]
+    #[case::healthy("healthy", Qualifier::Generous)]
+    #[case::at_least("at least", Qualifier::AtLeast)]
+    #[case::serde_name("at_least", Qualifier::AtLeast)]
+    fn parses_words(#[case] word: &str, #[case] want: Qualifier) {
+        assert_eq!(word.parse::<Qualifier>().unwrap(), want);
+    }
+
+    #[test]
+    fn rejects_other_words() {
+        assert!("less than".parse::<Qualifier>().is_err());
+        assert_eq!(Qualifier::from_word("large"), None);
+    }
+
+    #[test]
+    fn factors_bracket_the_stated_value() {
+        for q in Qualifier::ALL {
+            let f = q.factor();
+            assert!(f.lower <= 1.0 && 1.0 <= f.upper, "{q}");
+        }
+        assert_eq!(Qualifier::Heaping.factor().upper, 1.25);
+    }
+
+    #[test]
+    fn serde_is_snake_case() {
+        assert_eq!(
+            serde_json::to_string(&Qualifier::AtLeast).unwrap(),
+            "\"at_least\""
+        );
+    }
+}
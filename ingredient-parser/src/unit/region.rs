@@ -0,0 +1,130 @@
+//! Regional readings of the ambiguous volume words: a "pint" is 473 ml in an
+//! American recipe and 568 ml in a British one, an Australian tablespoon is
+//! 20 ml.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::Unit;
+
+/// Whose cups, pints and tablespoons a recipe means.
+///
+/// The bare words parse to the US units by default; a parser set to another
+/// region ([`IngredientParser::with_region`](crate::IngredientParser::with_region))
+/// reads them as that region's variant, which is kept on the [`Unit`] so later
+/// conversions use the right size. A spelling that names its region ("US cup",
+/// "imperial pint") means that unit wherever it appears.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Region {
+    /// US customary: 237 ml cup, 473 ml pint, 15 ml tablespoon.
+    #[default]
+    #[serde(rename = "us")]
+    US,
+    /// British: 250 ml metric cup and imperial pints, quarts, gallons and
+    /// fluid ounces.
+    #[serde(rename = "uk")]
+    UK,
+    /// Australian and New Zealand: as British, plus the 20 ml tablespoon.
+    #[serde(rename = "au")]
+    AU,
+}
+
+impl Region {
+    pub const ALL: [Region; 3] = [Region::US, Region::UK, Region::AU];
+
+    /// The unit a bare word resolving to `unit` means in this region.
+    pub fn resolve(self, unit: Unit) -> Unit {
+        match (self, unit) {
+            (Region::US, unit) => unit,
+            (_, Unit::Cup) => Unit::MetricCup,
+            (_, Unit::Pint) => Unit::ImperialPint,
+            (_, Unit::Quart) => Unit::ImperialQuart,
+            (_, Unit::Gallon) => Unit::ImperialGallon,
+            (_, Unit::FluidOunce) => Unit::ImperialFluidOunce,
+            (Region::AU, Unit::Tablespoon) => Unit::AustralianTablespoon,
+            (_, unit) => unit,
+        }
+    }
+
+    /// The region a BCP 47 language tag ("en-GB", "en_AU") points to, as found
+    /// in an EPUB's `dc:language` or a page's `lang`. `None` when the tag names
+    /// no region this knows (a bare "en", "fr-FR").
+    pub fn from_language_tag(tag: &str) -> Option<Region> {
+        let mut parts = tag.split(['-', '_']);
+        if !parts.next()?.eq_ignore_ascii_case("en") {
+            return None;
+        }
+        match parts.next()?.to_ascii_uppercase().as_str() {
+            "US" => Some(Region::US),
+            "GB" | "UK" | "IE" => Some(Region::UK),
+            "AU" | "NZ" => Some(Region::AU),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Region::US => "us",
+            Region::UK => "uk",
+            Region::AU => "au",
+        }
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Region {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Region::ALL
+            .into_iter()
+            .find(|r| r.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown region: {s} (expected us, uk or au)"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::us_keeps(Region::US, Unit::Pint, Unit::Pint)]
+    #[case::uk_pint(Region::UK, Unit::Pint, Unit::ImperialPint)]
+    #[case::uk_cup(Region::UK, Unit::Cup, Unit::MetricCup)]
+    #[case::uk_tbsp(Region::UK, Unit::Tablespoon, Unit::Tablespoon)]
+    #[case::au_tbsp(Region::AU, Unit::Tablespoon, Unit::AustralianTablespoon)]
+    #[case::weight_untouched(Region::AU, Unit::Ounce, Unit::Ounce)]
+    fn resolves_bare_units(#[case] region: Region, #[case] unit: Unit, #[case] want: Unit) {
+        assert_eq!(region.resolve(unit), want);
+    }
+
+    #[rstest]
+    #[case::gb("en-GB", Some(Region::UK))]
+    #[case::underscore("en_AU", Some(Region::AU))]
+    #[case::nz("en-nz", Some(Region::AU))]
+    #[case::us("en-US", Some(Region::US))]
+    #[case::bare("en", None)]
+    #[case::french("fr-FR", None)]
+    fn reads_language_tags(#[case] tag: &str, #[case] want: Option<Region>) {
+        assert_eq!(Region::from_language_tag(tag), want);
+    }
+
+    #[test]
+    fn round_trips_through_str_and_serde() {
+        for r in Region::ALL {
+            assert_eq!(r.to_string().parse::<Region>().unwrap(), r);
+            let json = serde_json::to_string(&r).unwrap();
+            assert_eq!(json, format!("\"{r}\""));
+        }
+        assert!("ca".parse::<Region>().is_err());
+    }
+}
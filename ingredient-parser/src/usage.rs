@@ -18,10 +18,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::parser::vocab::{
-    DREDGING_PHRASES, FRYING_PHRASES, GARNISH_PHRASES, MARINADE_PHRASES, MARINADE_SECTION_WORDS,
-    PAN_GREASE_PHRASES, SEASONING_PHRASES,
-};
+use crate::profile::{BUILTIN, ParserProfile};
 
 /// The role an ingredient line plays in a recipe, as declared by its text.
 ///
@@ -53,18 +50,6 @@ pub enum IngredientUsage {
     Marinade,
 }
 
-/// Rules in precedence order: the first phrase hit wins. Garnish outranks
-/// frying so "crispy fried shallots, for garnish" classifies by its declared
-/// purpose, not the cooking method embedded in the name.
-const RULES: &[(&[&str], IngredientUsage)] = &[
-    (GARNISH_PHRASES, IngredientUsage::Garnish),
-    (FRYING_PHRASES, IngredientUsage::FryingMedium),
-    (PAN_GREASE_PHRASES, IngredientUsage::PanGrease),
-    (DREDGING_PHRASES, IngredientUsage::Dredging),
-    (SEASONING_PHRASES, IngredientUsage::Seasoning),
-    (MARINADE_PHRASES, IngredientUsage::Marinade),
-];
-
 /// Classify an ingredient line's usage from its parsed parts.
 ///
 /// Searches the modifier first (where the parser's purpose-phrase extraction
@@ -73,6 +58,9 @@ const RULES: &[(&[&str], IngredientUsage)] = &[
 /// fallback parses where the whole line became the name). `section_name` is
 /// consulted last, only for marinade/brine sections.
 ///
+/// Uses the built-in phrase lists; [`classify_usage_with`] takes a
+/// [`ParserProfile`].
+///
 /// ```
 /// use ingredient::usage::{classify_usage, IngredientUsage};
 ///
@@ -96,24 +84,127 @@ pub fn classify_usage(
     raw_line: Option<&str>,
     section_name: Option<&str>,
 ) -> IngredientUsage {
-    let haystacks: Vec<String> = [modifier, raw_line, Some(name)]
-        .into_iter()
-        .flatten()
-        .map(str::to_lowercase)
-        .collect();
+    classify_usage_with(&BUILTIN, name, modifier, raw_line, section_name)
+}
+
+/// [`classify_usage`] with `profile`'s phrase lists. Rules apply in precedence
+/// order, first hit wins: garnish outranks frying so "crispy fried shallots,
+/// for garnish" classifies by its declared purpose, not the cooking method
+/// embedded in the name.
+pub fn classify_usage_with(
+    profile: &ParserProfile,
+    name: &str,
+    modifier: Option<&str>,
+    raw_line: Option<&str>,
+    section_name: Option<&str>,
+) -> IngredientUsage {
+    explain_usage_with(profile, name, modifier, raw_line, section_name).usage
+}
+
+/// Where a usage phrase was found.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Haystack {
+    Modifier,
+    RawLine,
+    Name,
+    /// The section header; only marinade/brine words are looked for here.
+    Section,
+}
+
+/// One phrase hit during classification.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct UsageMatch {
+    /// The usage the phrase's list declares.
+    pub usage: IngredientUsage,
+    /// The phrase as listed in the profile.
+    pub phrase: String,
+    pub haystack: Haystack,
+    /// The haystack, lowercased, as searched.
+    pub text: String,
+    /// Byte range of the phrase in `text`.
+    pub range: std::ops::Range<usize>,
+    /// A surplus marker ("plus more", "extra") before the phrase vetoed it:
+    /// it describes the extra, not the row.
+    pub vetoed: bool,
+}
+
+/// Why [`classify_usage`] chose a usage: every phrase hit it saw, in the order
+/// it saw them. The last hit decided, unless it was vetoed (then no phrase
+/// matched and the usage is [`Normal`](IngredientUsage::Normal)).
+///
+/// ```
+/// use ingredient::usage::{explain_usage, Haystack, IngredientUsage};
+///
+/// let why = explain_usage("flour", Some("plus more for dusting"), Some("flour, for dusting"), None);
+/// assert_eq!(why.usage, IngredientUsage::Dredging);
+/// assert_eq!(why.matches.len(), 2);
+/// assert!(why.matches[0].vetoed);
+/// assert_eq!(why.matches[1].haystack, Haystack::RawLine);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct UsageExplanation {
+    pub usage: IngredientUsage,
+    pub matches: Vec<UsageMatch>,
+}
+
+impl UsageExplanation {
+    /// The hit that decided the usage; `None` when the usage is the
+    /// [`Normal`](IngredientUsage::Normal) default.
+    pub fn decided_by(&self) -> Option<&UsageMatch> {
+        self.matches.last().filter(|m| !m.vetoed)
+    }
+}
 
-    for (phrases, usage) in RULES {
-        for hay in &haystacks {
-            for phrase in *phrases {
+/// [`classify_usage`], showing its work. Uses the built-in phrase lists;
+/// [`explain_usage_with`] takes a [`ParserProfile`].
+pub fn explain_usage(
+    name: &str,
+    modifier: Option<&str>,
+    raw_line: Option<&str>,
+    section_name: Option<&str>,
+) -> UsageExplanation {
+    explain_usage_with(&BUILTIN, name, modifier, raw_line, section_name)
+}
+
+/// [`classify_usage_with`], showing its work.
+pub fn explain_usage_with(
+    profile: &ParserProfile,
+    name: &str,
+    modifier: Option<&str>,
+    raw_line: Option<&str>,
+    section_name: Option<&str>,
+) -> UsageExplanation {
+    let haystacks: Vec<(Haystack, String)> = [
+        (Haystack::Modifier, modifier),
+        (Haystack::RawLine, raw_line),
+        (Haystack::Name, Some(name)),
+    ]
+    .into_iter()
+    .filter_map(|(kind, text)| Some((kind, text?.to_lowercase())))
+    .collect();
+
+    let mut matches = Vec::new();
+    for (phrases, usage) in profile.usage_rules() {
+        for (haystack, hay) in &haystacks {
+            for phrase in phrases {
                 if let Some(pos) = find_phrase(hay, phrase) {
                     // "plus more for dusting" / "plus 20 or so for garnish"
                     // describe surplus beyond the measured amount — the row's
                     // own role stays Normal. Only "for …" phrases can be
                     // surplus-qualified; "or more to taste" is still Seasoning.
-                    if phrase.starts_with("for ") && is_surplus_mention(hay, pos) {
-                        continue;
+                    let vetoed = phrase.starts_with("for ") && is_surplus_mention(hay, pos);
+                    matches.push(UsageMatch {
+                        usage,
+                        phrase: phrase.clone(),
+                        haystack: *haystack,
+                        text: hay.clone(),
+                        range: pos..pos + phrase.len(),
+                        vetoed,
+                    });
+                    if !vetoed {
+                        return UsageExplanation { usage, matches };
                     }
-                    return *usage;
                 }
             }
         }
@@ -121,15 +212,28 @@ pub fn classify_usage(
 
     if let Some(section) = section_name {
         let section = section.to_lowercase();
-        if MARINADE_SECTION_WORDS
-            .iter()
-            .any(|w| find_phrase(&section, w).is_some())
-        {
-            return IngredientUsage::Marinade;
+        for word in &profile.marinade_section_words {
+            if let Some(pos) = find_phrase(&section, word) {
+                matches.push(UsageMatch {
+                    usage: IngredientUsage::Marinade,
+                    phrase: word.clone(),
+                    haystack: Haystack::Section,
+                    range: pos..pos + word.len(),
+                    text: section,
+                    vetoed: false,
+                });
+                return UsageExplanation {
+                    usage: IngredientUsage::Marinade,
+                    matches,
+                };
+            }
         }
     }
 
-    IngredientUsage::Normal
+    UsageExplanation {
+        usage: IngredientUsage::Normal,
+        matches,
+    }
 }
 
 /// Find `phrase` in `haystack` at word boundaries (both already lowercase).
@@ -64,7 +64,7 @@ fn accuracy_corpus() {
     }
 
     let total = tally.total;
-    let [name_ok, amt_ok, mod_ok, opt_ok, use_ok] = tally.per_field;
+    let [name_ok, amt_ok, mod_ok, opt_ok, use_ok, qual_ok] = tally.per_field;
     // `matched()`, NOT `tally.exact`: a passing xfail row is a match, and this
     // line has always counted it. With zero xfail rows in the corpus the two
     // are equal, so reading the wrong one would move the headline number
@@ -76,7 +76,7 @@ fn accuracy_corpus() {
     eprintln!("exact matches:  {matched} ({:.1}%)", pct(matched));
     eprintln!("known gaps:     {} (xfail)", tally.xfail);
     eprintln!(
-        "per-field:      name {name_ok}/{total}  amounts {amt_ok}/{total}  modifier {mod_ok}/{total}  optional {opt_ok}/{total}  usage {use_ok}/{total}"
+        "per-field:      name {name_ok}/{total}  amounts {amt_ok}/{total}  modifier {mod_ok}/{total}  optional {opt_ok}/{total}  usage {use_ok}/{total}  qualifiers {qual_ok}/{total}"
     );
     eprintln!("============================================\n");
 
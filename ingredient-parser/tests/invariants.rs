@@ -0,0 +1,95 @@
+//! Parse invariants (`ingredient_corpus::invariants`) over every corpus row and
+//! over generated lines: corpus-seeded grammar lines, the character soup of
+//! `property.rs`, and constructed measures. The `invariants` cargo-fuzz target
+//! runs the same checks unbounded.
+
+#![allow(clippy::unwrap_used, clippy::panic)]
+
+use std::sync::LazyLock;
+
+use ingredient::IngredientParser;
+use ingredient::unit::Measure;
+use ingredient_corpus::grammar::Grammar;
+use ingredient_corpus::invariants::{check_line, check_measure, check_scale_round_trip};
+use proptest::prelude::*;
+use rstest::rstest;
+
+static PARSER: LazyLock<IngredientParser> = LazyLock::new(IngredientParser::new);
+
+static GRAMMAR: LazyLock<Grammar> = LazyLock::new(|| {
+    Grammar::from_corpus(
+        &PARSER,
+        &ingredient_corpus::parse(ingredient_corpus::embedded()),
+    )
+});
+
+#[test]
+fn every_corpus_row_keeps_the_invariants() {
+    let corpus = ingredient_corpus::parse(ingredient_corpus::embedded());
+    let failures: Vec<String> = corpus
+        .rows()
+        .filter_map(|row| check_line(&PARSER, &row.input).err())
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+/// Lines that once broke an invariant.
+#[rstest]
+#[case::marker_ahead_of_bullet("①* salt")]
+#[case::run_of_bullets("- • firm tofu")]
+#[case::negative_mixed_number("-1¼")]
+#[case::unit_glued_to_zero("0T")]
+fn regressions_keep_the_invariants(#[case] line: &str) {
+    check_line(&PARSER, line).unwrap();
+}
+
+/// A measure built from parts the parser produces: a unit from each kind, a
+/// cooking fraction, and an optional upper bound.
+fn arb_measure() -> impl Strategy<Value = Measure> {
+    let unit = prop::sample::select(vec![
+        "cup", "tbsp", "tsp", "ml", "l", "g", "kg", "oz", "lb", "whole", "clove", "pinch", "inch",
+        "cm", "minute", "hour", "°F", "$", "kcal",
+    ]);
+    let value = (0u32..200, prop::sample::select(vec![1u32, 2, 3, 4, 8]))
+        .prop_map(|(n, d)| f64::from(n) / f64::from(d));
+    (unit, value.clone(), prop::option::of(value)).prop_map(|(unit, lower, upper)| match upper {
+        Some(upper) => Measure::with_range(unit, lower, upper),
+        None => Measure::new(unit, lower),
+    })
+}
+
+proptest! {
+    /// Lines recombined from corpus amounts, names and modifiers.
+    #[test]
+    fn grammar_lines_keep_the_invariants(choices in prop::collection::vec(any::<u8>(), 0..12)) {
+        let line = GRAMMAR.line(&choices);
+        if let Err(e) = check_line(&PARSER, &line) {
+            prop_assert!(false, "{line:?}: {e}");
+        }
+    }
+
+    /// Arbitrary ingredient-ish characters, including the multibyte glyphs.
+    #[test]
+    fn arbitrary_lines_keep_the_invariants(
+        line in r"[a-zA-Z0-9 .,;/\-\(\)½¼¾⅓⅔İ\u{00A0}\u{2460}*]*"
+    ) {
+        if let Err(e) = check_line(&PARSER, &line) {
+            prop_assert!(false, "{line:?}: {e}");
+        }
+    }
+
+    #[test]
+    fn constructed_measures_keep_the_invariants(m in arb_measure()) {
+        if let Err(e) = check_measure(&PARSER, &m) {
+            prop_assert!(false, "{e}");
+        }
+    }
+
+    /// Any finite nonzero factor, not just the recipe-style ones.
+    #[test]
+    fn scaling_round_trips_by_any_factor(m in arb_measure(), factor in 0.01f64..100.0) {
+        if let Err(e) = check_scale_round_trip(&m, factor) {
+            prop_assert!(false, "{e}");
+        }
+    }
+}
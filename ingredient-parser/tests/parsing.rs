@@ -6,7 +6,7 @@ use ingredient::{
     IngredientParser, from_str,
     ingredient::Ingredient,
     rich_text::{Chunk, RichParser},
-    unit::{Measure, MeasureKind, Unit},
+    unit::{Dimension, Measure, MeasureKind, Region, Unit},
 };
 use rstest::{fixture, rstest};
 
@@ -62,9 +62,12 @@ fn parse_rich(input: &str, ingredient_names: &[&str]) -> Vec<Chunk> {
 #[case::or_range("1 or 2 cups flour", "1-2 cups flour")]
 #[case::through_range("2 through 4 cups flour", "2-4 cups flour")]
 #[case::implicit_unit("1 cinnamon stick", "1 whole cinnamon stick")]
-#[case::multiplier_2x("2 x 200g flour", "400g flour")]
-#[case::multiplier_3x("3 x 100g butter", "300g butter")]
-#[case::multiplier_decimal("1.5 x 100g flour", "150g flour")]
+#[case::multiplier_sign("2 x 200g flour", "2 × 200g flour")]
+#[case::multiplier_sign_tight("3 x 100g butter", "3×100g butter")]
+#[case::counting_couple("a couple of eggs", "2 eggs")]
+#[case::counting_dozen("1 dozen eggs", "12 eggs")]
+#[case::counting_dozens("2 dozen eggs", "24 eggs")]
+#[case::counting_half_dozen("a half-dozen eggs", "6 eggs")]
 #[case::of_keyword("pinch nutmeg", "pinch of nutmeg")]
 #[case::of_keyword_cup("1 cup of flour", "1 cup flour")]
 #[case::period_tbsp("1 Tbsp. flour", "1 tbsp flour")]
@@ -86,6 +89,48 @@ fn test_parsing_equivalence(parser: IngredientParser, #[case] left: &str, #[case
     );
 }
 
+/// A multiplier line stores the total as its amount — the same amounts as the
+/// line written out — and keeps the count × size as its package.
+#[rstest]
+#[case::multiplier_2x("2 x 200g flour", "400g flour", 2.0)]
+#[case::multiplier_3x("3 x 100g butter", "300g butter", 3.0)]
+#[case::multiplier_decimal("1.5 x 100g flour", "150g flour", 1.5)]
+fn test_multiplier_amounts_are_the_total(
+    parser: IngredientParser,
+    #[case] multiplied: &str,
+    #[case] total: &str,
+    #[case] count: f64,
+) {
+    let got = parser.from_str(multiplied);
+    let want = parser.from_str(total);
+    assert_eq!((&got.name, &got.amounts), (&want.name, &want.amounts));
+    assert_eq!(got.package.map(|p| p.count), Some(count));
+    assert_eq!(want.package, None);
+}
+
+/// Package lines scale by their count and convert as their total.
+#[rstest]
+fn test_package_scale_and_convert(parser: IngredientParser) {
+    let cans = parser.from_str("2 (14.5-ounce) cans diced tomatoes");
+    let total: Vec<String> = cans.total_amounts().iter().map(|m| m.to_string()).collect();
+    assert_eq!(total, ["2 can", "29 oz"]);
+
+    let halved = cans.scale(0.5);
+    assert_eq!(halved.to_string(), "1 can / 14½ oz tomatoes, diced");
+    assert_eq!(halved.package.as_ref().map(|p| p.count), Some(1.0));
+
+    let grams = cans.convert(MeasureKind::Weight, &[]).unwrap();
+    let per_can = Measure::new("oz", 14.5)
+        .convert_measure_via_mappings(MeasureKind::Weight, &[])
+        .unwrap();
+    assert!((grams.value() - 2.0 * per_can.value()).abs() < 1e-6);
+
+    // A multiplier without a container already holds the total.
+    let flour = parser.from_str("2 x 200g flour").scale(2.0);
+    assert_eq!(flour.to_string(), "800 g flour");
+    assert_eq!(flour.total_amounts(), flour.amounts);
+}
+
 /// Regression (found by cargo-fuzz): adjective extraction byte-sliced `name` at
 /// offsets taken from its lowercased form. For chars whose lowercase changes
 /// byte length (e.g. 'İ' U+0130 -> "i̇"), those offsets can land off a char
@@ -277,7 +322,9 @@ fn test_rich_text_complex_sentence() {
 fn test_rich_text_dimensions() {
     assert_eq!(
         parse_rich(r#"9" x 13""#, &[]),
-        vec![measure(r#"""#, 9.0), text(" x "), measure(r#"""#, 13.0)]
+        vec![Chunk::Dimension(
+            Dimension::new(vec![9.0, 13.0], Unit::Inch).unwrap()
+        )]
     );
 }
 
@@ -490,33 +537,138 @@ fn test_unit_to_str_fallback() {
 }
 
 // ============================================================================
-// Rich-text dimension measures are non-scalable
+// Region-aware unit resolution
 // ============================================================================
 
-/// A dimension surfaced in prose ("cut into 2-inch pieces") is highlighted as an
-/// `Inch` measure, but `Inch` is `MeasureKind::Length`, which is non-scalable —
-/// so doubling a recipe never turns "2-inch pieces" into "4-inch pieces". The
+#[rstest]
+#[case::us_default(Region::US, "1 pint milk", Unit::Pint)]
+#[case::uk_pint(Region::UK, "1 pint milk", Unit::ImperialPint)]
+#[case::uk_cup(Region::UK, "2 cups flour", Unit::MetricCup)]
+#[case::uk_fl_oz(Region::UK, "10 fl oz cream", Unit::ImperialFluidOunce)]
+#[case::uk_tbsp_unchanged(Region::UK, "1 tbsp oil", Unit::Tablespoon)]
+#[case::au_tbsp(Region::AU, "2 tablespoons oil", Unit::AustralianTablespoon)]
+#[case::au_capital_t(Region::AU, "1 T butter", Unit::AustralianTablespoon)]
+#[case::explicit_us(Region::UK, "1 US cup sugar", Unit::Cup)]
+#[case::explicit_imperial(Region::US, "2 imperial pints stock", Unit::ImperialPint)]
+#[case::historical(Region::US, "1 gill cream", Unit::Gill)]
+#[case::dessertspoon(Region::UK, "2 dessertspoons sugar", Unit::Dessertspoon)]
+fn test_region_resolves_units(#[case] region: Region, #[case] input: &str, #[case] unit: Unit) {
+    let ing = IngredientParser::new().with_region(region).from_str(input);
+    assert_eq!(*ing.amounts[0].unit(), unit, "{input}");
+    assert!(
+        !ing.name.is_empty() && !ing.name.contains("pint"),
+        "{input}: {}",
+        ing.name
+    );
+}
+
+#[test]
+fn test_region_applies_to_rich_text() {
+    let rich = RichParser::new(["milk"])
+        .with_region(Region::UK)
+        .parse("Whisk in 1 pint milk.")
+        .unwrap();
+    assert!(rich.contains(&Chunk::Measure(vec![Measure::new("imperial pint", 1.0)])));
+}
+
+// ============================================================================
+// Opt-in spellcheck
+// ============================================================================
+
+#[rstest]
+#[case::unit_typo("2 tablspoon sugar", "2 tbsp sugar")]
+#[case::plural_typo("3 tablepoons butter", "3 tbsp butter")]
+#[case::teaspoon("1 teaspon salt", "1 tsp salt")]
+#[case::ocr_one("l cup milk", "1 cup milk")]
+#[case::ocr_half("% cup sugar", "½ cup sugar")]
+#[case::ocr_mixed("1% cups flour", "1½ cups flour")]
+#[case::ocr_zero("1O0 g butter", "100 g butter")]
+#[case::glued_typo("1/2tablspoon sugar", "½ tbsp sugar")]
+#[case::glued_ocr("l/2c flour", "½ cup flour")]
+#[case::name_typo("2 cups flower", "2 cups flour")]
+fn test_spellcheck_corrects_amounts(#[case] input: &str, #[case] want: &str) {
+    let parser = IngredientParser::new().with_spellcheck(true);
+    let ing = parser.from_str(input);
+    assert_eq!(ing.to_string(), want, "{input}");
+}
+
+/// Nothing to correct: a "%" with no unit after it, a short word after a
+/// number, a real unit (glued on or not), a name that goes on past the typo.
+#[rstest]
+#[case::percent("2% milk")]
+#[case::short_word("2 pinto beans")]
+#[case::real_unit("2 cups flour, sifted")]
+#[case::glued_unit("1/2c flour")]
+#[case::flower_petals("1 cup flower petals")]
+fn test_spellcheck_leaves_clean_lines(#[case] input: &str) {
+    let parser = IngredientParser::new().with_spellcheck(true);
+    assert_eq!(parser.from_str(input), from_str(input), "{input}");
+}
+
+#[test]
+fn test_spellcheck_is_off_by_default_and_auditable() {
+    let line = "2 tablspoon sugar";
+    assert!(
+        IngredientParser::new()
+            .from_str(line)
+            .name
+            .contains("tablspoon")
+    );
+
+    let stages = IngredientParser::new()
+        .with_spellcheck(true)
+        .parse_with_trace("l teaspon salt")
+        .trace
+        .stages();
+    let fired: Vec<(&str, &str)> = stages
+        .normalize
+        .iter()
+        .map(|r| (r.name.as_str(), r.after.as_str()))
+        .collect();
+    assert_eq!(
+        fired,
+        [
+            ("fix_ocr_digits", "1 teaspon salt"),
+            ("correct_unit_spelling", "1 teaspoon salt"),
+        ]
+    );
+}
+
+#[test]
+fn test_spellcheck_knows_custom_units() {
+    let parser = IngredientParser::new()
+        .with_units(&["handful", "handfuls"])
+        .with_spellcheck(true);
+    let ing = parser.from_str("2 handfulls basil");
+    assert_eq!(ing.name, "basil");
+    assert_eq!(ing.amounts[0].unit().to_str(), "handful");
+}
+
+// ============================================================================
+// Rich-text dimensions are sizes, not amounts
+// ============================================================================
+
+/// A dimension in prose ("cut into 2-inch pieces") comes through as its own
+/// `Dimension` chunk rather than a `Measure`, so a caller scaling the step's
+/// measures never turns "2-inch pieces" into "4-inch pieces". The
 /// chunk-sequence accuracy lives in `tests/corpus/rich_text.jsonl`; this guards
 /// the behavioral property that corpus schema can't express.
 #[test]
 fn test_rich_text_dimension_is_non_scalable() {
     let result = parse_rich("cut into 2-inch pieces", &[]);
-    // Inch stringifies to `"`, so match on the measure kind, not the unit string.
-    let inch = result
+    assert!(
+        !result.iter().any(|c| matches!(c, Chunk::Measure(_))),
+        "a dimension must not surface as a scalable measure: {result:?}"
+    );
+    let size = result
         .iter()
         .find_map(|c| match c {
-            Chunk::Measure(m) if m[0].kind() == MeasureKind::Length => Some(&m[0]),
+            Chunk::Dimension(d) => Some(d),
             _ => None,
         })
         .unwrap();
-    assert_eq!(inch.value(), 2.0);
-    // Assert the guarantee, not the mechanism: scaling is what callers do, and
-    // `is_scalable` is the rule `Measure::scale` enforces on their behalf.
-    assert_eq!(
-        inch.scale(2.0),
-        *inch,
-        "dimensions must not scale with the recipe"
-    );
+    assert_eq!(size.sides(), [2.0]);
+    assert_eq!(size.to_metric().to_string(), "5 cm");
 }
 
 /// The regression this API exists for. `"1 (9-inch) pie crust"` parses to *two*
@@ -553,6 +705,23 @@ fn pie_crust_dimension_is_not_scaled() {
     let doubled = parsed.scale(2.0);
     assert_eq!(doubled.amounts.len(), parsed.amounts.len());
     assert!(doubled.amounts.contains(dimension), "dimension changed");
+    assert_eq!(doubled.dimensions(), parsed.dimensions());
+}
+
+/// A doubled 8-inch square brownie recipe wants a 9x13 pan; the suggestion
+/// stays in the recipe's own system.
+#[rstest]
+#[case::imperial("Spread in an 8-inch square pan.", 2.0, "9x13 inch")]
+#[case::metric("Line a 23 x 33 cm tin.", 0.5, "20 cm square")]
+fn test_scaled_pan_suggestion(#[case] step: &str, #[case] factor: f64, #[case] want: &str) {
+    let pan = parse_rich(step, &[])
+        .into_iter()
+        .find_map(|c| match c {
+            Chunk::Dimension(d) => Some(d),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(pan.matching_pan(factor).unwrap().to_string(), want);
 }
 
 // ============================================================================
@@ -2,8 +2,11 @@
 
 #![allow(clippy::unwrap_used)]
 
-use ingredient::IngredientParser;
-use ingredient::trace::{GrammarOutcome, ParseTrace, TraceNode, TraceOutcome};
+use std::borrow::Cow;
+
+use ingredient::pass::{CustomPass, Position, Refining};
+use ingredient::trace::{GrammarOutcome, NodeChange, ParseTrace, TraceNode, TraceOutcome};
+use ingredient::{Ingredient, IngredientError, IngredientParser};
 use rstest::{fixture, rstest};
 
 // ============================================================================
@@ -53,8 +56,8 @@ fn test_trace_node() {
     let trace = ParseTrace {
         input: "input text".to_string(),
         root,
-        baseline_instant: None,
         baseline_unix_micros: 0,
+        custom_recognizers: Vec::new(),
     };
 
     let output = trace.format_tree(false);
@@ -77,15 +80,65 @@ fn test_truncate_input_in_trace_node() {
 #[case::after_success(true)]
 fn test_trace_node_timing(#[case] complete: bool) {
     let mut node = TraceNode::new("timed", "input");
-    assert!(node.end_time.is_none());
+    assert!(node.start_micros.is_none());
 
     if complete {
         node.success(5, "result");
-        assert!(node.end_time.is_some());
-        assert!(node.start_time.is_some());
+        // Hand-built nodes carry no timing; only the collector records it.
+        assert!(node.duration_micros.is_none());
     }
 }
 
+#[rstest]
+fn test_collected_nodes_are_timed_from_the_trace_start(parser: IngredientParser) {
+    fn check(node: &TraceNode, parent_start: u64) {
+        let start = node.start_micros.unwrap();
+        assert!(
+            start >= parent_start,
+            "{} starts before its parent",
+            node.name
+        );
+        assert!(
+            node.duration_micros.is_some(),
+            "{} has no duration",
+            node.name
+        );
+        for child in &node.children {
+            check(child, start);
+        }
+    }
+    let trace = parser.parse_with_trace("2 cups flour").trace;
+    check(&trace.root, 0);
+}
+
+#[rstest]
+fn test_trace_serde_round_trip(parser: IngredientParser) {
+    let trace = parser
+        .parse_with_trace("1 (14-ounce) can tomatoes, drained")
+        .trace;
+    let json = serde_json::to_string(&trace).unwrap();
+    let back: ParseTrace = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.input, trace.input);
+    assert_eq!(back.baseline_unix_micros, trace.baseline_unix_micros);
+    assert_eq!(back.format_tree(false), trace.format_tree(false));
+    assert!(back.diff(&trace).first_divergence().is_none());
+}
+
+#[test]
+fn test_trace_outcome_serializes_with_a_status_tag() {
+    let mut node = TraceNode::new("parse_amount", "2 cups");
+    node.success(6, "2 cups");
+    let json = serde_json::to_value(&node).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "name": "parse_amount",
+            "input": "2 cups",
+            "outcome": {"status": "success", "consumed": 6, "output_preview": "2 cups"},
+        })
+    );
+}
+
 // NOTE: the thread-local tracing hooks (enable_tracing/trace_enter/…) are
 // crate-internal (`pub(crate)`); their unit tests live alongside them in
 // `src/trace/collector.rs`. The public entry point is `parse_with_trace`.
@@ -99,7 +152,7 @@ fn test_parse_trace_new() {
     let trace = ParseTrace::new("test input");
     assert_eq!(trace.input, "test input");
     assert_eq!(trace.root.name, "parse_ingredient");
-    assert!(trace.baseline_instant.is_none());
+    assert!(trace.root.start_micros.is_none());
     assert!(trace.baseline_unix_micros > 0);
 }
 
@@ -126,8 +179,8 @@ fn test_format_tree_colored() {
     let trace = ParseTrace {
         input: "input".to_string(),
         root,
-        baseline_instant: None,
         baseline_unix_micros: 0,
+        custom_recognizers: Vec::new(),
     };
 
     let colored = trace.format_tree(true);
@@ -147,8 +200,8 @@ fn test_trace_incomplete_outcome() {
     let trace = ParseTrace {
         input: "input".to_string(),
         root: node,
-        baseline_instant: None,
         baseline_unix_micros: 0,
+        custom_recognizers: Vec::new(),
     };
     assert!(trace.format_tree(false).contains("..."));
 }
@@ -175,8 +228,12 @@ fn test_custom_units(parser_with_units: IngredientParser) {
 // Jaeger JSON Export Tests
 // ============================================================================
 
-fn create_test_trace(with_children: bool, with_baseline: bool) -> ParseTrace {
+fn create_test_trace(with_children: bool, with_timing: bool) -> ParseTrace {
     let mut root = TraceNode::new("test_parser", "test input");
+    if with_timing {
+        root.start_micros = Some(0);
+        root.duration_micros = Some(42);
+    }
 
     if with_children {
         let mut child1 = TraceNode::new("child1", "test input");
@@ -193,21 +250,17 @@ fn create_test_trace(with_children: bool, with_baseline: bool) -> ParseTrace {
     ParseTrace {
         input: "test input".to_string(),
         root,
-        baseline_instant: if with_baseline {
-            Some(std::time::Instant::now())
-        } else {
-            None
-        },
         baseline_unix_micros: 1000000,
+        custom_recognizers: Vec::new(),
     }
 }
 
 #[rstest]
 #[case::basic(false, true)]
 #[case::with_children(true, true)]
-#[case::no_baseline(false, false)]
-fn test_jaeger_json_structure(#[case] with_children: bool, #[case] with_baseline: bool) {
-    let trace = create_test_trace(with_children, with_baseline);
+#[case::untimed(false, false)]
+fn test_jaeger_json_structure(#[case] with_children: bool, #[case] with_timing: bool) {
+    let trace = create_test_trace(with_children, with_timing);
     let json = trace.to_jaeger_json();
     let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
@@ -222,6 +275,11 @@ fn test_jaeger_json_structure(#[case] with_children: bool, #[case] with_baseline
     if with_children {
         assert_eq!(parsed["data"][0]["spans"].as_array().unwrap().len(), 3);
     }
+    if with_timing {
+        let root = &parsed["data"][0]["spans"][0];
+        assert_eq!(root["startTime"], 1000000);
+        assert_eq!(root["duration"], 42);
+    }
 }
 
 /// Each outcome emits its own tag set on the Jaeger span. `incomplete` is the
@@ -241,8 +299,8 @@ fn test_jaeger_json_tags(#[case] outcome: &str, #[case] expected_keys: &[&str])
     let trace = ParseTrace {
         input: "input".to_string(),
         root,
-        baseline_instant: Some(std::time::Instant::now()),
         baseline_unix_micros: 1000000,
+        custom_recognizers: Vec::new(),
     };
 
     let json = trace.to_jaeger_json();
@@ -271,8 +329,8 @@ fn test_jaeger_json_references() {
     let trace = ParseTrace {
         input: "input".to_string(),
         root,
-        baseline_instant: Some(std::time::Instant::now()),
         baseline_unix_micros: 1000000,
+        custom_recognizers: Vec::new(),
     };
 
     let json = trace.to_jaeger_json();
@@ -289,6 +347,327 @@ fn test_jaeger_json_references() {
     assert_eq!(references[0]["refType"], "CHILD_OF");
 }
 
+// ============================================================================
+// OTLP Export Tests (feature `otlp`)
+// ============================================================================
+
+#[cfg(feature = "otlp")]
+mod otlp {
+    use super::*;
+    use ingredient::trace::otlp::OtlpTrace;
+
+    fn attribute<'a>(span: &'a serde_json::Value, key: &str) -> Option<&'a serde_json::Value> {
+        span["attributes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|a| a["key"] == key)
+            .map(|a| &a["value"])
+    }
+
+    #[rstest]
+    fn spans_share_one_trace_and_link_to_their_parents(parser: IngredientParser) {
+        let trace = parser.parse_with_trace("2 cups flour, sifted").trace;
+        let json: serde_json::Value = serde_json::from_str(&trace.to_otlp_json()).unwrap();
+        let resource = &json["resourceSpans"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0]["value"]["stringValue"],
+            "ingredient-parser"
+        );
+        let spans = resource["scopeSpans"][0]["spans"].as_array().unwrap();
+        assert!(spans.len() > 1);
+
+        let trace_id = spans[0]["traceId"].as_str().unwrap();
+        assert_eq!(trace_id.len(), 32);
+        let ids: Vec<&str> = spans
+            .iter()
+            .map(|s| s["spanId"].as_str().unwrap())
+            .collect();
+        for span in spans {
+            assert_eq!(span["traceId"], trace_id);
+            if let Some(parent) = span["parentSpanId"].as_str() {
+                assert!(ids.contains(&parent), "dangling parent {parent}");
+            }
+        }
+        assert!(spans[0].get("parentSpanId").is_none());
+    }
+
+    #[rstest]
+    fn spans_carry_stage_pass_and_consumed_bytes(parser: IngredientParser) {
+        let trace = parser.parse_with_trace("2 cups flour").trace;
+        let mut export = OtlpTrace::new("test", "seed");
+        export.add_parse_trace(&trace, None);
+        let json = export.to_json();
+        let spans = json["resourceSpans"][0]["scopeSpans"][0]["spans"]
+            .as_array()
+            .unwrap();
+
+        let grammar = spans
+            .iter()
+            .find(|s| s["name"] == "parse_ingredient")
+            .unwrap();
+        assert_eq!(
+            attribute(grammar, "parser.stage").unwrap()["stringValue"],
+            "grammar"
+        );
+        assert_eq!(
+            attribute(grammar, "parser.pass").unwrap()["stringValue"],
+            "parse_ingredient"
+        );
+        assert_eq!(
+            attribute(grammar, "parser.consumed_bytes").unwrap()["intValue"],
+            "12"
+        );
+        assert_eq!(grammar["status"]["code"], 1);
+
+        let failed = spans.iter().find(|s| s["status"]["code"] == 2).unwrap();
+        assert!(failed["status"]["message"].is_string());
+        assert!(attribute(failed, "parser.consumed_bytes").is_none());
+    }
+
+    #[test]
+    fn ids_follow_the_seed() {
+        let trace = create_test_trace(true, false);
+        let export = |seed: &str| {
+            let mut e = OtlpTrace::new("test", seed);
+            e.add_parse_trace(&trace, None);
+            e
+        };
+        let (a, b, c) = (export("one"), export("one"), export("two"));
+        assert_eq!(a.trace_id(), b.trace_id());
+        assert_eq!(a.spans(), b.spans());
+        assert_ne!(a.trace_id(), c.trace_id());
+        // Pinned, so a toolchain or hasher change can't silently move them.
+        assert_eq!(a.trace_id().to_string(), "0715b7b46adb7d5d0715b6b46adb7baa");
+    }
+
+    /// Untimed nodes start at the trace's baseline and last no time.
+    #[test]
+    fn untimed_nodes_start_at_the_baseline() {
+        let trace = create_test_trace(true, false);
+        let mut export = OtlpTrace::new("test", "seed");
+        let root = export.add_parse_trace(&trace, None);
+        let spans = export.spans();
+        assert_eq!(spans[0].span_id, root);
+        for span in spans {
+            assert_eq!(span.start_unix_nanos, 1_000_000_000);
+            assert_eq!(span.end_unix_nanos, span.start_unix_nanos);
+        }
+        assert!(spans[1..].iter().all(|s| s.parent_span_id == Some(root)));
+    }
+}
+
+// ============================================================================
+// Pass Profile Tests (IngredientParser::profile_passes)
+// ============================================================================
+
+#[rstest]
+fn profile_counts_every_pass_on_every_line(parser: IngredientParser) {
+    let lines = ["2 cups flour", "\u{a0}1 tsp salt", "Juice of 1 lemon"];
+    let report = parser.profile_passes(lines);
+    assert_eq!(report.lines, 3);
+
+    let pass = |stage: &str, name: &str| {
+        report
+            .passes
+            .iter()
+            .find(|p| p.stage == stage && p.name == name)
+            .unwrap()
+    };
+    // Every line runs every rewrite; only the nbsp line fires this one.
+    let nbsp = pass("normalize", "strip_nbsp");
+    assert_eq!((nbsp.calls, nbsp.fired), (3, 1));
+    // Recognizers run until one claims the line, so the first runs on all.
+    let x_of = pass("recognize", "x_of_construction");
+    assert_eq!(x_of.fired, 1);
+    assert!(x_of.self_nanos <= x_of.total_nanos);
+    // Passes that never ran are listed too: the whole pipeline, once each.
+    let names = ingredient::trace::pipeline_stage_names();
+    assert_eq!(
+        report.passes.len(),
+        names.normalize.len() + names.recognizers.len() + names.refine.len()
+    );
+
+    let root = report
+        .stages
+        .iter()
+        .find(|s| s.name == "parse_line")
+        .unwrap();
+    assert_eq!(root.calls, 3);
+    assert!(root.total_nanos <= report.total_nanos);
+}
+
+#[rstest]
+fn profile_counts_regex_uses(parser: IngredientParser) {
+    let report = parser.profile_passes(["butter (see note)", "2 eggs"]);
+    let note_ref = report
+        .regexes
+        .iter()
+        .find(|r| r.name == "parser::paren::NOTE_REF")
+        .unwrap();
+    assert!(note_ref.uses >= 2);
+}
+
+#[rstest]
+fn profile_folds_stacks_for_a_flamegraph(parser: IngredientParser) {
+    let report = parser.profile_passes(["Juice of 1 lemon"]);
+    let folded = report.folded();
+    for line in folded.lines() {
+        let (stack, weight) = line.rsplit_once(' ').unwrap();
+        assert!(stack.starts_with("parse_line"), "{line}");
+        weight.parse::<u64>().unwrap();
+    }
+    // A recognizer that re-parses through the grammar nests it.
+    assert!(
+        folded.contains("parse_line;recognize;x_of_construction;grammar"),
+        "{folded}"
+    );
+    // Self times add up to the root's total.
+    let root = report
+        .stages
+        .iter()
+        .find(|s| s.name == "parse_line")
+        .unwrap();
+    let self_sum: u64 = report.stacks.iter().map(|(_, n)| n).sum();
+    assert_eq!(self_sum, root.total_nanos);
+}
+
+// ============================================================================
+// Custom Pass Tests (IngredientParser::with_pass)
+// ============================================================================
+
+/// "tbl" → "tbsp", after the first normalize rewrite.
+struct Tbl;
+
+impl CustomPass for Tbl {
+    fn name(&self) -> &'static str {
+        "expand_tbl"
+    }
+    fn position(&self) -> Position {
+        Position::After("strip_nbsp")
+    }
+    fn rewrite<'a>(&self, line: &'a str) -> Cow<'a, str> {
+        if line.contains(" tbl ") {
+            Cow::Owned(line.replace(" tbl ", " tbsp "))
+        } else {
+            Cow::Borrowed(line)
+        }
+    }
+}
+
+/// Claims "house blend" lines outright, ahead of every built-in recognizer.
+struct HouseBlend;
+
+impl CustomPass for HouseBlend {
+    fn name(&self) -> &'static str {
+        "house_blend"
+    }
+    fn position(&self) -> Position {
+        Position::Before("optional_wrapped")
+    }
+    fn recognize(&self, _parser: &IngredientParser, line: &str) -> Option<Ingredient> {
+        (line == "house blend").then(|| Ingredient::new("house spice blend", vec![], None))
+    }
+}
+
+/// Upper-cases the name, after every built-in refine pass.
+struct Shout(&'static str);
+
+impl CustomPass for Shout {
+    fn name(&self) -> &'static str {
+        self.0
+    }
+    fn position(&self) -> Position {
+        Position::After(
+            ingredient::trace::pipeline_stage_names()
+                .refine
+                .last()
+                .copied()
+                .unwrap(),
+        )
+    }
+    fn refine(&self, ingredient: &mut Refining<'_>) {
+        let name = ingredient.name().to_uppercase();
+        ingredient.set_name(name);
+    }
+}
+
+#[fixture]
+fn custom_parser() -> IngredientParser {
+    IngredientParser::new()
+        .with_pass(Tbl)
+        .unwrap()
+        .with_pass(HouseBlend)
+        .unwrap()
+        .with_pass(Shout("shout"))
+        .unwrap()
+}
+
+#[rstest]
+fn custom_passes_run_and_are_traced(custom_parser: IngredientParser) {
+    let traced = custom_parser.parse_with_trace("2 tbl sugar");
+    assert_eq!(traced.result.unwrap().to_string(), "2 tbsp SUGAR");
+    let stages = traced.trace.stages();
+    assert_eq!(stages.normalize[0].name, "expand_tbl");
+    assert_eq!(stages.refine.last().unwrap().name, "shout");
+
+    let stages = custom_parser.parse_with_trace("house blend").trace.stages();
+    assert_eq!(stages.recognizers.len(), 1);
+    assert_eq!(stages.recognizers[0].name, "house_blend");
+    assert!(stages.recognizer_matched());
+    // The built-in parser never heard of them.
+    assert_eq!(parser().from_str("2 tbl sugar").name, "tbl sugar");
+}
+
+#[rstest]
+fn custom_passes_are_listed_and_profiled(custom_parser: IngredientParser) {
+    let builtin = ingredient::trace::pipeline_stage_names();
+    let names = custom_parser.pipeline_stage_names();
+    assert_eq!(&names.normalize[..2], ["strip_nbsp", "expand_tbl"]);
+    assert_eq!(names.recognizers[0], "house_blend");
+    assert_eq!(names.refine.last(), Some(&"shout"));
+    assert_eq!(names.normalize.len(), builtin.normalize.len() + 1);
+    assert_eq!(names.segment, builtin.segment);
+
+    let report = custom_parser.profile_passes(["2 tbl sugar", "house blend"]);
+    let pass = |stage: &str, name: &str| {
+        report
+            .passes
+            .iter()
+            .find(|p| p.stage == stage && p.name == name)
+            .unwrap()
+    };
+    assert_eq!(pass("normalize", "expand_tbl").fired, 1);
+    assert_eq!(pass("recognize", "house_blend").calls, 2);
+    assert_eq!(pass("recognize", "house_blend").fired, 1);
+    assert_eq!(pass("refine", "shout").calls, 1);
+}
+
+#[rstest]
+#[case::unknown_anchor(IngredientParser::new(), Position::Before("no_such_pass"))]
+#[case::duplicate(custom_parser(), Position::After("strip_nbsp"))]
+fn custom_pass_registration_errors(#[case] parser: IngredientParser, #[case] at: Position) {
+    struct Placed(Position);
+    impl CustomPass for Placed {
+        fn name(&self) -> &'static str {
+            "shout"
+        }
+        fn position(&self) -> Position {
+            self.0
+        }
+    }
+    let err = parser.with_pass(Placed(at)).unwrap_err();
+    assert!(matches!(err, IngredientError::PassError { .. }), "{err}");
+}
+
+#[test]
+fn custom_pass_cannot_take_a_builtin_name() {
+    let err = IngredientParser::new()
+        .with_pass(Shout("strip_nbsp"))
+        .unwrap_err();
+    assert!(err.to_string().contains("'strip_nbsp'"), "{err}");
+}
+
 // ============================================================================
 // Edge Case Tests - Parser Robustness
 // ============================================================================
@@ -428,8 +807,8 @@ fn test_stages_synthetic_variants() {
         ParseTrace {
             input: "input".to_string(),
             root,
-            baseline_instant: None,
             baseline_unix_micros: 0,
+            custom_recognizers: Vec::new(),
         }
     }
 
@@ -492,3 +871,86 @@ fn test_parse_with_trace_range_unit_mismatch(parser: IngredientParser) {
         "trace tree should show the range parser attempt:\n{tree}"
     );
 }
+
+// ============================================================================
+// Trace Diff Tests (ParseTrace::diff)
+// ============================================================================
+
+/// root(amount ✓, name ✓ → `name`), as a parser would leave it.
+fn small_trace(name: &str) -> ParseTrace {
+    let mut root = TraceNode::new("parse_ingredient", "2 cups flour");
+    let mut amount = TraceNode::new("parse_amount", "2 cups flour");
+    amount.success(6, "2 cups");
+    root.add_child(amount);
+    let mut ingredient = TraceNode::new("parse_name", "flour");
+    ingredient.success(5, name);
+    root.add_child(ingredient);
+    root.success(12, name);
+    ParseTrace {
+        input: "2 cups flour".to_string(),
+        root,
+        baseline_unix_micros: 0,
+        custom_recognizers: Vec::new(),
+    }
+}
+
+#[rstest]
+fn test_diff_of_a_trace_with_itself_folds_to_the_root(parser: IngredientParser) {
+    let trace = parser.parse_with_trace("2 cups flour").trace;
+    let diff = trace.diff(&trace.clone());
+    assert!(diff.is_identical());
+    assert!(diff.first_divergence().is_none());
+    let out = diff.format(false);
+    assert_eq!(out.lines().count(), 1, "{out}");
+    assert!(out.contains("identical below"));
+}
+
+#[test]
+fn test_diff_descends_to_the_innermost_change() {
+    let diff = small_trace("flour").diff(&small_trace("flours"));
+    let path: Vec<&str> = diff
+        .first_divergence()
+        .unwrap()
+        .iter()
+        .map(|n| n.name.as_str())
+        .collect();
+    assert_eq!(path, ["parse_ingredient", "parse_name"]);
+    assert_eq!(diff.root.change(), NodeChange::Changed);
+    assert_eq!(diff.root.children[0].change(), NodeChange::Same);
+
+    let out = diff.format(false);
+    assert!(
+        out.contains("~    └─ parse_name \"flour\" ✓ → flour ⇒ ✓ → flours  ◀ first divergence"),
+        "{out}"
+    );
+}
+
+#[test]
+fn test_diff_aligns_children_by_name() {
+    let before = small_trace("flour");
+    let mut after = small_trace("flour");
+    let mut extra = TraceNode::new("parse_modifier", "");
+    extra.failure("no modifier");
+    after.root.children.insert(1, extra);
+
+    let diff = before.diff(&after);
+    let changes: Vec<NodeChange> = diff.root.children.iter().map(|c| c.change()).collect();
+    assert_eq!(
+        changes,
+        [NodeChange::Same, NodeChange::Added, NodeChange::Same]
+    );
+    let path = diff.first_divergence().unwrap();
+    assert_eq!(path.last().unwrap().name, "parse_modifier");
+    assert!(diff.format(false).contains("+    ├─ parse_modifier"));
+
+    let reverse = after.diff(&before);
+    assert_eq!(reverse.root.children[1].change(), NodeChange::Removed);
+}
+
+#[test]
+fn test_diff_reports_differing_inputs() {
+    let mut other = small_trace("flour");
+    other.input = "2 cups flour, sifted".to_string();
+    let out = small_trace("flour").diff(&other).format(false);
+    assert!(out.starts_with("- input \"2 cups flour\"\n+ input \"2 cups flour, sifted\"\n"));
+}
@@ -5,7 +5,10 @@
 use std::collections::HashSet;
 use std::str::FromStr;
 
-use ingredient::unit::{Measure, MeasureKind, Unit, is_valid, make_graph, print_graph};
+use ingredient::unit::{
+    Measure, MeasureKind, Qualifier, Unit, convert_measure_with_graph, is_valid, make_graph,
+    print_graph,
+};
 use ingredient::util::num_without_zeroes;
 use rstest::rstest;
 
@@ -35,6 +38,20 @@ use rstest::rstest;
 #[case::gallon_gal("gal", Unit::Gallon)]
 #[case::fl_oz("fl oz", Unit::FluidOunce)]
 #[case::fluid_oz("fluid oz", Unit::FluidOunce)]
+#[case::pint("pints", Unit::Pint)]
+#[case::us_pint("us pint", Unit::Pint)]
+#[case::us_cup("US cups", Unit::Cup)]
+#[case::imperial_pint("imperial pint", Unit::ImperialPint)]
+#[case::uk_pint("UK pints", Unit::ImperialPint)]
+#[case::imperial_fl_oz("imperial fl oz", Unit::ImperialFluidOunce)]
+#[case::metric_cup("metric cups", Unit::MetricCup)]
+#[case::au_tbsp("au tbsp", Unit::AustralianTablespoon)]
+#[case::dessertspoon("dessertspoons", Unit::Dessertspoon)]
+#[case::dsp("dsp", Unit::Dessertspoon)]
+#[case::gill("gills", Unit::Gill)]
+#[case::teacup("teacupful", Unit::Teacup)]
+#[case::cm("centimetres", Unit::Centimeter)]
+#[case::mm("mm", Unit::Millimeter)]
 #[case::oz("oz", Unit::Ounce)]
 #[case::ounce("ounce", Unit::Ounce)]
 #[case::lb("lb", Unit::Pound)]
@@ -81,6 +98,12 @@ fn test_unit_from_str_unknown() {
 #[case::cup(Unit::Cup, "cup")]
 #[case::gallon(Unit::Gallon, "gallon")]
 #[case::fluid_ounce(Unit::FluidOunce, "fl oz")]
+#[case::pint(Unit::Pint, "pint")]
+#[case::imperial_pint(Unit::ImperialPint, "imperial pint")]
+#[case::metric_cup(Unit::MetricCup, "metric cup")]
+#[case::au_tbsp(Unit::AustralianTablespoon, "au tbsp")]
+#[case::centimeter(Unit::Centimeter, "cm")]
+#[case::millimeter(Unit::Millimeter, "mm")]
 #[case::ounce(Unit::Ounce, "oz")]
 #[case::pound(Unit::Pound, "lb")]
 #[case::dollar(Unit::Dollar, "$")]
@@ -367,6 +390,40 @@ fn test_gallon_converts_to_volume_ml() {
     assert!((vol.value() - 3785.4).abs() < 1.0, "got {}", vol.value());
 }
 
+/// Each regional volume normalizes by its own size: the unit a region-aware
+/// parse keeps is what makes the conversion right.
+#[rstest]
+#[case::us_pint("pint", 473.18)]
+#[case::imperial_pint("imperial pint", 568.26)]
+#[case::imperial_fl_oz("imperial fl oz", 28.41)]
+#[case::imperial_gallon("imperial gallon", 4546.09)]
+#[case::metric_cup("metric cup", 250.0)]
+#[case::au_tbsp("au tbsp", 20.0)]
+#[case::dessertspoon("dessertspoon", 10.0)]
+#[case::gill("gill", 142.07)]
+#[case::teacup("teacup", 189.42)]
+fn test_regional_volumes_in_ml(#[case] unit: &str, #[case] ml: f64) {
+    let graph = make_graph(&[(Measure::new(unit, 1.0), Measure::new("dollar", 1.0))]);
+    let got =
+        convert_measure_with_graph(&Measure::new(unit, 1.0), MeasureKind::Volume, &graph).unwrap();
+    assert_eq!(*got.unit(), Unit::Milliliter, "{unit}");
+    assert!(
+        (got.value() - ml).abs() < 0.01,
+        "{unit}: got {}",
+        got.value()
+    );
+}
+
+#[test]
+fn test_regional_units_keep_their_name_in_display() {
+    assert_eq!(
+        Measure::new("uk pints", 2.0).to_string(),
+        "2 imperial pints"
+    );
+    assert_eq!(Measure::new("metric cup", 1.0).to_string(), "1 metric cup");
+    assert_eq!(Measure::new("au tbsp", 2.0).to_string(), "2 au tbsp");
+}
+
 #[rstest]
 // Money rounds at the cent, so these carry the true imperial-mass factors
 // (1 lb = 453.592 g) rather than a whole-cent approximation of them.
@@ -544,3 +601,39 @@ fn test_measure_custom_unit_singularized() {
     let m = Measure::new("packets", 2.0);
     assert_eq!(m.unit_as_string(), "packet");
 }
+
+// ============================================================================
+// Qualified amounts ("about", "scant", "heaping", …)
+// ============================================================================
+
+#[rstest]
+#[case::heaping(Qualifier::Heaping, Measure::with_range("g", 15.0, 18.75))]
+#[case::scant(Qualifier::Scant, Measure::with_range("g", 13.5, 15.0))]
+#[case::about(Qualifier::Approximate, Measure::with_range("g", 13.5, 16.5))]
+#[case::at_least(
+    Qualifier::AtLeast,
+    Measure::new("g", 15.0).with_qualifier(Qualifier::AtLeast)
+)]
+fn test_qualifier_flows_through_conversion(#[case] q: Qualifier, #[case] want: Measure) {
+    let graph = make_graph(&[(Measure::new("tbsp", 1.0), Measure::new("g", 15.0))]);
+    let tbsp = Measure::new("tbsp", 1.0).with_qualifier(q);
+    assert_eq!(
+        convert_measure_with_graph(&tbsp, MeasureKind::Weight, &graph),
+        Some(want)
+    );
+}
+
+#[test]
+fn test_qualifier_survives_scaling_and_display() {
+    let scant = ingredient::from_str("scant ½ cup flour");
+    assert_eq!(scant.amounts[0].qualifier(), Some(Qualifier::Scant));
+    assert_eq!(scant.scale(2.0).to_string(), "scant 1 cup flour");
+    // Serialized under its snake_case name; absent when unqualified.
+    let json = serde_json::to_string(&scant.amounts[0]).unwrap();
+    assert!(json.contains(r#""qualifier":"scant""#), "{json}");
+    assert!(
+        !serde_json::to_string(&Measure::new("cup", 1.0))
+            .unwrap()
+            .contains("qualifier")
+    );
+}
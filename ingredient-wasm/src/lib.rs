@@ -15,8 +15,8 @@ use ingredient::{
     ingredient::Ingredient,
     rich_text::{Chunk, RichParser},
     unit::{
-        Measure, MeasureKind, convert_measure_with_graph, is_valid, make_graph,
-        mapping_target_kind, print_graph,
+        Aspect, Dimension, Measure, MeasureKind, Qualifier, convert_measure_with_graph, is_valid,
+        make_graph, mapping_target_kind, print_graph,
     },
     unit_mapping::{ParsedUnitMapping, parse_unit_mapping as parse_unit_mapping_internal},
     util::truncate_3_decimals,
@@ -53,13 +53,24 @@ pub struct WAmount {
     pub value: f64,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub upper_value: Option<f64>,
+    /// "about", "scant", … when the recipe qualified the amount.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[tsify(
+        optional,
+        type = "\"approximate\" | \"scant\" | \"heaping\" | \"generous\" | \"at_least\""
+    )]
+    pub qualifier: Option<Qualifier>,
 }
 
 impl WAmount {
     fn to_measure(&self) -> Measure {
-        match self.upper_value {
+        let m = match self.upper_value {
             Some(upper) => Measure::with_range(&self.unit, self.value, upper),
             None => Measure::new(&self.unit, self.value),
+        };
+        match self.qualifier {
+            Some(q) => m.with_qualifier(q),
+            None => m,
         }
     }
 }
@@ -72,6 +83,7 @@ impl From<&Measure> for WAmount {
             unit: m.unit().to_str().into_owned(),
             value: m.value(),
             upper_value: m.upper_value(),
+            qualifier: m.qualifier(),
         }
     }
 }
@@ -305,6 +317,30 @@ impl From<ScrapedRecipe> for WScrapedRecipe {
     }
 }
 
+/// A pan or cut size (mirrors `Dimension`). Into-only.
+#[derive(Tsify, Serialize)]
+pub struct WDimension {
+    pub sides: Vec<f64>,
+    pub unit: String,
+    /// "thick", "round", … when the recipe named the extent or pan shape.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[tsify(
+        optional,
+        type = "\"thick\" | \"long\" | \"wide\" | \"deep\" | \"tall\" | \"round\" | \"square\""
+    )]
+    pub aspect: Option<Aspect>,
+}
+
+impl From<Dimension> for WDimension {
+    fn from(d: Dimension) -> Self {
+        Self {
+            sides: d.sides().to_vec(),
+            unit: d.unit().to_str().into_owned(),
+            aspect: d.aspect(),
+        }
+    }
+}
+
 /// One span of measurement-aware instruction text (mirrors `Chunk`). Into-only.
 #[derive(Tsify, Serialize)]
 #[serde(tag = "kind", content = "value")]
@@ -312,6 +348,7 @@ pub enum RichItem {
     Text(String),
     Ing(String),
     Measure(Vec<WAmount>),
+    Dimension(WDimension),
 }
 
 impl From<Chunk> for RichItem {
@@ -320,6 +357,7 @@ impl From<Chunk> for RichItem {
             Chunk::Text(t) => RichItem::Text(t),
             Chunk::Ing(i) => RichItem::Ing(i),
             Chunk::Measure(ms) => RichItem::Measure(ms.iter().map(WAmount::from).collect()),
+            Chunk::Dimension(d) => RichItem::Dimension(d.into()),
         }
     }
 }
@@ -331,13 +369,17 @@ impl From<Chunk> for RichItem {
 pub struct RichItems(pub Vec<RichItem>);
 
 /// Which output field a decomposition segment became (mirrors `Field`). Renders
-/// as the TS string union `"amount" | "name" | "modifier"`.
+/// as the TS string union `"amount" | "name" | "modifier" | "alternative" |
+/// "usage" | "optional"`.
 #[derive(Tsify, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum WField {
     Amount,
     Name,
     Modifier,
+    Alternative,
+    Usage,
+    Optional,
 }
 
 impl From<Field> for WField {
@@ -346,6 +388,9 @@ impl From<Field> for WField {
             Field::Amount => WField::Amount,
             Field::Name => WField::Name,
             Field::Modifier => WField::Modifier,
+            Field::Alternative => WField::Alternative,
+            Field::Usage => WField::Usage,
+            Field::Optional => WField::Optional,
         }
     }
 }
@@ -361,9 +406,9 @@ pub struct WSegment {
     pub field: Option<WField>,
 }
 
-/// How the grammar carved a line into fields (mirrors `Decomposition`), as an
+/// How the parse carved a line into fields (mirrors `Decomposition`), as an
 /// ordered list of segments covering the whole source. `segments` carries no
-/// labeled entries when a recognizer or name-only fallback produced the result.
+/// labeled entries only when the name-only fallback produced the result.
 /// Into-only.
 #[derive(Tsify, Serialize)]
 #[tsify(into_wasm_abi)]
@@ -439,8 +484,8 @@ pub fn parse_ingredient(input: &str) -> WIngredient {
 }
 
 /// Decompose a line into ordered `{text, field?}` segments showing how the
-/// grammar carved it into amount / name / modifier spans (for the demo's
-/// diagnostic-style annotation).
+/// parse carved it into amount / name / modifier / alternative / usage /
+/// optional spans (for the demo's diagnostic-style annotation).
 #[wasm_bindgen]
 pub fn decompose_ingredient(input: &str) -> WDecomposition {
     decompose_str(input).into()
@@ -592,6 +637,7 @@ mod tests {
             unit: unit.to_string(),
             value,
             upper_value: None,
+            qualifier: None,
         }
     }
 
@@ -632,6 +678,7 @@ mod tests {
                 unit: unit.to_string(),
                 value: 1.0,
                 upper_value: None,
+                qualifier: None,
             }
             .to_measure()
             .kind()
@@ -672,7 +719,7 @@ mod tests {
                 Some(WField::Amount) => Some(("amount", s.text.as_str())),
                 Some(WField::Name) => Some(("name", s.text.as_str())),
                 Some(WField::Modifier) => Some(("modifier", s.text.as_str())),
-                None => None,
+                Some(_) | None => None,
             })
             .collect();
         assert_eq!(
@@ -696,11 +743,13 @@ mod tests {
                 unit: "cup".into(),
                 value: 1.0,
                 upper_value: None,
+                qualifier: None,
             },
             b: WAmount {
                 unit: "g".into(),
                 value: 120.0,
                 upper_value: None,
+                qualifier: None,
             },
             source: None,
         }]);
@@ -711,6 +760,7 @@ mod tests {
                 unit: "g".into(),
                 value: 240.0,
                 upper_value: None,
+                qualifier: None,
             },
         )
         .unwrap();
@@ -718,13 +768,19 @@ mod tests {
         assert!((converted.value - 2.0).abs() < 1e-9);
     }
 
-    /// A recognizer-handled line has no grammar carve → all segments unlabeled,
+    /// A recognizer-handled line is carved by its recognizer, the segments
     /// still reconstructing the source.
     #[test]
-    fn recognizer_line_has_no_labeled_segments() {
+    fn recognizer_line_has_labeled_segments() {
         let w: WDecomposition = decompose_str("Juice of 1 lemon").into();
         let joined: String = w.segments.iter().map(|s| s.text.as_str()).collect();
         assert_eq!(joined, w.source);
-        assert!(w.segments.iter().all(|s| s.field.is_none()));
+        let labeled: Vec<&str> = w
+            .segments
+            .iter()
+            .filter(|s| s.field.is_some())
+            .map(|s| s.text.as_str())
+            .collect();
+        assert_eq!(labeled, ["Juice of", "1", "lemon"]);
     }
 }
@@ -11,6 +11,7 @@ fn amount(unit: &str, value: f64) -> WAmount {
         unit: unit.to_string(),
         value,
         upper_value: None,
+        qualifier: None,
     }
 }
 
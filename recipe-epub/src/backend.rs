@@ -9,17 +9,22 @@
 
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
 use std::time::Duration;
 
 use futures::stream::{self, StreamExt};
 use serde::Deserialize;
 use serde_json::json;
+use tracing::Instrument;
+use tracing::field::Empty;
 
+use crate::epub_text::chunk_epub_with_outline;
 use crate::library::BookMeta;
 use crate::{
-    CallResult, Chunk, ChunkOutcome, CookbookRecipe, EpubError, ExtractProgress, ExtractedRecipe,
-    ExtractionStats, Link, RecipeExtractor, Usage, assemble, build_chunk_request, cache,
-    chunk_epub, parse_recipes_payload, resolve_references, try_extract_chunk,
+    CallResult, Chunk, ChunkOutcome, ChunkQuality, CookbookRecipe, CostEstimate, EpubError,
+    ExtractProgress, ExtractedRecipe, ExtractionStats, Link, RecipeExtractor, Usage, apply_outline,
+    assemble, build_chunk_request, cache, chunk_epub, estimate_chunk_usage, estimate_cost,
+    parse_recipes_payload, resolve_references, try_extract_chunk, verify_chunk,
 };
 
 // ===========================================================================
@@ -47,6 +52,18 @@ pub struct Options {
     /// and under-escapes), so a same-model retry can't fix them — but a *different*
     /// model usually can; the models' failure sets are effectively disjoint.
     pub escalate_model: Option<String>,
+    /// Also escalate a chunk whose output *parsed* but scored below this on
+    /// [`crate::verify_chunk`] (e.g. `0.8`: paraphrased, invented or dropped
+    /// lines). The better-scoring of the two outputs is kept. Needs
+    /// `escalate_model`; `None` escalates only on parse failure.
+    pub escalate_below_quality: Option<f64>,
+    /// Hard spending cap in USD for one run, escalations included. A chunk whose
+    /// estimated cost no longer fits under it is skipped (counted in
+    /// `ExtractionStats::chunks_over_budget`) and the run returns what it has;
+    /// an escalation that doesn't fit is skipped and the primary's output kept.
+    /// Cached chunks are free and always run. Refused for models without known
+    /// pricing.
+    pub max_cost_usd: Option<f64>,
 }
 
 impl Default for Options {
@@ -57,6 +74,8 @@ impl Default for Options {
             cache_dir: None,
             concurrency: 8,
             escalate_model: None,
+            escalate_below_quality: None,
+            max_cost_usd: None,
         }
     }
 }
@@ -85,14 +104,14 @@ pub async fn extract_cookbook_with_progress(
 ) -> Result<(Vec<CookbookRecipe>, ExtractionStats), EpubError> {
     let extractor = Backend::from_env(opts, source)?;
     // Build the escalation backend once (a different, usually stronger model),
-    // skipped when unset or identical to the primary. Deliberately uncached: it
-    // only runs for the rare chunk the primary can't parse.
+    // skipped when unset or identical to the primary. Cached like the primary:
+    // a primary output that scored low is served from the cache on a re-run,
+    // so without its own cache the escalation would be paid for again.
     let escalation = match &opts.escalate_model {
         Some(m) if m.as_str() != extractor.model() => {
             let esc_opts = Options {
                 model: Some(m.clone()),
                 escalate_model: None,
-                use_cache: false,
                 ..opts.clone()
             };
             Some(Backend::from_env(&esc_opts, source)?)
@@ -100,16 +119,15 @@ pub async fn extract_cookbook_with_progress(
         _ => None,
     };
     if opts.use_cache {
-        let caching = CachingExtractor {
-            inner: &extractor,
-            dir: opts.cache_dir.clone().unwrap_or_else(cache::default_dir),
-            model: extractor.model().to_string(),
-        };
+        let dir = opts.cache_dir.clone().unwrap_or_else(cache::default_dir);
+        let escalation = escalation
+            .as_ref()
+            .map(|esc| CachingExtractor::new(esc, dir.clone()));
         extract_cookbook_with_stats(
             bytes,
             source,
             opts,
-            &caching,
+            &CachingExtractor::new(&extractor, dir),
             escalation.as_ref(),
             &progress,
         )
@@ -127,6 +145,31 @@ pub async fn extract_cookbook_with_progress(
     }
 }
 
+/// Pre-flight estimate of extracting `bytes` with each of `models` (see
+/// [`crate::estimate_cost`]). With `opts.use_cache`, chunks the on-disk cache
+/// already holds for a model are counted as free. Makes no calls and needs no
+/// credentials.
+pub fn estimate_cookbook(
+    bytes: &[u8],
+    opts: &Options,
+    models: &[&str],
+) -> Result<Vec<CostEstimate>, EpubError> {
+    let chunks = chunk_epub(bytes)?;
+    let dir = opts.cache_dir.clone().unwrap_or_else(cache::default_dir);
+    Ok(models
+        .iter()
+        .map(|model| {
+            estimate_cost(&chunks, model, |c| {
+                opts.use_cache
+                    && cache::contains(
+                        &dir,
+                        &cache::key(model, &c.text, c.title_hint.as_deref().unwrap_or("")),
+                    )
+            })
+        })
+        .collect())
+}
+
 /// Like [`extract_cookbook`] but with a caller-supplied extractor (used by tests
 /// with [`crate::MockExtractor`]) and a progress sink (pass `|_| {}` to ignore it).
 pub async fn extract_cookbook_with<E: RecipeExtractor>(
@@ -137,7 +180,8 @@ pub async fn extract_cookbook_with<E: RecipeExtractor>(
     progress: impl Fn(ExtractProgress) + Send + Sync,
 ) -> Result<Vec<CookbookRecipe>, EpubError> {
     let (recipes, _stats) =
-        extract_cookbook_with_stats(bytes, source, opts, extractor, None, &progress).await?;
+        extract_cookbook_with_stats(bytes, source, opts, extractor, None::<&Backend>, &progress)
+            .await?;
     Ok(recipes)
 }
 
@@ -165,6 +209,9 @@ pub struct ChunkDebug {
     pub error: Option<String>,
     /// The model hit the token limit, so the payload may be incomplete.
     pub truncated: bool,
+    /// How well the parsed output aligns with the chunk text (verbatim lines,
+    /// paraphrases, inventions, dropped ingredients). `None` when it didn't parse.
+    pub quality: Option<ChunkQuality>,
 }
 
 /// Re-run an EPUB's chunks through the live model, capturing each chunk's RAW
@@ -195,6 +242,7 @@ pub async fn debug_extract_cookbook(
                     parsed: None,
                     error: None,
                     truncated: false,
+                    quality: None,
                 };
                 let call = backend
                     .call_tool(
@@ -218,7 +266,10 @@ pub async fn debug_extract_cookbook(
                         dbg.raw_input = input.clone();
                         match input {
                             Some(v) => match parse_recipes_payload(v) {
-                                Ok(rs) => dbg.parsed = Some(rs.len()),
+                                Ok(rs) => {
+                                    dbg.parsed = Some(rs.len());
+                                    dbg.quality = Some(verify_chunk(chunk, &rs));
+                                }
                                 Err(e) => dbg.error = Some(e.to_string()),
                             },
                             None => dbg.error = Some("model returned no tool block".to_string()),
@@ -237,23 +288,35 @@ pub async fn debug_extract_cookbook(
 
 /// Like [`extract_cookbook_with`] but also returns token-usage/cost stats and
 /// reports per-chunk progress through `progress`.
-async fn extract_cookbook_with_stats<E: RecipeExtractor>(
+///
+/// Runs in an `extract_cookbook` span with one `extract_chunk` span per chunk
+/// (doc path, model, cache hit, recipes found, tokens), so an OTLP export of
+/// an import shows where the time and tokens went.
+#[tracing::instrument(name = "extract_cookbook", skip_all, fields(source = %source, chunks = Empty))]
+async fn extract_cookbook_with_stats<E: RecipeExtractor, X: RecipeExtractor>(
     bytes: &[u8],
     source: &str,
     opts: &Options,
     extractor: &E,
-    escalation: Option<&Backend>,
+    escalation: Option<&X>,
     progress: &(impl Fn(ExtractProgress) + Send + Sync),
 ) -> Result<(Vec<CookbookRecipe>, ExtractionStats), EpubError> {
-    let chunks = chunk_epub(bytes)?;
+    let ledger = Ledger::new(
+        opts.max_cost_usd,
+        extractor.model(),
+        escalation.map(|e| e.model()),
+    )?;
+    let (chunks, outline) = chunk_epub_with_outline(bytes)?;
     let total = chunks.len();
     tracing::info!("epub {source}: {total} chunk(s)");
+    tracing::Span::current().record("chunks", total);
     // Emit the initial snapshot now that the total is known, so the UI can switch
     // from an indeterminate spinner to a determinate bar before any chunk lands.
     progress(ExtractProgress {
         done: 0,
         total,
-        cached: 0,
+        spent_usd: ledger.spent(),
+        ..ExtractProgress::default()
     });
 
     // Book-wide internal anchor links (author hyperlinks between recipes) —
@@ -271,60 +334,145 @@ async fn extract_cookbook_with_stats<E: RecipeExtractor>(
     let done = AtomicUsize::new(0);
     let cached = AtomicUsize::new(0);
     let failed = AtomicUsize::new(0);
+    let quality_escalated = AtomicUsize::new(0);
+    let over_budget = AtomicUsize::new(0);
     let per_chunk: Vec<(Chunk, ChunkOutcome)> = stream::iter(chunks.iter())
-        .map(|chunk| async {
-            let empty = || ChunkOutcome {
-                recipes: Vec::new(),
-                usage: Usage::default(),
-                cached: false,
-                truncated: false,
-            };
-            let outcome = match extractor.extract(chunk).await {
-                Ok(o) => o,
-                // Primary couldn't return a parseable payload (after its own
-                // in-call retry). Escalate this one chunk to the fallback model,
-                // then fall back to skip-and-salvage if that fails too.
-                Err(primary_err) => match escalation {
-                    Some(esc) => match esc.extract(chunk).await {
-                        Ok(o) => {
-                            tracing::info!(
-                                "chunk {} recovered by escalating to {}",
-                                chunk.doc_path,
-                                esc.model()
-                            );
-                            o
-                        }
-                        Err(esc_err) => {
-                            failed.fetch_add(1, Ordering::Relaxed);
-                            tracing::error!(
-                                "chunk {} failed on primary ({primary_err}) and escalation ({esc_err}); skipping — its recipes are lost",
-                                chunk.doc_path
-                            );
-                            empty()
-                        }
-                    },
+        .map(|chunk| {
+            let span = tracing::info_span!(
+                "extract_chunk",
+                doc_path = %chunk.doc_path,
+                model = extractor.model(),
+                cached = Empty,
+                recipes = Empty,
+                input_tokens = Empty,
+                output_tokens = Empty,
+                otel.status_code = Empty,
+            );
+            async {
+                let empty = || ChunkOutcome {
+                    recipes: Vec::new(),
+                    usage: Usage::default(),
+                    cached: false,
+                    truncated: false,
+                };
+                // Hold the call's estimated cost against the cap while it runs;
+                // skip the chunk if it doesn't fit. A cached chunk is free.
+                let outcome = match ledger.hold(extractor, chunk) {
                     None => {
-                        failed.fetch_add(1, Ordering::Relaxed);
-                        tracing::error!(
-                            "chunk {} extraction failed: {primary_err}; skipping — its recipes are lost",
-                            chunk.doc_path
-                        );
+                        over_budget.fetch_add(1, Ordering::Relaxed);
                         empty()
                     }
-                },
-            };
-            if outcome.cached {
-                cached.fetch_add(1, Ordering::Relaxed);
+                    Some(held) => {
+                        let result = extractor.extract(chunk).await;
+                        ledger.settle_call(held, extractor, &result);
+                        match result {
+                            // Parsed — but a low verification score means the model
+                            // paraphrased, invented or dropped lines. Give the escalation
+                            // model a go and keep whichever output aligns better.
+                            Ok(o) => match (escalation, opts.escalate_below_quality) {
+                                (Some(esc), Some(min)) => {
+                                    let score = verify_chunk(chunk, &o.recipes).score();
+                                    if score >= min {
+                                        o
+                                    } else {
+                                        let escalated = escalate(&ledger, esc, chunk).await;
+                                        if escalated.is_some() {
+                                            quality_escalated.fetch_add(1, Ordering::Relaxed);
+                                        }
+                                        match escalated {
+                                            Some(Ok(mut better))
+                                                if verify_chunk(chunk, &better.recipes).score()
+                                                    > score =>
+                                            {
+                                                tracing::info!(
+                                                    "chunk {} scored {score:.2}; replaced by {}'s output",
+                                                    chunk.doc_path,
+                                                    esc.model()
+                                                );
+                                                better.usage.add(&o.usage);
+                                                better
+                                            }
+                                            Some(Ok(worse)) => {
+                                                let mut o = o;
+                                                o.usage.add(&worse.usage);
+                                                o
+                                            }
+                                            Some(Err(e)) => {
+                                                tracing::warn!(
+                                                    "chunk {} scored {score:.2}; escalation failed ({e}), keeping it",
+                                                    chunk.doc_path
+                                                );
+                                                o
+                                            }
+                                            None => o,
+                                        }
+                                    }
+                                }
+                                _ => o,
+                            },
+                            // Primary couldn't return a parseable payload (after its own
+                            // in-call retry). Escalate this one chunk to the fallback model,
+                            // then fall back to skip-and-salvage if that fails too.
+                            Err(primary_err) => {
+                                let escalated = match escalation {
+                                    Some(esc) => escalate(&ledger, esc, chunk).await,
+                                    None => None,
+                                };
+                                match escalated {
+                                    Some(Ok(o)) => {
+                                        tracing::info!(
+                                            "chunk {} recovered by escalating to {}",
+                                            chunk.doc_path,
+                                            escalation.map(|e| e.model()).unwrap_or_default()
+                                        );
+                                        o
+                                    }
+                                    Some(Err(esc_err)) => {
+                                        failed.fetch_add(1, Ordering::Relaxed);
+                                        tracing::Span::current()
+                                            .record("otel.status_code", "ERROR");
+                                        tracing::error!(
+                                            "chunk {} failed on primary ({primary_err}) and escalation ({esc_err}); skipping — its recipes are lost",
+                                            chunk.doc_path
+                                        );
+                                        empty()
+                                    }
+                                    None => {
+                                        failed.fetch_add(1, Ordering::Relaxed);
+                                        tracing::Span::current()
+                                            .record("otel.status_code", "ERROR");
+                                        tracing::error!(
+                                            "chunk {} extraction failed: {primary_err}; skipping — its recipes are lost",
+                                            chunk.doc_path
+                                        );
+                                        empty()
+                                    }
+                                }
+                            }
+                        }
+                    }
+                };
+                if outcome.cached {
+                    cached.fetch_add(1, Ordering::Relaxed);
+                }
+                let span = tracing::Span::current();
+                span.record("cached", outcome.cached);
+                span.record("recipes", outcome.recipes.len());
+                span.record("input_tokens", outcome.usage.input_tokens);
+                span.record("output_tokens", outcome.usage.output_tokens);
+                let done_now = done.fetch_add(1, Ordering::Relaxed) + 1;
+                progress(ExtractProgress {
+                    done: done_now,
+                    total,
+                    cached: cached.load(Ordering::Relaxed),
+                    skipped: over_budget.load(Ordering::Relaxed),
+                    spent_usd: ledger.spent(),
+                });
+                // Carry the whole chunk (its text lines + image positions) so the
+                // assembler can bind each recipe's hero photo by title proximity.
+                (chunk.clone(), outcome)
             }
-            let done_now = done.fetch_add(1, Ordering::Relaxed) + 1;
-            progress(ExtractProgress {
-                done: done_now,
-                total,
-                cached: cached.load(Ordering::Relaxed),
-            });
-            // Carry the whole chunk (its text lines + image positions) so the
-            // assembler can bind each recipe's hero photo by title proximity.
-            (chunk.clone(), outcome)
+            .instrument(span)
         })
         .buffered(opts.concurrency.max(1))
         .collect::<Vec<_>>()
@@ -334,6 +482,9 @@ async fn extract_cookbook_with_stats<E: RecipeExtractor>(
         model: extractor.model().to_string(),
         chunks_total: per_chunk.len(),
         chunks_failed: failed.load(Ordering::Relaxed),
+        chunks_quality_escalated: quality_escalated.load(Ordering::Relaxed),
+        chunks_over_budget: over_budget.load(Ordering::Relaxed),
+        spent_usd: ledger.spent(),
         ..Default::default()
     };
     let recipes_by_chunk: Vec<(Chunk, Vec<ExtractedRecipe>)> = per_chunk
@@ -349,6 +500,20 @@ async fn extract_cookbook_with_stats<E: RecipeExtractor>(
 
     let mut recipes = assemble(recipes_by_chunk, source);
     resolve_references(&mut recipes, &links);
+    stats.missed_titles = apply_outline(&mut recipes, &outline);
+    if stats.chunks_over_budget > 0 {
+        tracing::warn!(
+            "epub {source}: budget of ${:.2} reached; {} chunk(s) not extracted",
+            opts.max_cost_usd.unwrap_or_default(),
+            stats.chunks_over_budget
+        );
+    }
+    if !stats.missed_titles.is_empty() {
+        tracing::warn!(
+            "epub {source}: listed in the TOC and index but not extracted: {}",
+            stats.missed_titles.join(", ")
+        );
+    }
     tracing::info!(
         "epub {source}: {} recipe(s); {}",
         recipes.len(),
@@ -357,6 +522,117 @@ async fn extract_cookbook_with_stats<E: RecipeExtractor>(
     Ok((recipes, stats))
 }
 
+/// Run the escalation model on `chunk`, holding its estimated cost against the
+/// cap like any call. `None` when it doesn't fit: the caller keeps what it has.
+async fn escalate<X: RecipeExtractor>(
+    ledger: &Ledger,
+    esc: &X,
+    chunk: &Chunk,
+) -> Option<Result<ChunkOutcome, EpubError>> {
+    let Some(held) = ledger.hold(esc, chunk) else {
+        tracing::warn!(
+            "chunk {}: escalation to {} skipped, over budget",
+            chunk.doc_path,
+            esc.model()
+        );
+        return None;
+    };
+    let result = esc.extract(chunk).await;
+    ledger.settle_call(held, esc, &result);
+    Some(result)
+}
+
+/// Running spend of one extraction run, checked against
+/// [`Options::max_cost_usd`]. Before each call — the primary's or an
+/// escalation's — its estimated cost ([`crate::estimate_chunk_usage`]) is
+/// *reserved*, and the call is skipped when that wouldn't fit, so concurrent
+/// calls can't jointly overshoot the cap on the estimate; afterwards the
+/// reservation is swapped for the real cost. A cache hit reserves nothing.
+struct Ledger {
+    cap: Option<f64>,
+    /// Every model the run may call has known pricing.
+    priced: bool,
+    /// (spent, reserved) in USD.
+    state: Mutex<(f64, f64)>,
+}
+
+impl Ledger {
+    /// Refuses a cap it couldn't enforce: one on a model with unknown pricing.
+    fn new(cap: Option<f64>, model: &str, escalation: Option<&str>) -> Result<Self, EpubError> {
+        let unpriced = std::iter::once(model)
+            .chain(escalation)
+            .find(|m| Usage::default().cost_usd(m).is_none());
+        if let (Some(cap), Some(m)) = (cap, unpriced) {
+            return Err(EpubError::Budget(format!(
+                "can't hold model {m:?} to a ${cap:.2} cap: its pricing is unknown"
+            )));
+        }
+        Ok(Self {
+            cap,
+            priced: unpriced.is_none(),
+            state: Mutex::new((0.0, 0.0)),
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, (f64, f64)> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Reserve the estimated cost of `extractor` extracting `chunk`: the amount
+    /// held, or `None` (nothing held) when it doesn't fit. Nothing is held for
+    /// a chunk `extractor` has cached.
+    fn hold<E: RecipeExtractor>(&self, extractor: &E, chunk: &Chunk) -> Option<f64> {
+        if extractor.is_cached(chunk) {
+            return Some(0.0);
+        }
+        let amount = self.price(extractor.model(), &estimate_chunk_usage(chunk));
+        self.reserve(amount).then_some(amount)
+    }
+
+    /// [`Self::settle`] a call `extractor` made, at what its result reports
+    /// it used (nothing known for a failed call).
+    fn settle_call<E: RecipeExtractor>(
+        &self,
+        held: f64,
+        extractor: &E,
+        result: &Result<ChunkOutcome, EpubError>,
+    ) {
+        let cost = result
+            .as_ref()
+            .map_or(0.0, |o| self.price(extractor.model(), &o.usage));
+        self.settle(held, cost);
+    }
+
+    /// Cost of `usage` at `model`'s rates (zero when unpriced, in which case
+    /// there is no cap and [`Self::spent`] reports `None`).
+    fn price(&self, model: &str, usage: &Usage) -> f64 {
+        usage.cost_usd(model).unwrap_or_default()
+    }
+
+    /// Hold `amount` against the cap; `false` (and nothing held) if it
+    /// doesn't fit in what's left.
+    fn reserve(&self, amount: f64) -> bool {
+        let mut state = self.lock();
+        let (spent, reserved) = *state;
+        if self.cap.is_some_and(|cap| spent + reserved + amount > cap) {
+            return false;
+        }
+        state.1 += amount;
+        true
+    }
+
+    /// Release a reservation and record what the call actually cost.
+    fn settle(&self, reserved: f64, actual: f64) {
+        let mut state = self.lock();
+        state.1 = (state.1 - reserved).max(0.0);
+        state.0 += actual;
+    }
+
+    fn spent(&self) -> Option<f64> {
+        self.priced.then(|| self.lock().0)
+    }
+}
+
 /// Wraps any extractor with the on-disk cache (see [`crate::cache`]).
 struct CachingExtractor<'a, E> {
     inner: &'a E,
@@ -364,17 +640,35 @@ struct CachingExtractor<'a, E> {
     model: String,
 }
 
+impl<'a, E: RecipeExtractor> CachingExtractor<'a, E> {
+    fn new(inner: &'a E, dir: PathBuf) -> Self {
+        Self {
+            inner,
+            dir,
+            model: inner.model().to_string(),
+        }
+    }
+
+    fn key(&self, chunk: &Chunk) -> String {
+        cache::key(
+            &self.model,
+            &chunk.text,
+            chunk.title_hint.as_deref().unwrap_or(""),
+        )
+    }
+}
+
 impl<E: RecipeExtractor> RecipeExtractor for CachingExtractor<'_, E> {
     fn model(&self) -> &str {
         self.inner.model()
     }
 
+    fn is_cached(&self, chunk: &Chunk) -> bool {
+        cache::contains(&self.dir, &self.key(chunk))
+    }
+
     async fn extract(&self, chunk: &Chunk) -> Result<ChunkOutcome, EpubError> {
-        let key = cache::key(
-            &self.model,
-            &chunk.text,
-            chunk.title_hint.as_deref().unwrap_or(""),
-        );
+        let key = self.key(chunk);
         if let Some(hit) = cache::read(&self.dir, &key) {
             // Cache hit: no API call, so no usage/cost is incurred.
             return Ok(ChunkOutcome {
@@ -446,6 +740,17 @@ fn build_client() -> Result<reqwest::Client, EpubError> {
 /// (auth, API version, …) plus the Cloudflare gateway authorization, and
 /// return the response body text. Maps a non-2xx status to [`EpubError::Api`].
 /// Owns the build-request / send / status-check mechanics shared by both backends.
+#[tracing::instrument(
+    name = "llm_request",
+    skip_all,
+    fields(
+        otel.kind = "client",
+        http.request.method = "POST",
+        url.full = endpoint,
+        http.response.status_code = Empty,
+        otel.status_code = Empty,
+    )
+)]
 async fn post_json(
     client: &reqwest::Client,
     endpoint: &str,
@@ -463,6 +768,11 @@ async fn post_json(
     let resp = req.json(body).send().await?;
 
     let status = resp.status();
+    let span = tracing::Span::current();
+    span.record("http.response.status_code", status.as_u16());
+    if !status.is_success() {
+        span.record("otel.status_code", "ERROR");
+    }
     let text = resp.text().await?;
     if !status.is_success() {
         return Err(EpubError::Api {
@@ -1263,6 +1573,228 @@ mod tests {
         }
     }
 
+    /// Extractor on a priced model that bills a fixed 1M input tokens per call
+    /// ($0.30 on gemini-2.5-flash), or fails every call, for budget tests.
+    struct PricedExtractor {
+        model: &'static str,
+        fails: bool,
+    }
+
+    const FLASH: PricedExtractor = PricedExtractor {
+        model: "gemini-2.5-flash",
+        fails: false,
+    };
+
+    impl RecipeExtractor for PricedExtractor {
+        fn model(&self) -> &str {
+            self.model
+        }
+
+        async fn extract(&self, chunk: &Chunk) -> Result<ChunkOutcome, EpubError> {
+            if self.fails {
+                return FailingExtractor.extract(chunk).await;
+            }
+            Ok(ChunkOutcome {
+                recipes: vec![],
+                usage: Usage {
+                    input_tokens: 1_000_000,
+                    ..Usage::default()
+                },
+                cached: false,
+                truncated: false,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn budget_cap_skips_chunks_that_dont_fit_and_reports_spend() {
+        let bytes = minimal_epub();
+        let run = |cap: Option<f64>| {
+            let opts = Options {
+                max_cost_usd: cap,
+                ..Options::default()
+            };
+            let snaps = std::sync::Mutex::new(Vec::new());
+            let bytes = &bytes;
+            async move {
+                let out = extract_cookbook_with_stats(
+                    bytes,
+                    "b.epub",
+                    &opts,
+                    &FLASH,
+                    None::<&Backend>,
+                    &|p| snaps.lock().unwrap().push(p),
+                )
+                .await;
+                (out, snaps.into_inner().unwrap())
+            }
+        };
+
+        let (out, snaps) = run(Some(1.0)).await;
+        let (_, stats) = out.unwrap();
+        assert_eq!(stats.chunks_over_budget, 0);
+        assert!((stats.spent_usd.unwrap() - 0.30).abs() < 1e-9);
+        assert_eq!(snaps.first().unwrap().spent_usd, Some(0.0));
+        assert!((snaps.last().unwrap().spent_usd.unwrap() - 0.30).abs() < 1e-9);
+
+        // A cap below even one chunk's estimate stops before any call.
+        let (out, snaps) = run(Some(0.0)).await;
+        let (_, stats) = out.unwrap();
+        assert_eq!(stats.chunks_over_budget, stats.chunks_total);
+        assert_eq!(stats.spent_usd, Some(0.0));
+        assert_eq!(snaps.last().unwrap().skipped, stats.chunks_total);
+        assert!(stats.summary().contains("budget reached"));
+    }
+
+    #[tokio::test]
+    async fn cached_chunks_run_after_the_budget_is_spent() {
+        let dir =
+            std::env::temp_dir().join(format!("recipe-epub-budget-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let bytes = minimal_epub();
+        for c in chunk_epub(&bytes).unwrap() {
+            let key = cache::key(FLASH.model, &c.text, c.title_hint.as_deref().unwrap_or(""));
+            cache::write(&dir, &key, &[]).unwrap();
+        }
+        let caching = CachingExtractor::new(&FLASH, dir.clone());
+        let opts = Options {
+            max_cost_usd: Some(0.0),
+            ..Options::default()
+        };
+        let (_, stats) = extract_cookbook_with_stats(
+            &bytes,
+            "b.epub",
+            &opts,
+            &caching,
+            None::<&Backend>,
+            &|_| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(stats.chunks_over_budget, 0);
+        assert_eq!(stats.chunks_cached, stats.chunks_total);
+        assert_eq!(stats.spent_usd, Some(0.0));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn cached_escalation_is_not_paid_for_again() {
+        let dir =
+            std::env::temp_dir().join(format!("recipe-epub-esc-cache-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let bytes = minimal_epub();
+        let primary = PricedExtractor {
+            fails: true,
+            ..FLASH
+        };
+        let escalation = PricedExtractor {
+            model: "claude-haiku-4-5",
+            fails: false,
+        };
+        let primary = CachingExtractor::new(&primary, dir.clone());
+        let escalation = CachingExtractor::new(&escalation, dir.clone());
+        let opts = Options::default();
+        let run = || {
+            extract_cookbook_with_stats(
+                &bytes,
+                "b.epub",
+                &opts,
+                &primary,
+                Some(&escalation),
+                &|_| {},
+            )
+        };
+        let (_, first) = run().await.unwrap();
+        assert!(first.spent_usd.unwrap() > 0.0);
+        let (_, second) = run().await.unwrap();
+        assert_eq!(second.chunks_failed, 0);
+        assert_eq!(second.spent_usd, Some(0.0));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn escalation_that_doesnt_fit_the_budget_is_skipped() {
+        let bytes = minimal_epub();
+        let chunk = &chunk_epub(&bytes).unwrap()[0];
+        let primary = PricedExtractor {
+            fails: true,
+            ..FLASH
+        };
+        let escalation = PricedExtractor {
+            model: "claude-opus-4-5",
+            fails: false,
+        };
+        // Room for the primary's call, not for the pricier escalation's.
+        let usage = estimate_chunk_usage(chunk);
+        let cap = usage.cost_usd(primary.model).unwrap();
+        assert!(usage.cost_usd(escalation.model).unwrap() > cap);
+        let opts = Options {
+            max_cost_usd: Some(cap),
+            ..Options::default()
+        };
+        let (_, stats) = extract_cookbook_with_stats(
+            &bytes,
+            "b.epub",
+            &opts,
+            &primary,
+            Some(&escalation),
+            &|_| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(stats.chunks_over_budget, 0);
+        assert_eq!(stats.chunks_failed, 1);
+        assert_eq!(stats.spent_usd, Some(0.0));
+    }
+
+    #[tokio::test]
+    async fn budget_cap_is_refused_for_unpriced_models() {
+        let opts = Options {
+            max_cost_usd: Some(1.0),
+            ..Options::default()
+        };
+        let err = extract_cookbook_with_stats(
+            &minimal_epub(),
+            "b.epub",
+            &opts,
+            &FailingExtractor,
+            None::<&Backend>,
+            &|_| {},
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, EpubError::Budget(_)), "{err}");
+    }
+
+    #[test]
+    fn estimate_counts_cached_chunks_as_free() {
+        let dir =
+            std::env::temp_dir().join(format!("recipe-epub-estimate-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let bytes = minimal_epub();
+        let opts = Options {
+            cache_dir: Some(dir.clone()),
+            ..Options::default()
+        };
+        let cold = estimate_cookbook(&bytes, &opts, &["gemini-2.5-flash"]).unwrap();
+        assert_eq!((cold[0].chunks, cold[0].calls()), (1, 1));
+        for c in chunk_epub(&bytes).unwrap() {
+            let key = cache::key(
+                "gemini-2.5-flash",
+                &c.text,
+                c.title_hint.as_deref().unwrap_or(""),
+            );
+            cache::write(&dir, &key, &[]).unwrap();
+        }
+        let warm =
+            estimate_cookbook(&bytes, &opts, &["gemini-2.5-flash", "claude-haiku-4-5"]).unwrap();
+        assert_eq!(warm[0].calls(), 0);
+        assert_eq!(warm[0].cost_usd(), Some(0.0));
+        // The cache is per model.
+        assert_eq!(warm[1].calls(), 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     /// Smallest possible valid EPUB (zip) with one spine doc, enough for
     /// `chunk_epub` to yield exactly one chunk.
     fn minimal_epub() -> Vec<u8> {
@@ -1281,6 +1813,7 @@ mod tests {
   <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
     <dc:title>Failing Book</dc:title>
     <dc:identifier id="bookid">urn:uuid:failing-book</dc:identifier>
+    <dc:language>en-GB</dc:language>
   </metadata>
   <manifest>
     <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
@@ -1333,6 +1866,37 @@ mod tests {
         assert_eq!(meta.identifiers, vec!["urn:uuid:failing-book".to_string()]);
     }
 
+    /// A British book's `dc:language` selects the region its recipes' pints
+    /// are read in.
+    #[test]
+    fn epub_metadata_region_comes_from_dc_language() {
+        use crate::{CookbookRecipe, CookbookRecipeExt, RecipeMeta, RecipeSection};
+        use ingredient::unit::{Region, Unit};
+
+        let meta = crate::epub_metadata(&minimal_epub()).unwrap();
+        assert_eq!(meta.languages, vec!["en-GB".to_string()]);
+        let region = meta.region().unwrap();
+        assert_eq!(region, Region::UK);
+
+        let recipe = CookbookRecipe {
+            meta: RecipeMeta {
+                title: "Custard".to_string(),
+                ..Default::default()
+            },
+            sections: vec![RecipeSection {
+                ingredients: vec!["1 pint milk".to_string()],
+                ..Default::default()
+            }],
+            source: "failing.epub".to_string(),
+            url: "failing.epub#custard".to_string(),
+            references: vec![],
+            image: None,
+        };
+        let parsed = recipe.parse_in(region);
+        let milk = &parsed.sections[0].ingredients[0];
+        assert_eq!(*milk.amounts[0].unit(), Unit::ImperialPint);
+    }
+
     /// A chunk whose extraction fails (no escalation configured) must be
     /// counted in `chunks_failed` and NOT silently treated as "found nothing
     /// here" — the whole point of the counter (see the `backend.rs:300`
@@ -1346,7 +1910,7 @@ mod tests {
             "failing.epub",
             &Options::default(),
             &FailingExtractor,
-            None,
+            None::<&Backend>,
             &|_| {},
         )
         .await
@@ -1358,6 +1922,59 @@ mod tests {
         assert!(stats.summary().contains("1 chunk(s) FAILED"));
     }
 
+    /// A chunk that parses but paraphrases its lines is re-extracted by the
+    /// escalation model when it scores under `escalate_below_quality`, and the
+    /// verbatim output wins.
+    #[tokio::test]
+    async fn low_quality_chunk_escalates_and_keeps_the_better_output() {
+        use crate::{MockExtractor, RecipeMeta, RecipeSection};
+        let recipe = |ing: &str, step: &str| ExtractedRecipe {
+            meta: RecipeMeta {
+                title: "Some Recipe".to_string(),
+                ..Default::default()
+            },
+            sections: vec![RecipeSection::new(
+                vec![ing.to_string()],
+                vec![step.to_string()],
+            )],
+        };
+        let sloppy = MockExtractor::new(vec![(
+            "Some Recipe".to_string(),
+            vec![recipe("one cup of flour", "Stir everything together.")],
+        )]);
+        let exact = MockExtractor::new(vec![(
+            "Some Recipe".to_string(),
+            vec![recipe("1 cup flour", "Mix it.")],
+        )]);
+        let opts = Options {
+            escalate_below_quality: Some(0.8),
+            ..Options::default()
+        };
+        let bytes = minimal_epub();
+
+        let (recipes, stats) =
+            extract_cookbook_with_stats(&bytes, "q.epub", &opts, &sloppy, Some(&exact), &|_| {})
+                .await
+                .unwrap();
+        assert_eq!(stats.chunks_quality_escalated, 1);
+        assert_eq!(recipes[0].sections[0].ingredients, ["1 cup flour"]);
+        assert!(stats.summary().contains("1 low-quality chunk(s) escalated"));
+
+        // Without a threshold the sloppy output stands.
+        let (recipes, stats) = extract_cookbook_with_stats(
+            &bytes,
+            "q.epub",
+            &Options::default(),
+            &sloppy,
+            Some(&exact),
+            &|_| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(stats.chunks_quality_escalated, 0);
+        assert_eq!(recipes[0].sections[0].ingredients, ["one cup of flour"]);
+    }
+
     #[test]
     fn routes_models_to_backends() {
         assert!(is_openai_compatible_model("gpt-4o-mini"));
@@ -0,0 +1,232 @@
+//! Pre-flight cost estimates: what extracting a book will cost *before* any
+//! call is made.
+//!
+//! [`ExtractionStats::cost_usd`](crate::ExtractionStats::cost_usd) only prices a
+//! run after the money is spent. [`estimate_cost`] prices it from the chunks
+//! alone: each chunk's request (system prompt + tool schema + text, at
+//! [`CHARS_PER_TOKEN`]) for input, and an output guess proportional to the text
+//! (the model echoes ingredient and instruction lines verbatim, wrapped in
+//! JSON). Chunks already in the on-disk cache cost nothing, and on Claude
+//! models a static prompt prefix long enough to cache is billed as a
+//! prompt-cache read after the first call. Pure, so the wasm consumer can
+//! estimate too; the native `estimate_cookbook` adds the on-disk cache lookup.
+
+use serde::Serialize;
+
+use crate::{Chunk, Usage, build_chunk_request};
+
+/// Rough characters per token for English prose and JSON. Deliberately a
+/// constant rather than a tokenizer: the estimate only has to be within the
+/// error bars of `OUTPUT_PER_INPUT`.
+pub const CHARS_PER_TOKEN: f64 = 4.0;
+
+/// Output tokens per chunk-text token. Recipes are re-emitted near-verbatim but
+/// prose between them (headnotes aside) is not, and the JSON keys add back a
+/// little — measured at roughly 0.5–0.7 on real books.
+const OUTPUT_PER_INPUT: f64 = 0.6;
+
+/// Per-call output floor: the tool-call envelope even for an empty section.
+const OUTPUT_OVERHEAD: u64 = 30;
+
+/// The extraction call's `max_tokens`; output can't exceed it.
+const MAX_OUTPUT: u64 = 16_000;
+
+/// Models [`estimate_cost`] is usually asked about, cheapest first: the ones
+/// `food-cli estimate-epub` projects when no `--model` is given.
+pub const ESTIMATE_MODELS: &[&str] = &[
+    "gemini-2.5-flash-lite",
+    "gemini-2.5-flash",
+    "claude-haiku-4-5",
+    "claude-sonnet-4-6",
+    "claude-opus-4-5",
+];
+
+/// Estimated tokens in `text`.
+pub fn estimate_tokens(text: &str) -> u64 {
+    (text.chars().count() as f64 / CHARS_PER_TOKEN).ceil() as u64
+}
+
+/// Smallest prefix the model's prompt cache will store, or `None` when the
+/// backend doesn't mark the prefix cacheable (only the Claude backend does).
+fn prompt_cache_min(model: &str) -> Option<u64> {
+    let m = model.to_lowercase();
+    if !m.starts_with("claude") {
+        return None;
+    }
+    Some(if m.contains("haiku") { 4096 } else { 1024 })
+}
+
+/// Estimated usage of one uncached extraction call for `chunk`, with the whole
+/// prompt billed as fresh input.
+pub fn estimate_chunk_usage(chunk: &Chunk) -> Usage {
+    let req = build_chunk_request(chunk);
+    let text = estimate_tokens(&req.user);
+    Usage {
+        input_tokens: estimate_tokens(&req.system)
+            + estimate_tokens(&req.tool_schema.to_string())
+            + text,
+        output_tokens: ((text as f64 * OUTPUT_PER_INPUT) as u64 + OUTPUT_OVERHEAD).min(MAX_OUTPUT),
+        ..Usage::default()
+    }
+}
+
+/// Projected tokens and cost of extracting one book with one model.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CostEstimate {
+    pub model: String,
+    /// Chunks the book splits into.
+    pub chunks: usize,
+    /// Of those, chunks already in the on-disk cache (no call, no cost).
+    pub cached_chunks: usize,
+    /// Estimated usage of the calls that would be made. Prompt-cache reads and
+    /// writes are split out where the model's backend uses them.
+    pub usage: Usage,
+}
+
+impl CostEstimate {
+    /// Projected USD cost, or `None` when the model's pricing is unknown.
+    pub fn cost_usd(&self) -> Option<f64> {
+        self.usage.cost_usd(&self.model)
+    }
+
+    /// Chunks that would actually be sent to the model.
+    pub fn calls(&self) -> usize {
+        self.chunks - self.cached_chunks
+    }
+
+    /// Fold another book's estimate (same model) into this one.
+    pub fn add(&mut self, other: &CostEstimate) {
+        self.chunks += other.chunks;
+        self.cached_chunks += other.cached_chunks;
+        self.usage.add(&other.usage);
+    }
+
+    /// One-line human summary, e.g.
+    /// `gemini-2.5-flash: 42 chunk(s), 40 to call · ~180000 in / ~30000 out tok · ~$0.1290`.
+    pub fn summary(&self) -> String {
+        let u = &self.usage;
+        let cost = self
+            .cost_usd()
+            .map_or_else(|| "cost: n/a".to_string(), |c| format!("~${c:.4}"));
+        format!(
+            "{}: {} chunk(s), {} to call · ~{} in / ~{} out tok · {cost}",
+            self.model,
+            self.chunks,
+            self.calls(),
+            u.input_tokens + u.cache_creation_input_tokens + u.cache_read_input_tokens,
+            u.output_tokens
+        )
+    }
+}
+
+/// Estimate extracting `chunks` with `model`. `is_cached` says whether a chunk
+/// would be served from the on-disk cache (pass `|_| false` to price a cold
+/// run).
+pub fn estimate_cost(
+    chunks: &[Chunk],
+    model: &str,
+    is_cached: impl Fn(&Chunk) -> bool,
+) -> CostEstimate {
+    estimate_with_cache_min(chunks, model, is_cached, prompt_cache_min(model))
+}
+
+/// [`estimate_cost`] with the prompt-cache threshold passed in (the real
+/// prompt prefix is currently below every model's minimum).
+fn estimate_with_cache_min(
+    chunks: &[Chunk],
+    model: &str,
+    is_cached: impl Fn(&Chunk) -> bool,
+    cache_min: Option<u64>,
+) -> CostEstimate {
+    let mut est = CostEstimate {
+        model: model.to_string(),
+        chunks: chunks.len(),
+        ..CostEstimate::default()
+    };
+    let mut first_call = true;
+    for chunk in chunks {
+        if is_cached(chunk) {
+            est.cached_chunks += 1;
+            continue;
+        }
+        let mut u = estimate_chunk_usage(chunk);
+        // The system prompt + tool schema are identical on every call: the
+        // first call writes them to the prompt cache, later ones read them.
+        let req = build_chunk_request(chunk);
+        let prefix = estimate_tokens(&req.system) + estimate_tokens(&req.tool_schema.to_string());
+        if cache_min.is_some_and(|min| prefix >= min) {
+            u.input_tokens -= prefix;
+            if first_call {
+                u.cache_creation_input_tokens = prefix;
+            } else {
+                u.cache_read_input_tokens = prefix;
+            }
+        }
+        first_call = false;
+        est.usage.add(&u);
+    }
+    est
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn chunk(text: &str) -> Chunk {
+        Chunk {
+            title_hint: None,
+            text: text.to_string(),
+            doc_path: "ch1.xhtml".to_string(),
+            links: vec![],
+            images: vec![],
+        }
+    }
+
+    #[test]
+    fn cached_chunks_are_free() {
+        let chunks = [chunk(&"1 cup flour\n".repeat(200)), chunk("Pancakes")];
+        let cold = estimate_cost(&chunks, "gemini-2.5-flash", |_| false);
+        let warm = estimate_cost(&chunks, "gemini-2.5-flash", |c| c.text.len() > 100);
+        assert_eq!((cold.chunks, cold.calls()), (2, 2));
+        assert_eq!((warm.chunks, warm.calls()), (2, 1));
+        assert!(warm.cost_usd().unwrap() < cold.cost_usd().unwrap());
+        let all = estimate_cost(&chunks, "gemini-2.5-flash", |_| true);
+        assert_eq!(all.usage, Usage::default());
+        assert_eq!(all.cost_usd(), Some(0.0));
+    }
+
+    #[test]
+    fn output_scales_with_text_and_is_capped() {
+        let small = estimate_chunk_usage(&chunk(&"x".repeat(4_000)));
+        assert_eq!(small.output_tokens, 600 + OUTPUT_OVERHEAD);
+        let huge = estimate_chunk_usage(&chunk(&"x".repeat(400_000)));
+        assert_eq!(huge.output_tokens, MAX_OUTPUT);
+        assert!(huge.input_tokens > 100_000);
+    }
+
+    #[test]
+    fn claude_prefix_is_billed_as_prompt_cache_after_the_first_call() {
+        let chunks = vec![chunk("Pancakes\n1 cup flour"); 3];
+        let est = estimate_with_cache_min(&chunks, "claude-sonnet-4-6", |_| false, Some(0));
+        let one = estimate_chunk_usage(&chunks[0]);
+        let prefix = est.usage.cache_creation_input_tokens;
+        assert!(prefix > 0);
+        assert_eq!(est.usage.cache_read_input_tokens, 2 * prefix);
+        assert_eq!(est.usage.input_tokens, 3 * (one.input_tokens - prefix));
+        // Same calls, full price, on a backend without prompt caching.
+        let flat = estimate_cost(&chunks, "gemini-2.5-flash", |_| false);
+        assert_eq!(flat.usage.input_tokens, 3 * one.input_tokens);
+        assert_eq!(flat.usage.cache_read_input_tokens, 0);
+        // Below the model's minimum cacheable prefix, nothing is discounted.
+        let small = estimate_with_cache_min(&chunks, "claude-sonnet-4-6", |_| false, Some(1 << 20));
+        assert_eq!(small.usage, flat.usage);
+    }
+
+    #[test]
+    fn unknown_model_has_no_cost() {
+        let est = estimate_cost(&[chunk("Pancakes")], "llama-3", |_| false);
+        assert!(est.cost_usd().is_none());
+        assert!(est.summary().ends_with("cost: n/a"));
+    }
+}
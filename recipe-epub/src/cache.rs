@@ -55,6 +55,11 @@ pub(crate) fn read(dir: &Path, key: &str) -> Option<Vec<ExtractedRecipe>> {
     serde_json::from_slice(&bytes).ok()
 }
 
+/// Whether an entry exists for `key`, without reading it (for estimates).
+pub(crate) fn contains(dir: &Path, key: &str) -> bool {
+    dir.join(format!("{key}.json")).is_file()
+}
+
 /// Write a result to the cache (creating the directory if needed).
 pub(crate) fn write(dir: &Path, key: &str, recipes: &[ExtractedRecipe]) -> Result<(), EpubError> {
     std::fs::create_dir_all(dir).map_err(|e| EpubError::Cache(e.to_string()))?;
@@ -0,0 +1,640 @@
+//! Persistent cookbook catalog: every book in a library, every recipe extracted
+//! from it, each recipe's parsed ingredient lines, and the cross-recipe
+//! references [`resolve_references`](crate::resolve_references) found.
+//!
+//! Stored as JSON lines — one [`CatalogBook`] per line — so a library of a few
+//! hundred books stays a greppable text file with no database dependency. The
+//! catalog is rebuilt incrementally by [`Catalog::sync`]: a book whose file size
+//! and mtime are unchanged is kept as-is, a new or changed book is extracted
+//! (through the usual on-disk chunk cache, so a re-extract is usually free), and
+//! a book no longer in the library is dropped. `food-cli catalog` and the app's
+//! Cookbook tab both read it.
+#![cfg(feature = "native")]
+
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+
+use ingredient::unit::Region;
+use ingredient::{Ingredient, IngredientParser};
+use serde::{Deserialize, Serialize};
+
+use crate::library::{BookMeta, book_metadata};
+use crate::{
+    CookbookRecipe, EpubError, Options, ParsedCookbookRecipe, RecipeExtractor, RecipeIndex,
+    RecipeTimes, contains_whole_tokens, normalize_title,
+};
+
+/// Default catalog file: `$XDG_DATA_HOME/recipe-epub/catalog.jsonl`, else
+/// `~/.local/share/recipe-epub/catalog.jsonl`. Unlike the extraction cache this
+/// is data the user built up, so it doesn't default into a temp directory unless
+/// there is no home to put it in.
+pub fn default_catalog_path() -> PathBuf {
+    let base = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/share")))
+        .unwrap_or_else(std::env::temp_dir);
+    base.join("recipe-epub").join("catalog.jsonl")
+}
+
+/// What identifies one version of a book file on disk. Cheap to read (one
+/// `stat`), and a re-downloaded or re-converted EPUB changes at least one of the
+/// two, which is what marks a cataloged book stale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BookFingerprint {
+    /// File size in bytes.
+    pub len: u64,
+    /// Modification time, whole seconds since the Unix epoch (0 when the
+    /// platform can't report one).
+    pub modified: u64,
+}
+
+impl BookFingerprint {
+    /// Fingerprint the file at `path`.
+    pub fn of(path: &Path) -> std::io::Result<Self> {
+        let md = std::fs::metadata(path)?;
+        let modified = md
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_secs());
+        Ok(Self {
+            len: md.len(),
+            modified,
+        })
+    }
+}
+
+/// One extracted recipe plus its ingredient lines run through the core parser,
+/// in section order (one [`Ingredient`] per verbatim line).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogRecipe {
+    pub recipe: CookbookRecipe,
+    pub ingredients: Vec<Ingredient>,
+}
+
+impl CatalogRecipe {
+    /// Parse `recipe`'s ingredient lines with `ip`, each in its section (as
+    /// [`CookbookRecipeExt::parse`](crate::CookbookRecipeExt::parse) does).
+    fn parse(recipe: CookbookRecipe, ip: &IngredientParser) -> Self {
+        let ingredients = recipe
+            .sections
+            .iter()
+            .flat_map(|s| s.ingredients.iter().map(|line| (line, s.name.as_deref())))
+            .map(|(line, section)| ip.from_str_in_section(line, section))
+            .collect();
+        Self {
+            recipe,
+            ingredients,
+        }
+    }
+
+    /// Total time in minutes: the printed total, else prep + cook when both are
+    /// known. `None` when the book didn't print enough to say.
+    pub fn total_minutes(&self) -> Option<u32> {
+        self.recipe
+            .meta
+            .times
+            .as_ref()
+            .and_then(RecipeTimes::known_total_minutes)
+    }
+
+    /// The recipe with its stored ingredient parses split back into their
+    /// sections; only the instructions are parsed here.
+    pub fn parsed(&self) -> ParsedCookbookRecipe {
+        let mut ingredients = self.ingredients.iter().cloned();
+        let per_section = self
+            .recipe
+            .sections
+            .iter()
+            .map(|s| ingredients.by_ref().take(s.ingredients.len()).collect())
+            .collect();
+        ParsedCookbookRecipe {
+            meta: self.recipe.meta.clone(),
+            source: self.recipe.source.clone(),
+            url: self.recipe.url.clone(),
+            sections: recipe_scraper::parse_sections_with(
+                &self.recipe.sections,
+                per_section,
+                Region::US,
+            ),
+            references: self.recipe.references.clone(),
+        }
+    }
+
+    /// The first parsed ingredient whose name contains `term` on whole-word
+    /// boundaries (case- and punctuation-insensitive), so "buttermilk" matches
+    /// "low-fat buttermilk" but "milk" does not.
+    pub fn uses(&self, term: &str) -> Option<&Ingredient> {
+        let term = normalize_title(term);
+        if term.is_empty() {
+            return None;
+        }
+        self.ingredients
+            .iter()
+            .find(|i| contains_whole_tokens(&normalize_title(&i.name), &term))
+    }
+}
+
+/// One cataloged book: where it lives, which version of the file was
+/// extracted, and every recipe that came out of it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogBook {
+    pub meta: BookMeta,
+    pub fingerprint: BookFingerprint,
+    pub recipes: Vec<CatalogRecipe>,
+}
+
+/// A query over the catalog. Every set field must match; a default query
+/// matches every recipe.
+#[derive(Debug, Clone, Default)]
+pub struct CatalogQuery {
+    /// Ingredient names every hit must use (see [`CatalogRecipe::uses`]).
+    pub ingredients: Vec<String>,
+    /// Only recipes whose total time is known and at most this many minutes.
+    pub max_total_minutes: Option<u32>,
+    /// Only recipes whose title contains this text (case-insensitive).
+    pub title: Option<String>,
+}
+
+/// One recipe matching a [`CatalogQuery`], with the book it came from.
+#[derive(Debug, Clone, Copy)]
+pub struct CatalogHit<'a> {
+    pub book: &'a CatalogBook,
+    pub recipe: &'a CatalogRecipe,
+}
+
+/// What one [`Catalog::sync`] changed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncReport {
+    /// Books extracted for the first time.
+    pub added: Vec<PathBuf>,
+    /// Cataloged books whose file changed, re-extracted.
+    pub updated: Vec<PathBuf>,
+    /// Cataloged books no longer in the library, dropped.
+    pub removed: Vec<PathBuf>,
+    /// Books whose fingerprint matched, kept without extraction.
+    pub unchanged: usize,
+    /// Books that couldn't be read or extracted, with the reason. A failed
+    /// book keeps its previous catalog entry, if it had one.
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+impl SyncReport {
+    /// One-line human summary for CLI stderr / UI.
+    pub fn summary(&self) -> String {
+        let failed = if self.failed.is_empty() {
+            String::new()
+        } else {
+            format!(" · {} FAILED", self.failed.len())
+        };
+        format!(
+            "{} added · {} updated · {} removed · {} unchanged{failed}",
+            self.added.len(),
+            self.updated.len(),
+            self.removed.len(),
+            self.unchanged
+        )
+    }
+}
+
+/// The persistent catalog, loaded into memory. Books are kept sorted by path so
+/// the file diffs cleanly between syncs.
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    path: PathBuf,
+    books: Vec<CatalogBook>,
+}
+
+impl Catalog {
+    /// Load the catalog at `path`. A missing file is an empty catalog. A line
+    /// that no longer deserializes (an older catalog shape) is skipped with a
+    /// warning, so that book reads as new and is re-extracted on the next sync.
+    pub fn open(path: &Path) -> Result<Self, EpubError> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(t) => t,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(EpubError::Catalog(format!("{}: {e}", path.display()))),
+        };
+        let mut books: Vec<CatalogBook> = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(book) => books.push(book),
+                Err(e) => tracing::warn!(
+                    "catalog {} line {}: {e}; skipping (re-extracted on next sync)",
+                    path.display(),
+                    i + 1
+                ),
+            }
+        }
+        books.sort_by(|a, b| a.meta.path.cmp(&b.meta.path));
+        Ok(Self {
+            path: path.to_path_buf(),
+            books,
+        })
+    }
+
+    /// Write the catalog back to its file, creating the directory if needed.
+    /// Written to a sibling temp file and renamed over the old one, so an
+    /// interrupted save never leaves a half-written catalog behind.
+    pub fn save(&self) -> Result<(), EpubError> {
+        let err = |e: std::io::Error| EpubError::Catalog(format!("{}: {e}", self.path.display()));
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).map_err(err)?;
+        }
+        let tmp = self.path.with_extension("jsonl.tmp");
+        let mut out = std::io::BufWriter::new(std::fs::File::create(&tmp).map_err(err)?);
+        for book in &self.books {
+            serde_json::to_writer(&mut out, book)?;
+            out.write_all(b"\n").map_err(err)?;
+        }
+        out.into_inner()
+            .map_err(|e| err(e.into_error()))?
+            .sync_all()
+            .map_err(err)?;
+        std::fs::rename(&tmp, &self.path).map_err(err)
+    }
+
+    /// The file this catalog loads from and saves to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Every cataloged book, sorted by path.
+    pub fn books(&self) -> &[CatalogBook] {
+        &self.books
+    }
+
+    /// The cataloged entry for the book at `path`, if any. `path` is
+    /// canonicalized first, as [`Self::sync`] stores it, so `./books/a.epub`
+    /// finds the book synced as `/home/me/books/a.epub`.
+    pub fn book(&self, path: &Path) -> Option<&CatalogBook> {
+        let path = canonical(path);
+        self.books
+            .binary_search_by(|b| b.meta.path.cmp(&path))
+            .ok()
+            .map(|i| &self.books[i])
+    }
+
+    /// Every cataloged recipe, book by book.
+    pub fn recipes(&self) -> impl Iterator<Item = CatalogHit<'_>> {
+        self.books.iter().flat_map(|book| {
+            book.recipes
+                .iter()
+                .map(move |recipe| CatalogHit { book, recipe })
+        })
+    }
+
+    /// A [`RecipeIndex`] over every cataloged recipe (instructions included),
+    /// for ranked "what can I make with …" search.
+    pub fn search_index(&self) -> RecipeIndex {
+        RecipeIndex::new(self.recipes().map(|h| h.recipe.parsed()))
+    }
+
+    /// Recipes matching every field of `query`, in catalog order.
+    pub fn query(&self, query: &CatalogQuery) -> Vec<CatalogHit<'_>> {
+        let title = query.title.as_deref().map(str::to_lowercase);
+        self.recipes()
+            .filter(|hit| {
+                let r = hit.recipe;
+                query.ingredients.iter().all(|term| r.uses(term).is_some())
+                    && query
+                        .max_total_minutes
+                        .is_none_or(|max| r.total_minutes().is_some_and(|t| t <= max))
+                    && title
+                        .as_deref()
+                        .is_none_or(|t| r.recipe.meta.title.to_lowercase().contains(t))
+            })
+            .collect()
+    }
+
+    /// Insert or replace the entry for `meta.path`, parsing every recipe's
+    /// ingredient lines. The recipes should already carry their cross-recipe
+    /// references (as [`crate::extract_cookbook`] returns them).
+    pub fn upsert(
+        &mut self,
+        meta: BookMeta,
+        fingerprint: BookFingerprint,
+        recipes: Vec<CookbookRecipe>,
+    ) {
+        let ip = IngredientParser::new();
+        let book = CatalogBook {
+            recipes: recipes
+                .into_iter()
+                .map(|r| CatalogRecipe::parse(r, &ip))
+                .collect(),
+            meta,
+            fingerprint,
+        };
+        match self
+            .books
+            .binary_search_by(|b| b.meta.path.cmp(&book.meta.path))
+        {
+            Ok(i) => self.books[i] = book,
+            Err(i) => self.books.insert(i, book),
+        }
+    }
+
+    /// Drop every book under `dir` whose path isn't in `present`, returning
+    /// the dropped paths. Books elsewhere belong to another library directory
+    /// and are kept.
+    pub fn retain_present(&mut self, dir: &Path, present: &[PathBuf]) -> Vec<PathBuf> {
+        let mut removed = Vec::new();
+        self.books.retain(|b| {
+            let keep = !b.meta.path.starts_with(dir) || present.contains(&b.meta.path);
+            if !keep {
+                removed.push(b.meta.path.clone());
+            }
+            keep
+        });
+        removed
+    }
+
+    /// Bring the catalog's books under `dir` in line with the EPUBs there
+    /// (found by [`crate::find_epubs`]) using the default backend. Books gone
+    /// from `dir` are dropped; new and changed books are extracted; books
+    /// under other directories are left alone. Paths are stored canonicalized,
+    /// so syncing `./books` and then `/home/me/books` is a no-op. Call
+    /// [`Self::save`] afterwards to persist.
+    pub async fn sync(&mut self, dir: &Path, opts: &Options) -> SyncReport {
+        self.sync_by(dir, async |bytes: &[u8], source: &str| {
+            crate::extract_cookbook(bytes, source, opts)
+                .await
+                .map(|(recipes, _stats)| recipes)
+        })
+        .await
+    }
+
+    /// Like [`Self::sync`] with a caller-supplied extractor (e.g. a
+    /// [`crate::MockExtractor`] in tests).
+    pub async fn sync_with<E: RecipeExtractor>(
+        &mut self,
+        dir: &Path,
+        opts: &Options,
+        extractor: &E,
+    ) -> SyncReport {
+        self.sync_by(dir, async |bytes: &[u8], source: &str| {
+            crate::extract_cookbook_with(bytes, source, opts, extractor, |_| {}).await
+        })
+        .await
+    }
+
+    /// The shared sync loop; `extract` is the only part that differs between
+    /// the live backend and a caller-supplied extractor. Books are extracted one
+    /// at a time — each already parallelizes its own chunks.
+    async fn sync_by(
+        &mut self,
+        dir: &Path,
+        extract: impl AsyncFn(&[u8], &str) -> Result<Vec<CookbookRecipe>, EpubError>,
+    ) -> SyncReport {
+        let dir = canonical(dir);
+        let mut epubs: Vec<PathBuf> = crate::find_epubs(&dir)
+            .iter()
+            .map(|p| canonical(p))
+            .collect();
+        epubs.sort();
+        epubs.dedup();
+        let mut report = SyncReport {
+            removed: self.retain_present(&dir, &epubs),
+            ..Default::default()
+        };
+        for path in &epubs {
+            let fingerprint = match BookFingerprint::of(path) {
+                Ok(f) => f,
+                Err(e) => {
+                    report.failed.push((path.clone(), e.to_string()));
+                    continue;
+                }
+            };
+            let known = self.book(path).map(|b| b.fingerprint);
+            if known == Some(fingerprint) {
+                report.unchanged += 1;
+                continue;
+            }
+            let source = path.to_string_lossy();
+            let extracted = async {
+                let meta = book_metadata(path)?;
+                let bytes = std::fs::read(path).map_err(|e| EpubError::Open(e.to_string()))?;
+                let recipes = extract(&bytes, &source).await?;
+                Ok::<_, EpubError>((meta, recipes))
+            }
+            .await;
+            match extracted {
+                Ok((meta, recipes)) => {
+                    self.upsert(meta, fingerprint, recipes);
+                    if known.is_some() {
+                        report.updated.push(path.clone());
+                    } else {
+                        report.added.push(path.clone());
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("catalog: {source}: {e}");
+                    report.failed.push((path.clone(), e.to_string()));
+                }
+            }
+        }
+        report
+    }
+}
+
+/// `path` with symlinks and `.`/`..` resolved, or as given when it doesn't
+/// exist (a book deleted since it was cataloged).
+fn canonical(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::{RecipeMeta, RecipeSection};
+
+    fn recipe(title: &str, ings: &[&str], times: Option<RecipeTimes>) -> CookbookRecipe {
+        CookbookRecipe {
+            meta: RecipeMeta {
+                title: title.to_string(),
+                times,
+                ..Default::default()
+            },
+            sections: vec![RecipeSection::new(
+                ings.iter().map(|s| s.to_string()).collect(),
+                vec![],
+            )],
+            source: "book.epub".to_string(),
+            url: "book.epub#ch1.xhtml".to_string(),
+            references: vec![],
+            image: None,
+        }
+    }
+
+    fn meta(path: &str) -> BookMeta {
+        BookMeta {
+            path: PathBuf::from(path),
+            title: path.to_string(),
+            authors: vec![],
+            subjects: vec![],
+        }
+    }
+
+    const FP: BookFingerprint = BookFingerprint {
+        len: 10,
+        modified: 1,
+    };
+
+    fn sample() -> Catalog {
+        let mut c = Catalog::default();
+        c.upsert(
+            meta("/lib/b.epub"),
+            FP,
+            vec![
+                recipe(
+                    "Buttermilk Biscuits",
+                    &["2 cups flour", "1 cup cold buttermilk"],
+                    Some(RecipeTimes {
+                        total_minutes: Some(25),
+                        ..Default::default()
+                    }),
+                ),
+                recipe(
+                    "Slow Braise",
+                    &["1 cup whole milk", "2 lb short ribs"],
+                    Some(RecipeTimes {
+                        prep_minutes: Some(20),
+                        cook_minutes: Some(180),
+                        ..Default::default()
+                    }),
+                ),
+            ],
+        );
+        c.upsert(
+            meta("/lib/a.epub"),
+            FP,
+            vec![recipe("Pancakes", &["1 cup buttermilk", "2 eggs"], None)],
+        );
+        c
+    }
+
+    #[test]
+    fn books_stay_sorted_and_upsert_replaces() {
+        let mut c = sample();
+        let paths: Vec<_> = c.books().iter().map(|b| b.meta.path.clone()).collect();
+        assert_eq!(paths, [PathBuf::from("/lib/a.epub"), "/lib/b.epub".into()]);
+        c.upsert(meta("/lib/a.epub"), FP, vec![]);
+        assert_eq!(c.books().len(), 2);
+        assert!(c.book(Path::new("/lib/a.epub")).unwrap().recipes.is_empty());
+    }
+
+    #[test]
+    fn query_by_ingredient_is_whole_word() {
+        let c = sample();
+        let q = CatalogQuery {
+            ingredients: vec!["Buttermilk".to_string()],
+            ..Default::default()
+        };
+        let titles: Vec<_> = c
+            .query(&q)
+            .iter()
+            .map(|h| h.recipe.recipe.meta.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Pancakes", "Buttermilk Biscuits"]);
+
+        // "milk" is a whole word only in "whole milk", not inside "buttermilk".
+        let q = CatalogQuery {
+            ingredients: vec!["milk".to_string()],
+            ..Default::default()
+        };
+        let titles: Vec<_> = c
+            .query(&q)
+            .iter()
+            .map(|h| h.recipe.recipe.meta.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Slow Braise"]);
+    }
+
+    #[test]
+    fn query_by_total_time_needs_a_known_time() {
+        let c = sample();
+        let q = CatalogQuery {
+            max_total_minutes: Some(30),
+            ..Default::default()
+        };
+        let titles: Vec<_> = c
+            .query(&q)
+            .iter()
+            .map(|h| h.recipe.recipe.meta.title.as_str())
+            .collect();
+        // Pancakes has no times at all, so it can't be claimed to be quick.
+        assert_eq!(titles, ["Buttermilk Biscuits"]);
+        // Prep + cook stands in for a missing total.
+        let braise = c
+            .recipes()
+            .find(|h| h.recipe.recipe.meta.title == "Slow Braise");
+        assert_eq!(braise.unwrap().recipe.total_minutes(), Some(200));
+    }
+
+    #[test]
+    fn parsed_reuses_the_stored_ingredients() {
+        use crate::CookbookRecipeExt;
+        let mut r = recipe("Two Parts", &["1 cup flour"], None);
+        r.sections.push(RecipeSection {
+            name: Some("For the marinade".to_string()),
+            ..RecipeSection::new(vec!["2 tbsp soy sauce".to_string()], vec![])
+        });
+        let c = CatalogRecipe::parse(r, &IngredientParser::new());
+        // Same sections, ingredients and instructions as parsing afresh.
+        let json = |r: &ParsedCookbookRecipe| serde_json::to_value(r).unwrap();
+        assert_eq!(json(&c.parsed()), json(&c.recipe.parse()));
+        assert_eq!(
+            c.ingredients[1].usage,
+            ingredient::IngredientUsage::Marinade
+        );
+    }
+
+    #[test]
+    fn retain_present_drops_removed_books() {
+        let mut c = sample();
+        let removed = c.retain_present(Path::new("/lib"), &[PathBuf::from("/lib/b.epub")]);
+        assert_eq!(removed, [PathBuf::from("/lib/a.epub")]);
+        assert_eq!(c.books().len(), 1);
+    }
+
+    #[test]
+    fn retain_present_keeps_books_outside_the_synced_dir() {
+        let mut c = sample();
+        c.upsert(meta("/other/c.epub"), FP, vec![]);
+        let removed = c.retain_present(Path::new("/lib"), &[]);
+        assert_eq!(removed.len(), 2);
+        let paths: Vec<_> = c.books().iter().map(|b| b.meta.path.clone()).collect();
+        assert_eq!(paths, [PathBuf::from("/other/c.epub")]);
+    }
+
+    #[test]
+    fn save_and_open_round_trip() {
+        let dir = std::env::temp_dir().join(format!("recipe-epub-catalog-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("catalog.jsonl");
+        let mut c = sample();
+        c.path = path.clone();
+        c.save().unwrap();
+
+        // One book per line, and a stale line is skipped rather than fatal.
+        let mut text = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(text.lines().count(), 2);
+        text.push_str("{\"not\": \"a book\"}\n");
+        std::fs::write(&path, text).unwrap();
+
+        let back = Catalog::open(&path).unwrap();
+        assert_eq!(back.books(), c.books());
+        assert_eq!(back.path(), path);
+        assert!(
+            Catalog::open(&dir.join("missing.jsonl"))
+                .unwrap()
+                .books()
+                .is_empty()
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -4,8 +4,11 @@
 //! line boundary), the lines are concatenated in reading order, then windowed
 //! into ~`CHUNK_BUDGET`-sized chunks broken at title-like lines (see
 //! `window_chunks`), with the last-seen title carried forward as a `title_hint`
-//! when a chunk has to be cut mid-recipe.
+//! when a chunk has to be cut mid-recipe. When the book's TOC names its recipes,
+//! those names are the title lines; otherwise `looks_like_title` guesses. The
+//! back-of-book index is read into the [`BookOutline`] instead of being chunked.
 
+use std::collections::HashSet;
 use std::io::Cursor;
 
 use ego_tree::iter::Edge;
@@ -13,7 +16,8 @@ use epub::doc::EpubDoc;
 use ingredient::unit::Unit;
 use scraper::{Html, Node};
 
-use crate::{Chunk, EpubError, ImageRef, Link};
+use crate::outline::{BookOutline, TocEntry, parse_index_line, toc_entries};
+use crate::{Chunk, EpubError, ImageRef, Link, normalize_title};
 
 /// A cleaned text line plus any internal anchor links and embedded images it
 /// contained (images that sat in their own empty block attach to the nearest line).
@@ -29,7 +33,7 @@ struct CleanLine {
 /// have no archive entry. Normalizes `.`/`..` segments and a leading `/`
 /// (archive-root) so e.g. `../images/p12.jpg` from `OEBPS/text/ch1.xhtml`
 /// resolves to `OEBPS/images/p12.jpg`.
-fn resolve_relative(doc_path: &str, src: &str) -> Option<String> {
+pub(crate) fn resolve_relative(doc_path: &str, src: &str) -> Option<String> {
     // Drop any URL fragment/query before resolving (image srcs rarely carry them,
     // but a stray `#anchor` would otherwise leak into the path).
     let src = src.split(['#', '?']).next().unwrap_or(src).trim();
@@ -141,12 +145,32 @@ const CHUNK_SLACK: usize = 6000;
 /// model call's output (no truncation on dense chapters) while merging small
 /// consecutive docs so a recipe split across files stays in one chunk.
 pub fn chunk_epub(bytes: &[u8]) -> Result<Vec<Chunk>, EpubError> {
+    chunk_epub_with_outline(bytes).map(|(chunks, _)| chunks)
+}
+
+/// The book's TOC and index (see [`crate::apply_outline`]). Pair with
+/// [`chunk_epub`] when driving extraction chunk by chunk.
+pub fn book_outline(bytes: &[u8]) -> Result<BookOutline, EpubError> {
+    chunk_epub_with_outline(bytes).map(|(_, outline)| outline)
+}
+
+/// [`chunk_epub`] plus the [`BookOutline`] read in the same pass, for the native
+/// orchestration (one EPUB open instead of two).
+pub(crate) fn chunk_epub_with_outline(
+    bytes: &[u8],
+) -> Result<(Vec<Chunk>, BookOutline), EpubError> {
     // Borrow the bytes (`Cursor<&[u8]>` is Read+Seek) rather than copying them.
     // Image-heavy cookbooks can be hundreds of MB — an extra `.to_vec()` would
     // double that in (wasm) memory for nothing.
     let mut doc =
         EpubDoc::from_reader(Cursor::new(bytes)).map_err(|e| EpubError::Open(e.to_string()))?;
 
+    let mut outline = BookOutline {
+        toc: toc_entries(&mut doc),
+        ..Default::default()
+    };
+    let index_docs = outline.index_docs();
+
     let spine_len = doc.get_num_chapters();
     let mut tagged: Vec<(String, CleanLine)> = Vec::new();
     loop {
@@ -166,8 +190,17 @@ pub fn chunk_epub(bytes: &[u8]) -> Result<Vec<Chunk>, EpubError> {
             let decoded = String::from_utf8_lossy(&raw);
             // Strip a leading BOM so the html parser sees a clean root.
             let content = decoded.strip_prefix('\u{feff}').unwrap_or(decoded.as_ref());
-            for line in clean_xhtml_to_lines(content, &doc_path) {
-                tagged.push((doc_path.clone(), line));
+            let lines = clean_xhtml_to_lines(content, &doc_path);
+            if index_docs.contains(&doc_path) {
+                // The back-of-book index names every recipe but contains none:
+                // read it for page numbers rather than paying for model calls.
+                outline
+                    .index
+                    .extend(lines.iter().filter_map(|l| parse_index_line(&l.text)));
+            } else {
+                for line in lines {
+                    tagged.push((doc_path.clone(), line));
+                }
             }
         }
         if !doc.go_next() {
@@ -185,11 +218,36 @@ pub fn chunk_epub(bytes: &[u8]) -> Result<Vec<Chunk>, EpubError> {
         );
     }
 
-    Ok(window_chunks(tagged))
+    let titles = toc_titles(&outline.toc, &tagged);
+    outline.recipe_level = titles.is_some();
+    Ok((window_chunks(tagged, titles.as_ref()), outline))
+}
+
+/// The TOC labels to use as recipe boundaries, or `None` when the TOC is only
+/// chapter-level. A label counts when some text line equals it (normalized); the
+/// TOC is recipe-level when more labels match than there are documents they
+/// match in — a chapter TOC names at most one heading per document.
+fn toc_titles(toc: &[TocEntry], tagged: &[(String, CleanLine)]) -> Option<HashSet<String>> {
+    let labels: HashSet<String> = toc
+        .iter()
+        .map(|e| normalize_title(&e.title))
+        .filter(|l| !l.is_empty())
+        .collect();
+    let mut matched = HashSet::new();
+    let mut docs = HashSet::new();
+    for (path, line) in tagged {
+        let n = normalize_title(&line.text);
+        if labels.contains(&n) && matched.insert(n) {
+            docs.insert(path.as_str());
+        }
+    }
+    (matched.len() > docs.len()).then_some(matched)
 }
 
 /// Greedily window `(doc_path, line)` pairs into chunks, preferring to break
-/// before a title-like line once over budget.
+/// before a title-like line once over budget. With `titles` (the recipe-level
+/// TOC's normalized labels) a title line is exactly one of those; without, it's
+/// [`looks_like_title`]'s guess.
 ///
 /// When a chunk is cut mid-recipe (a *hard* split at `CHUNK_BUDGET+CHUNK_SLACK`
 /// rather than a clean title boundary), the recipe's tail — its remaining steps
@@ -197,7 +255,11 @@ pub fn chunk_epub(bytes: &[u8]) -> Result<Vec<Chunk>, EpubError> {
 /// and be dropped downstream. To avoid that, the continuation chunk inherits the
 /// last-seen title as its [`Chunk::title_hint`], so the model re-emits the same
 /// titled recipe and `assemble()` merges the two halves.
-fn window_chunks(tagged: Vec<(String, CleanLine)>) -> Vec<Chunk> {
+fn window_chunks(tagged: Vec<(String, CleanLine)>, titles: Option<&HashSet<String>>) -> Vec<Chunk> {
+    let is_title = |text: &str| match titles {
+        Some(t) => t.contains(&normalize_title(text)),
+        None => looks_like_title(text),
+    };
     let mut chunks = Vec::new();
     let mut lines: Vec<String> = Vec::new();
     let mut chunk_links: Vec<Link> = Vec::new();
@@ -211,7 +273,7 @@ fn window_chunks(tagged: Vec<(String, CleanLine)>) -> Vec<Chunk> {
     let mut next_hint: Option<String> = None;
 
     for (path, line) in tagged {
-        let at_title = len >= CHUNK_BUDGET && looks_like_title(&line.text);
+        let at_title = len >= CHUNK_BUDGET && is_title(&line.text);
         let hard_split = len >= CHUNK_BUDGET + CHUNK_SLACK;
         let want_break = !lines.is_empty() && (at_title || hard_split);
         if want_break {
@@ -233,7 +295,7 @@ fn window_chunks(tagged: Vec<(String, CleanLine)>) -> Vec<Chunk> {
         if doc.is_none() {
             doc = Some(path);
         }
-        if looks_like_title(&line.text) {
+        if is_title(&line.text) {
             last_title = Some(line.text.clone());
         }
         // This line's index within the chunk is its position in `lines` (the same
@@ -771,7 +833,7 @@ mod tests {
                 tagged.push(tag("big.html", &line));
             }
         }
-        let chunks = window_chunks(tagged);
+        let chunks = window_chunks(tagged, None);
         assert!(chunks.len() >= 2, "expected a split, got {}", chunks.len());
         // No chunk wildly exceeds the budget+slack guard.
         assert!(
@@ -784,11 +846,37 @@ mod tests {
 
         // Two tiny docs merge into a single chunk (split-across-files case).
         let small = vec![tag("a.html", "Pancakes"), tag("b.html", "1 cup flour")];
-        let merged = window_chunks(small);
+        let merged = window_chunks(small, None);
         assert_eq!(merged.len(), 1);
         assert_eq!(merged[0].doc_path, "a.html");
     }
 
+    #[test]
+    fn window_breaks_only_at_toc_titles_when_given() {
+        // Over budget at a sub-heading: the heuristic breaks there (severing
+        // the recipe), a recipe-level TOC waits for the next real title.
+        let tagged = || {
+            let line = "x".repeat(140);
+            let mut tagged = vec![tag("c.html", "Chocolate Cake")];
+            for _ in 0..CHUNK_BUDGET / line.len() + 1 {
+                tagged.push(tag("c.html", &line));
+            }
+            tagged.push(tag("c.html", "For the Frosting"));
+            tagged.push(tag("c.html", "2 cups sugar"));
+            tagged.push(tag("c.html", "Vanilla Cake"));
+            tagged
+        };
+
+        let guessed = window_chunks(tagged(), None);
+        assert!(guessed[1].text.starts_with("For the Frosting"));
+
+        let titles = HashSet::from(["chocolate cake".to_string(), "vanilla cake".to_string()]);
+        let exact = window_chunks(tagged(), Some(&titles));
+        assert_eq!(exact.len(), 2);
+        assert!(exact[0].text.contains("For the Frosting"));
+        assert!(exact[1].text.starts_with("Vanilla Cake"));
+    }
+
     #[test]
     fn window_tags_images_with_chunk_line_index() {
         let hero = ImageRef {
@@ -808,7 +896,7 @@ mod tests {
             ),
             tag("c.html", "2 cups flour"),
         ];
-        let chunks = window_chunks(tagged);
+        let chunks = window_chunks(tagged, None);
         assert_eq!(chunks.len(), 1);
         // The image rides on the chunk, tagged with line index 1 (the title line),
         // which actually points at the title within the joined chunk text.
@@ -920,7 +1008,7 @@ mod tests {
         for _ in 0..lines {
             tagged.push(tag("big.html", &line));
         }
-        let chunks = window_chunks(tagged);
+        let chunks = window_chunks(tagged, None);
         assert!(
             chunks.len() >= 2,
             "expected a hard split, got {}",
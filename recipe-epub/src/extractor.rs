@@ -89,6 +89,19 @@ impl Usage {
         self.cache_creation_input_tokens += other.cache_creation_input_tokens;
         self.cache_read_input_tokens += other.cache_read_input_tokens;
     }
+
+    /// USD cost at `model`'s rates, or `None` if its pricing is unknown. Cache
+    /// writes bill ~1.25× input, cache reads ~0.1× input.
+    pub fn cost_usd(&self, model: &str) -> Option<f64> {
+        let (in_rate, out_rate) = crate::price_per_mtok(model)?;
+        Some(
+            (self.input_tokens as f64 * in_rate
+                + self.cache_creation_input_tokens as f64 * in_rate * 1.25
+                + self.cache_read_input_tokens as f64 * in_rate * 0.1
+                + self.output_tokens as f64 * out_rate)
+                / 1_000_000.0,
+        )
+    }
 }
 
 /// One chunk's extraction result plus its cost signal.
@@ -332,6 +345,12 @@ pub trait RecipeExtractor {
     fn model(&self) -> &str {
         ""
     }
+
+    /// Whether [`extract`](Self::extract) would serve `chunk` without a call,
+    /// and so at no cost (a cache hit).
+    fn is_cached(&self, _chunk: &Chunk) -> bool {
+        false
+    }
 }
 
 // The forced tool's name + the system prompt + the input schema are the LLM
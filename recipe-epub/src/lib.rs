@@ -11,14 +11,20 @@
 //! Entry point: [`extract_cookbook`] (selects a backend from `Options.model`) or
 //! [`extract_cookbook_with`] (any [`RecipeExtractor`], e.g. a mock in tests).
 
-// `backend`, `cache`, and `library` are native-only — each gates itself with an
-// inner `#![cfg(feature = "native")]`, so their `mod` lines stay unconditional
-// here. `epub_text` + `extractor` are the pure contract, compiled everywhere.
+// `backend`, `cache`, `catalog`, and `library` are native-only — each gates
+// itself with an inner `#![cfg(feature = "native")]`, so their `mod` lines stay
+// unconditional here. `epub_text` + `extractor` are the pure contract, compiled
+// everywhere, as are `budget`, `outline`, `search` and `verify`.
 mod backend;
+mod budget;
 mod cache;
+mod catalog;
 mod epub_text;
 mod extractor;
 mod library;
+mod outline;
+mod search;
+mod verify;
 
 // Pure extraction API — compiles to wasm32: EPUB unzip + text chunking
 // (`chunk_epub`), per-chunk request building (`build_chunk_request`), LLM
@@ -29,12 +35,18 @@ mod library;
 // the browser; a repo-local caller search will say they are dead (see
 // CONTRIBUTING.md). CI keeps them honest with
 // `cargo check -p recipe-epub --no-default-features`.
-pub use epub_text::chunk_epub;
+pub use budget::{
+    CHARS_PER_TOKEN, CostEstimate, ESTIMATE_MODELS, estimate_chunk_usage, estimate_cost,
+    estimate_tokens,
+};
+pub use epub_text::{book_outline, chunk_epub};
 pub use extractor::{
     CallResult, ChunkOutcome, ChunkRequest, DrivenChunk, ExtractedRecipe, MockExtractor, MockMatch,
     PARSE_RETRIES, RecipeExtractor, RecipeMeta, Usage, build_chunk_request, parse_recipes_payload,
     recipes_tool_schema, try_extract_chunk,
 };
+pub use outline::{BookOutline, IndexEntry, TocEntry, apply_outline};
+pub use verify::{Alignment, ChunkQuality, LineCheck, LineKind, RecipeQuality, verify_chunk};
 // Library scanning: list + classify the cookbooks in a directory of epubs
 // (native: needs std::fs + the LLM classifier).
 #[cfg(feature = "native")]
@@ -42,13 +54,23 @@ pub use library::{
     BookMeta, CookbookGuess, book_cover, book_metadata, classify_by_tags, classify_cookbooks_ai,
     find_epubs,
 };
+// Cross-book search ranked by ingredient coverage — pure, so it also runs over
+// recipes a wasm consumer extracted.
+pub use search::{RecipeIndex, SearchHit, SearchQuery};
+// Persistent library catalog: every book's recipes + parsed ingredients,
+// synced incrementally and queryable (native: std::fs + extraction).
+#[cfg(feature = "native")]
+pub use catalog::{
+    BookFingerprint, Catalog, CatalogBook, CatalogHit, CatalogQuery, CatalogRecipe, SyncReport,
+    default_catalog_path,
+};
 // The native extraction orchestration (backends + cache + async) lives in
 // `backend`; re-export the public entry points so `recipe_epub::extract_cookbook`
 // (etc.) paths stay stable.
 #[cfg(feature = "native")]
 pub use backend::{
-    ChunkDebug, Options, debug_extract_cookbook, extract_cookbook, extract_cookbook_with,
-    extract_cookbook_with_progress,
+    ChunkDebug, Options, debug_extract_cookbook, estimate_cookbook, extract_cookbook,
+    extract_cookbook_with, extract_cookbook_with_progress,
 };
 // Section + time types are shared with the web scraper — one shape workspace-wide.
 pub use recipe_scraper::{ParsedSection, RecipeSection, RecipeTimes};
@@ -56,7 +78,8 @@ pub use recipe_scraper::{ParsedSection, RecipeSection, RecipeTimes};
 use std::io::Cursor;
 
 use epub::doc::EpubDoc;
-use recipe_scraper::parse_sections;
+use ingredient::unit::Region;
+use recipe_scraper::parse_sections_in;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -91,6 +114,13 @@ pub enum EpubError {
     /// The on-disk cache could not be read or written.
     #[error("cache error: {0}")]
     Cache(String),
+    /// The persistent library catalog could not be read or written.
+    #[error("catalog error: {0}")]
+    Catalog(String),
+    /// A spending cap was set but a model's spend can't be tracked against it
+    /// (its pricing is unknown), so the run was refused rather than uncapped.
+    #[error("budget error: {0}")]
+    Budget(String),
     /// The chunk-extraction call supplied by the caller failed. Used by the wasm
     /// driver, whose "call" is a JS proxy callback (threw or rejected); the
     /// native backends raise [`EpubError::Http`]/[`EpubError::Api`] instead.
@@ -159,6 +189,10 @@ pub trait CookbookRecipeExt {
     /// [`recipe_scraper::parse_sections`] the web scraper uses).
     fn parse(&self) -> ParsedCookbookRecipe;
 
+    /// [`parse`](Self::parse) reading units as `region`'s — the book's, via
+    /// [`EpubMeta::region`].
+    fn parse_in(&self, region: Region) -> ParsedCookbookRecipe;
+
     /// Ingredient lines that look quantified (contain a digit or unicode
     /// fraction) but which the nom parser extracts **no** amount from — i.e.
     /// likely parser gaps worth adding to the accuracy corpus. Vocab-free: the
@@ -168,11 +202,15 @@ pub trait CookbookRecipeExt {
 
 impl CookbookRecipeExt for CookbookRecipe {
     fn parse(&self) -> ParsedCookbookRecipe {
+        self.parse_in(Region::US)
+    }
+
+    fn parse_in(&self, region: Region) -> ParsedCookbookRecipe {
         ParsedCookbookRecipe {
             meta: self.meta.clone(),
             source: self.source.clone(),
             url: self.url.clone(),
-            sections: parse_sections(&self.sections),
+            sections: parse_sections_in(&self.sections, region),
             references: self.references.clone(),
         }
     }
@@ -192,8 +230,9 @@ impl CookbookRecipeExt for CookbookRecipe {
 
 /// Progress snapshot emitted during [`extract_cookbook_with_progress`]: how many
 /// chunks have finished extracting (`done`) out of `total`, and how many of those
-/// came from the on-disk cache (`cached`). Each snapshot is internally consistent
-/// (the counts come from monotonic atomic increments).
+/// came from the on-disk cache (`cached`), and what the calls have cost so far
+/// (`spent_usd`). Each snapshot is internally consistent (the counts come from
+/// monotonic atomic increments).
 #[derive(Debug, Clone, Copy, Default)]
 pub struct ExtractProgress {
     /// Chunks finished so far.
@@ -202,6 +241,12 @@ pub struct ExtractProgress {
     pub total: usize,
     /// Of the finished chunks, how many were served from cache (no API call).
     pub cached: usize,
+    /// Of the finished chunks, how many were skipped unextracted because the
+    /// run reached `Options::max_cost_usd`.
+    pub skipped: usize,
+    /// USD spent on API calls so far, escalations included; `None` when a
+    /// model's pricing is unknown.
+    pub spent_usd: Option<f64>,
 }
 
 /// Token usage + cost summary for one `extract_cookbook` run.
@@ -219,6 +264,21 @@ pub struct ExtractionStats {
     /// content was silently dropped" — a non-zero count means the returned
     /// recipe list is incomplete.
     pub chunks_failed: usize,
+    /// Chunks whose [`ChunkQuality::score`] fell below
+    /// `Options::escalate_below_quality` and were re-extracted by the
+    /// escalation model (whichever output scored better was kept).
+    pub chunks_quality_escalated: usize,
+    /// Chunks never sent to the model because the run reached
+    /// `Options::max_cost_usd`. Non-zero means the recipe list is incomplete;
+    /// re-running with a higher cap only pays for these (the rest are cached).
+    pub chunks_over_budget: usize,
+    /// USD actually spent, with each call priced at the model that made it
+    /// (unlike [`Self::cost_usd`], which prices all of `usage` at `model`).
+    /// `None` when a model's pricing is unknown.
+    pub spent_usd: Option<f64>,
+    /// Recipes the book's own TOC and index both list but that nothing was
+    /// extracted for (see [`apply_outline`]). Empty for books without an index.
+    pub missed_titles: Vec<String>,
     /// Summed token usage across the API calls actually made.
     pub usage: Usage,
 }
@@ -227,14 +287,7 @@ impl ExtractionStats {
     /// Estimated USD cost of the API calls, or `None` if the model's pricing is
     /// unknown. Cache writes bill ~1.25× input, cache reads ~0.1× input.
     pub fn cost_usd(&self) -> Option<f64> {
-        let (in_rate, out_rate) = price_per_mtok(&self.model)?;
-        let u = &self.usage;
-        let cost = (u.input_tokens as f64 * in_rate
-            + u.cache_creation_input_tokens as f64 * in_rate * 1.25
-            + u.cache_read_input_tokens as f64 * in_rate * 0.1
-            + u.output_tokens as f64 * out_rate)
-            / 1_000_000.0;
-        Some(cost)
+        self.usage.cost_usd(&self.model)
     }
 
     /// One-line human summary for CLI stderr / UI.
@@ -254,8 +307,32 @@ impl ExtractionStats {
         } else {
             String::new()
         };
+        let escalated = if self.chunks_quality_escalated > 0 {
+            format!(
+                " · {} low-quality chunk(s) escalated",
+                self.chunks_quality_escalated
+            )
+        } else {
+            String::new()
+        };
+        let over_budget = if self.chunks_over_budget > 0 {
+            format!(
+                " · budget reached, {} chunk(s) NOT extracted",
+                self.chunks_over_budget
+            )
+        } else {
+            String::new()
+        };
+        let missed = if self.missed_titles.is_empty() {
+            String::new()
+        } else {
+            format!(
+                " · {} indexed recipe(s) not extracted",
+                self.missed_titles.len()
+            )
+        };
         format!(
-            "{}/{} chunks cached · {} in / {} out tok · {} cache-read tok · {cost}{failed}",
+            "{}/{} chunks cached · {} in / {} out tok · {} cache-read tok · {cost}{failed}{escalated}{over_budget}{missed}",
             self.chunks_cached,
             self.chunks_total,
             u.input_tokens,
@@ -696,6 +773,20 @@ pub struct EpubMeta {
     /// ranks them: which scheme a consumer wants is the consumer's business,
     /// and dropping the ones this crate doesn't recognise would be lossy.
     pub identifiers: Vec<String>,
+    /// OPF `<dc:language>` tags ("en-GB"), verbatim.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
+}
+
+impl EpubMeta {
+    /// Whose cups and pints the book's recipes mean, from the first language
+    /// tag that names a region; `None` leaves the parser's US default. Pass it
+    /// to [`CookbookRecipeExt::parse_in`].
+    pub fn region(&self) -> Option<Region> {
+        self.languages
+            .iter()
+            .find_map(|tag| Region::from_language_tag(tag))
+    }
 }
 
 /// Extract title/authors/subjects/identifiers from an already-open EPUB's OPF.
@@ -719,6 +810,7 @@ pub(crate) fn meta_from_doc<R: std::io::Read + std::io::Seek>(doc: &EpubDoc<R>)
         authors: collect("creator"),
         subjects: collect("subject"),
         identifiers: collect("identifier"),
+        languages: collect("language"),
     }
 }
 
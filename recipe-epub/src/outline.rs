@@ -0,0 +1,313 @@
+//! A book's own map of its recipes: the table of contents (EPUB2 NCX, or the
+//! EPUB3 `nav` document when there is no NCX) and the back-of-book index.
+//!
+//! Both are signals the book ships for free. A *recipe-level* TOC — one that
+//! names individual recipes rather than just chapters — gives the chunker exact
+//! recipe boundaries and title hints (see `epub_text::window_chunks`) instead of
+//! the `looks_like_title` guess. The index is never sent to the model (pages of
+//! "Leeks, 45, 112" yield no recipes, only cost); instead it confirms titles,
+//! fills in [`RecipeMeta::page`](crate::RecipeMeta::page), and lets
+//! [`apply_outline`] flag recipes the extractor missed.
+
+use std::collections::HashSet;
+use std::io::{Read, Seek};
+
+use epub::doc::{EpubDoc, NavPoint};
+use scraper::{Html, Selector};
+use serde::Serialize;
+
+use crate::CookbookRecipe;
+use crate::epub_text::resolve_relative;
+use crate::normalize_title;
+
+/// One table-of-contents entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TocEntry {
+    /// The entry's label, as the TOC prints it.
+    pub title: String,
+    /// Archive path of the content document the entry points into.
+    pub doc_path: String,
+    /// The `#fragment` within that document, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<String>,
+}
+
+/// One back-of-book index entry: a heading and the first page it cites.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IndexEntry {
+    pub title: String,
+    pub page: String,
+}
+
+/// The TOC and index of one book.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BookOutline {
+    /// Every TOC entry, flattened in reading order.
+    pub toc: Vec<TocEntry>,
+    /// Index entries with a page number, in index order.
+    pub index: Vec<IndexEntry>,
+    /// Whether the TOC names recipes (not just chapters), i.e. whether its
+    /// entries were used as chunk boundaries.
+    pub recipe_level: bool,
+}
+
+impl BookOutline {
+    /// The index page for `title` (normalized match), if the index lists it.
+    pub fn page_for(&self, title: &str) -> Option<&str> {
+        let title = normalize_title(title);
+        self.index
+            .iter()
+            .find(|e| normalize_title(&e.title) == title)
+            .map(|e| e.page.as_str())
+    }
+
+    /// Content documents the TOC labels as an index ("Index", "Recipe Index").
+    /// These are read for [`Self::index`] and never chunked.
+    pub(crate) fn index_docs(&self) -> HashSet<String> {
+        self.toc
+            .iter()
+            .filter(|e| normalize_title(&e.title).split(' ').any(|t| t == "index"))
+            .map(|e| e.doc_path.clone())
+            .collect()
+    }
+}
+
+/// Split a TOC href (`OEBPS/ch1.xhtml#pancakes`) into its path and fragment.
+fn split_href(href: &str) -> (String, Option<String>) {
+    match href.split_once('#') {
+        Some((path, frag)) => (
+            path.to_string(),
+            Some(frag.to_string()).filter(|f| !f.is_empty()),
+        ),
+        None => (href.to_string(), None),
+    }
+}
+
+/// Flatten NCX nav points depth-first (a chapter, then its recipes).
+fn flatten_ncx(points: &[NavPoint], out: &mut Vec<TocEntry>) {
+    for p in points {
+        let (doc_path, anchor) = split_href(&p.content.to_string_lossy());
+        out.push(TocEntry {
+            title: p.label.trim().to_string(),
+            doc_path,
+            anchor,
+        });
+        flatten_ncx(&p.children, out);
+    }
+}
+
+/// The entries of an EPUB3 nav document: every link in its `toc` nav (or its
+/// first `<nav>` when none is marked), resolved against `nav_path`.
+fn nav_entries(xhtml: &str, nav_path: &str) -> Vec<TocEntry> {
+    let dom = Html::parse_document(xhtml);
+    let (Ok(nav_sel), Ok(a_sel)) = (Selector::parse("nav"), Selector::parse("a[href]")) else {
+        return Vec::new();
+    };
+    let navs: Vec<_> = dom.select(&nav_sel).collect();
+    let Some(toc) = navs
+        .iter()
+        .find(|n| n.value().attrs().any(|(_, v)| v == "toc"))
+        .or(navs.first())
+    else {
+        return Vec::new();
+    };
+    toc.select(&a_sel)
+        .filter_map(|a| {
+            let href = a.value().attr("href")?;
+            let (rel, anchor) = split_href(href);
+            let title = a.text().collect::<String>().trim().to_string();
+            if title.is_empty() {
+                return None;
+            }
+            Some(TocEntry {
+                title,
+                doc_path: resolve_relative(nav_path, &rel)?,
+                anchor,
+            })
+        })
+        .collect()
+}
+
+/// Read `doc`'s table of contents: the NCX if it has one, else the EPUB3 nav.
+pub(crate) fn toc_entries<R: Read + Seek>(doc: &mut EpubDoc<R>) -> Vec<TocEntry> {
+    let mut out = Vec::new();
+    flatten_ncx(&doc.toc, &mut out);
+    if !out.is_empty() {
+        return out;
+    }
+    let nav_path = doc
+        .resources
+        .values()
+        .find(|r| {
+            r.properties
+                .as_deref()
+                .is_some_and(|p| p.split_whitespace().any(|p| p == "nav"))
+        })
+        .map(|r| r.path.to_string_lossy().into_owned());
+    match nav_path {
+        Some(path) => doc
+            .get_resource_str_by_path(&path)
+            .map(|x| nav_entries(&x, &path))
+            .unwrap_or_default(),
+        None => Vec::new(),
+    }
+}
+
+/// Parse one index line into a heading and its first page: "Leek and Potato
+/// Soup, 45" or "Pancakes 12–13, 88". Lines without a trailing page locator
+/// (letter headings, "see also" cross-references) yield `None`.
+pub(crate) fn parse_index_line(line: &str) -> Option<IndexEntry> {
+    let t = line.trim();
+    let head = t
+        .trim_end_matches(|c: char| c.is_ascii_digit() || matches!(c, ',' | ' ' | '-' | '–' | '—'));
+    let page = t[head.len()..]
+        .split(|c: char| !c.is_ascii_digit())
+        .find(|s| !s.is_empty())?;
+    let title = head.trim().trim_end_matches([',', ':', ';']).trim();
+    (!title.is_empty()).then(|| IndexEntry {
+        title: title.to_string(),
+        page: page.to_string(),
+    })
+}
+
+/// Reconcile assembled recipes with the book's outline: fill each recipe's
+/// missing `page` from the index, and return the titles the book lists as
+/// recipes that no extracted recipe matches — the extractor's misses.
+///
+/// A title counts as a listed recipe only when it is in both the TOC and the
+/// index: chapter names and "Introduction" are TOC-only, ingredient headings
+/// ("Leeks") are index-only, so the intersection is the recipes. A book without
+/// an index (or without a TOC) flags nothing rather than guessing.
+pub fn apply_outline(recipes: &mut [CookbookRecipe], outline: &BookOutline) -> Vec<String> {
+    for r in recipes.iter_mut() {
+        if r.meta.page.is_none() {
+            r.meta.page = outline.page_for(&r.meta.title).map(str::to_string);
+        }
+    }
+    let indexed: HashSet<String> = outline
+        .index
+        .iter()
+        .map(|e| normalize_title(&e.title))
+        .collect();
+    let extracted: HashSet<String> = recipes
+        .iter()
+        .map(|r| normalize_title(&r.meta.title))
+        .collect();
+    let mut seen = HashSet::new();
+    outline
+        .toc
+        .iter()
+        .filter(|e| {
+            let n = normalize_title(&e.title);
+            indexed.contains(&n) && !extracted.contains(&n) && seen.insert(n)
+        })
+        .map(|e| e.title.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::{RecipeMeta, RecipeSection};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Leek and Potato Soup, 45", Some(("Leek and Potato Soup", "45")))]
+    #[case("Pancakes 12–13, 88", Some(("Pancakes", "12")))]
+    #[case("Buttermilk: 45", Some(("Buttermilk", "45")))]
+    #[case("B", None)]
+    #[case("Leeks. See also Onions", None)]
+    #[case("45", None)]
+    fn parses_index_lines(#[case] line: &str, #[case] expected: Option<(&str, &str)>) {
+        let got = parse_index_line(line);
+        assert_eq!(
+            got.as_ref().map(|e| (e.title.as_str(), e.page.as_str())),
+            expected
+        );
+    }
+
+    #[test]
+    fn reads_the_toc_nav_over_landmarks() {
+        let xhtml = r##"<html><body>
+            <nav epub:type="landmarks"><a href="cover.xhtml">Cover</a></nav>
+            <nav epub:type="toc"><ol>
+              <li><a href="text/ch1.xhtml">Breakfast</a>
+                <ol><li><a href="text/ch1.xhtml#p1">Pancakes</a></li></ol></li>
+            </ol></nav>
+        </body></html>"##;
+        let toc = nav_entries(xhtml, "OEBPS/nav.xhtml");
+        let got: Vec<_> = toc
+            .iter()
+            .map(|e| (e.title.as_str(), e.doc_path.as_str(), e.anchor.as_deref()))
+            .collect();
+        assert_eq!(
+            got,
+            [
+                ("Breakfast", "OEBPS/text/ch1.xhtml", None),
+                ("Pancakes", "OEBPS/text/ch1.xhtml", Some("p1")),
+            ]
+        );
+    }
+
+    fn toc(title: &str) -> TocEntry {
+        TocEntry {
+            title: title.to_string(),
+            doc_path: "ch1.xhtml".to_string(),
+            anchor: None,
+        }
+    }
+
+    fn entry(title: &str, page: &str) -> IndexEntry {
+        IndexEntry {
+            title: title.to_string(),
+            page: page.to_string(),
+        }
+    }
+
+    #[test]
+    fn apply_outline_fills_pages_and_flags_misses() {
+        let outline = BookOutline {
+            toc: vec![
+                TocEntry {
+                    doc_path: "index.xhtml".to_string(),
+                    ..toc("Index")
+                },
+                toc("Breakfast"),
+                toc("Pancakes"),
+                toc("The Omelette"),
+                toc("Waffles"),
+            ],
+            index: vec![
+                entry("Eggs", "3"),
+                entry("Omelette", "14"),
+                entry("Pancakes", "12"),
+                entry("Waffles", "16"),
+            ],
+            recipe_level: true,
+        };
+        let recipe = |title: &str, page: Option<&str>| CookbookRecipe {
+            meta: RecipeMeta {
+                title: title.to_string(),
+                page: page.map(str::to_string),
+                ..Default::default()
+            },
+            sections: vec![RecipeSection::new(vec!["2 eggs".to_string()], vec![])],
+            source: "book.epub".to_string(),
+            url: "book.epub#ch1.xhtml".to_string(),
+            references: vec![],
+            image: None,
+        };
+        let mut recipes = vec![recipe("Pancakes", None), recipe("Omelette", Some("xiv"))];
+        let missed = apply_outline(&mut recipes, &outline);
+        assert_eq!(recipes[0].meta.page.as_deref(), Some("12"));
+        // A page the model read off the text wins over the index.
+        assert_eq!(recipes[1].meta.page.as_deref(), Some("xiv"));
+        // "Breakfast" is TOC-only and "Eggs" index-only; only Waffles is a miss.
+        assert_eq!(missed, ["Waffles"]);
+        assert_eq!(
+            outline.index_docs(),
+            HashSet::from(["index.xhtml".to_string()])
+        );
+    }
+}
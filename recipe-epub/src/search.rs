@@ -0,0 +1,353 @@
+//! Cross-book recipe search: "what can I make with leeks, potatoes and cream".
+//!
+//! [`RecipeIndex`] indexes a set of [`ParsedCookbookRecipe`]s — ingredient names,
+//! titles, categories and instruction text — and ranks them by ingredient
+//! *coverage*: how many of a recipe's required ingredients the cook has on hand.
+//! "Required" excludes optional lines and the [`IngredientUsage::Seasoning`] /
+//! [`IngredientUsage::Garnish`] ones (salt to taste and a parsley garnish
+//! shouldn't stop anyone from making soup), so coverage reflects what you'd
+//! actually have to shop for.
+//!
+//! Pure (no I/O), so it compiles everywhere; `food-cli search` builds an index
+//! from the persistent catalog.
+
+use ingredient::rich_text::Chunk;
+use ingredient::{Ingredient, IngredientUsage};
+
+use crate::{ParsedCookbookRecipe, RecipeTimes, contains_whole_tokens, normalize_title};
+
+/// Normalize a name or search term for matching: [`normalize_title`], then a
+/// crude plural fold on every token ("leeks" → "leek", "potatoes" → "potato",
+/// but "glass" stays) so the cook's phrasing and the book's needn't agree.
+fn fold(s: &str) -> String {
+    normalize_title(s)
+        .split(' ')
+        .map(|t| {
+            if t.len() > 4 && t.ends_with("oes") {
+                &t[..t.len() - 2]
+            } else if t.len() > 3 && t.ends_with('s') && !t.ends_with("ss") {
+                &t[..t.len() - 1]
+            } else {
+                t
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether a line counts toward coverage: not optional, not a seasoning or
+/// garnish.
+fn is_required(i: &Ingredient) -> bool {
+    !i.optional
+        && !matches!(
+            i.usage,
+            IngredientUsage::Seasoning | IngredientUsage::Garnish
+        )
+}
+
+/// The leading whole number of a yield line ("Serves 4 to 6" → 4, "Makes 12
+/// pancakes" → 12), the figure the yield filter compares against.
+fn yield_count(s: &str) -> Option<u32> {
+    let start = s.find(|c: char| c.is_ascii_digit())?;
+    let digits: String = s[start..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+/// A search over a [`RecipeIndex`]. Every set field must match; a default query
+/// matches every recipe (shortest ingredient list first).
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    /// Ingredients on hand. When non-empty, a recipe must use at least one of
+    /// them, and hits rank by how much of the recipe they cover.
+    pub have: Vec<String>,
+    /// Free-text terms that must each appear in the title, category, an
+    /// ingredient name or the instructions (whole-word).
+    pub text: Vec<String>,
+    /// Only recipes whose total time (or prep + cook) is known and at most this.
+    pub max_total_minutes: Option<u32>,
+    /// Only recipes whose active time is known and at most this.
+    pub max_active_minutes: Option<u32>,
+    /// Only recipes whose printed yield is known and at least this many
+    /// (servings, pieces — whatever the book counts in).
+    pub min_yield: Option<u32>,
+}
+
+/// One ranked search result.
+#[derive(Debug, Clone)]
+pub struct SearchHit<'a> {
+    pub recipe: &'a ParsedCookbookRecipe,
+    /// Required ingredient names (as the book wrote them) the cook has.
+    pub matched: Vec<&'a str>,
+    /// Required ingredient names the cook is missing — the shopping list.
+    pub missing: Vec<&'a str>,
+}
+
+impl SearchHit<'_> {
+    /// Fraction of the recipe's required ingredients on hand, `0.0..=1.0`. A
+    /// recipe with no required ingredients is fully covered.
+    pub fn coverage(&self) -> f64 {
+        let total = self.matched.len() + self.missing.len();
+        if total == 0 {
+            1.0
+        } else {
+            self.matched.len() as f64 / total as f64
+        }
+    }
+}
+
+/// One indexed recipe: the recipe plus its pre-folded search fields, so a query
+/// doesn't re-normalize every recipe's text.
+#[derive(Debug)]
+struct Entry {
+    recipe: ParsedCookbookRecipe,
+    /// `(folded, verbatim)` names of the required ingredients.
+    required: Vec<(String, String)>,
+    /// Folded title, category, every ingredient name and the instruction text,
+    /// space-joined — the free-text haystack.
+    text: String,
+}
+
+impl Entry {
+    fn new(recipe: ParsedCookbookRecipe) -> Self {
+        let ingredients = || recipe.sections.iter().flat_map(|s| &s.ingredients);
+        let required = ingredients()
+            .filter(|i| is_required(i) && !i.name.trim().is_empty())
+            .map(|i| (fold(&i.name), i.name.clone()))
+            .collect();
+        let mut text = vec![fold(&recipe.meta.title)];
+        text.extend(recipe.meta.category.as_deref().map(fold));
+        text.extend(ingredients().map(|i| fold(&i.name)));
+        for step in recipe.sections.iter().flat_map(|s| &s.instructions) {
+            for chunk in step {
+                if let Chunk::Text(s) | Chunk::Ing(s) = chunk {
+                    text.push(fold(s));
+                }
+            }
+        }
+        Self {
+            text: text.join(" "),
+            required,
+            recipe,
+        }
+    }
+
+    /// Whether the recipe passes the query's time and yield filters.
+    fn passes_filters(&self, q: &SearchQuery) -> bool {
+        let times = self.recipe.meta.times.as_ref();
+        let total = times.and_then(RecipeTimes::known_total_minutes);
+        let active = times.and_then(|t| t.active_minutes);
+        let yields = self
+            .recipe
+            .meta
+            .recipe_yield
+            .as_deref()
+            .and_then(yield_count);
+        q.max_total_minutes
+            .is_none_or(|max| total.is_some_and(|t| t <= max))
+            && q.max_active_minutes
+                .is_none_or(|max| active.is_some_and(|t| t <= max))
+            && q.min_yield
+                .is_none_or(|min| yields.is_some_and(|y| y >= min))
+    }
+}
+
+/// A searchable set of parsed recipes, typically every recipe in a library.
+#[derive(Debug, Default)]
+pub struct RecipeIndex {
+    entries: Vec<Entry>,
+}
+
+impl RecipeIndex {
+    /// Index `recipes`. Building folds every name and instruction once; queries
+    /// are then a linear scan, which is plenty for a personal library's few
+    /// thousand recipes.
+    pub fn new(recipes: impl IntoIterator<Item = ParsedCookbookRecipe>) -> Self {
+        Self {
+            entries: recipes.into_iter().map(Entry::new).collect(),
+        }
+    }
+
+    /// Number of indexed recipes.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// `true` when nothing is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Recipes matching `query`, best first: highest coverage, then most
+    /// ingredients on hand, then fewest missing, then title.
+    pub fn search(&self, query: &SearchQuery) -> Vec<SearchHit<'_>> {
+        let have: Vec<String> = query
+            .have
+            .iter()
+            .map(|h| fold(h))
+            .filter(|h| !h.is_empty())
+            .collect();
+        let text: Vec<String> = query
+            .text
+            .iter()
+            .map(|t| fold(t))
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        let mut hits: Vec<SearchHit<'_>> = self
+            .entries
+            .iter()
+            .filter(|e| e.passes_filters(query))
+            .filter(|e| text.iter().all(|t| contains_whole_tokens(&e.text, t)))
+            .filter_map(|e| {
+                let (matched, missing): (Vec<_>, Vec<_>) = e
+                    .required
+                    .iter()
+                    .partition(|(name, _)| have.iter().any(|h| contains_whole_tokens(name, h)));
+                if !have.is_empty() && matched.is_empty() {
+                    return None;
+                }
+                Some(SearchHit {
+                    recipe: &e.recipe,
+                    matched: matched.into_iter().map(|(_, v)| v.as_str()).collect(),
+                    missing: missing.into_iter().map(|(_, v)| v.as_str()).collect(),
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.coverage()
+                .total_cmp(&a.coverage())
+                .then(b.matched.len().cmp(&a.matched.len()))
+                .then(a.missing.len().cmp(&b.missing.len()))
+                .then_with(|| a.recipe.meta.title.cmp(&b.recipe.meta.title))
+        });
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::{CookbookRecipe, CookbookRecipeExt, RecipeMeta, RecipeSection, RecipeTimes};
+    use rstest::rstest;
+
+    fn recipe(
+        title: &str,
+        ings: &[&str],
+        steps: &[&str],
+        times: Option<RecipeTimes>,
+        recipe_yield: Option<&str>,
+    ) -> ParsedCookbookRecipe {
+        CookbookRecipe {
+            meta: RecipeMeta {
+                title: title.to_string(),
+                times,
+                recipe_yield: recipe_yield.map(str::to_string),
+                ..Default::default()
+            },
+            sections: vec![RecipeSection::new(
+                ings.iter().map(|s| s.to_string()).collect(),
+                steps.iter().map(|s| s.to_string()).collect(),
+            )],
+            source: "book.epub".to_string(),
+            url: "book.epub#ch1.xhtml".to_string(),
+            references: vec![],
+            image: None,
+        }
+        .parse()
+    }
+
+    fn index() -> RecipeIndex {
+        RecipeIndex::new([
+            recipe(
+                "Leek and Potato Soup",
+                &[
+                    "2 leeks, sliced",
+                    "1 lb potatoes",
+                    "1 cup heavy cream",
+                    "salt, to taste",
+                    "chives, for garnish",
+                ],
+                &["Sweat the leeks, add the potatoes and simmer."],
+                Some(RecipeTimes {
+                    total_minutes: Some(45),
+                    ..Default::default()
+                }),
+                Some("Serves 4"),
+            ),
+            recipe(
+                "Gratin Dauphinois",
+                &[
+                    "2 lb potatoes",
+                    "2 cups cream",
+                    "1 clove garlic",
+                    "1 cup gruyère",
+                ],
+                &["Layer and bake."],
+                Some(RecipeTimes {
+                    prep_minutes: Some(20),
+                    cook_minutes: Some(70),
+                    ..Default::default()
+                }),
+                Some("Serves 6 to 8"),
+            ),
+            recipe(
+                "Omelette",
+                &["3 eggs", "1 tbsp butter"],
+                &["Fry."],
+                None,
+                None,
+            ),
+        ])
+    }
+
+    fn titles(hits: &[SearchHit<'_>]) -> Vec<String> {
+        hits.iter().map(|h| h.recipe.meta.title.clone()).collect()
+    }
+
+    #[test]
+    fn ranks_by_coverage_ignoring_seasoning_and_garnish() {
+        let idx = index();
+        let q = SearchQuery {
+            have: vec!["leeks".into(), "potatoes".into(), "cream".into()],
+            ..Default::default()
+        };
+        let hits = idx.search(&q);
+        // The soup needs nothing else (salt and chives don't count); the gratin
+        // is missing garlic and cheese; the omelette shares nothing and drops out.
+        assert_eq!(titles(&hits), ["Leek and Potato Soup", "Gratin Dauphinois"]);
+        assert_eq!(hits[0].coverage(), 1.0);
+        assert_eq!(hits[1].missing.len(), 2);
+        assert_eq!(hits[1].coverage(), 0.5);
+    }
+
+    #[rstest]
+    #[case::total_time(SearchQuery { max_total_minutes: Some(60), ..Default::default() }, &["Leek and Potato Soup"])]
+    #[case::prep_plus_cook(SearchQuery { max_total_minutes: Some(90), ..Default::default() }, &["Leek and Potato Soup", "Gratin Dauphinois"])]
+    #[case::min_yield(SearchQuery { min_yield: Some(6), ..Default::default() }, &["Gratin Dauphinois"])]
+    #[case::text_in_instructions(SearchQuery { text: vec!["simmer".into()], ..Default::default() }, &["Leek and Potato Soup"])]
+    #[case::text_in_ingredients(SearchQuery { text: vec!["egg".into()], ..Default::default() }, &["Omelette"])]
+    fn filters(#[case] q: SearchQuery, #[case] expected: &[&str]) {
+        assert_eq!(titles(&index().search(&q)), expected);
+    }
+
+    #[rstest]
+    #[case("leeks", "leek")]
+    #[case("Potatoes", "potato")]
+    #[case("glass", "glass")]
+    #[case("heavy creams", "heavy cream")]
+    fn fold_singularizes(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(fold(input), expected);
+    }
+
+    #[rstest]
+    #[case("Serves 4 to 6", Some(4))]
+    #[case("Makes 12 pancakes", Some(12))]
+    #[case("Makes one loaf", None)]
+    fn yield_counts(#[case] input: &str, #[case] expected: Option<u32>) {
+        assert_eq!(yield_count(input), expected);
+    }
+}
@@ -0,0 +1,480 @@
+//! Extraction quality: check the model's output against the chunk it read.
+//!
+//! The extraction contract is that ingredient and instruction strings come back
+//! **verbatim** — the core parser, not the model, interprets quantities. Nothing
+//! enforced that. [`verify_chunk`] aligns every extracted line back to the
+//! source [`Chunk`] text and reports lines that were paraphrased or invented,
+//! recipes none of whose lines align (a hallucinated recipe), and quantity lines
+//! in the source that no recipe claimed (dropped ingredients).
+//!
+//! Pure and cheap (whole-token containment and overlap, no model call), so the
+//! native orchestration can score every chunk and escalate the poor ones (see
+//! `Options::escalate_below_quality`), and `food-cli debug-epub` can print it.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::{Chunk, ExtractedRecipe};
+
+/// Minimum share of a line's words found in the source for it to count as a
+/// paraphrase rather than an invention.
+const PARAPHRASE_MIN: f64 = 0.6;
+
+/// Which list an extracted line came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineKind {
+    Ingredient,
+    Instruction,
+}
+
+/// How one extracted line relates to the source text.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "verdict")]
+pub enum Alignment {
+    /// Found in the source as-is (modulo whitespace, case, quote and dash style).
+    Verbatim,
+    /// Not in the source, but most of its words are; `source` is the closest
+    /// source line and `overlap` the share of the line's words found there.
+    Paraphrased { source: String, overlap: f64 },
+    /// Mostly words the source doesn't contain.
+    Hallucinated { overlap: f64 },
+}
+
+/// One extracted line and its verdict.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LineCheck {
+    pub kind: LineKind,
+    pub line: String,
+    pub alignment: Alignment,
+}
+
+/// Every line of one extracted recipe, checked.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecipeQuality {
+    pub title: String,
+    pub lines: Vec<LineCheck>,
+}
+
+impl RecipeQuality {
+    /// `true` when the recipe has lines and not one of them aligns to the
+    /// source — the whole recipe is probably invented (or from another chunk).
+    pub fn is_unaligned(&self) -> bool {
+        !self.lines.is_empty()
+            && self
+                .lines
+                .iter()
+                .all(|l| matches!(l.alignment, Alignment::Hallucinated { .. }))
+    }
+}
+
+/// The quality report for one chunk.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChunkQuality {
+    pub doc_path: String,
+    pub recipes: Vec<RecipeQuality>,
+    /// Ingredient-looking source lines (a leading quantity) that no extracted
+    /// ingredient line accounts for.
+    pub unclaimed: Vec<String>,
+}
+
+impl ChunkQuality {
+    /// Share of lines that are right, `0.0..=1.0`: verbatim extracted lines
+    /// over all extracted lines plus unclaimed source lines (each a line that
+    /// should have been extracted). `1.0` for a chunk with nothing either way.
+    pub fn score(&self) -> f64 {
+        let lines = self.recipes.iter().flat_map(|r| &r.lines);
+        let total = lines.clone().count() + self.unclaimed.len();
+        if total == 0 {
+            return 1.0;
+        }
+        let verbatim = lines.filter(|l| l.alignment == Alignment::Verbatim).count();
+        verbatim as f64 / total as f64
+    }
+
+    /// Lines that aren't verbatim, in recipe order.
+    pub fn flagged(&self) -> impl Iterator<Item = (&RecipeQuality, &LineCheck)> {
+        self.recipes.iter().flat_map(|r| {
+            r.lines
+                .iter()
+                .filter(|l| l.alignment != Alignment::Verbatim)
+                .map(move |l| (r, l))
+        })
+    }
+
+    /// `true` when nothing is flagged.
+    pub fn is_clean(&self) -> bool {
+        self.flagged().next().is_none() && self.unclaimed.is_empty()
+    }
+
+    /// One-line human summary, e.g. `score 0.92 · 1 paraphrased · 2 unclaimed`.
+    pub fn summary(&self) -> String {
+        let (mut paraphrased, mut hallucinated) = (0, 0);
+        for (_, l) in self.flagged() {
+            match l.alignment {
+                Alignment::Paraphrased { .. } => paraphrased += 1,
+                Alignment::Hallucinated { .. } => hallucinated += 1,
+                Alignment::Verbatim => {}
+            }
+        }
+        let unaligned = self.recipes.iter().filter(|r| r.is_unaligned()).count();
+        let mut parts = vec![format!("score {:.2}", self.score())];
+        for (n, what) in [
+            (paraphrased, "paraphrased"),
+            (hallucinated, "hallucinated"),
+            (unaligned, "unaligned recipe(s)"),
+            (self.unclaimed.len(), "unclaimed"),
+        ] {
+            if n > 0 {
+                parts.push(format!("{n} {what}"));
+            }
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Normalize for alignment: lowercase, curly quotes straightened, dashes
+/// unified, a leading bullet dropped, whitespace collapsed. Models routinely
+/// re-type these, and none of them change what the line says.
+fn norm(s: &str) -> String {
+    let mapped: String = s
+        .chars()
+        .map(|c| match c {
+            '\u{2018}' | '\u{2019}' | '\u{2032}' => '\'',
+            '\u{201C}' | '\u{201D}' | '\u{2033}' => '"',
+            '\u{2010}'..='\u{2015}' | '\u{2212}' => '-',
+            '\u{a0}' => ' ',
+            c => c,
+        })
+        .collect();
+    let words: Vec<&str> = mapped.split_whitespace().collect();
+    let joined = words.join(" ").to_lowercase();
+    joined
+        .trim_start_matches(['•', '·', '*', '-', '▪'])
+        .trim_start()
+        .to_string()
+}
+
+/// Whether `needle` occurs in `haystack` as whole tokens: not glued to a word
+/// or a number on either side, so "2 eggs" isn't found in "12 eggs" or
+/// "1/2 eggs".
+fn contains_tokens(haystack: &str, needle: &str) -> bool {
+    let (Some(first), Some(last)) = (needle.chars().next(), needle.chars().last()) else {
+        return false;
+    };
+    let mut start = 0;
+    while let Some(pos) = haystack[start..].find(needle) {
+        let at = start + pos;
+        let before = haystack[..at].chars().rev();
+        let after = haystack[at + needle.len()..].chars();
+        if !glued(first, before) && !glued(last, after) {
+            return true;
+        }
+        start = at + first.len_utf8();
+    }
+    false
+}
+
+/// Whether a match whose edge character is `edge` runs on into `beyond` (the
+/// characters past that edge, outward): a word continuing, or a number going
+/// on past a "/", "." or ",".
+fn glued(edge: char, mut beyond: impl Iterator<Item = char>) -> bool {
+    let is_number = |c: char| c.is_ascii_digit() || ingredient::fraction::is_vulgar(c);
+    let is_word = |c: char| c.is_alphanumeric() || is_number(c);
+    let Some(next) = beyond.next() else {
+        return false;
+    };
+    is_word(edge)
+        && (is_word(next)
+            || (is_number(edge)
+                && matches!(next, '/' | '.' | ',')
+                && beyond.next().is_some_and(is_number)))
+}
+
+/// The alphanumeric words of `s`, lowercased.
+fn words(s: &str) -> Vec<String> {
+    s.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Share of `line`'s words that occur in `pool`.
+fn overlap(line: &[String], pool: &HashSet<&str>) -> f64 {
+    if line.is_empty() {
+        return 0.0;
+    }
+    let hit = line.iter().filter(|w| pool.contains(w.as_str())).count();
+    hit as f64 / line.len() as f64
+}
+
+/// Whether a source line looks like an ingredient: short, starting with a
+/// quantity, and not a numbered step ("1. Preheat the oven.").
+fn looks_like_ingredient(line: &str) -> bool {
+    let t = line.trim();
+    let Some(first) = t.chars().next() else {
+        return false;
+    };
+    if !(first.is_ascii_digit() || ingredient::fraction::is_vulgar(first)) || t.len() > 80 {
+        return false;
+    }
+    let after_number = t.trim_start_matches(|c: char| c.is_ascii_digit());
+    !(after_number.starts_with(". ") || after_number.starts_with(") "))
+}
+
+/// Align `recipes` (one chunk's extractor output) against `chunk`'s text.
+pub fn verify_chunk(chunk: &Chunk, recipes: &[ExtractedRecipe]) -> ChunkQuality {
+    let source_lines: Vec<&str> = chunk
+        .text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+    let normed: Vec<String> = source_lines.iter().map(|l| norm(l)).collect();
+    let haystack = normed.join(" ");
+    let line_words: Vec<Vec<String>> = source_lines.iter().map(|l| words(l)).collect();
+    let pool: HashSet<&str> = line_words.iter().flatten().map(String::as_str).collect();
+
+    // Source lines some extracted ingredient accounts for (by containment
+    // either way, or as a paraphrase's closest line).
+    let mut claimed = vec![false; source_lines.len()];
+
+    let mut check = |kind: LineKind, line: &str| -> Option<LineCheck> {
+        let n = norm(line);
+        if n.is_empty() {
+            return None;
+        }
+        let claim_by_containment = |claimed: &mut Vec<bool>| {
+            for (i, s) in normed.iter().enumerate() {
+                if !s.is_empty() && (contains_tokens(s, &n) || contains_tokens(&n, s)) {
+                    claimed[i] = true;
+                }
+            }
+        };
+        if contains_tokens(&haystack, &n) {
+            if kind == LineKind::Ingredient {
+                claim_by_containment(&mut claimed);
+            }
+            return Some(LineCheck {
+                kind,
+                line: line.to_string(),
+                alignment: Alignment::Verbatim,
+            });
+        }
+        let w = words(line);
+        let total = overlap(&w, &pool);
+        let alignment = if total >= PARAPHRASE_MIN {
+            // Closest single source line, for the report (and the claim).
+            let best = line_words
+                .iter()
+                .enumerate()
+                .map(|(i, lw)| {
+                    let set: HashSet<&str> = lw.iter().map(String::as_str).collect();
+                    (i, overlap(&w, &set))
+                })
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            match best {
+                Some((i, _)) => {
+                    if kind == LineKind::Ingredient {
+                        claimed[i] = true;
+                    }
+                    Alignment::Paraphrased {
+                        source: source_lines[i].to_string(),
+                        overlap: total,
+                    }
+                }
+                None => Alignment::Hallucinated { overlap: total },
+            }
+        } else {
+            Alignment::Hallucinated { overlap: total }
+        };
+        Some(LineCheck {
+            kind,
+            line: line.to_string(),
+            alignment,
+        })
+    };
+
+    let recipes = recipes
+        .iter()
+        .map(|r| {
+            let mut lines = Vec::new();
+            for s in &r.sections {
+                lines.extend(
+                    s.ingredients
+                        .iter()
+                        .filter_map(|l| check(LineKind::Ingredient, l)),
+                );
+                lines.extend(
+                    s.instructions
+                        .iter()
+                        .filter_map(|l| check(LineKind::Instruction, l)),
+                );
+            }
+            RecipeQuality {
+                title: r.meta.title.clone(),
+                lines,
+            }
+        })
+        .collect();
+
+    let unclaimed = source_lines
+        .iter()
+        .zip(&claimed)
+        .filter(|(l, c)| !**c && looks_like_ingredient(l))
+        .map(|(l, _)| l.to_string())
+        .collect();
+
+    ChunkQuality {
+        doc_path: chunk.doc_path.clone(),
+        recipes,
+        unclaimed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::{RecipeMeta, RecipeSection};
+    use rstest::rstest;
+
+    fn chunk(text: &str) -> Chunk {
+        Chunk {
+            title_hint: None,
+            text: text.to_string(),
+            doc_path: "ch1.xhtml".to_string(),
+            links: vec![],
+            images: vec![],
+        }
+    }
+
+    fn recipe(title: &str, ings: &[&str], steps: &[&str]) -> ExtractedRecipe {
+        ExtractedRecipe {
+            meta: RecipeMeta {
+                title: title.to_string(),
+                ..Default::default()
+            },
+            sections: vec![RecipeSection::new(
+                ings.iter().map(|s| s.to_string()).collect(),
+                steps.iter().map(|s| s.to_string()).collect(),
+            )],
+        }
+    }
+
+    const SOURCE: &str = "Pancakes\n1\u{00bd} cups flour\n2 eggs\n1 cup buttermilk\n\
+        Whisk the dry ingredients. Beat in the eggs and buttermilk.\n\
+        Cook on a hot griddle until golden.";
+
+    #[test]
+    fn verbatim_extraction_is_clean() {
+        let q = verify_chunk(
+            &chunk(SOURCE),
+            &[recipe(
+                "Pancakes",
+                &["1½ cups flour", "2 eggs", "1 cup buttermilk"],
+                // One step may legitimately span a source sentence boundary.
+                &[
+                    "Whisk the dry ingredients. Beat in the eggs and buttermilk.",
+                    "Cook on a hot griddle until golden.",
+                ],
+            )],
+        );
+        assert!(q.is_clean(), "{q:#?}");
+        assert_eq!(q.score(), 1.0);
+        assert_eq!(q.summary(), "score 1.00");
+    }
+
+    #[test]
+    fn flags_paraphrase_hallucination_and_dropped_lines() {
+        let q = verify_chunk(
+            &chunk(SOURCE),
+            &[recipe(
+                "Pancakes",
+                // "1 cup buttermilk" dropped; flour line rewritten.
+                &["1.5 cups of flour", "2 eggs"],
+                &["Fold in blueberries and maple syrup."],
+            )],
+        );
+        let verdicts: Vec<_> = q.flagged().map(|(_, l)| &l.alignment).collect();
+        assert!(
+            matches!(verdicts[0], Alignment::Paraphrased { source, .. } if source == "1½ cups flour")
+        );
+        assert!(matches!(verdicts[1], Alignment::Hallucinated { .. }));
+        assert_eq!(q.unclaimed, ["1 cup buttermilk"]);
+        // 1 of 3 extracted lines verbatim, plus one dropped source line.
+        assert_eq!(q.score(), 0.25);
+        assert_eq!(
+            q.summary(),
+            "score 0.25 · 1 paraphrased · 1 hallucinated · 1 unclaimed"
+        );
+    }
+
+    /// A changed quantity isn't verbatim just because the text is a substring,
+    /// and doesn't claim the source line it came from.
+    #[rstest]
+    #[case("12 eggs", "2 eggs")]
+    #[case("1/2 cup sugar", "2 cup sugar")]
+    #[case("1½ cups flour", "½ cups flour")]
+    #[case("2 eggs, beaten", "2 egg")]
+    fn a_line_inside_another_token_is_not_verbatim(#[case] source: &str, #[case] line: &str) {
+        let q = verify_chunk(&chunk(source), &[recipe("Eggs", &[line], &[])]);
+        assert_ne!(
+            q.recipes[0].lines[0].alignment,
+            Alignment::Verbatim,
+            "{line:?}"
+        );
+    }
+
+    #[test]
+    fn two_eggs_is_not_found_in_twelve_eggs() {
+        let q = verify_chunk(
+            &chunk("Omelette\n12 eggs"),
+            &[recipe("Omelette", &["2 eggs"], &[])],
+        );
+        assert!(!q.is_clean());
+        assert_eq!(q.unclaimed, ["12 eggs"]);
+    }
+
+    #[rstest]
+    #[case("12 eggs", "2 eggs", false)]
+    #[case("add 2 eggs.", "2 eggs", true)]
+    #[case("1.5 cups", "5 cups", false)]
+    #[case("(optional) salt", "(optional)", true)]
+    #[case("½ cup", "½ cup", true)]
+    fn whole_token_containment(#[case] haystack: &str, #[case] needle: &str, #[case] found: bool) {
+        assert_eq!(contains_tokens(haystack, needle), found);
+    }
+
+    #[test]
+    fn flags_a_recipe_with_no_aligned_lines() {
+        let q = verify_chunk(
+            &chunk(SOURCE),
+            &[recipe(
+                "Lasagna",
+                &["12 lasagna noodles"],
+                &["Layer with ricotta."],
+            )],
+        );
+        assert!(q.recipes[0].is_unaligned());
+    }
+
+    #[rstest]
+    #[case("2 eggs", true)]
+    #[case("½ cup sugar", true)]
+    #[case("1. Preheat the oven.", false)]
+    #[case("Pancakes", false)]
+    fn ingredient_looking_lines(#[case] line: &str, #[case] expected: bool) {
+        assert_eq!(looks_like_ingredient(line), expected);
+    }
+
+    #[test]
+    fn norm_ignores_typography() {
+        assert_eq!(
+            norm("•  Baker’s  chocolate – chopped"),
+            "baker's chocolate - chopped"
+        );
+    }
+}
@@ -321,6 +321,65 @@ async fn progress_sink_reports_each_chunk() {
     );
 }
 
+#[tokio::test]
+async fn catalog_sync_is_incremental_and_queryable() {
+    use recipe_epub::{Catalog, CatalogQuery};
+
+    let mock = MockExtractor::new(vec![
+        (
+            "Pancakes".to_string(),
+            vec![er("Pancakes", &["1 cup buttermilk", "2 eggs"], &["Mix."])],
+        ),
+        (
+            "Omelette".to_string(),
+            vec![er("Omelette", &["3 eggs"], &["Fry."])],
+        ),
+    ]);
+    let dir = std::env::temp_dir().join(format!("recipe-epub-catalog-it-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let book = dir.join("book.epub");
+    std::fs::write(&book, build_epub()).unwrap();
+    let epubs = vec![std::fs::canonicalize(&book).unwrap()];
+
+    let mut catalog = Catalog::open(&dir.join("catalog.jsonl")).unwrap();
+    let report = catalog.sync_with(&dir, &Options::default(), &mock).await;
+    assert_eq!(report.added, epubs);
+    assert!(report.failed.is_empty(), "{:?}", report.failed);
+    catalog.save().unwrap();
+
+    // Reopened from disk, an unchanged book is not re-extracted, even when
+    // the directory is spelled differently.
+    let mut catalog = Catalog::open(catalog.path()).unwrap();
+    assert_eq!(catalog.books()[0].meta.title, "Test Cookbook");
+    let report = catalog
+        .sync_with(&dir.join("."), &Options::default(), &mock)
+        .await;
+    assert_eq!((report.added.len(), report.unchanged), (0, 1));
+    assert!(report.removed.is_empty());
+    assert!(catalog.book(&dir.join(".").join("book.epub")).is_some());
+
+    // Ingredient lines were run through the core parser, so queries match names.
+    let q = CatalogQuery {
+        ingredients: vec!["eggs".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(catalog.query(&q).len(), 2);
+    let q = CatalogQuery {
+        ingredients: vec!["buttermilk".to_string(), "eggs".to_string()],
+        ..Default::default()
+    };
+    let hits = catalog.query(&q);
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].recipe.recipe.meta.title, "Pancakes");
+
+    // A book gone from the library is dropped.
+    std::fs::remove_file(&book).unwrap();
+    let report = catalog.sync_with(&dir, &Options::default(), &mock).await;
+    assert_eq!(report.removed, epubs);
+    assert!(catalog.books().is_empty());
+    std::fs::remove_dir_all(&dir).ok();
+}
+
 // ── the continuation contract, end to end ───────────────────────────────────
 
 /// Build an epub whose single recipe body is long enough to force a mid-recipe
@@ -435,3 +494,110 @@ async fn continuation_chunk_merges_back_into_one_recipe() {
         merged.meta.notes
     );
 }
+
+// ── TOC + index awareness ───────────────────────────────────────────────────
+
+const INDEXED_OPF: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" unique-identifier="bookid" version="2.0">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:title>Indexed Cookbook</dc:title>
+    <dc:identifier id="bookid">urn:uuid:indexed-cookbook</dc:identifier>
+    <dc:language>en</dc:language>
+  </metadata>
+  <manifest>
+    <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
+    <item id="chap" href="chapter.xhtml" media-type="application/xhtml+xml"/>
+    <item id="index" href="index.xhtml" media-type="application/xhtml+xml"/>
+  </manifest>
+  <spine toc="ncx">
+    <itemref idref="chap"/>
+    <itemref idref="index"/>
+  </spine>
+</package>"#;
+
+// A recipe-level TOC: the chapter, then each recipe by anchor, then the index.
+const INDEXED_NCX: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+  <head/>
+  <docTitle><text>Indexed Cookbook</text></docTitle>
+  <navMap>
+    <navPoint id="n1" playOrder="1"><navLabel><text>Breakfast</text></navLabel><content src="chapter.xhtml"/>
+      <navPoint id="n2" playOrder="2"><navLabel><text>Pancakes</text></navLabel><content src="chapter.xhtml#p"/></navPoint>
+      <navPoint id="n3" playOrder="3"><navLabel><text>Omelette</text></navLabel><content src="chapter.xhtml#o"/></navPoint>
+      <navPoint id="n4" playOrder="4"><navLabel><text>Waffles</text></navLabel><content src="chapter.xhtml#w"/></navPoint>
+    </navPoint>
+    <navPoint id="n5" playOrder="5"><navLabel><text>Index</text></navLabel><content src="index.xhtml"/></navPoint>
+  </navMap>
+</ncx>"#;
+
+const INDEXED_CHAPTER: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<html xmlns="http://www.w3.org/1999/xhtml"><body>
+  <h1>Breakfast</h1>
+  <h2 id="p">Pancakes</h2><p>1 cup flour</p><p>Mix and cook.</p>
+  <h2 id="o">Omelette</h2><p>3 eggs</p><p>Whisk and fry.</p>
+  <h2 id="w">Waffles</h2><p>2 cups flour</p><p>Cook in the iron.</p>
+</body></html>"#;
+
+const INDEX: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<html xmlns="http://www.w3.org/1999/xhtml"><body>
+  <h1>Index</h1>
+  <p>E</p><p>Eggs, 14</p>
+  <p>O</p><p>Omelette, 14</p>
+  <p>P</p><p>Pancakes, 12–13</p>
+  <p>W</p><p>Waffles, 16</p>
+</body></html>"#;
+
+fn build_indexed_epub() -> Vec<u8> {
+    let mut zw = ZipWriter::new(Cursor::new(Vec::new()));
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    zw.start_file("mimetype", stored).unwrap();
+    zw.write_all(b"application/epub+zip").unwrap();
+    for (name, body) in [
+        ("META-INF/container.xml", CONTAINER),
+        ("OEBPS/content.opf", INDEXED_OPF),
+        ("OEBPS/toc.ncx", INDEXED_NCX),
+        ("OEBPS/chapter.xhtml", INDEXED_CHAPTER),
+        ("OEBPS/index.xhtml", INDEX),
+    ] {
+        zw.start_file(name, deflated).unwrap();
+        zw.write_all(body.as_bytes()).unwrap();
+    }
+    zw.finish().unwrap().into_inner()
+}
+
+#[test]
+fn index_is_read_for_pages_not_chunked() {
+    let bytes = build_indexed_epub();
+    let outline = recipe_epub::book_outline(&bytes).unwrap();
+    assert!(outline.recipe_level, "three recipes named in one doc's TOC");
+    assert_eq!(outline.page_for("pancakes"), Some("12"));
+    assert_eq!(outline.index.len(), 4);
+
+    // The index pages never reach the model.
+    let chunks = recipe_epub::chunk_epub(&bytes).unwrap();
+    assert!(chunks.iter().all(|c| !c.text.contains("Waffles, 16")));
+}
+
+#[tokio::test]
+async fn outline_fills_pages_and_flags_missed_recipes() {
+    // A model that found two of the three recipes the book lists.
+    let mock = MockExtractor::new(vec![(
+        "Pancakes".to_string(),
+        vec![
+            er("Pancakes", &["1 cup flour"], &["Mix and cook."]),
+            er("Omelette", &["3 eggs"], &["Whisk and fry."]),
+        ],
+    )]);
+    let bytes = build_indexed_epub();
+    let mut recipes =
+        extract_cookbook_with(&bytes, "indexed.epub", &Options::default(), &mock, |_| {})
+            .await
+            .unwrap();
+    let pages: Vec<_> = recipes.iter().map(|r| r.meta.page.as_deref()).collect();
+    assert_eq!(pages, [Some("12"), Some("14")]);
+
+    let outline = recipe_epub::book_outline(&bytes).unwrap();
+    let missed = recipe_epub::apply_outline(&mut recipes, &outline);
+    assert_eq!(missed, ["Waffles"]);
+}
@@ -1,6 +1,7 @@
+use ingredient::section::{LineKind, classify_line};
 use scraper::{Html, Selector};
 
-use crate::{ScrapeError, ScrapedRecipe};
+use crate::{ScrapeError, ScrapedRecipe, group_sections};
 
 fn parse_selector(selector: &str) -> Result<Selector, ScrapeError> {
     Selector::parse(selector)
@@ -17,11 +18,29 @@ pub fn scrape_from_html(dom: Html, url: &str) -> Result<ScrapedRecipe, ScrapeErr
     // NOTE: this "fallback" only understands Jetpack Recipe markup (Smitten
     // Kitchen and other Jetpack-powered WordPress sites). It is not a general HTML
     // scraper — sites without these classes hit the error below.
-    let ingredient_selector = parse_selector("li.jetpack-recipe-ingredient")?;
-    let ingredients = dom
+    // Component headers ("Garnishes") sit between the items as `<h5>`s; select
+    // both, in document order, and split the list at them. The markup already
+    // says an `<h5>` is a header, so give it the colon that tells
+    // `classify_line` so too.
+    let ingredient_selector =
+        parse_selector("li.jetpack-recipe-ingredient, div.jetpack-recipe-ingredients h5")?;
+    let lines = dom
         .select(&ingredient_selector)
-        .map(|i| i.text().collect::<Vec<_>>().join(""))
+        .map(|i| {
+            let text = i.text().collect::<Vec<_>>().join("");
+            if i.value().name() == "h5" {
+                format!("{}:", text.trim_end_matches(':'))
+            } else {
+                text
+            }
+        })
         .collect::<Vec<String>>();
+    let notes = lines
+        .iter()
+        .filter(|l| classify_line(l) == LineKind::Note)
+        .cloned()
+        .collect();
+    let mut sections = group_sections(&lines);
 
     let ul_selector = parse_selector(r#"div.jetpack-recipe-directions"#)?;
 
@@ -48,11 +67,19 @@ pub fn scrape_from_html(dom: Html, url: &str) -> Result<ScrapedRecipe, ScrapeErr
         .next()
         .and_then(|i| i.value().attr("content").map(|s| s.to_string()));
 
+    // The directions are one block for the whole recipe; they go with the main
+    // (first) component.
+    match sections.first_mut() {
+        Some(main) => main.instructions = instructions,
+        None => sections.push(crate::RecipeSection::new(vec![], instructions)),
+    }
+
     Ok(ScrapedRecipe {
-        sections: vec![crate::RecipeSection::new(ingredients, instructions)],
+        sections,
         name: title,
         url: url.to_string(),
         image,
+        notes,
         // HTML fallback doesn't have yield/metadata data.
         ..Default::default()
     })
@@ -19,6 +19,8 @@ use ingredient::{
     IngredientParser,
     ingredient::Ingredient,
     rich_text::{Rich, RichParser},
+    section::{LineKind, classify_line, section_title},
+    unit::Region,
 };
 use ld_json::extract_ld;
 // Re-exported on purpose: cubby's recipebridge wasm crate (separate repo)
@@ -108,22 +110,81 @@ pub struct ParsedRecipe {
     pub sections: Vec<ParsedSection>,
 }
 
+/// Split a flat ingredient list into sections at its inline headers ("For the
+/// crust:", "FILLING"; see [`classify_line`]). Lines before the first header
+/// form an unnamed section. Notes and non-ingredient lines ("Ingredients",
+/// "Serves 4") are dropped, as is a header with no ingredients under it; the
+/// sections' instructions are left empty.
+///
+/// ```
+/// use recipe_scraper::group_sections;
+///
+/// let sections = group_sections(["For the crust:", "1 cup flour", "FILLING", "2 lb apples"]);
+/// assert_eq!(sections.len(), 2);
+/// assert_eq!(sections[0].name.as_deref(), Some("For the crust"));
+/// assert_eq!(sections[1].ingredients, vec!["2 lb apples"]);
+/// ```
+pub fn group_sections<S: AsRef<str>>(lines: impl IntoIterator<Item = S>) -> Vec<RecipeSection> {
+    let mut sections = vec![RecipeSection::new(vec![], vec![])];
+    for line in lines {
+        let line = line.as_ref();
+        match classify_line(line) {
+            LineKind::Ingredient => {
+                if let Some(section) = sections.last_mut() {
+                    section.ingredients.push(line.to_string());
+                }
+            }
+            LineKind::SectionHeader => sections.push(RecipeSection {
+                name: section_title(line),
+                ..RecipeSection::new(vec![], vec![])
+            }),
+            LineKind::Note | LineKind::NonIngredient => {}
+        }
+    }
+    sections.retain(|s| !s.ingredients.is_empty());
+    sections
+}
+
 /// Parse each section's raw ingredient/instruction lines with the core parser.
 /// The [`RichParser`] is seeded with every ingredient name across all sections
 /// so instructions in one component can reference ingredients from another.
+/// Each ingredient is parsed in its section, so a "For the marinade" section
+/// marks its lines [`Marinade`](ingredient::IngredientUsage::Marinade).
 /// Shared by [`ScrapedRecipe::parse`] and `recipe-epub`.
 pub fn parse_sections(sections: &[RecipeSection]) -> Vec<ParsedSection> {
-    let ip = IngredientParser::new();
+    parse_sections_in(sections, Region::US)
+}
+
+/// [`parse_sections`] reading cups, pints and tablespoons as `region`'s, for
+/// a source whose language tag names one ([`Region::from_language_tag`]).
+pub fn parse_sections_in(sections: &[RecipeSection], region: Region) -> Vec<ParsedSection> {
+    let ip = IngredientParser::new().with_region(region);
     let parsed_ings: Vec<Vec<Ingredient>> = sections
         .iter()
-        .map(|s| s.ingredients.iter().map(|i| ip.from_str(i)).collect())
+        .map(|s| {
+            s.ingredients
+                .iter()
+                .map(|i| ip.from_str_in_section(i, s.name.as_deref()))
+                .collect()
+        })
         .collect();
+    parse_sections_with(sections, parsed_ings, region)
+}
+
+/// [`parse_sections_in`] for sections whose ingredient lines are already
+/// parsed (`parsed_ings`, one `Vec` per section): only the instructions are
+/// parsed, against those ingredients' names.
+pub fn parse_sections_with(
+    sections: &[RecipeSection],
+    parsed_ings: Vec<Vec<Ingredient>>,
+    region: Region,
+) -> Vec<ParsedSection> {
     let names: Vec<String> = parsed_ings
         .iter()
         .flatten()
         .map(|i| i.name.clone())
         .collect();
-    let rtp = RichParser::new(names);
+    let rtp = RichParser::new(names).with_region(region);
     sections
         .iter()
         .zip(parsed_ings)
@@ -324,4 +385,42 @@ mod tests {
         assert_eq!(scraped.notes, meta.notes);
         assert_eq!(scraped.equipment, meta.equipment);
     }
+
+    #[test]
+    fn group_sections_splits_at_headers() {
+        let lines = [
+            "Ingredients:",
+            "1 lb chicken thighs",
+            "For the marinade:",
+            "2 tbsp soy sauce",
+            "Note: tamari works too.",
+            "SAUCE",
+            "TOPPING",
+            "1 tsp sesame seeds",
+        ];
+        let sections = group_sections(lines);
+        let names: Vec<_> = sections.iter().map(|s| s.name.as_deref()).collect();
+        // "SAUCE" has nothing under it before "TOPPING", so it is dropped.
+        assert_eq!(names, vec![None, Some("For the marinade"), Some("TOPPING")]);
+        assert_eq!(sections[1].ingredients, vec!["2 tbsp soy sauce"]);
+        assert!(group_sections(["Serves 4", ""]).is_empty());
+    }
+
+    #[test]
+    fn parse_sections_classifies_by_section() {
+        let sections = group_sections([
+            "1 lb chicken thighs",
+            "For the marinade",
+            "2 tbsp soy sauce",
+        ]);
+        let parsed = parse_sections(&sections);
+        assert_eq!(
+            parsed[0].ingredients[0].usage,
+            ingredient::IngredientUsage::Normal
+        );
+        assert_eq!(
+            parsed[1].ingredients[0].usage,
+            ingredient::IngredientUsage::Marinade
+        );
+    }
 }
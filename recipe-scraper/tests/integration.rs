@@ -101,6 +101,10 @@ fn scrape_from_cache_html() {
     let res = scrape_url("https://smittenkitchen.com/2018/04/crispy-tofu-pad-thai/").unwrap();
     assert_eq!(res.ingredients().count(), 17);
     assert_eq!(res.instructions().count(), 16);
+    // The `<h5>Garnishes</h5>` header splits off the last four items.
+    let names: Vec<_> = res.sections.iter().map(|s| s.name.as_deref()).collect();
+    assert_eq!(names, vec![None, Some("Garnishes")]);
+    assert_eq!(res.sections[1].ingredients.len(), 4);
     assert_eq!(res.name, "crispy tofu pad thai – smitten kitchen");
     assert_eq!(res.image, Some("https://i1.wp.com/smittenkitchen.com/wp-content/uploads//2018/04/crispy-tofu-pad-thai.jpg?fit=1200%2C800&ssl=1".to_string()));
 }
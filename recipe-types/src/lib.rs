@@ -115,6 +115,13 @@ impl RecipeTimes {
             && self.prep_minutes.is_none()
             && self.cook_minutes.is_none()
     }
+
+    /// Total time in minutes: the printed total, else prep + cook when both
+    /// are known. `None` when the source didn't give enough to say.
+    pub fn known_total_minutes(&self) -> Option<u32> {
+        self.total_minutes
+            .or_else(|| Some(self.prep_minutes? + self.cook_minutes?))
+    }
 }
 
 /// One component of a recipe (e.g. "For the sauce"). A recipe is fundamentally